@@ -0,0 +1,209 @@
+//! Append-only, hash-chained audit log of protocol operations
+//!
+//! Every keygen, refresh, derive and sign this node performs appends one
+//! JSON line recording when it ran, under which session, with which
+//! participants, over what message and with what result. Each line
+//! carries the BLAKE3 hash of the previous line, so editing or dropping
+//! a past record breaks every hash after it — `audit verify` walks the
+//! chain and reports the head hash, which noted somewhere external also
+//! pins the log's length. Complements the transparency log: that
+//! publishes ceremony outcomes under a signed Merkle head for other
+//! parties to check, this records locally everything the signer did,
+//! including individual sign operations.
+
+use anyhow::{anyhow, bail, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+/// Domain separating audit record hashes from other BLAKE3 uses
+const RECORD_CONTEXT: &[u8] = b"dkls-party audit record v1";
+
+/// One audited operation, one JSON line in the log
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Position in the log, starting at 0
+    pub seq: u64,
+    /// RFC 3339 timestamp of when the operation completed
+    pub recorded_at: String,
+    /// BLAKE3 hash of the previous log line (all zeros for the first)
+    pub prev: String,
+    /// Operation kind ("keygen", "refresh", "derive", "sign", ...)
+    pub operation: String,
+    /// Ceremony session ID (hex), when the operation ran one
+    pub session_id: Option<String>,
+    /// Participating party IDs
+    pub participants: Vec<usize>,
+    /// Message digest signed (hex), or the derivation path for derive
+    pub message: Option<String>,
+    /// Operation outcome: the public key produced or the signature (hex)
+    pub result: String,
+}
+
+/// Hash pinning one log line into the chain
+fn record_hash(line: &str) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(RECORD_CONTEXT);
+    hasher.update(line.as_bytes());
+    hex::encode(hasher.finalize().as_bytes())
+}
+
+/// The `prev` value of a log's first record
+fn genesis() -> String {
+    hex::encode([0u8; 32])
+}
+
+/// Append one operation to the audit log at `path`
+pub fn record(
+    path: &Path,
+    operation: &str,
+    session_id: Option<String>,
+    participants: &[usize],
+    message: Option<String>,
+    result: String,
+) -> Result<()> {
+    let (seq, prev) = match std::fs::read_to_string(path) {
+        Ok(existing) => match existing.lines().rfind(|l| !l.trim().is_empty()) {
+            Some(last) => {
+                let parsed: AuditRecord = serde_json::from_str(last)
+                    .map_err(|e| anyhow!("Audit log tail is not a record: {}", e))?;
+                (parsed.seq + 1, record_hash(last))
+            }
+            None => (0, genesis()),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => (0, genesis()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let record = AuditRecord {
+        seq,
+        recorded_at: chrono::Utc::now().to_rfc3339(),
+        prev,
+        operation: operation.to_string(),
+        session_id,
+        participants: participants.to_vec(),
+        message,
+        result,
+    };
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(&record)?)?;
+    Ok(())
+}
+
+/// Walk the whole chain, returning the record count and head hash
+pub fn verify(path: &Path) -> Result<(u64, String)> {
+    let content = std::fs::read_to_string(path)?;
+    let mut expected_prev = genesis();
+    let mut expected_seq = 0u64;
+    for (lineno, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: AuditRecord = serde_json::from_str(line)
+            .map_err(|e| anyhow!("Line {} is not an audit record: {}", lineno + 1, e))?;
+        if record.seq != expected_seq {
+            bail!(
+                "Line {}: sequence {} where {} was expected (records dropped or reordered)",
+                lineno + 1,
+                record.seq,
+                expected_seq
+            );
+        }
+        if record.prev != expected_prev {
+            bail!(
+                "Line {}: hash chain broken; the log was modified at or before this record",
+                lineno + 1
+            );
+        }
+        expected_prev = record_hash(line);
+        expected_seq += 1;
+    }
+    Ok((expected_seq, expected_prev))
+}
+
+/// Verify the chain and print the summary
+pub fn run_verify(path: &Path) -> Result<()> {
+    let (count, head) = verify(path)?;
+    if count == 0 {
+        println!("Audit log {} is empty", path.display());
+        return Ok(());
+    }
+    println!("Audit log OK: {} records, chain intact", count);
+    println!("Head hash: {}", head);
+    println!("Publishing the head hash externally also pins the log's length.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_log(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("audit-{}-{}.jsonl", name, std::process::id()))
+    }
+
+    fn append_three(path: &Path) {
+        record(path, "keygen", Some("aa".into()), &[0, 1, 2], None, "02ab".into()).unwrap();
+        record(
+            path,
+            "sign",
+            None,
+            &[0, 1],
+            Some("deadbeef".into()),
+            "3045".into(),
+        )
+        .unwrap();
+        record(path, "refresh", Some("bb".into()), &[0, 1, 2], None, "02ab".into()).unwrap();
+    }
+
+    #[test]
+    fn test_chain_appends_and_verifies() {
+        let path = temp_log("ok");
+        append_three(&path);
+
+        let (count, head) = verify(&path).unwrap();
+        assert_eq!(count, 3);
+        assert_ne!(head, genesis());
+
+        // Appending moves the head; the chain stays intact
+        record(&path, "derive", None, &[], Some("m/0/1".into()), "02cd".into()).unwrap();
+        let (count, new_head) = verify(&path).unwrap();
+        assert_eq!(count, 4);
+        assert_ne!(new_head, head);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_edited_record_breaks_the_chain() {
+        let path = temp_log("edited");
+        append_three(&path);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, content.replace("deadbeef", "deadc0de")).unwrap();
+
+        let err = verify(&path).err().unwrap();
+        assert!(err.to_string().contains("hash chain broken"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_dropped_record_is_detected() {
+        let path = temp_log("dropped");
+        append_three(&path);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let kept: Vec<&str> = content
+            .lines()
+            .filter(|line| !line.contains("\"sign\""))
+            .collect();
+        std::fs::write(&path, format!("{}\n", kept.join("\n"))).unwrap();
+
+        let err = verify(&path).err().unwrap();
+        assert!(err.to_string().contains("sequence"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
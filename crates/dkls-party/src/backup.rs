@@ -0,0 +1,249 @@
+//! Shamir backup fragments for a single key share
+//!
+//! A device's key share needs a recovery story independent of the MPC
+//! quorum: losing the device must not force a resharing ceremony, but no
+//! single backup custodian may hold the share either. The share bytes
+//! are split k-of-m with Shamir's scheme over GF(2^8) — each fragment is
+//! one polynomial evaluation per byte, so fewer than k fragments reveal
+//! nothing about the share.
+//!
+//! Fragments are printable one-liners in the spirit of the QR frames:
+//! `DKLS-BACKUP:<index>/<total>:<threshold>:<check8>:<base64 data>`. The
+//! checksum catches transcription errors per fragment; whether the
+//! reassembled share is genuine is decided by its own integrity tag and
+//! consistency check at restore time.
+
+use anyhow::Result;
+use rand::RngCore;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// Leading tag of every fragment line
+const FRAGMENT_PREFIX: &str = "DKLS-BACKUP";
+
+/// Multiply in GF(2^8) with the AES reduction polynomial
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80;
+        a <<= 1;
+        if carry != 0 {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Invert in GF(2^8): a^254, since the multiplicative group has order 255
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exp = 254u32;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Evaluate a polynomial (coefficients low-order first) at x via Horner
+fn poly_eval(coeffs: &[u8], x: u8) -> u8 {
+    let mut y = 0u8;
+    for &coeff in coeffs.iter().rev() {
+        y = gf_mul(y, x) ^ coeff;
+    }
+    y
+}
+
+/// Checksum guarding one fragment line against transcription errors
+fn fragment_check(index: usize, total: usize, threshold: usize, data: &[u8]) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"dkls-party backup fragment v1");
+    hasher.update(&(index as u64).to_be_bytes());
+    hasher.update(&(total as u64).to_be_bytes());
+    hasher.update(&(threshold as u64).to_be_bytes());
+    hasher.update(data);
+    hex::encode(&hasher.finalize().as_bytes()[..4])
+}
+
+/// Split `secret` into `total` fragments, any `threshold` of which
+/// reassemble it
+pub fn split(secret: &[u8], threshold: usize, total: usize) -> Result<Vec<String>> {
+    if threshold < 2 || threshold > total {
+        anyhow::bail!("Threshold {} invalid for {} fragments", threshold, total);
+    }
+    if total > 255 {
+        anyhow::bail!("At most 255 fragments (one GF(2^8) point each)");
+    }
+
+    // One random polynomial per secret byte, constant term the byte
+    let mut fragments = vec![Vec::with_capacity(secret.len()); total];
+    let mut coeffs = vec![0u8; threshold];
+    for &byte in secret {
+        coeffs[0] = byte;
+        rand::thread_rng().fill_bytes(&mut coeffs[1..]);
+        for (i, fragment) in fragments.iter_mut().enumerate() {
+            fragment.push(poly_eval(&coeffs, (i + 1) as u8));
+        }
+    }
+
+    Ok(fragments
+        .iter()
+        .enumerate()
+        .map(|(i, data)| {
+            format!(
+                "{}:{}/{}:{}:{}:{}",
+                FRAGMENT_PREFIX,
+                i + 1,
+                total,
+                threshold,
+                fragment_check(i + 1, total, threshold, data),
+                STANDARD.encode(data)
+            )
+        })
+        .collect())
+}
+
+/// One parsed fragment line
+struct Fragment {
+    index: usize,
+    total: usize,
+    threshold: usize,
+    data: Vec<u8>,
+}
+
+/// Parse and checksum-verify one fragment line
+fn parse_fragment(line: &str) -> Result<Fragment> {
+    let parts: Vec<&str> = line.trim().split(':').collect();
+    let [prefix, position, threshold, check, data] = parts.as_slice() else {
+        anyhow::bail!("Malformed backup fragment");
+    };
+    if *prefix != FRAGMENT_PREFIX {
+        anyhow::bail!("Not a backup fragment (expected {} tag)", FRAGMENT_PREFIX);
+    }
+    let (index, total) = position
+        .split_once('/')
+        .ok_or_else(|| anyhow::anyhow!("Malformed fragment position"))?;
+    let index: usize = index.parse()?;
+    let total: usize = total.parse()?;
+    let threshold: usize = threshold.parse()?;
+    let data = STANDARD.decode(data)?;
+    if index == 0 || index > total {
+        anyhow::bail!("Fragment index {} out of range 1..={}", index, total);
+    }
+    if *check != fragment_check(index, total, threshold, &data) {
+        anyhow::bail!("Fragment {} failed its checksum; re-enter it", index);
+    }
+    Ok(Fragment {
+        index,
+        total,
+        threshold,
+        data,
+    })
+}
+
+/// Reassemble the secret from at least the backup threshold of fragments
+pub fn combine(lines: &[String]) -> Result<Vec<u8>> {
+    let mut fragments = Vec::with_capacity(lines.len());
+    for line in lines {
+        fragments.push(parse_fragment(line)?);
+    }
+    let Some(first) = fragments.first() else {
+        anyhow::bail!("No fragments given");
+    };
+    let (threshold, total, len) = (first.threshold, first.total, first.data.len());
+    if fragments
+        .iter()
+        .any(|f| f.threshold != threshold || f.total != total || f.data.len() != len)
+    {
+        anyhow::bail!("Fragments are from different backups");
+    }
+    fragments.sort_by_key(|f| f.index);
+    fragments.dedup_by_key(|f| f.index);
+    if fragments.len() < threshold {
+        anyhow::bail!(
+            "Need {} distinct fragments, got {}",
+            threshold,
+            fragments.len()
+        );
+    }
+    // Exactly threshold points determine the polynomial; extras would
+    // only mask a corrupted fragment as an interpolation mismatch
+    fragments.truncate(threshold);
+
+    // Lagrange interpolation at x = 0, one byte position at a time
+    let mut secret = vec![0u8; fragments[0].data.len()];
+    for (j, fragment) in fragments.iter().enumerate() {
+        let x_j = fragment.index as u8;
+        let mut weight = 1u8;
+        for (l, other) in fragments.iter().enumerate() {
+            if l == j {
+                continue;
+            }
+            let x_l = other.index as u8;
+            weight = gf_mul(weight, gf_mul(x_l, gf_inv(x_l ^ x_j)));
+        }
+        for (byte, &y) in secret.iter_mut().zip(&fragment.data) {
+            *byte ^= gf_mul(weight, y);
+        }
+    }
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_any_threshold_subset_restores() {
+        let secret = b"attack at dawn, sign at noon".to_vec();
+        let fragments = split(&secret, 3, 5).unwrap();
+        assert_eq!(fragments.len(), 5);
+
+        for subset in [[0, 1, 2], [0, 2, 4], [1, 3, 4], [2, 3, 4]] {
+            let picked: Vec<String> = subset.iter().map(|&i| fragments[i].clone()).collect();
+            assert_eq!(combine(&picked).unwrap(), secret);
+        }
+    }
+
+    #[test]
+    fn test_below_threshold_is_rejected() {
+        let fragments = split(b"secret bytes", 3, 4).unwrap();
+        let err = combine(&fragments[..2]).err().unwrap();
+        assert!(err.to_string().contains("Need 3 distinct fragments"));
+
+        // Duplicates of one fragment do not count toward the threshold
+        let dupes = vec![
+            fragments[0].clone(),
+            fragments[0].clone(),
+            fragments[0].clone(),
+        ];
+        assert!(combine(&dupes).is_err());
+    }
+
+    #[test]
+    fn test_transcription_errors_are_caught() {
+        let fragments = split(b"secret bytes", 2, 3).unwrap();
+
+        // Flip a character in the base64 payload
+        let mut corrupted = fragments[0].clone();
+        let flipped = if corrupted.ends_with('A') { 'B' } else { 'A' };
+        corrupted.pop();
+        corrupted.push(flipped);
+        let err = combine(&[corrupted, fragments[1].clone()]).err().unwrap();
+        assert!(err.to_string().contains("checksum"));
+
+        // Fragments from two different backups never silently mix
+        let other = split(b"different bytes!", 2, 3).unwrap();
+        assert!(combine(&[fragments[0].clone(), other[1].clone()]).is_err());
+
+        assert!(combine(&["not a fragment".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Operator configuration file (`dkls-party.toml`)
+//!
+//! Operators running several parties on one host end up juggling long
+//! command lines and environment variables; a config file in the working
+//! directory (or named by `DKLS_PARTY_CONFIG`) pins the stable settings
+//! instead. The file only supplies defaults: environment variables
+//! override it, and flags override both, so one-off overrides on the
+//! command line keep working unchanged.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Default file name, looked up in the current directory
+pub const DEFAULT_CONFIG_FILE: &str = "dkls-party.toml";
+
+/// Environment variable naming an explicit config file
+pub const CONFIG_ENV: &str = "DKLS_PARTY_CONFIG";
+
+/// Settings an operator can pin in the config file
+///
+/// Every field is optional; unknown keys are rejected so a typo fails
+/// loudly instead of silently falling back to a built-in default.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    /// Relay service URL (`--relay`)
+    pub relay: Option<String>,
+
+    /// This party's ID (`--party-id`)
+    pub party_id: Option<usize>,
+
+    /// Data directory for key shares (`--dest`)
+    pub dest: Option<PathBuf>,
+
+    /// Bearer token for a relay requiring authentication; a rotated
+    /// `relay.json` in the data directory still takes precedence
+    pub token: Option<String>,
+
+    /// Relay request timeout in seconds
+    pub relay_timeout_secs: Option<u64>,
+
+    /// Transparency log to publish ceremonies to (`--transparency-log`)
+    pub transparency_log: Option<PathBuf>,
+
+    /// Derivation paths pinned to labels, consulted before the key tree
+    /// by `derive --label`
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+/// Load the config file, if any
+///
+/// `DKLS_PARTY_CONFIG` names the file explicitly (and it must then
+/// exist); otherwise `./dkls-party.toml` is used when present. No file
+/// means an empty config.
+pub fn load() -> Result<ConfigFile> {
+    match std::env::var_os(CONFIG_ENV) {
+        Some(path) => read(Path::new(&path)),
+        None => {
+            let path = Path::new(DEFAULT_CONFIG_FILE);
+            if path.exists() {
+                read(path)
+            } else {
+                Ok(ConfigFile::default())
+            }
+        }
+    }
+}
+
+/// Parse one config file
+fn read(path: &Path) -> Result<ConfigFile> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Cannot read config file {}", path.display()))?;
+    toml::from_str(&text).with_context(|| format!("Invalid config file {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_config_parses() {
+        let config: ConfigFile = toml::from_str(
+            r#"
+            relay = "http://relay.internal:8080"
+            party_id = 2
+            dest = "/var/lib/dkls/party2"
+            token = "secret"
+            relay_timeout_secs = 45
+            transparency_log = "/var/lib/dkls/translog"
+
+            [labels]
+            treasury = "m/0/0"
+            ops = "m/0/1"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.relay.as_deref(), Some("http://relay.internal:8080"));
+        assert_eq!(config.party_id, Some(2));
+        assert_eq!(config.dest, Some(PathBuf::from("/var/lib/dkls/party2")));
+        assert_eq!(config.token.as_deref(), Some("secret"));
+        assert_eq!(config.relay_timeout_secs, Some(45));
+        assert_eq!(config.labels.get("treasury").map(String::as_str), Some("m/0/0"));
+    }
+
+    #[test]
+    fn test_empty_config_is_all_defaults() {
+        let config: ConfigFile = toml::from_str("").unwrap();
+        assert!(config.relay.is_none());
+        assert!(config.party_id.is_none());
+        assert!(config.labels.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        assert!(toml::from_str::<ConfigFile>("relay_ur = \"typo\"").is_err());
+    }
+}
@@ -0,0 +1,229 @@
+//! Outbox-based export of signed artifacts to downstream systems
+//!
+//! Broadcasting systems should not have to poll a party for results.
+//! After a ceremony the signed artifact is enqueued into a durable
+//! on-disk outbox, then a delivery pass POSTs every pending entry to the
+//! configured webhook endpoints. An entry is removed only once every
+//! endpoint has acknowledged it with a 2xx, which gives at-least-once
+//! delivery: a crash between the POST and the removal re-delivers on the
+//! next pass, so consumers must de-duplicate on the trace ID carried in
+//! the payload.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// One queued artifact awaiting delivery
+#[derive(Serialize, Deserialize)]
+struct OutboxEntry {
+    /// When the artifact was enqueued (RFC 3339)
+    enqueued_at: String,
+    /// Delivery attempts so far, across all endpoints
+    attempts: u32,
+    /// The artifact itself, POSTed verbatim as the request body
+    payload: serde_json::Value,
+}
+
+/// Outcome of one delivery pass
+pub struct FlushReport {
+    /// Entries acknowledged by every endpoint and removed
+    pub delivered: usize,
+    /// Entries still queued after the pass
+    pub pending: usize,
+}
+
+/// Durable on-disk queue of artifacts awaiting delivery
+///
+/// Each entry is one JSON file named `outbox-<millis>-<counter>.json` so
+/// a directory listing yields enqueue order.
+pub struct Outbox {
+    dir: PathBuf,
+}
+
+impl Outbox {
+    /// Open (creating if needed) the outbox at `dir`
+    pub fn open(dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+        })
+    }
+
+    /// Enqueue an artifact for delivery
+    pub fn enqueue(&self, payload: &serde_json::Value) -> Result<PathBuf> {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+        let entry = OutboxEntry {
+            enqueued_at: chrono::Utc::now().to_rfc3339(),
+            attempts: 0,
+            payload: payload.clone(),
+        };
+        let name = format!(
+            "outbox-{:013}-{:04}.json",
+            chrono::Utc::now().timestamp_millis(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = self.dir.join(name);
+        std::fs::write(&path, serde_json::to_string_pretty(&entry)?)?;
+        Ok(path)
+    }
+
+    /// Pending entry files in enqueue order
+    pub fn pending(&self) -> Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if name.starts_with("outbox-") && name.ends_with(".json") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Attempt delivery of every pending entry to every endpoint
+    ///
+    /// Entries that any endpoint rejects stay queued with their attempt
+    /// count bumped; a later pass (the next ceremony, or an explicit
+    /// `export-flush`) retries them.
+    pub async fn flush(&self, endpoints: &[String], timeout: Duration) -> Result<FlushReport> {
+        let client = reqwest::Client::new();
+        let mut delivered = 0;
+        let mut pending = 0;
+
+        for path in self.pending()? {
+            let mut entry: OutboxEntry = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+            let mut all_acked = true;
+
+            for endpoint in endpoints {
+                let result = client
+                    .post(endpoint)
+                    .timeout(timeout)
+                    .json(&entry.payload)
+                    .send()
+                    .await;
+                match result {
+                    Ok(response) if response.status().is_success() => {}
+                    Ok(response) => {
+                        warn!(
+                            endpoint,
+                            status = %response.status(),
+                            entry = %path.display(),
+                            "Downstream endpoint rejected artifact"
+                        );
+                        all_acked = false;
+                    }
+                    Err(e) => {
+                        warn!(
+                            endpoint,
+                            error = %e,
+                            entry = %path.display(),
+                            "Cannot reach downstream endpoint"
+                        );
+                        all_acked = false;
+                    }
+                }
+            }
+
+            if all_acked {
+                std::fs::remove_file(&path)?;
+                delivered += 1;
+            } else {
+                entry.attempts += 1;
+                std::fs::write(&path, serde_json::to_string_pretty(&entry)?)?;
+                pending += 1;
+            }
+        }
+
+        if delivered > 0 || pending > 0 {
+            info!(delivered, pending, "Outbox delivery pass finished");
+        }
+        Ok(FlushReport { delivered, pending })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_outbox(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("outbox-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Minimal webhook endpoint: accept one request, reply 200
+    async fn one_shot_webhook() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+            if let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                    .await;
+            }
+        });
+        format!("http://{}/hook", addr)
+    }
+
+    #[tokio::test]
+    async fn test_delivered_entries_leave_the_outbox() {
+        let dir = temp_outbox("delivered");
+        let outbox = Outbox::open(&dir).unwrap();
+        outbox
+            .enqueue(&serde_json::json!({"trace_id": "t-1"}))
+            .unwrap();
+
+        let endpoint = one_shot_webhook().await;
+        let report = outbox
+            .flush(&[endpoint], Duration::from_secs(2))
+            .await
+            .unwrap();
+        assert_eq!(report.delivered, 1);
+        assert_eq!(report.pending, 0);
+        assert!(outbox.pending().unwrap().is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint_keeps_entry_queued() {
+        let dir = temp_outbox("unreachable");
+        let outbox = Outbox::open(&dir).unwrap();
+        let path = outbox
+            .enqueue(&serde_json::json!({"trace_id": "t-2"}))
+            .unwrap();
+
+        // A port nothing listens on: delivery must fail, not drop the entry
+        let report = outbox
+            .flush(
+                &["http://127.0.0.1:9/hook".to_string()],
+                Duration::from_secs(2),
+            )
+            .await
+            .unwrap();
+        assert_eq!(report.delivered, 0);
+        assert_eq!(report.pending, 1);
+
+        let entry: OutboxEntry =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(entry.attempts, 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_pending_preserves_enqueue_order() {
+        let dir = temp_outbox("order");
+        let outbox = Outbox::open(&dir).unwrap();
+        let first = outbox.enqueue(&serde_json::json!({"n": 1})).unwrap();
+        let second = outbox.enqueue(&serde_json::json!({"n": 2})).unwrap();
+        assert_eq!(outbox.pending().unwrap(), vec![first, second]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,237 @@
+//! Hardware-token protection of the key share at rest
+//!
+//! Moves the share's at-rest protection into a PKCS#11 token — a YubiKey
+//! PIV slot via its PKCS#11 module, or any HSM. The share file holds
+//! ChaCha20-Poly1305 ciphertext under a fresh data-encryption key, and
+//! that DEK rides alongside it encrypted under the token's RSA key, so
+//! opening the file requires the hardware: its PIN and, on tokens
+//! configured for it, a touch. The decrypted share itself stays in
+//! software — the MPC math needs it — and only exists in memory.
+//!
+//! The module path and key label ride inside the envelope, in the spirit
+//! of the KDF parameters in the passphrase wrap; the PIN is the only
+//! thing asked for at load time. Builds without the `hardware-token`
+//! feature can neither produce nor open these envelopes.
+
+use anyhow::{anyhow, bail, Result};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::types::AuthPin;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::io::IsTerminal;
+use std::path::Path;
+use tracing::info;
+use zeroize::Zeroize;
+
+/// Hardware envelope format version
+const HW_WRAP_VERSION: u32 = 1;
+
+/// Environment variable carrying the token PIN for unattended use
+const PIN_ENV: &str = "DKLS_HSM_PIN";
+
+/// A hardware-sealed key share as stored on disk
+///
+/// Distinguished from both plaintext shares and passphrase envelopes by
+/// its fields, so loaders accept all three without a separate marker.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HwWrappedShare {
+    /// Envelope format version
+    pub version: u32,
+    /// PKCS#11 module library the share was sealed with
+    pub module: String,
+    /// Label of the token key pair wrapping the DEK
+    pub key_label: String,
+    /// DEK encrypted under the token's RSA public key (hex)
+    pub wrapped_dek: String,
+    /// Random 96-bit nonce (hex)
+    pub nonce: String,
+    /// ChaCha20-Poly1305 ciphertext of the share JSON (hex)
+    pub ciphertext: String,
+}
+
+/// Encrypt the share bytes under a DEK, returning (nonce, ciphertext)
+/// hex pairs for the envelope
+fn encrypt_with_dek(dek: &[u8; 32], plaintext: &[u8]) -> Result<(String, String)> {
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let cipher = ChaCha20Poly1305::new(dek.into());
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| anyhow!("Share encryption failed"))?;
+    Ok((hex::encode(nonce), hex::encode(ciphertext)))
+}
+
+/// Decrypt the envelope's ciphertext with an unwrapped DEK
+fn decrypt_with_dek(dek: &[u8; 32], nonce_hex: &str, ciphertext_hex: &str) -> Result<Vec<u8>> {
+    let nonce = hex::decode(nonce_hex)?;
+    if nonce.len() != 12 {
+        bail!("Invalid hardware envelope nonce length");
+    }
+    let ciphertext = hex::decode(ciphertext_hex)?;
+    let cipher = ChaCha20Poly1305::new(dek.into());
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("Share decryption failed; wrong token key or corrupted file"))
+}
+
+/// Token PIN from the environment, or a terminal prompt
+pub fn token_pin() -> Result<String> {
+    if let Ok(pin) = std::env::var(PIN_ENV) {
+        return Ok(pin);
+    }
+    if !std::io::stdin().is_terminal() {
+        bail!("Token PIN required; set {} or run interactively", PIN_ENV);
+    }
+    Ok(rpassword::prompt_password("Token PIN: ")?)
+}
+
+/// Open a logged-in session against the first token the module exposes
+fn token_session(module: &str, pin: &str) -> Result<Session> {
+    let pkcs11 = Pkcs11::new(module)
+        .map_err(|e| anyhow!("Cannot load PKCS#11 module {}: {}", module, e))?;
+    pkcs11.initialize(CInitializeArgs::OsThreads)?;
+    let slot = pkcs11
+        .get_slots_with_token()?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("No token present behind {}", module))?;
+    let session = pkcs11.open_rw_session(slot)?;
+    session.login(UserType::User, Some(&AuthPin::new(pin.to_string())))?;
+    Ok(session)
+}
+
+/// Find the token key object with the given class and label
+fn find_key(session: &Session, class: ObjectClass, label: &str) -> Result<ObjectHandle> {
+    session
+        .find_objects(&[
+            Attribute::Class(class),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ])?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("No {:?} labeled '{}' on the token", class, label))
+}
+
+/// Seal serialized share bytes under the token's RSA key
+pub fn seal(module: &str, key_label: &str, pin: &str, plaintext: &[u8]) -> Result<HwWrappedShare> {
+    let mut dek = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut dek);
+    let (nonce, ciphertext) = encrypt_with_dek(&dek, plaintext)?;
+
+    let session = token_session(module, pin)?;
+    let key = find_key(&session, ObjectClass::PUBLIC_KEY, key_label)?;
+    let wrapped_dek = session.encrypt(&Mechanism::RsaPkcs, key, &dek)?;
+    dek.zeroize();
+
+    Ok(HwWrappedShare {
+        version: HW_WRAP_VERSION,
+        module: module.to_string(),
+        key_label: key_label.to_string(),
+        wrapped_dek: hex::encode(wrapped_dek),
+        nonce,
+        ciphertext,
+    })
+}
+
+/// Open a hardware envelope; the token decrypts the DEK, which is where
+/// the PIN (and touch policy) is enforced
+pub fn open(sealed: &HwWrappedShare, pin: &str) -> Result<Vec<u8>> {
+    if sealed.version != HW_WRAP_VERSION {
+        bail!(
+            "Unsupported hardware envelope version {} (this build supports {})",
+            sealed.version,
+            HW_WRAP_VERSION
+        );
+    }
+    let session = token_session(&sealed.module, pin)?;
+    let key = find_key(&session, ObjectClass::PRIVATE_KEY, &sealed.key_label)?;
+    let wrapped = hex::decode(&sealed.wrapped_dek)?;
+    let mut dek: [u8; 32] = session
+        .decrypt(&Mechanism::RsaPkcs, key, &wrapped)?
+        .try_into()
+        .map_err(|_| anyhow!("Token returned a DEK of the wrong length"))?;
+    let plaintext = decrypt_with_dek(&dek, &sealed.nonce, &sealed.ciphertext);
+    dek.zeroize();
+    plaintext
+}
+
+/// Seal this party's key share file in place under the token
+pub fn run_seal(cli: &crate::Cli, module: &Path, key_label: &str) -> Result<()> {
+    let mut share = crate::load_key_share(cli)?;
+    share.seal_integrity();
+    let plaintext = serde_json::to_vec(&share)?;
+
+    let pin = token_pin()?;
+    let module = module.to_string_lossy();
+    let sealed = seal(&module, key_label, &pin, &plaintext)?;
+
+    let path = match cli.key_id {
+        Some(ref prefix) => crate::find_key_share_by_id(cli, prefix)?,
+        None => cli.dest.join(format!("keyshare.{}.json", cli.party_id)),
+    };
+    std::fs::write(&path, serde_json::to_string_pretty(&sealed)?)?;
+
+    info!(
+        path = ?path,
+        key_label,
+        "Key share sealed under hardware token"
+    );
+    println!("Key share sealed under token key '{}'", key_label);
+    println!("Decrypting {} now requires the token and its PIN.", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dek_layer_roundtrip() {
+        let dek = [7u8; 32];
+        let (nonce, ciphertext) = encrypt_with_dek(&dek, br#"{"party_id":0}"#).unwrap();
+        let plaintext = decrypt_with_dek(&dek, &nonce, &ciphertext).unwrap();
+        assert_eq!(plaintext, br#"{"party_id":0}"#);
+
+        // A different DEK — i.e. a different token key — must not open it
+        assert!(decrypt_with_dek(&[8u8; 32], &nonce, &ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_is_rejected() {
+        let dek = [7u8; 32];
+        let (nonce, ciphertext) = encrypt_with_dek(&dek, b"secret").unwrap();
+        let mut raw = hex::decode(&ciphertext).unwrap();
+        raw[0] ^= 0x01;
+        assert!(decrypt_with_dek(&dek, &nonce, &hex::encode(raw)).is_err());
+    }
+
+    #[test]
+    fn test_envelope_kinds_do_not_cross_parse() {
+        // A passphrase envelope must not be mistaken for a hardware one,
+        // and vice versa, since the loader tells them apart by fields
+        let cheap = crate::wrap::KdfParams {
+            memory_kib: 1024,
+            iterations: 1,
+            parallelism: 1,
+        };
+        let passphrase = crate::wrap::wrap(b"secret", "pass", &cheap).unwrap();
+        let json = serde_json::to_string(&passphrase).unwrap();
+        assert!(serde_json::from_str::<HwWrappedShare>(&json).is_err());
+
+        let hardware = HwWrappedShare {
+            version: HW_WRAP_VERSION,
+            module: "/usr/lib/libykcs11.so".into(),
+            key_label: "dkls-share-wrap".into(),
+            wrapped_dek: "00".into(),
+            nonce: "00".into(),
+            ciphertext: "00".into(),
+        };
+        let json = serde_json::to_string(&hardware).unwrap();
+        assert!(serde_json::from_str::<crate::wrap::WrappedShare>(&json).is_err());
+    }
+}
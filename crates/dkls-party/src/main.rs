@@ -7,13 +7,31 @@
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use dkls23_core::{keygen, sign, KeyShare, SessionConfig};
+use futures_util::StreamExt;
+use dkls23_core::mpc::{FileRelay, MeteredRelay, Relay, RelayStats};
+use dkls23_core::{keygen, keytree::KeyTree, sign, KeyShare, SessionConfig};
 use msg_relay_client::RelayClient;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{info, Level};
 
+mod audit;
+mod backup;
+mod config;
+mod export;
+#[cfg(feature = "hardware-token")]
+mod hsm;
+mod preflight;
+mod psbt;
+mod qr;
+mod server;
+mod store;
+mod translog;
+#[cfg(feature = "tui")]
+mod tui;
+mod wrap;
+
 /// DKLs Party - MPC Party Node
-#[derive(Parser)]
+#[derive(Parser, Clone)]
 #[command(name = "dkls-party")]
 #[command(about = "Threshold ECDSA MPC party node")]
 #[command(version)]
@@ -30,11 +48,88 @@ struct Cli {
     #[arg(short, long, env = "DEST", default_value = "./data")]
     dest: PathBuf,
 
+    /// Named wallet scoping all state to <dest>/wallets/<name>, so one
+    /// node can hold shares for many customers or keys
+    #[arg(short, long, env = "DKLS_WALLET")]
+    wallet: Option<String>,
+
+    /// Select a key share by public-key hex prefix when the wallet
+    /// holds several (e.g. after keygen --count or derive)
+    #[arg(long)]
+    key_id: Option<String>,
+
+    /// Where the key share lives at rest: 'file' under the data
+    /// directory, or 'keychain' in the OS credential store
+    #[arg(long, env = "DKLS_SHARE_STORE", default_value = "file")]
+    share_store: String,
+
+    /// Record every relay envelope sent/received to this file
+    #[arg(long)]
+    capture: Option<PathBuf>,
+
+    /// Passphrase encrypting key shares at rest (Argon2id-wrapped);
+    /// omitted, interactive runs prompt and scripted runs store
+    /// unencrypted
+    #[arg(long, env = "SHARE_PASSPHRASE")]
+    share_passphrase: Option<String>,
+
+    /// Read the share passphrase from the first line of this file
+    #[arg(long, conflicts_with = "share_passphrase")]
+    passphrase_file: Option<PathBuf>,
+
+    /// Publish each completed keygen/resharing ceremony's public key and
+    /// transcript hash to this append-only Merkle transparency log
+    #[arg(long, env = "TRANSPARENCY_LOG")]
+    transparency_log: Option<PathBuf>,
+
+    /// Trace ID linking this invocation to the originating request
+    /// (generated randomly when omitted)
+    #[arg(long, env = "TRACE_ID")]
+    trace_id: Option<String>,
+
+    /// Air-gapped mode: write outgoing round messages to this directory
+    /// instead of using the network relay (requires --inbox)
+    #[arg(long, requires = "inbox")]
+    outbox: Option<PathBuf>,
+
+    /// Air-gapped mode: read incoming round messages from this directory
+    #[arg(long, requires = "outbox")]
+    inbox: Option<PathBuf>,
+
+    /// In air-gapped mode, prompt for Enter after each media transfer
+    /// instead of polling the inbox
+    #[arg(long, requires = "inbox")]
+    await_files: bool,
+
+    /// Print a per-round bandwidth and timing summary after the ceremony
+    #[arg(long)]
+    timings: bool,
+
+    /// Warn when total ceremony traffic approaches this many bytes
+    /// (for co-signers on metered connections)
+    #[arg(long)]
+    bandwidth_budget: Option<u64>,
+
+    /// Restore a quarantined key share to service after the integrity
+    /// failure or abort that triggered the quarantine has been
+    /// investigated
+    #[arg(long)]
+    force_unquarantine: bool,
+
     #[command(subcommand)]
     command: Commands,
+
+    /// Settings loaded from `dkls-party.toml`, filled in after parsing
+    #[arg(skip)]
+    config: config::ConfigFile,
+
+    /// Directory holding the named wallets, kept around so `wallets`
+    /// can enumerate them after --wallet rewrites `dest`
+    #[arg(skip)]
+    wallets_root: PathBuf,
 }
 
-#[derive(Subcommand)]
+#[derive(Subcommand, Clone)]
 enum Commands {
     /// Run distributed key generation
     Keygen {
@@ -45,31 +140,435 @@ enum Commands {
         /// Threshold (t-of-n)
         #[arg(short, long)]
         t: usize,
+
+        /// Number of independent keys to generate in one ceremony
+        #[arg(long, default_value = "1")]
+        count: usize,
+
+        /// Shared session ID from `session create` (hex)
+        #[arg(long)]
+        session: Option<String>,
     },
 
     /// Refresh key shares
     Refresh,
 
+    /// Provision a share for a brand-new party without changing the key
+    AddParty {
+        /// Existing parties provisioning the share (comma-separated)
+        #[arg(short, long)]
+        dealers: String,
+
+        /// Relay ID of the joining party
+        #[arg(short, long)]
+        new_party: usize,
+
+        /// Run as the joining party (no existing key share)
+        #[arg(long)]
+        join: bool,
+    },
+
+    /// Re-deal the key to a new committee, optionally changing n and t
+    Reshare {
+        /// Old-committee members dealing their shares (comma-separated;
+        /// at least the old threshold)
+        #[arg(short, long)]
+        dealers: String,
+
+        /// Relay IDs of the new committee (comma-separated); position in
+        /// the list becomes the member's new party ID
+        #[arg(long)]
+        new_parties: String,
+
+        /// Threshold of the new committee
+        #[arg(long)]
+        new_t: usize,
+
+        /// Run as a new-committee member with no existing key share
+        #[arg(long)]
+        join: bool,
+
+        /// Group public key to expect (hex; recommended with --join)
+        #[arg(long)]
+        expect_pubkey: Option<String>,
+    },
+
+    /// Generate pre-signatures before any message is known, for
+    /// near-instant signing later with sign --use-presig
+    Presign {
+        /// Number of pre-signatures to generate
+        #[arg(long, default_value = "1")]
+        count: usize,
+
+        /// Participating party IDs (comma-separated)
+        #[arg(short, long)]
+        parties: String,
+    },
+
     /// Sign a message
     Sign {
         /// Message to sign (hex encoded hash)
+        #[arg(short, long, required_unless_present = "eth_message")]
+        message: Option<String>,
+
+        /// UTF-8 message to sign as EIP-191 personal_sign (prefixed and
+        /// Keccak-hashed before the ceremony)
+        #[arg(long, conflicts_with = "message")]
+        eth_message: Option<String>,
+
+        /// Consume the stored pre-signature with this ID instead of
+        /// running the full protocol (see presign)
+        #[arg(long)]
+        use_presig: Option<String>,
+
+        /// Shared session ID from `session create` (hex); pre-signatures
+        /// carry their own session
+        #[arg(long, conflicts_with = "use_presig")]
+        session: Option<String>,
+
+        /// Participating party IDs (comma-separated)
         #[arg(short, long)]
-        message: String,
+        parties: String,
+
+        /// Push the signed result to this webhook URL after the ceremony
+        /// (repeatable; failed deliveries stay queued in the outbox)
+        #[arg(long)]
+        webhook: Vec<String>,
+    },
+
+    /// Sign every message hash in a file, one ceremony per line
+    SignBatch {
+        /// File of hex-encoded 32-byte hashes, one per line (blank lines
+        /// and # comments skipped)
+        #[arg(short, long)]
+        file: PathBuf,
+
+        /// Participating party IDs (comma-separated)
+        #[arg(short, long)]
+        parties: String,
+    },
+
+    /// Co-sign a Bitcoin PSBT's P2WPKH inputs with the group key
+    SignPsbt {
+        /// Binary PSBT file to sign
+        #[arg(short, long)]
+        file: PathBuf,
 
         /// Participating party IDs (comma-separated)
         #[arg(short, long)]
         parties: String,
     },
 
+    /// Sign an EIP-712 typed-data document (eth_signTypedData_v4 JSON)
+    SignTypedData {
+        /// JSON file with types, primaryType, domain and message
+        #[arg(short, long)]
+        file: PathBuf,
+
+        /// Participating party IDs (comma-separated)
+        #[arg(short, long)]
+        parties: String,
+
+        /// Push the signed result to this webhook URL after the ceremony
+        /// (repeatable; failed deliveries stay queued in the outbox)
+        #[arg(long)]
+        webhook: Vec<String>,
+    },
+
     /// Derive a child key
     Derive {
         /// BIP32 derivation path (e.g., m/0/1/42)
+        #[arg(short, long, conflicts_with = "label")]
+        path: Option<String>,
+
+        /// Named key in the key tree (allocates a fresh index if new)
         #[arg(short, long)]
-        path: String,
+        label: Option<String>,
     },
 
     /// Show key share info
     Info,
+
+    /// Reconstruct the private key from a threshold quorum of share
+    /// files (break-glass disaster recovery; voids threshold protection)
+    ExportKey {
+        /// Key share file (repeatable; at least threshold shares from
+        /// distinct parties)
+        #[arg(long = "share", required = true)]
+        shares: Vec<PathBuf>,
+
+        /// Output encoding: hex or wif
+        #[arg(long, default_value = "hex")]
+        format: String,
+    },
+
+    /// Split the key share into k-of-m printable backup fragments
+    Backup {
+        /// Fragments needed to restore
+        #[arg(short = 'k', long)]
+        threshold: usize,
+
+        /// Total fragments to produce
+        #[arg(short = 'm', long)]
+        fragments: usize,
+
+        /// Directory for the fragment files (defaults to the data dir)
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+
+    /// Reassemble the key share from backup fragments
+    Restore {
+        /// Fragment file (repeatable; at least the backup threshold)
+        #[arg(long = "fragment", required = true)]
+        fragments: Vec<PathBuf>,
+    },
+
+    /// Write the key share out of the encrypted keystore
+    ExportShare {
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Required acknowledgement that the exported share is
+        /// unencrypted
+        #[arg(long)]
+        plaintext: bool,
+    },
+
+    /// Check ceremony prerequisites and report blockers before committing
+    /// other parties' time
+    Preflight {
+        /// Emit the report as JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Retry delivery of queued signed results to downstream webhooks
+    ExportFlush {
+        /// Webhook URL to deliver to (repeatable)
+        #[arg(long)]
+        webhook: Vec<String>,
+    },
+
+    /// Summarize a protocol capture file for post-mortem debugging
+    Replay {
+        /// Capture file recorded with --capture
+        #[arg(short, long)]
+        file: PathBuf,
+    },
+
+    /// Export a file as QR frames for air-gapped transfer
+    ExportQr {
+        /// File to export (key share, capture, round messages)
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Directory receiving the frame files
+        #[arg(short, long)]
+        out_dir: PathBuf,
+
+        /// Encrypt the payload under this passphrase before chunking
+        #[arg(long)]
+        passphrase: Option<String>,
+
+        /// Bytes per QR frame
+        #[arg(long, default_value_t = qr::DEFAULT_CHUNK_SIZE)]
+        chunk_size: usize,
+    },
+
+    /// Generate a ceremony report for key-management filing
+    Report {
+        /// Where to write the Markdown report (stdout when omitted)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Capture file of the ceremony, for the transcript hash
+        #[arg(long)]
+        capture: Option<PathBuf>,
+    },
+
+    /// Reassemble scanned QR frame payloads into a file
+    ImportQr {
+        /// Directory containing scanned .payload files
+        #[arg(short, long)]
+        in_dir: PathBuf,
+
+        /// Where to write the reassembled file
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// Passphrase the payload was encrypted under, if any
+        #[arg(long)]
+        passphrase: Option<String>,
+
+        /// Guardian approval file (repeatable; required when an escrow
+        /// policy is configured in the data directory)
+        #[arg(long)]
+        approval: Vec<PathBuf>,
+    },
+
+    /// Generate a guardian keypair for the escrow approval policy
+    GuardianKeygen {
+        /// Where to write the guardian's secret key (hex)
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Benchmark Argon2 share-wrapping parameters for this host
+    KdfBenchmark {
+        /// Target derivation time per unlock, in milliseconds
+        #[arg(long, default_value = "500")]
+        target_ms: u64,
+
+        /// Persist the recommendation so future wraps use it
+        #[arg(long)]
+        apply: bool,
+    },
+
+    /// Rotate to a new relay endpoint and/or credential without downtime
+    RelayRotate {
+        /// Relay URL new ceremonies should use
+        #[arg(long)]
+        new_url: String,
+
+        /// Bearer token for the new relay, if it requires one
+        #[arg(long)]
+        new_token: Option<String>,
+
+        /// Seconds to wait for in-flight sessions on the old relay to
+        /// drain before switching over
+        #[arg(long, default_value = "60")]
+        drain_timeout: u64,
+    },
+
+    /// Print chain addresses and the master xpub of the group key
+    Address {
+        /// Bitcoin network for the segwit addresses and xpub version
+        #[arg(long, default_value = "bitcoin")]
+        network: String,
+    },
+
+    /// Negotiate a shared session ID and participant list via the relay
+    Session {
+        #[command(subcommand)]
+        action: SessionCommands,
+    },
+
+    /// Interactive operator console: relay activity, presignature
+    /// inventory and sign-request approval (requires the tui feature)
+    #[cfg(feature = "tui")]
+    Tui,
+
+    /// Inspect the append-only audit log of protocol operations
+    Audit {
+        #[command(subcommand)]
+        action: AuditCommands,
+    },
+
+    /// List named wallets and the key shares each one holds
+    Wallets,
+
+    /// Run as a daemon exposing a JSON-RPC signing API
+    Serve {
+        /// Listen address
+        #[arg(long, default_value = "127.0.0.1:9000")]
+        listen: String,
+    },
+
+    /// Verify a signature against the group public key
+    Verify {
+        /// Signed message (hex encoded 32-byte hash)
+        #[arg(short, long)]
+        message: String,
+
+        /// Signature as hex: compact r||s (64 bytes), Ethereum r||s||v
+        /// (65 bytes), or strict DER
+        #[arg(short, long)]
+        signature: String,
+
+        /// Compressed public key to check against (defaults to the
+        /// stored key share's group key)
+        #[arg(long)]
+        pubkey: Option<String>,
+    },
+
+    /// Verify the transparency log's Merkle root and signed tree head
+    TranslogVerify {
+        /// Log file to verify (defaults to --transparency-log)
+        #[arg(long)]
+        log: Option<PathBuf>,
+    },
+
+    /// Seal the key share file under a PKCS#11 hardware token, so
+    /// decrypting it requires the token's PIN (and touch, if configured)
+    #[cfg(feature = "hardware-token")]
+    HwSeal {
+        /// PKCS#11 module library, e.g. /usr/lib/libykcs11.so
+        #[arg(long)]
+        module: PathBuf,
+
+        /// Label of the token RSA key pair to wrap under
+        #[arg(long, default_value = "dkls-share-wrap")]
+        key_label: String,
+    },
+
+    /// Approve restoration of an escrowed backup as a guardian
+    GuardianApprove {
+        /// Guardian secret key file written by guardian-keygen
+        #[arg(short, long)]
+        key: PathBuf,
+
+        /// Directory containing the escrowed backup's .payload frames
+        #[arg(short, long)]
+        in_dir: PathBuf,
+
+        /// Where to write the approval file
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+enum AuditCommands {
+    /// Walk the hash chain and report any tampering
+    Verify,
+}
+
+#[derive(Subcommand, Clone)]
+enum SessionCommands {
+    /// Announce a new session under a tag for the other parties to join
+    Create {
+        /// Tag the parties agreed on out of band (any short string)
+        #[arg(long)]
+        tag: String,
+
+        /// Number of parties
+        #[arg(short, long)]
+        n: usize,
+
+        /// Threshold (t-of-n)
+        #[arg(short, long)]
+        t: usize,
+
+        /// Participating party IDs, comma-separated (defaults to 0..n)
+        #[arg(short, long)]
+        parties: Option<String>,
+    },
+
+    /// Fetch the announcement for a tag and register as joined
+    Join {
+        /// Tag the session was created under
+        #[arg(long)]
+        tag: String,
+    },
+
+    /// Show a session's parameters and which parties have joined
+    Status {
+        /// Tag the session was created under
+        #[arg(long)]
+        tag: String,
+    },
 }
 
 #[tokio::main]
@@ -83,95 +582,1107 @@ async fn main() -> Result<()> {
         )
         .init();
 
-    let cli = Cli::parse();
+    // The config file only supplies defaults, injected into clap before
+    // parsing so environment variables and flags still win
+    let config = config::load()?;
+    let mut cmd = <Cli as clap::CommandFactory>::command();
+    if let Some(ref relay) = config.relay {
+        cmd = cmd.mut_arg("relay", |arg| arg.default_value(relay.clone()));
+    }
+    if let Some(party_id) = config.party_id {
+        cmd = cmd.mut_arg("party_id", |arg| {
+            arg.default_value(party_id.to_string()).required(false)
+        });
+    }
+    if let Some(ref dest) = config.dest {
+        cmd = cmd.mut_arg("dest", |arg| arg.default_value(dest.clone().into_os_string()));
+    }
+    if let Some(ref log) = config.transparency_log {
+        cmd = cmd.mut_arg("transparency_log", |arg| {
+            arg.default_value(log.clone().into_os_string())
+        });
+    }
+    let mut cli = <Cli as clap::FromArgMatches>::from_arg_matches(&cmd.get_matches())
+        .unwrap_or_else(|e| e.exit());
+    cli.config = config;
+
+    // A passphrase file stands in for the flag on hosts where the
+    // environment is visible to other processes
+    if cli.share_passphrase.is_none() {
+        if let Some(ref path) = cli.passphrase_file {
+            let text = std::fs::read_to_string(path)?;
+            cli.share_passphrase = Some(text.lines().next().unwrap_or("").to_string());
+        }
+    }
+
+    // A named wallet scopes every file the node touches to its own
+    // directory; without one the flat layout stays where it always was
+    cli.wallets_root = cli.dest.join("wallets");
+    if let Some(ref wallet) = cli.wallet {
+        if wallet.contains(['/', '\\']) || wallet == "." || wallet == ".." {
+            anyhow::bail!("Wallet names must not contain path separators");
+        }
+        cli.dest = cli.wallets_root.join(wallet);
+    }
+
+    // Every invocation carries a trace ID so audit records, relay session
+    // metadata and the final signature can be tied back to one request
+    let trace_id = cli
+        .trace_id
+        .clone()
+        .unwrap_or_else(|| hex::encode(rand::random::<[u8; 8]>()));
+    let span = tracing::info_span!("request", trace_id = %trace_id);
+    let _guard = span.enter();
 
     // Ensure data directory exists
     std::fs::create_dir_all(&cli.dest)?;
 
-    let relay = RelayClient::new(&cli.relay, cli.party_id);
+    // Quarantines are only ever lifted by an explicit operator decision
+    if cli.force_unquarantine {
+        lift_quarantine(&cli)?;
+    }
+
+    // When run under a service manager (systemd Type=notify or a Windows
+    // service wrapper), report readiness and heartbeat while a ceremony
+    // is in flight
+    msg_relay::service::notify_ready();
+    msg_relay::service::spawn_watchdog();
 
     match cli.command {
-        Commands::Keygen { n, t } => {
-            run_keygen(&cli, &relay, n, t).await?;
+        Commands::Derive { ref path, ref label } => {
+            run_derive(&cli, path.as_deref(), label.as_deref())?;
+        }
+        Commands::Info => {
+            show_info(&cli)?;
         }
-        Commands::Refresh => {
-            run_refresh(&cli, &relay).await?;
+        Commands::ExportKey {
+            ref shares,
+            ref format,
+        } => {
+            run_export_key(&cli, shares, format)?;
         }
-        Commands::Sign { ref message, ref parties } => {
-            run_sign(&cli, &relay, message, parties).await?;
+        Commands::Preflight { json } => {
+            run_preflight_command(&cli, json, &trace_id).await?;
         }
-        Commands::Derive { ref path } => {
-            run_derive(&cli, path)?;
+        Commands::Replay { ref file } => {
+            run_replay(file)?;
         }
-        Commands::Info => {
-            show_info(&cli)?;
+        Commands::ExportFlush { ref webhook } => {
+            run_export_flush(&cli, webhook).await?;
+        }
+        Commands::ExportQr {
+            ref input,
+            ref out_dir,
+            ref passphrase,
+            chunk_size,
+        } => {
+            run_export_qr(input, out_dir, passphrase.as_deref(), chunk_size)?;
+        }
+        Commands::ImportQr {
+            ref in_dir,
+            ref output,
+            ref passphrase,
+            ref approval,
+        } => {
+            run_import_qr(&cli, in_dir, output, passphrase.as_deref(), approval)?;
+        }
+        Commands::GuardianKeygen { ref output } => {
+            run_guardian_keygen(output)?;
+        }
+        Commands::KdfBenchmark { target_ms, apply } => {
+            run_kdf_benchmark(&cli, target_ms, apply)?;
+        }
+        Commands::Address { ref network } => {
+            run_address(&cli, network)?;
+        }
+        Commands::Verify {
+            ref message,
+            ref signature,
+            ref pubkey,
+        } => {
+            run_verify(&cli, message, signature, pubkey.as_deref())?;
+        }
+        Commands::Wallets => {
+            run_wallets(&cli)?;
+        }
+        Commands::ExportShare {
+            ref output,
+            plaintext,
+        } => {
+            run_export_share(&cli, output.as_deref(), plaintext)?;
+        }
+        Commands::Backup {
+            threshold,
+            fragments,
+            ref out_dir,
+        } => {
+            run_backup(&cli, threshold, fragments, out_dir.as_deref())?;
+        }
+        Commands::Restore { ref fragments } => {
+            run_restore(&cli, fragments)?;
+        }
+        Commands::Serve { ref listen } => {
+            server::run_serve(&cli, listen).await?;
+        }
+        Commands::TranslogVerify { ref log } => {
+            let log_path = log
+                .as_ref()
+                .or(cli.transparency_log.as_ref())
+                .ok_or_else(|| anyhow::anyhow!("No log given; pass --log or --transparency-log"))?;
+            translog::run_verify(log_path)?;
+        }
+        Commands::RelayRotate {
+            ref new_url,
+            ref new_token,
+            drain_timeout,
+        } => {
+            run_relay_rotate(&cli, new_url, new_token.as_deref(), drain_timeout).await?;
+        }
+        Commands::GuardianApprove {
+            ref key,
+            ref in_dir,
+            ref output,
+        } => {
+            run_guardian_approve(key, in_dir, output)?;
+        }
+        Commands::Report {
+            ref output,
+            ref capture,
+        } => {
+            run_report(&cli, output.as_deref(), capture.as_deref())?;
+        }
+        Commands::Session { ref action } => {
+            run_session(&cli, action).await?;
+        }
+        Commands::Audit { ref action } => match action {
+            AuditCommands::Verify => audit::run_verify(&audit_log_path(&cli))?,
+        },
+        #[cfg(feature = "tui")]
+        Commands::Tui => {
+            tui::run(&cli).await?;
+        }
+        #[cfg(feature = "hardware-token")]
+        Commands::HwSeal {
+            ref module,
+            ref key_label,
+        } => {
+            hsm::run_seal(&cli, module, key_label)?;
+        }
+        // Commands below talk to a relay: HTTP by default, or the
+        // file-based sneakernet relay when --outbox/--inbox are given.
+        // Either transport is metered so traffic can be reported and
+        // checked against a bandwidth budget.
+        ref command => {
+            let mut stats = RelayStats::new();
+            if let Some(budget) = cli.bandwidth_budget {
+                stats = stats.with_budget(budget);
+            }
+
+            match (&cli.outbox, &cli.inbox) {
+                (Some(outbox), Some(inbox)) => {
+                    let mut relay = FileRelay::new(cli.party_id, outbox, inbox)
+                        .map_err(|e| anyhow::anyhow!(e))?;
+                    if cli.await_files {
+                        relay = relay.with_await_prompt();
+                    }
+                    let relay = MeteredRelay::new(relay, stats.clone());
+                    let result = run_relay_command(&cli, command, &relay, &trace_id).await;
+                    quarantine_if_self_blamed(&cli, &result);
+                    result?;
+                }
+                _ => {
+                    let settings = load_relay_settings(&cli)?;
+                    let mut relay =
+                        RelayClient::new(&settings.url, cli.party_id).with_trace_id(&trace_id);
+                    if let Some(secs) = cli.config.relay_timeout_secs {
+                        relay = relay.with_timeout(std::time::Duration::from_secs(secs));
+                    }
+                    if let Some(ref token) = settings.token {
+                        relay = relay.with_auth_token(token)?;
+                    }
+                    // A signing subset like {0,3,7} is not the dense range
+                    // the client's collect loops assume by default
+                    if let Commands::Sign { ref parties, .. }
+                    | Commands::Presign { ref parties, .. }
+                    | Commands::SignBatch { ref parties, .. }
+                    | Commands::SignTypedData { ref parties, .. }
+                    | Commands::SignPsbt { ref parties, .. } = command
+                    {
+                        relay = relay.with_parties(&parse_parties(parties)?);
+                    }
+                    if let Some(ref capture) = cli.capture {
+                        relay = relay.with_capture(capture)?;
+                    }
+                    let relay = MeteredRelay::new(relay, stats.clone());
+                    let result = run_relay_command(&cli, command, &relay, &trace_id).await;
+                    // An identifiable abort against an HTTP relay leaves
+                    // conflict evidence behind; pull it into a report
+                    // before surfacing the error
+                    if let Err(ref error) = result {
+                        if let Some(dkls23_core::Error::MaliciousParty(party)) =
+                            error.downcast_ref::<dkls23_core::Error>()
+                        {
+                            write_equivocation_report(&cli, &settings, *party, &trace_id)
+                                .await;
+                        }
+                    }
+                    quarantine_if_self_blamed(&cli, &result);
+                    result?;
+                }
+            }
+
+            if cli.timings {
+                print_traffic_summary(&stats);
+            }
         }
     }
 
+    msg_relay::service::notify_stopping();
     Ok(())
 }
 
-async fn run_keygen(cli: &Cli, relay: &RelayClient, n: usize, t: usize) -> Result<()> {
+/// Dispatch the relay-backed subcommands over any relay implementation
+async fn run_relay_command<R: Relay>(
+    cli: &Cli,
+    command: &Commands,
+    relay: &R,
+    trace_id: &str,
+) -> Result<()> {
+    match command {
+        Commands::Keygen {
+            n,
+            t,
+            count,
+            session,
+        } => run_keygen(cli, relay, *n, *t, *count, session.as_deref()).await,
+        Commands::Refresh => run_refresh(cli, relay).await,
+        Commands::AddParty {
+            dealers,
+            new_party,
+            join,
+        } => run_add_party(cli, relay, dealers, *new_party, *join).await,
+        Commands::Reshare {
+            dealers,
+            new_parties,
+            new_t,
+            join,
+            expect_pubkey,
+        } => {
+            let expect = expect_pubkey.as_deref();
+            run_reshare(cli, relay, dealers, new_parties, *new_t, *join, expect).await
+        }
+        Commands::Presign { count, parties } => run_presign(cli, relay, *count, parties).await,
+        Commands::Sign {
+            message,
+            eth_message,
+            use_presig,
+            session,
+            parties,
+            webhook,
+        } => {
+            // clap guarantees exactly one of the two is present
+            let digest_hex = match (message, eth_message) {
+                (Some(message), _) => message.clone(),
+                (None, Some(text)) => {
+                    let digest = dkls23_core::eth::personal_sign_hash(text.as_bytes());
+                    info!(digest = %hex::encode(digest), "EIP-191 message hashed");
+                    hex::encode(digest)
+                }
+                (None, None) => unreachable!("clap enforces message or eth-message"),
+            };
+            let presig = use_presig.as_deref();
+            let session = session.as_deref();
+            run_sign(
+                cli, relay, &digest_hex, parties, presig, session, webhook, trace_id,
+            )
+            .await
+        }
+        Commands::SignBatch { file, parties } => run_sign_batch(cli, relay, file, parties).await,
+        Commands::SignTypedData {
+            file,
+            parties,
+            webhook,
+        } => run_sign_typed_data(cli, relay, file, parties, webhook, trace_id).await,
+        Commands::SignPsbt { file, parties } => run_sign_psbt(cli, relay, file, parties).await,
+        _ => unreachable!("non-relay command dispatched to relay handler"),
+    }
+}
+
+/// Record an equivocation report after an identifiable abort
+///
+/// When a ceremony aborts blaming a party, the relay may hold the hard
+/// evidence: conflict events pairing the content hashes of the two
+/// contradictory payloads the party posted under one message ID. This
+/// pulls every conflict the relay has recorded and writes it alongside
+/// the abort details to `equivocation.<party>.json`, so the operator has
+/// a self-contained dossier to take to the other participants. Best
+/// effort — a report failure must not mask the abort itself.
+async fn write_equivocation_report(
+    cli: &Cli,
+    settings: &RelaySettings,
+    offending_party: usize,
+    trace_id: &str,
+) {
+    let client = RelayClient::new(&settings.url, cli.party_id);
+    let client = match settings.token.as_deref() {
+        Some(token) => match client.with_auth_token(token) {
+            Ok(client) => client,
+            Err(_) => return,
+        },
+        None => client,
+    };
+
+    let mut conflicts = Vec::new();
+    if let Ok(sessions) = client.sessions().await {
+        for summary in sessions {
+            let Ok(decoded) = hex::decode(&summary.session_id) else {
+                continue;
+            };
+            let Ok(session_id) = <[u8; 32]>::try_from(decoded) else {
+                continue;
+            };
+            if let Ok(events) = client.conflicts(&session_id).await {
+                conflicts.extend(events);
+            }
+        }
+    }
+
+    let report = serde_json::json!({
+        "reported_at": chrono::Utc::now().to_rfc3339(),
+        "reporter": cli.party_id,
+        "offending_party": offending_party,
+        "trace_id": trace_id,
+        "relay": settings.url,
+        "conflicts": conflicts,
+    });
+
+    let path = cli.dest.join(format!("equivocation.{}.json", offending_party));
+    match serde_json::to_vec_pretty(&report)
+        .map_err(std::io::Error::other)
+        .and_then(|bytes| std::fs::write(&path, bytes))
+    {
+        Ok(()) => {
+            tracing::warn!(
+                offending_party,
+                report = %path.display(),
+                "Equivocation report written"
+            );
+            eprintln!(
+                "Party {} equivocated; evidence written to {}",
+                offending_party,
+                path.display()
+            );
+        }
+        Err(e) => tracing::warn!(error = %e, "Failed to write equivocation report"),
+    }
+}
+
+async fn run_keygen<R: Relay>(
+    cli: &Cli,
+    relay: &R,
+    n: usize,
+    t: usize,
+    count: usize,
+    session: Option<&str>,
+) -> Result<()> {
+    // Settle the passphrase before the ceremony, so the new share never
+    // touches disk unencrypted by accident on an interactive run
+    if cli.share_passphrase.is_none() {
+        prompted_passphrase(true)?;
+    }
+
     info!(
         party_id = cli.party_id,
         n_parties = n,
         threshold = t,
+        count,
         "Starting DKG"
     );
 
-    let config = SessionConfig::new(n, t, cli.party_id)?;
-    let key_share = keygen::run_dkg(&config, relay).await?;
+    let mut config = SessionConfig::new(n, t, cli.party_id)?;
+    if let Some(session) = session {
+        // A negotiated session ID replaces the random one, so parties
+        // started independently end up in the same ceremony
+        config.session_id = parse_session_id(session)?;
+    }
 
-    // Save key share
-    let key_share_path = cli.dest.join(format!("keyshare.{}.json", cli.party_id));
-    let json = serde_json::to_string_pretty(&key_share)?;
-    std::fs::write(&key_share_path, json)?;
+    let participants: Vec<usize> = (0..n).collect();
+    if count == 1 {
+        let key_share = keygen::run_dkg(&config, relay).await?;
+        save_key_share(cli, &key_share, None)?;
+        publish_to_translog(cli, "dkg", &key_share)?;
+        audit_record(
+            cli,
+            "keygen",
+            Some(hex::encode(config.session_id)),
+            &participants,
+            None,
+            hex::encode(&key_share.public_key),
+        )?;
+        println!("Public Key: {}", hex::encode(&key_share.public_key));
+        return Ok(());
+    }
 
-    info!(
-        public_key = hex::encode(&key_share.public_key),
-        path = ?key_share_path,
-        "DKG completed, key share saved"
-    );
+    // Batch mode: save each key share as it completes
+    let mut instances = std::pin::pin!(keygen::run_dkg_batch(&config, count, relay));
+    let mut completed = 0usize;
+    while let Some(result) = instances.next().await {
+        let (index, key_share) = result?;
+        save_key_share(cli, &key_share, Some(index))?;
+        publish_to_translog(cli, "dkg", &key_share)?;
+        audit_record(
+            cli,
+            "keygen",
+            Some(hex::encode(config.session_id)),
+            &participants,
+            None,
+            hex::encode(&key_share.public_key),
+        )?;
+        println!("Public Key {}: {}", index, hex::encode(&key_share.public_key));
+        completed += 1;
+    }
 
-    // Print public key
-    println!("Public Key: {}", hex::encode(&key_share.public_key));
+    info!(count = completed, "Batch DKG completed");
 
     Ok(())
 }
 
-async fn run_refresh(cli: &Cli, relay: &RelayClient) -> Result<()> {
-    let key_share = load_key_share(cli)?;
+/// File in the data directory recording the configured KDF parameters
+const KDF_PARAMS_FILE: &str = "kdf.json";
 
-    info!(
-        party_id = cli.party_id,
-        "Starting key refresh"
-    );
+/// Effective share-wrapping parameters: the persisted file if present,
+/// else the safe defaults
+fn load_kdf_params(cli: &Cli) -> Result<wrap::KdfParams> {
+    let path = cli.dest.join(KDF_PARAMS_FILE);
+    if path.exists() {
+        let params: wrap::KdfParams = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+        Ok(params)
+    } else {
+        Ok(wrap::KdfParams::default())
+    }
+}
 
-    let config = SessionConfig::new(
-        key_share.n_parties,
-        key_share.threshold,
-        cli.party_id,
-    )?;
+/// Persist KDF parameters atomically (write a temp file, then rename)
+fn store_kdf_params(cli: &Cli, params: &wrap::KdfParams) -> Result<()> {
+    let path = cli.dest.join(KDF_PARAMS_FILE);
+    let tmp = cli.dest.join(format!("{}.tmp", KDF_PARAMS_FILE));
+    std::fs::write(&tmp, serde_json::to_vec_pretty(params)?)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(())
+}
 
-    let new_key_share = keygen::run_key_refresh(&config, &key_share, relay).await?;
+/// Benchmark Argon2 parameters on this host and optionally persist the
+/// recommendation
+fn run_kdf_benchmark(cli: &Cli, target_ms: u64, apply: bool) -> Result<()> {
+    let target = std::time::Duration::from_millis(target_ms);
+    println!("Benchmarking share-wrapping KDF (target {} ms per unlock)", target_ms);
 
-    // Save new key share
-    let key_share_path = cli.dest.join(format!("keyshare.{}.json", cli.party_id));
-    let json = serde_json::to_string_pretty(&new_key_share)?;
-    std::fs::write(&key_share_path, json)?;
+    let (points, recommended) = wrap::calibrate(target)?;
+    println!("{:>12} {:>11} {:>13} {:>11}", "memory", "iterations", "parallelism", "elapsed");
+    for point in &points {
+        println!(
+            "{:>8} MiB {:>11} {:>13} {:>8} ms",
+            point.params.memory_kib / 1024,
+            point.params.iterations,
+            point.params.parallelism,
+            point.elapsed.as_millis()
+        );
+    }
+    println!(
+        "Recommended: {} MiB, {} iterations, {} lane(s)",
+        recommended.memory_kib / 1024,
+        recommended.iterations,
+        recommended.parallelism
+    );
 
-    info!("Key refresh completed");
+    if apply {
+        store_kdf_params(cli, &recommended)?;
+        println!(
+            "Parameters saved to {}; shares are re-wrapped on next load",
+            cli.dest.join(KDF_PARAMS_FILE).display()
+        );
+    } else {
+        println!("Run again with --apply to persist them");
+    }
+    Ok(())
+}
 
+/// Publish a completed ceremony to the transparency log, when configured
+fn publish_to_translog(cli: &Cli, ceremony: &str, key_share: &KeyShare) -> Result<()> {
+    if let Some(log_path) = &cli.transparency_log {
+        let key_path = cli.dest.join("translog.key");
+        let tree_size = translog::publish(
+            log_path,
+            &key_path,
+            ceremony,
+            &key_share.public_key,
+            &key_share.transcript_digest,
+        )?;
+        info!(ceremony, tree_size, "Ceremony published to transparency log");
+    }
     Ok(())
 }
 
-async fn run_sign(
-    cli: &Cli,
-    relay: &RelayClient,
-    message: &str,
-    parties_str: &str,
-) -> Result<()> {
-    let key_share = load_key_share(cli)?;
+/// The vault configured by --share-passphrase, if any
+/// Passphrase typed at an interactive prompt, cached so one invocation
+/// never asks twice
+static PROMPTED_PASSPHRASE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Ask for the share passphrase on the controlling terminal
+///
+/// Returns `None` when stdin is not a terminal (scripted runs keep the
+/// explicit-flag behavior) or when the operator answers with an empty
+/// line, which means "store plaintext". With `confirm` the passphrase is
+/// typed twice, for flows that set it rather than recall it.
+fn prompted_passphrase(confirm: bool) -> Result<Option<String>> {
+    use std::io::IsTerminal;
+    if let Some(cached) = PROMPTED_PASSPHRASE.get() {
+        return Ok(Some(cached.clone()));
+    }
+    if !std::io::stdin().is_terminal() {
+        return Ok(None);
+    }
+    let passphrase = rpassword::prompt_password("Share passphrase (empty for none): ")?;
+    if passphrase.is_empty() {
+        return Ok(None);
+    }
+    if confirm {
+        let again = rpassword::prompt_password("Confirm passphrase: ")?;
+        if passphrase != again {
+            anyhow::bail!("Passphrases do not match");
+        }
+    }
+    Ok(Some(PROMPTED_PASSPHRASE.get_or_init(|| passphrase).clone()))
+}
+
+fn share_vault(cli: &Cli) -> Result<Option<wrap::KeyShareVault>> {
+    let passphrase = cli
+        .share_passphrase
+        .clone()
+        .or_else(|| PROMPTED_PASSPHRASE.get().cloned());
+    match passphrase {
+        Some(passphrase) => Ok(Some(wrap::KeyShareVault::new(
+            passphrase,
+            load_kdf_params(cli)?,
+        ))),
+        None => Ok(None),
+    }
+}
+
+fn audit_log_path(cli: &Cli) -> PathBuf {
+    cli.dest.join(format!("audit.{}.jsonl", cli.party_id))
+}
+
+/// Append one operation to this party's audit log
+///
+/// Compliance records must not silently go missing, so append failures
+/// abort the command reporting success.
+fn audit_record(
+    cli: &Cli,
+    operation: &str,
+    session_id: Option<String>,
+    participants: &[usize],
+    message: Option<String>,
+    result: String,
+) -> Result<()> {
+    audit::record(
+        &audit_log_path(cli),
+        operation,
+        session_id,
+        participants,
+        message,
+        result,
+    )
+}
+
+/// The storage backend selected by --share-store
+fn key_share_store(cli: &Cli) -> Result<Box<dyn store::KeyShareStore>> {
+    store::open(&cli.share_store, &cli.dest)
+}
+
+/// Storage entry name for a share path: its file name, so the file and
+/// keychain backends agree on naming
+fn entry_name(path: &Path) -> Result<String> {
+    Ok(path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid key share path"))?
+        .to_string_lossy()
+        .into_owned())
+}
+
+/// Write a stored share, through the vault when one is configured
+///
+/// The stored share carries an integrity tag so corruption at rest is
+/// caught at load time rather than at signing time.
+fn write_key_share(cli: &Cli, path: &Path, key_share: &KeyShare) -> Result<()> {
+    let mut sealed = key_share.clone();
+    sealed.seal_integrity();
+    let json = serde_json::to_string_pretty(&sealed)?;
+    let content = match share_vault(cli)? {
+        Some(vault) => vault.encrypt_to_string(json.as_bytes())?,
+        None => json,
+    };
+    key_share_store(cli)?.save(&entry_name(path)?, &content)
+}
+
+fn save_key_share(cli: &Cli, key_share: &KeyShare, index: Option<usize>) -> Result<()> {
+    let file_name = match index {
+        Some(index) => format!("keyshare.{}.{}.json", cli.party_id, index),
+        None => format!("keyshare.{}.json", cli.party_id),
+    };
+    let key_share_path = cli.dest.join(file_name);
+    write_key_share(cli, &key_share_path, key_share)?;
+
+    info!(
+        public_key = hex::encode(&key_share.public_key),
+        path = ?key_share_path,
+        "Key share saved"
+    );
+
+    Ok(())
+}
+
+async fn run_refresh<R: Relay>(cli: &Cli, relay: &R) -> Result<()> {
+    let key_share = load_key_share(cli)?;
+
+    info!(
+        party_id = cli.party_id,
+        "Starting key refresh"
+    );
+
+    let config = SessionConfig::new(
+        key_share.n_parties,
+        key_share.threshold,
+        cli.party_id,
+    )?;
+
+    let new_key_share = keygen::run_key_refresh(&config, &key_share, relay).await?;
+
+    save_key_share(cli, &new_key_share, None)?;
+    publish_to_translog(cli, "refresh", &new_key_share)?;
+    audit_record(
+        cli,
+        "refresh",
+        Some(hex::encode(config.session_id)),
+        &(0..key_share.n_parties).collect::<Vec<_>>(),
+        None,
+        hex::encode(&new_key_share.public_key),
+    )?;
+
+    info!("Key refresh completed");
+
+    Ok(())
+}
+
+/// Re-deal the key to a new committee over the relay
+///
+/// Dealers keep their DKG-era relay IDs; parties joining with no share
+/// run with --join and should pin the group key with --expect-pubkey. A
+/// dealer left off the new committee gets no new share — its old one
+/// stays valid until the old committee deletes it, which is the actual
+/// revocation step.
+async fn run_reshare<R: Relay>(
+    cli: &Cli,
+    relay: &R,
+    dealers_str: &str,
+    new_parties_str: &str,
+    new_t: usize,
+    join: bool,
+    expect_pubkey: Option<&str>,
+) -> Result<()> {
+    let dealers = parse_parties(dealers_str)?;
+    let receivers = parse_parties(new_parties_str)?;
+
+    // All participants derive the same session ID from the ceremony
+    // parameters, so no out-of-band coordination is needed
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"dkls-party reshare session v1");
+    for &dealer in &dealers {
+        hasher.update(&(dealer as u64).to_be_bytes());
+    }
+    for &receiver in &receivers {
+        hasher.update(&(receiver as u64).to_be_bytes());
+    }
+    hasher.update(&(new_t as u64).to_be_bytes());
+    let session_id = *hasher.finalize().as_bytes();
+
+    let config = keygen::ReshareConfig {
+        session_id,
+        dealers,
+        receivers,
+        new_threshold: new_t,
+        party_id: cli.party_id,
+        expected_public_key: expect_pubkey.map(hex::decode).transpose()?,
+    };
+
+    let old_share = if join { None } else { Some(load_key_share(cli)?) };
+
+    info!(
+        party_id = cli.party_id,
+        dealers = ?config.dealers,
+        receivers = ?config.receivers,
+        new_threshold = new_t,
+        "Starting reshare"
+    );
+
+    match keygen::run_reshare(&config, old_share.as_ref(), relay).await? {
+        Some(new_share) => {
+            save_key_share(cli, &new_share, None)?;
+            publish_to_translog(cli, "reshare", &new_share)?;
+            println!("Public Key: {}", hex::encode(&new_share.public_key));
+            println!(
+                "Committee: {} parties, threshold {}",
+                new_share.n_parties, new_share.threshold
+            );
+        }
+        None => {
+            println!("Resharing complete; this dealer is not on the new committee.");
+            println!("Delete the old share once the new committee confirms its shares.");
+        }
+    }
+
+    Ok(())
+}
+
+/// Final signing output written to disk alongside the printout
+///
+/// Carries the trace ID so a signature found on chain can be pivoted back
+/// to the request trace that produced it.
+#[derive(serde::Serialize)]
+struct SignedResult {
+    r: String,
+    s: String,
+    recovery_id: u8,
+    der: String,
+    trace_id: String,
+}
+
+/// Onboard a new party: dealers provision, the joiner receives
+async fn run_add_party<R: Relay>(
+    cli: &Cli,
+    relay: &R,
+    dealers_str: &str,
+    new_party: usize,
+    join: bool,
+) -> Result<()> {
+    let dealers = parse_parties(dealers_str)?;
+
+    // All participants derive the same session ID from the ceremony
+    // parameters, so no out-of-band coordination is needed
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"dkls-party add-party session v1");
+    for &dealer in &dealers {
+        hasher.update(&(dealer as u64).to_be_bytes());
+    }
+    hasher.update(&(new_party as u64).to_be_bytes());
+    let session_id = *hasher.finalize().as_bytes();
+
+    let config = keygen::AddPartyConfig {
+        session_id,
+        dealers,
+        new_party,
+        party_id: cli.party_id,
+    };
+
+    let key_share = if join {
+        keygen::run_add_party(&config, None, relay).await?
+    } else {
+        let existing = load_key_share(cli)?;
+        keygen::run_add_party(&config, Some(&existing), relay).await?
+    };
+
+    save_key_share(cli, &key_share, None)?;
+    publish_to_translog(cli, "add-party", &key_share)?;
+    println!("Public Key: {}", hex::encode(&key_share.public_key));
+    println!(
+        "Committee: {} parties, threshold {}",
+        key_share.n_parties, key_share.threshold
+    );
+
+    Ok(())
+}
+
+/// Re-drive the outbox of signed results awaiting downstream delivery
+async fn run_export_flush(cli: &Cli, webhooks: &[String]) -> Result<()> {
+    if webhooks.is_empty() {
+        anyhow::bail!("No --webhook endpoints given");
+    }
+    let outbox = export::Outbox::open(&cli.dest.join("outbox"))?;
+    let report = outbox
+        .flush(webhooks, std::time::Duration::from_secs(10))
+        .await?;
+    println!("Delivered: {}", report.delivered);
+    println!("Pending:   {}", report.pending);
+    Ok(())
+}
+
+/// File in the data directory recording the relay endpoint and credential
+const RELAY_SETTINGS_FILE: &str = "relay.json";
+
+/// Persisted relay endpoint and credential
+///
+/// When present in the data directory this takes precedence over
+/// `--relay`, so a completed rotation sticks across invocations without
+/// every caller updating its flags or environment.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct RelaySettings {
+    /// Relay service URL
+    url: String,
+    /// Bearer token, when the relay requires one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+}
+
+/// Effective relay settings: the rotated file if present, else the flags
+fn load_relay_settings(cli: &Cli) -> Result<RelaySettings> {
+    let path = cli.dest.join(RELAY_SETTINGS_FILE);
+    if path.exists() {
+        let settings: RelaySettings = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+        Ok(settings)
+    } else {
+        Ok(RelaySettings {
+            url: cli.relay.clone(),
+            token: cli.config.token.clone(),
+        })
+    }
+}
+
+/// Persist relay settings atomically (write a temp file, then rename)
+fn store_relay_settings(cli: &Cli, settings: &RelaySettings) -> Result<()> {
+    let path = cli.dest.join(RELAY_SETTINGS_FILE);
+    let tmp = cli.dest.join(format!("{}.tmp", RELAY_SETTINGS_FILE));
+    std::fs::write(&tmp, serde_json::to_vec_pretty(settings)?)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// Rotate to a new relay endpoint and/or credential
+///
+/// Verifies the new relay is healthy, waits for in-flight sessions on
+/// the old relay to drain (bounded by `--drain-timeout`), then updates
+/// the persisted settings atomically so every later invocation uses the
+/// new endpoint. The relay's own token rotation keeps the old credential
+/// valid for an overlap window, so nothing mid-ceremony is cut off.
+async fn run_relay_rotate(
+    cli: &Cli,
+    new_url: &str,
+    new_token: Option<&str>,
+    drain_timeout: u64,
+) -> Result<()> {
+    let current = load_relay_settings(cli)?;
+
+    // The new relay must answer before anything switches
+    let mut probe = RelayClient::new(new_url, cli.party_id);
+    if let Some(token) = new_token {
+        probe = probe.with_auth_token(token)?;
+    }
+    probe
+        .health()
+        .await
+        .map_err(|e| anyhow::anyhow!("New relay is not healthy, aborting rotation: {}", e))?;
+
+    // Drain: wait for the old relay's in-flight sessions to finish so no
+    // ceremony is cut off mid-round
+    let mut old = RelayClient::new(&current.url, cli.party_id);
+    if let Some(ref token) = current.token {
+        old = old.with_auth_token(token)?;
+    }
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(drain_timeout);
+    loop {
+        match old.sessions().await {
+            Ok(sessions) if sessions.is_empty() => {
+                info!("Old relay has drained");
+                break;
+            }
+            Ok(sessions) => {
+                if std::time::Instant::now() >= deadline {
+                    tracing::warn!(
+                        in_flight = sessions.len(),
+                        "Drain timeout reached; switching with sessions still in flight"
+                    );
+                    break;
+                }
+                info!(in_flight = sessions.len(), "Waiting for the old relay to drain");
+                tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Old relay is unreachable; nothing to drain");
+                break;
+            }
+        }
+    }
+
+    store_relay_settings(
+        cli,
+        &RelaySettings {
+            url: new_url.to_string(),
+            token: new_token.map(str::to_string),
+        },
+    )?;
+
+    info!(url = %new_url, "Relay settings rotated");
+    println!("Relay rotated to {}", new_url);
+    println!(
+        "Future invocations will use {}",
+        cli.dest.join(RELAY_SETTINGS_FILE).display()
+    );
+    Ok(())
+}
+
+/// Parse a comma-separated signing set like `0,3,7`
+fn parse_parties(parties_str: &str) -> Result<Vec<usize>> {
+    parties_str
+        .split(',')
+        .map(|s| s.trim().parse())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(Into::into)
+}
+
+/// Stored pre-signatures are named by the leading bytes of their session
+/// ID, so every participant files a given pre-signature under the same ID
+fn presig_id(session_id: &[u8; 32]) -> String {
+    hex::encode(&session_id[..8])
+}
+
+/// Path of this party's stored pre-signature with the given ID
+fn presig_path(cli: &Cli, id: &str) -> PathBuf {
+    cli.dest.join(format!("presig.{}.{}.json", cli.party_id, id))
+}
+
+/// Persisted presign counter; strictly increasing and burned before each
+/// ceremony, so a session ID is never reused across runs or crashes
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+struct PresignState {
+    next_index: u64,
+}
+
+/// Run pre-signing ceremonies and store the results on disk
+///
+/// Every listed party must run the same command over the same party set:
+/// session IDs are derived from the key, the party set and a persisted
+/// counter, pairing the ceremonies up without out-of-band coordination
+/// (the same scheme the in-process pool uses). A stored entry is as
+/// sensitive as the key share next to it — each one is good for exactly
+/// one signature.
+async fn run_presign<R: Relay>(
+    cli: &Cli,
+    relay: &R,
+    count: usize,
+    parties_str: &str,
+) -> Result<()> {
+    let key_share = load_key_share(cli)?;
+    let parties = parse_parties(parties_str)?;
+
+    let state_path = cli.dest.join(format!("presign.state.{}.json", cli.party_id));
+    let mut state: PresignState = match std::fs::read_to_string(&state_path) {
+        Ok(json) => serde_json::from_str(&json)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => PresignState::default(),
+        Err(e) => return Err(e.into()),
+    };
+
+    info!(
+        party_id = cli.party_id,
+        participants = ?parties,
+        count,
+        "Starting presign"
+    );
+
+    let mut ids = Vec::with_capacity(count);
+    for _ in 0..count {
+        let index = state.next_index;
+        state.next_index += 1;
+        std::fs::write(&state_path, serde_json::to_string(&state)?)?;
+
+        // All participants derive the same session ID from the ceremony
+        // parameters, as in add-party
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"dkls-party presign session v1");
+        hasher.update(&key_share.public_key);
+        for &party in &parties {
+            hasher.update(&(party as u64).to_be_bytes());
+        }
+        hasher.update(&index.to_be_bytes());
+        let session_id = *hasher.finalize().as_bytes();
+
+        let config = SessionConfig {
+            session_id,
+            n_parties: parties.len(),
+            threshold: key_share.threshold,
+            party_id: cli.party_id,
+            parties: parties.clone(),
+        };
+
+        let pre_sig = sign::pre_signature(&key_share, &config, relay).await?;
+        let id = presig_id(&session_id);
+        std::fs::write(presig_path(cli, &id), serde_json::to_vec(&pre_sig)?)?;
+        ids.push(id);
+    }
+
+    println!("Stored {} pre-signature(s):", ids.len());
+    for id in &ids {
+        println!("  {}", id);
+    }
+    println!("Consume with: sign --use-presig <id>");
+
+    Ok(())
+}
+
+/// Load, validate and burn a stored pre-signature
+///
+/// The file is deleted before signing starts: the nonces are single-use,
+/// and a crash mid-ceremony must burn the entry rather than leave it
+/// around for a second message.
+fn consume_presig(cli: &Cli, id: &str, parties: &[usize]) -> Result<sign::PreSignatureToken> {
+    let path = presig_path(cli, id);
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("No stored pre-signature {}: {}", id, e))?;
+    let pre_sig: sign::PreSignature = serde_json::from_str(&json)?;
+    if pre_sig.parties != parties {
+        anyhow::bail!(
+            "Pre-signature {} belongs to parties {:?}, not {:?}",
+            id,
+            pre_sig.parties,
+            parties
+        );
+    }
+    std::fs::remove_file(&path)?;
+    Ok(sign::PreSignatureToken::new(pre_sig))
+}
+
+/// IDs of this party's stored pre-signatures, oldest first
+///
+/// Sorted by ID so every party consumes its stash in the same order
+/// during a batch.
+fn stored_presig_ids(cli: &Cli) -> Vec<String> {
+    let prefix = format!("presig.{}.", cli.party_id);
+    let Ok(entries) = std::fs::read_dir(&cli.dest) else {
+        return Vec::new();
+    };
+    let mut ids: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter_map(|name| {
+            name.strip_prefix(&prefix)?
+                .strip_suffix(".json")
+                .map(str::to_string)
+        })
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_sign<R: Relay>(
+    cli: &Cli,
+    relay: &R,
+    message: &str,
+    parties_str: &str,
+    use_presig: Option<&str>,
+    session: Option<&str>,
+    webhooks: &[String],
+    trace_id: &str,
+) -> Result<()> {
+    let key_share = load_key_share(cli)?;
 
     // Parse message (expected hex-encoded 32-byte hash)
     let message_bytes: [u8; 32] = hex::decode(message)?
@@ -179,10 +1690,7 @@ async fn run_sign(
         .map_err(|_| anyhow::anyhow!("Message must be 32 bytes"))?;
 
     // Parse parties
-    let parties: Vec<usize> = parties_str
-        .split(',')
-        .map(|s| s.trim().parse())
-        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let parties = parse_parties(parties_str)?;
 
     info!(
         party_id = cli.party_id,
@@ -191,43 +1699,309 @@ async fn run_sign(
         "Starting DSG"
     );
 
-    let signature = sign::run_dsg(&key_share, &message_bytes, &parties, relay).await?;
+    let signature = match use_presig {
+        Some(id) => {
+            let token = consume_presig(cli, id, &parties)?;
+            sign::sign_with_presignature(&key_share, token, &message_bytes, relay).await?
+        }
+        None => match session {
+            Some(session) => {
+                let session_id = parse_session_id(session)?;
+                sign::run_dsg_with_session(&key_share, &message_bytes, &parties, relay, session_id)
+                    .await?
+            }
+            None => sign::run_dsg(&key_share, &message_bytes, &parties, relay).await?,
+        },
+    };
+
+    // Check the assembled signature against the group key before
+    // reporting success; a bad signature here means a faulty ceremony
+    signature.verify(&key_share.public_key, &message_bytes)?;
+
+    audit_record(
+        cli,
+        "sign",
+        session.map(str::to_string),
+        &parties,
+        Some(message.to_string()),
+        hex::encode(signature.to_der()),
+    )?;
 
     info!(
-        r = hex::encode(&signature.r),
-        s = hex::encode(&signature.s),
+        r = hex::encode(signature.r),
+        s = hex::encode(signature.s),
         recovery_id = signature.recovery_id,
         "Signature generated"
     );
 
+    let result = SignedResult {
+        r: hex::encode(signature.r),
+        s: hex::encode(signature.s),
+        recovery_id: signature.recovery_id,
+        der: hex::encode(signature.to_der()),
+        trace_id: trace_id.to_string(),
+    };
+    let result_path = cli.dest.join(format!("signature.{}.json", cli.party_id));
+    std::fs::write(&result_path, serde_json::to_string_pretty(&result)?)?;
+
+    // Push to downstream systems via the durable outbox, so a failed
+    // delivery survives this process and can be retried
+    if !webhooks.is_empty() {
+        let outbox = export::Outbox::open(&cli.dest.join("outbox"))?;
+        outbox.enqueue(&serde_json::to_value(&result)?)?;
+        let report = outbox
+            .flush(webhooks, std::time::Duration::from_secs(10))
+            .await?;
+        if report.pending > 0 {
+            println!(
+                "  {} result(s) still queued in the outbox; retry with export-flush",
+                report.pending
+            );
+        }
+    }
+
     // Print signature
     println!("Signature:");
-    println!("  r: {}", hex::encode(&signature.r));
-    println!("  s: {}", hex::encode(&signature.s));
-    println!("  v: {}", signature.recovery_id);
-    println!("  DER: {}", hex::encode(signature.to_der()));
+    println!("  r: {}", result.r);
+    println!("  s: {}", result.s);
+    println!("  v: {}", result.recovery_id);
+    println!("  DER: {}", result.der);
+    println!("  trace_id: {}", trace_id);
+
+    Ok(())
+}
+
+/// Sign every hash in a file over one relay connection
+///
+/// Stored pre-signatures are consumed first (oldest ID first, so all
+/// parties drain their stash in step), then the batch falls back to full
+/// ceremonies. One JSON line per signature goes to stdout and to
+/// `signatures.{party}.jsonl`. The batch stops at the first failure —
+/// carrying on would leave the parties' ceremony counters out of step.
+async fn run_sign_batch<R: Relay>(
+    cli: &Cli,
+    relay: &R,
+    file: &Path,
+    parties_str: &str,
+) -> Result<()> {
+    let key_share = load_key_share(cli)?;
+    let parties = parse_parties(parties_str)?;
+
+    let mut digests = Vec::new();
+    for (line_no, line) in std::fs::read_to_string(file)?.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let digest: [u8; 32] = hex::decode(line)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Line {}: not a hex-encoded 32-byte hash", line_no + 1)
+            })?;
+        digests.push(digest);
+    }
+    if digests.is_empty() {
+        anyhow::bail!("No hashes found in {}", file.display());
+    }
+
+    let mut presigs: std::collections::VecDeque<String> = stored_presig_ids(cli).into();
+    info!(
+        party_id = cli.party_id,
+        hashes = digests.len(),
+        presignatures = presigs.len(),
+        "Starting batch signing"
+    );
+
+    let mut lines = String::new();
+    for digest in &digests {
+        let signature = match presigs.pop_front() {
+            Some(id) => {
+                let token = consume_presig(cli, &id, &parties)?;
+                sign::sign_with_presignature(&key_share, token, digest, relay).await?
+            }
+            None => sign::run_dsg(&key_share, digest, &parties, relay).await?,
+        };
+        signature.verify(&key_share.public_key, digest)?;
+        audit_record(
+            cli,
+            "sign",
+            None,
+            &parties,
+            Some(hex::encode(digest)),
+            hex::encode(signature.to_der()),
+        )?;
+
+        let line = serde_json::json!({
+            "message": hex::encode(digest),
+            "r": hex::encode(signature.r),
+            "s": hex::encode(signature.s),
+            "recovery_id": signature.recovery_id,
+            "der": hex::encode(signature.to_der()),
+        })
+        .to_string();
+        println!("{}", line);
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+
+    let out_path = cli.dest.join(format!("signatures.{}.jsonl", cli.party_id));
+    std::fs::write(&out_path, lines)?;
+    info!(signatures = digests.len(), path = ?out_path, "Batch complete");
 
     Ok(())
 }
 
-fn run_derive(cli: &Cli, path: &str) -> Result<()> {
+/// Hash an EIP-712 typed-data document and sign the digest
+async fn run_sign_typed_data<R: Relay>(
+    cli: &Cli,
+    relay: &R,
+    file: &Path,
+    parties_str: &str,
+    webhooks: &[String],
+    trace_id: &str,
+) -> Result<()> {
+    let json = std::fs::read_to_string(file)?;
+    let typed_data = dkls23_core::eth::TypedData::from_json(&json)?;
+    let digest = typed_data.signing_hash()?;
+
+    info!(
+        primary_type = typed_data.primary_type,
+        digest = %hex::encode(digest),
+        "Typed data hashed"
+    );
+    run_sign(
+        cli,
+        relay,
+        &hex::encode(digest),
+        parties_str,
+        None,
+        None,
+        webhooks,
+        trace_id,
+    )
+    .await
+}
+
+/// Co-sign a PSBT: run one DSG per P2WPKH input the group key controls
+///
+/// Inputs locked to other keys (or already finalized) are left alone, so
+/// the updated PSBT can continue through a multi-signer workflow. Only
+/// the un-derived group key is matched; inputs under derived child keys
+/// are reported and skipped.
+async fn run_sign_psbt<R: Relay>(
+    cli: &Cli,
+    relay: &R,
+    file: &Path,
+    parties_str: &str,
+) -> Result<()> {
+    use dkls23_core::hashing::{bip143_sighash, SIGHASH_ALL};
+
+    let key_share = load_key_share(cli)?;
+    let parties = parse_parties(parties_str)?;
+    let mut psbt = psbt::Psbt::parse(&std::fs::read(file)?)?;
+
+    let pubkey_hash = dkls23_core::address::hash160(&key_share.public_key);
+    let our_script = psbt::p2wpkh_script(&pubkey_hash);
+    let script_code = psbt::p2wpkh_script_code(&pubkey_hash);
+
+    let mut signed = 0usize;
+    for input_index in 0..psbt.unsigned_tx.inputs.len() {
+        if psbt.is_finalized(input_index)
+            || psbt.has_partial_sig(input_index, &key_share.public_key)
+        {
+            continue;
+        }
+        let Some(utxo) = psbt.witness_utxo(input_index)? else {
+            info!(input_index, "No witness UTXO; skipping input");
+            continue;
+        };
+        if utxo.script_pubkey != our_script {
+            info!(input_index, "Input is not locked to the group key; skipping");
+            continue;
+        }
+
+        let sighash_type = psbt.sighash_type(input_index)?.unwrap_or(SIGHASH_ALL);
+        let digest = bip143_sighash(
+            &psbt.unsigned_tx,
+            input_index,
+            &script_code,
+            utxo.value,
+            sighash_type,
+        )?;
+
+        info!(input_index, digest = %hex::encode(digest), "Signing PSBT input");
+        let signature = sign::run_dsg(&key_share, &digest, &parties, relay).await?;
+        signature.verify(&key_share.public_key, &digest)?;
+
+        let mut der = signature.to_der();
+        der.push(sighash_type);
+        psbt.add_partial_sig(input_index, &key_share.public_key, der);
+        signed += 1;
+    }
+
+    if signed == 0 {
+        anyhow::bail!("No signable P2WPKH inputs for the group key in this PSBT");
+    }
+
+    let out_path = file.with_extension("signed.psbt");
+    std::fs::write(&out_path, psbt.serialize())?;
+    println!(
+        "Signed {} input(s); updated PSBT written to {}",
+        signed,
+        out_path.display()
+    );
+    Ok(())
+}
+
+fn run_derive(cli: &Cli, path: Option<&str>, label: Option<&str>) -> Result<KeyShare> {
     let key_share = load_key_share(cli)?;
 
+    // Resolve the derivation path: explicit, or via the labeled key tree
+    let (path, file_tag) = match (path, label) {
+        (Some(path), None) => (path.to_string(), "derived".to_string()),
+        // A label pinned in the config file beats the key tree, so a
+        // fleet can share fixed assignments without a tree file
+        (None, Some(label)) if cli.config.labels.contains_key(label) => {
+            (cli.config.labels[label].clone(), label.to_string())
+        }
+        (None, Some(label)) => {
+            let mut tree = load_key_tree(cli, &key_share)?;
+            let path = match tree.path(label) {
+                Ok(existing) => existing.to_string(),
+                Err(_) => {
+                    let allocated = tree.allocate(label)?;
+                    save_key_tree(cli, &tree)?;
+                    allocated
+                }
+            };
+            (path, label.to_string())
+        }
+        _ => anyhow::bail!("Specify exactly one of --path or --label"),
+    };
+
     info!(
         party_id = cli.party_id,
-        path = path,
+        path = %path,
         "Deriving child key"
     );
 
-    let derived = key_share.derive_child(path)?;
+    let derived = key_share.derive_child(&path)?;
 
     // Save derived key share
     let derived_path = cli.dest.join(format!(
-        "keyshare.{}.derived.json",
-        cli.party_id
+        "keyshare.{}.{}.json",
+        cli.party_id, file_tag
     ));
-    let json = serde_json::to_string_pretty(&derived)?;
-    std::fs::write(&derived_path, json)?;
+    write_key_share(cli, &derived_path, &derived)?;
+    audit_record(
+        cli,
+        "derive",
+        None,
+        &[],
+        Some(path.clone()),
+        hex::encode(&derived.public_key),
+    )?;
 
     info!(
         public_key = hex::encode(&derived.public_key),
@@ -237,6 +2011,566 @@ fn run_derive(cli: &Cli, path: &str) -> Result<()> {
 
     println!("Derived Public Key: {}", hex::encode(&derived.public_key));
 
+    Ok(derived)
+}
+
+/// Run the pre-flight checks against the HTTP relay and print the report
+async fn run_preflight_command(cli: &Cli, json: bool, trace_id: &str) -> Result<()> {
+    let relay = RelayClient::new(&cli.relay, cli.party_id).with_trace_id(trace_id);
+    let key_share = load_key_share(cli).map_err(|e| format!("Cannot load key share: {}", e));
+    let report = preflight::run_preflight(&relay, cli.party_id, &cli.dest, key_share).await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("Preflight checks:");
+        for check in &report.checks {
+            let tag = match check.status {
+                preflight::CheckStatus::Pass => "PASS",
+                preflight::CheckStatus::Warn => "WARN",
+                preflight::CheckStatus::Blocker => "BLOCK",
+            };
+            println!("  [{:<5}] {:<14} {}", tag, check.name, check.detail);
+        }
+    }
+
+    let blockers = report.blockers();
+    if blockers > 0 {
+        anyhow::bail!(
+            "{} blocker(s) found; resolve them before starting a ceremony",
+            blockers
+        );
+    }
+    info!("Preflight passed with no blockers");
+    Ok(())
+}
+
+/// Announcement posted under a tag's coordination session
+///
+/// Round 0 of the coordination session carries the single announcement;
+/// round 1 collects one presence message per party as they join.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SessionAnnouncement {
+    session_id: String,
+    n_parties: usize,
+    threshold: usize,
+    parties: Vec<usize>,
+    created_by: usize,
+}
+
+/// One party registering under a tag's coordination session
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SessionPresence {
+    party_id: usize,
+}
+
+/// Coordination session for a tag
+///
+/// Every party derives it from the tag alone, so the tag is the only
+/// thing that has to be agreed out of band.
+fn session_tag_id(tag: &str) -> dkls23_core::SessionId {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"dkls-party session tag v1");
+    hasher.update(tag.as_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+/// Parse a hex session ID as negotiated by `session create`
+fn parse_session_id(hex_id: &str) -> Result<dkls23_core::SessionId> {
+    hex::decode(hex_id)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Session ID must be 32 bytes of hex"))
+}
+
+/// HTTP relay client for session coordination, honoring the configured
+/// token and timeout
+fn session_client(cli: &Cli) -> Result<RelayClient> {
+    let settings = load_relay_settings(cli)?;
+    let mut client = RelayClient::new(&settings.url, cli.party_id);
+    if let Some(secs) = cli.config.relay_timeout_secs {
+        client = client.with_timeout(std::time::Duration::from_secs(secs));
+    }
+    if let Some(token) = settings.token.as_deref() {
+        client = client.with_auth_token(token)?;
+    }
+    Ok(client)
+}
+
+/// Read the tag's announcement from the relay, optionally waiting for
+/// the creator to post it
+async fn fetch_announcement(
+    client: &RelayClient,
+    coord: &dkls23_core::SessionId,
+    tag: &str,
+    wait: bool,
+) -> Result<SessionAnnouncement> {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(60);
+    loop {
+        let messages = client.query_messages(coord, 0, None, None).await?;
+        if let Some(message) = messages.first() {
+            return Ok(serde_json::from_slice(&message.payload)?);
+        }
+        if !wait || std::time::Instant::now() >= deadline {
+            anyhow::bail!("No session announced under tag '{}'", tag);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    }
+}
+
+/// Negotiate a shared session ID and participant list through the relay
+///
+/// The creator picks the ceremony session ID and posts it with the
+/// parameters under a coordination session derived from the tag; joiners
+/// fetch it and register their presence, so `status` can show who is
+/// still missing before anyone starts the ceremony itself.
+async fn run_session(cli: &Cli, action: &SessionCommands) -> Result<()> {
+    let client = session_client(cli)?;
+    match action {
+        SessionCommands::Create { tag, n, t, parties } => {
+            let parties = match parties {
+                Some(parties) => parse_parties(parties)?,
+                None => (0..*n).collect(),
+            };
+            if parties.len() != *n {
+                anyhow::bail!("Expected {} parties, got {:?}", n, parties);
+            }
+            if *t < 2 || *t > *n {
+                anyhow::bail!("Invalid threshold {} for {} parties", t, n);
+            }
+
+            let session_id = hex::encode(rand::random::<[u8; 32]>());
+            let announcement = SessionAnnouncement {
+                session_id: session_id.clone(),
+                n_parties: *n,
+                threshold: *t,
+                parties: parties.clone(),
+                created_by: cli.party_id,
+            };
+            let coord = session_tag_id(tag);
+            client.broadcast(&coord, 0, &announcement).await?;
+            client
+                .broadcast(
+                    &coord,
+                    1,
+                    &SessionPresence {
+                        party_id: cli.party_id,
+                    },
+                )
+                .await?;
+
+            info!(tag, session_id = %session_id, "Session announced");
+            println!("Session created under tag '{}'", tag);
+            println!("  session: {}", session_id);
+            println!("  parties: {:?} (threshold {})", parties, t);
+            println!("Others join with: session join --tag {}", tag);
+            Ok(())
+        }
+        SessionCommands::Join { tag } => {
+            let coord = session_tag_id(tag);
+            let announcement = fetch_announcement(&client, &coord, tag, true).await?;
+            if !announcement.parties.contains(&cli.party_id) {
+                anyhow::bail!(
+                    "Party {} is not in the session's participant list {:?}",
+                    cli.party_id,
+                    announcement.parties
+                );
+            }
+            client
+                .broadcast(
+                    &coord,
+                    1,
+                    &SessionPresence {
+                        party_id: cli.party_id,
+                    },
+                )
+                .await?;
+
+            info!(tag, session_id = %announcement.session_id, "Session joined");
+            println!("Joined session under tag '{}'", tag);
+            println!("  session: {}", announcement.session_id);
+            println!(
+                "  parties: {:?} (threshold {})",
+                announcement.parties, announcement.threshold
+            );
+            println!(
+                "Pass --session {} to keygen or sign",
+                announcement.session_id
+            );
+            Ok(())
+        }
+        SessionCommands::Status { tag } => {
+            let coord = session_tag_id(tag);
+            let announcement = fetch_announcement(&client, &coord, tag, false).await?;
+            let joined: std::collections::BTreeSet<usize> = client
+                .query_messages(&coord, 1, None, None)
+                .await?
+                .iter()
+                .filter_map(|m| serde_json::from_slice::<SessionPresence>(&m.payload).ok())
+                .map(|p| p.party_id)
+                .collect();
+
+            println!("Session under tag '{}'", tag);
+            println!("  session: {}", announcement.session_id);
+            println!(
+                "  threshold {} of {} (created by party {})",
+                announcement.threshold, announcement.n_parties, announcement.created_by
+            );
+            for party in &announcement.parties {
+                let status = if joined.contains(party) {
+                    "joined"
+                } else {
+                    "awaited"
+                };
+                println!("  party {}: {}", party, status);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Print the per-round traffic table and totals recorded by the metered
+/// relay during a ceremony
+fn print_traffic_summary(stats: &RelayStats) {
+    let snapshot = stats.snapshot();
+    if snapshot.is_empty() {
+        println!("No relay traffic recorded");
+        return;
+    }
+
+    println!("Relay traffic:");
+    println!("  Round  Peer       Sent          Received");
+    for ((round, peer), counters) in &snapshot {
+        let peer = match peer {
+            Some(peer) => peer.to_string(),
+            None => "*".to_string(),
+        };
+        println!(
+            "  {:<5}  {:<9}  {:>3} msg {:>6} B  {:>3} msg {:>6} B",
+            round,
+            peer,
+            counters.messages_sent,
+            counters.bytes_sent,
+            counters.messages_received,
+            counters.bytes_received,
+        );
+    }
+
+    println!("Round timings:");
+    for (round, duration) in stats.round_durations() {
+        println!("  round {}: {} ms", round, duration.as_millis());
+    }
+
+    let totals = stats.totals();
+    let total_bytes = totals.bytes_sent + totals.bytes_received;
+    println!(
+        "Total: {} bytes sent, {} bytes received ({} bytes on the wire)",
+        totals.bytes_sent, totals.bytes_received, total_bytes
+    );
+    if let Some(budget) = stats.budget_bytes() {
+        let status = if stats.budget_exceeded() {
+            "EXCEEDED"
+        } else {
+            "within budget"
+        };
+        println!("Budget: {} of {} bytes ({})", total_bytes, budget, status);
+    }
+}
+
+fn run_replay(file: &Path) -> Result<()> {
+    use msg_relay_client::capture::read_capture;
+    use std::collections::BTreeMap;
+
+    let envelopes = read_capture(file)?;
+    if envelopes.is_empty() {
+        println!("Capture is empty");
+        return Ok(());
+    }
+
+    let start = envelopes[0].timestamp_ms;
+    println!("Capture: {} envelopes", envelopes.len());
+
+    // Per (session, round) summary in capture order
+    let mut rounds: BTreeMap<(String, u32), (usize, usize)> = BTreeMap::new();
+    for envelope in &envelopes {
+        let entry = rounds
+            .entry((envelope.session_id.clone(), envelope.round))
+            .or_insert((0, 0));
+        entry.0 += 1;
+        entry.1 += envelope.payload.len() / 2;
+    }
+
+    for ((session_id, round), (count, bytes)) in &rounds {
+        println!(
+            "  session {} round {}: {} envelopes, {} bytes",
+            session_id, round, count, bytes
+        );
+    }
+
+    println!("Timeline:");
+    for envelope in &envelopes {
+        println!(
+            "  +{:>6}ms {:?} round {} from {:?} to {:?} tag {} ({} bytes)",
+            envelope.timestamp_ms.saturating_sub(start),
+            envelope.direction,
+            envelope.round,
+            envelope.from,
+            envelope.to,
+            envelope.tag,
+            envelope.payload.len() / 2,
+        );
+    }
+
+    Ok(())
+}
+
+fn run_export_qr(
+    input: &Path,
+    out_dir: &Path,
+    passphrase: Option<&str>,
+    chunk_size: usize,
+) -> Result<()> {
+    let mut payload = std::fs::read(input)?;
+    if let Some(passphrase) = passphrase {
+        payload = qr::encrypt_payload(&payload, passphrase)?;
+    }
+
+    std::fs::create_dir_all(out_dir)?;
+    let frames = qr::encode_frames(&payload, chunk_size)?;
+    let total = frames.len();
+    for (index, frame) in frames.iter().enumerate() {
+        // The .txt file is the displayable QR code; the .payload file is
+        // the exact string a scanner app will produce, kept alongside for
+        // loopback testing and non-camera transfer
+        std::fs::write(
+            out_dir.join(format!("frame-{:03}.txt", index + 1)),
+            qr::render_frame(frame)?,
+        )?;
+        std::fs::write(out_dir.join(format!("frame-{:03}.payload", index + 1)), frame)?;
+    }
+
+    info!(frames = total, out_dir = ?out_dir, "QR export written");
+    println!("Wrote {} QR frames to {}", total, out_dir.display());
+
+    Ok(())
+}
+
+/// Collect the scanned .payload frames from a directory
+fn read_frames(in_dir: &Path) -> Result<Vec<String>> {
+    let mut frames = Vec::new();
+    for entry in std::fs::read_dir(in_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "payload") {
+            frames.push(std::fs::read_to_string(&path)?);
+        }
+    }
+    Ok(frames)
+}
+
+fn run_import_qr(
+    cli: &Cli,
+    in_dir: &Path,
+    output: &Path,
+    passphrase: Option<&str>,
+    approval_files: &[PathBuf],
+) -> Result<()> {
+    let frames = read_frames(in_dir)?;
+    let mut payload = qr::decode_frames(&frames)?;
+
+    // When an escrow policy is configured, m-of-k guardians must have
+    // approved this exact backup before it is released
+    let policy_path = cli.dest.join("escrow-policy.json");
+    if policy_path.exists() {
+        let policy: dkls23_core::escrow::GuardianPolicy =
+            serde_json::from_str(&std::fs::read_to_string(&policy_path)?)?;
+        let mut approvals = Vec::new();
+        for path in approval_files {
+            approvals.push(serde_json::from_str::<dkls23_core::escrow::Approval>(
+                &std::fs::read_to_string(path)?,
+            )?);
+        }
+        let approvers = policy
+            .verify_restore(&payload, &approvals)
+            .map_err(|e| anyhow::anyhow!("Escrow policy not satisfied: {}", e))?;
+
+        // Record who released the backup before touching the plaintext
+        let audit = serde_json::json!({
+            "at": chrono::Utc::now().to_rfc3339(),
+            "backup": fingerprint(&payload),
+            "approvers": approvers.iter().map(hex::encode).collect::<Vec<_>>(),
+        });
+        let mut log = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(cli.dest.join("escrow-audit.log"))?;
+        use std::io::Write;
+        writeln!(log, "{}", audit)?;
+        info!(
+            approvers = approvers.len(),
+            backup = %fingerprint(&payload),
+            "Escrow restore approved by guardians"
+        );
+    }
+
+    if let Some(passphrase) = passphrase {
+        payload = qr::decrypt_payload(&payload, passphrase)?;
+    }
+
+    std::fs::write(output, &payload)?;
+
+    info!(frames = frames.len(), output = ?output, "QR import reassembled");
+    println!(
+        "Reassembled {} bytes from {} frames into {}",
+        payload.len(),
+        frames.len(),
+        output.display()
+    );
+
+    Ok(())
+}
+
+/// Generate and store a guardian keypair for the escrow policy
+fn run_guardian_keygen(output: &Path) -> Result<()> {
+    let key = dkls23_core::escrow::GuardianKey::generate();
+    std::fs::write(output, hex::encode(key.to_bytes()))?;
+    println!("Guardian public key: {}", hex::encode(key.public_key()));
+    println!("Secret key written to {}", output.display());
+    Ok(())
+}
+
+/// Sign off on restoring an escrowed backup as a guardian
+fn run_guardian_approve(key_path: &Path, in_dir: &Path, output: &Path) -> Result<()> {
+    let secret = hex::decode(std::fs::read_to_string(key_path)?.trim())?;
+    let key = dkls23_core::escrow::GuardianKey::from_bytes(&secret)?;
+
+    let frames = read_frames(in_dir)?;
+    let payload = qr::decode_frames(&frames)?;
+
+    let approval = key.approve(&payload);
+    std::fs::write(output, serde_json::to_string_pretty(&approval)?)?;
+    println!(
+        "Approved backup {} as guardian {}",
+        fingerprint(&payload),
+        hex::encode(approval.guardian)
+    );
+    Ok(())
+}
+
+/// Short hex fingerprint of arbitrary bytes, as printed in reports
+fn fingerprint(data: &[u8]) -> String {
+    hex::encode(blake3::hash(data).as_bytes())[..16].to_string()
+}
+
+/// Generate a human-readable ceremony report
+///
+/// Institutional operators file these as part of their key-management
+/// procedures: participants, key fingerprints, the transcript hash of the
+/// captured ceremony traffic, timestamps and software versions, plus a
+/// verification bundle hash over all public key material so independent
+/// parties can confirm they filed reports about the same ceremony.
+fn run_report(cli: &Cli, output: Option<&Path>, capture: Option<&Path>) -> Result<()> {
+    use std::fmt::Write;
+
+    let key_share = load_key_share(cli)?;
+    let mut report = String::new();
+
+    writeln!(report, "# Key Ceremony Report")?;
+    writeln!(report)?;
+    writeln!(report, "- Generated: {}", chrono::Utc::now().to_rfc3339())?;
+    writeln!(report, "- Software: dkls-party {}", env!("CARGO_PKG_VERSION"))?;
+    writeln!(
+        report,
+        "- Protocol version: {}",
+        dkls23_core::PROTOCOL_VERSION
+    )?;
+    writeln!(report)?;
+
+    writeln!(report, "## Key")?;
+    writeln!(report)?;
+    writeln!(report, "- Scheme: {}", key_share.scheme)?;
+    writeln!(
+        report,
+        "- Threshold: {}-of-{}",
+        key_share.threshold, key_share.n_parties
+    )?;
+    writeln!(
+        report,
+        "- Public key: `{}`",
+        hex::encode(&key_share.public_key)
+    )?;
+    writeln!(
+        report,
+        "- Public key fingerprint: `{}`",
+        fingerprint(&key_share.public_key)
+    )?;
+    writeln!(report)?;
+
+    writeln!(report, "## Participants")?;
+    writeln!(report)?;
+    writeln!(report, "| Party | Public share fingerprint |")?;
+    writeln!(report, "|-------|--------------------------|")?;
+    for (party_id, public_share) in key_share.public_shares.iter().enumerate() {
+        let marker = if party_id == key_share.party_id {
+            " (this node)"
+        } else {
+            ""
+        };
+        writeln!(
+            report,
+            "| {}{} | `{}` |",
+            party_id,
+            marker,
+            fingerprint(public_share)
+        )?;
+    }
+    writeln!(report)?;
+
+    writeln!(report, "## Transcript")?;
+    writeln!(report)?;
+    match capture {
+        Some(capture) => {
+            let bytes = std::fs::read(capture)?;
+            writeln!(
+                report,
+                "- Transcript hash: `{}`",
+                hex::encode(blake3::hash(&bytes).as_bytes())
+            )?;
+            writeln!(report, "- Capture file: `{}`", capture.display())?;
+        }
+        None => {
+            writeln!(
+                report,
+                "- No capture file supplied; ceremony traffic was not recorded"
+            )?;
+        }
+    }
+    writeln!(report)?;
+
+    // Hash over every piece of public key material, in a fixed order, so
+    // independently generated reports about the same ceremony agree
+    let mut bundle = blake3::Hasher::new();
+    bundle.update(&key_share.public_key);
+    for public_share in &key_share.public_shares {
+        bundle.update(public_share);
+    }
+    bundle.update(&(key_share.threshold as u64).to_be_bytes());
+    bundle.update(&(key_share.n_parties as u64).to_be_bytes());
+    writeln!(report, "## Verification")?;
+    writeln!(report)?;
+    writeln!(
+        report,
+        "- Verification bundle hash: `{}`",
+        hex::encode(bundle.finalize().as_bytes())
+    )?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &report)?;
+            info!(path = ?path, "Ceremony report written");
+            println!("Report written to {}", path.display());
+        }
+        None => print!("{}", report),
+    }
+
     Ok(())
 }
 
@@ -247,15 +2581,474 @@ fn show_info(cli: &Cli) -> Result<()> {
     println!("  Party ID: {}", key_share.party_id);
     println!("  N Parties: {}", key_share.n_parties);
     println!("  Threshold: {}", key_share.threshold);
+    println!("  Scheme: {}", key_share.scheme);
     println!("  Public Key: {}", hex::encode(&key_share.public_key));
     println!("  Chain Code: {}", hex::encode(&key_share.chain_code));
 
     Ok(())
 }
 
+fn key_tree_path(cli: &Cli) -> PathBuf {
+    cli.dest.join(format!("keytree.{}.json", cli.party_id))
+}
+
+fn load_key_tree(cli: &Cli, master: &KeyShare) -> Result<KeyTree> {
+    let path = key_tree_path(cli);
+    if path.exists() {
+        let json = std::fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&json)?)
+    } else {
+        Ok(KeyTree::new(master))
+    }
+}
+
+fn save_key_tree(cli: &Cli, tree: &KeyTree) -> Result<()> {
+    let json = serde_json::to_string_pretty(tree)?;
+    std::fs::write(key_tree_path(cli), json)?;
+    Ok(())
+}
+
+/// This party's key share files in a directory: the primary
+/// `keyshare.{party}.json` plus any indexed, derived or labeled variants
+fn key_share_files(dir: &Path, party_id: usize) -> Vec<PathBuf> {
+    let stem = format!("keyshare.{}.", party_id);
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&stem) && name.ends_with(".json"))
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Resolve --key-id: the share file whose public key starts with the
+/// given hex prefix; zero or several matches are both errors
+fn find_key_share_by_id(cli: &Cli, prefix: &str) -> Result<PathBuf> {
+    let mut matches = Vec::new();
+    for path in key_share_files(&cli.dest, cli.party_id) {
+        let Ok(share) = load_key_share_from(cli, &path) else {
+            continue;
+        };
+        if hex::encode(&share.public_key).starts_with(prefix) {
+            matches.push(path);
+        }
+    }
+    match matches.len() {
+        0 => anyhow::bail!("No key share with public key prefix {}", prefix),
+        1 => Ok(matches.remove(0)),
+        n => anyhow::bail!(
+            "Key ID prefix {} is ambiguous ({} shares match); give more hex digits",
+            prefix,
+            n
+        ),
+    }
+}
+
 fn load_key_share(cli: &Cli) -> Result<KeyShare> {
-    let key_share_path = cli.dest.join(format!("keyshare.{}.json", cli.party_id));
-    let json = std::fs::read_to_string(&key_share_path)?;
-    let key_share: KeyShare = serde_json::from_str(&json)?;
+    ensure_not_quarantined(cli)?;
+    let key_share_path = match cli.key_id {
+        Some(ref prefix) => find_key_share_by_id(cli, prefix)?,
+        None => cli.dest.join(format!("keyshare.{}.json", cli.party_id)),
+    };
+    let key_share = load_key_share_from(cli, &key_share_path)?;
+
+    // A share whose secret no longer matches its own public commitment
+    // is corrupted; pull it out of rotation instead of letting it feed
+    // garbage into ceremonies
+    if let Err(e) = key_share.verify_consistency() {
+        quarantine_key_share(cli, &format!("consistency check failed: {}", e));
+        anyhow::bail!(
+            "Key share failed its consistency check and has been quarantined: {}",
+            e
+        );
+    }
+    Ok(key_share)
+}
+
+fn quarantine_marker_path(cli: &Cli) -> PathBuf {
+    cli.dest.join(format!("quarantine.{}.json", cli.party_id))
+}
+
+fn quarantined_share_path(cli: &Cli) -> PathBuf {
+    cli.dest
+        .join(format!("keyshare.{}.json.quarantined", cli.party_id))
+}
+
+/// Refuse to touch a quarantined key share
+fn ensure_not_quarantined(cli: &Cli) -> Result<()> {
+    let marker = quarantine_marker_path(cli);
+    if !marker.exists() {
+        return Ok(());
+    }
+    let detail = std::fs::read_to_string(&marker).unwrap_or_default();
+    anyhow::bail!(
+        "Key share for party {} is quarantined; investigate the incident \
+         recorded in {} and re-run with --force-unquarantine to restore it.\n{}",
+        cli.party_id,
+        marker.display(),
+        detail.trim()
+    )
+}
+
+/// Pull this party's key share out of rotation
+///
+/// Moves the share file aside and records why, so every later command
+/// refuses it until an operator lifts the quarantine explicitly. Best
+/// effort — a quarantine failure must not mask the error that caused it.
+fn quarantine_key_share(cli: &Cli, reason: &str) {
+    let marker = serde_json::json!({
+        "quarantined_at": chrono::Utc::now().to_rfc3339(),
+        "party_id": cli.party_id,
+        "reason": reason,
+    });
+    let marker_path = quarantine_marker_path(cli);
+    if let Err(e) = serde_json::to_vec_pretty(&marker)
+        .map_err(std::io::Error::other)
+        .and_then(|bytes| std::fs::write(&marker_path, bytes))
+    {
+        tracing::warn!(error = %e, "Failed to write quarantine marker");
+        return;
+    }
+
+    let share_path = cli.dest.join(format!("keyshare.{}.json", cli.party_id));
+    if share_path.exists() {
+        if let Err(e) = std::fs::rename(&share_path, quarantined_share_path(cli)) {
+            tracing::warn!(error = %e, "Failed to move quarantined key share aside");
+        }
+    }
+
+    tracing::warn!(
+        party_id = cli.party_id,
+        reason,
+        marker = %marker_path.display(),
+        "Key share quarantined"
+    );
+    eprintln!(
+        "Key share for party {} has been QUARANTINED: {}",
+        cli.party_id, reason
+    );
+    eprintln!(
+        "Investigate, then run with --force-unquarantine to restore it."
+    );
+}
+
+/// A ceremony abort blaming this party means local key material may be
+/// the corrupted side; quarantine it rather than let it keep signing
+fn quarantine_if_self_blamed(cli: &Cli, result: &Result<()>) {
+    if let Err(error) = result {
+        if let Some(dkls23_core::Error::MaliciousParty(party)) =
+            error.downcast_ref::<dkls23_core::Error>()
+        {
+            if *party == cli.party_id {
+                quarantine_key_share(cli, "ceremony abort named this party as the cheater");
+            }
+        }
+    }
+}
+
+/// Lift a quarantine after operator investigation
+fn lift_quarantine(cli: &Cli) -> Result<()> {
+    let marker = quarantine_marker_path(cli);
+    if !marker.exists() {
+        anyhow::bail!("No quarantine is in effect for party {}", cli.party_id);
+    }
+
+    let quarantined = quarantined_share_path(cli);
+    if quarantined.exists() {
+        std::fs::rename(
+            &quarantined,
+            cli.dest.join(format!("keyshare.{}.json", cli.party_id)),
+        )?;
+    }
+    std::fs::remove_file(&marker)?;
+
+    tracing::warn!(party_id = cli.party_id, "Quarantine lifted by operator");
+    eprintln!("Quarantine lifted for party {}", cli.party_id);
+    Ok(())
+}
+
+/// Load a key share from an explicit path, decrypting wrapped envelopes
+fn load_key_share_from(cli: &Cli, key_share_path: &Path) -> Result<KeyShare> {
+    let store = key_share_store(cli)?;
+    let name = entry_name(key_share_path)?;
+    let content = store.load(&name)?;
+
+    // Hardware-sealed envelopes name their own token; only the PIN is
+    // asked for here. A build without the feature cannot open them.
+    #[cfg(feature = "hardware-token")]
+    {
+        if let Ok(sealed) = serde_json::from_str::<hsm::HwWrappedShare>(&content) {
+            let pin = hsm::token_pin()?;
+            let key_share: KeyShare = serde_json::from_slice(&hsm::open(&sealed, &pin)?)?;
+            key_share.verify_integrity()?;
+            dkls23_core::scheme::ensure_supported(key_share.scheme)?;
+            return Ok(key_share);
+        }
+    }
+
+    let vault = match share_vault(cli)? {
+        Some(vault) => Some(vault),
+        None if serde_json::from_str::<wrap::WrappedShare>(&content).is_ok() => {
+            // Encrypted share but no passphrase configured: ask at the
+            // terminal before giving up
+            let Some(passphrase) = prompted_passphrase(false)? else {
+                anyhow::bail!(
+                    "Key share is encrypted; pass --share-passphrase or --passphrase-file"
+                );
+            };
+            Some(wrap::KeyShareVault::new(passphrase, load_kdf_params(cli)?))
+        }
+        None => None,
+    };
+
+    let key_share: KeyShare = match vault {
+        Some(vault) => {
+            let (plaintext, upgrade) = vault.decrypt_content(&content)?;
+            // Encrypt legacy plaintext entries on first contact, and
+            // re-wrap ones stored under weaker KDF parameters
+            if upgrade {
+                store.save(&name, &vault.encrypt_to_string(&plaintext)?)?;
+                info!(entry = %store.location(&name), "Key share (re-)wrapped in place");
+            }
+            serde_json::from_slice(&plaintext)?
+        }
+        None => serde_json::from_str(&content)?,
+    };
+
+    key_share.verify_integrity()?;
+    dkls23_core::scheme::ensure_supported(key_share.scheme)?;
     Ok(key_share)
 }
+
+/// Print the group key's chain addresses and master xpub
+///
+/// Pure public-key derivations; nothing secret leaves the share file.
+fn run_address(cli: &Cli, network: &str) -> Result<()> {
+    let key_share = load_key_share(cli)?;
+    let network: dkls23_core::address::Network = network.parse()?;
+
+    println!("Public key: {}", hex::encode(&key_share.public_key));
+    println!("Ethereum:   {}", key_share.eth_address()?);
+    println!("P2WPKH:     {}", key_share.btc_p2wpkh_address(network)?);
+    println!("P2TR:       {}", key_share.btc_p2tr_address(network)?);
+    println!("xpub:       {}", key_share.xpub(network)?);
+    Ok(())
+}
+
+/// Split the key share into Shamir backup fragments
+///
+/// Each fragment lands in its own file for separate custody (and is QR
+/// export material via export-qr); fewer than the backup threshold of
+/// them reveal nothing about the share. This backs up one device's
+/// share, not the key — restoring still leaves the MPC quorum intact.
+fn run_backup(cli: &Cli, threshold: usize, total: usize, out_dir: Option<&Path>) -> Result<()> {
+    let mut share = load_key_share(cli)?;
+    share.seal_integrity();
+    let plaintext = serde_json::to_vec(&share)?;
+    let fragments = backup::split(&plaintext, threshold, total)?;
+
+    let dir = out_dir.unwrap_or(&cli.dest);
+    std::fs::create_dir_all(dir)?;
+    for (i, fragment) in fragments.iter().enumerate() {
+        let path = dir.join(format!("backup.{}.{}.txt", cli.party_id, i + 1));
+        std::fs::write(&path, format!("{}\n", fragment))?;
+        println!("Fragment {}/{}: {}", i + 1, total, path.display());
+    }
+    println!(
+        "Any {} fragments restore this share; store them with separate custodians.",
+        threshold
+    );
+    Ok(())
+}
+
+/// Reassemble the key share from backup fragments and save it
+///
+/// The reassembled bytes must parse as a key share and pass its
+/// integrity and consistency checks before anything is written, so a
+/// wrong or corrupted fragment set cannot replace a good share file.
+fn run_restore(cli: &Cli, fragment_paths: &[PathBuf]) -> Result<()> {
+    let mut lines = Vec::with_capacity(fragment_paths.len());
+    for path in fragment_paths {
+        lines.push(std::fs::read_to_string(path)?.trim().to_string());
+    }
+
+    let plaintext = backup::combine(&lines)?;
+    let share: KeyShare = serde_json::from_slice(&plaintext)?;
+    share.verify_integrity()?;
+    share.verify_consistency()?;
+
+    if share.party_id != cli.party_id {
+        anyhow::bail!(
+            "Fragments restore party {}'s share, not party {}'s",
+            share.party_id,
+            cli.party_id
+        );
+    }
+
+    save_key_share(cli, &share, None)?;
+    println!("Public Key: {}", hex::encode(&share.public_key));
+    println!(
+        "Restored party {} share ({}-of-{})",
+        share.party_id, share.threshold, share.n_parties
+    );
+    Ok(())
+}
+
+/// Write the decrypted key share out, only with explicit consent
+///
+/// The share leaves the vault's protection here — for migration to
+/// another tool or a build without the keystore — so the --plaintext
+/// flag is mandatory, not implied.
+fn run_export_share(cli: &Cli, output: Option<&Path>, plaintext: bool) -> Result<()> {
+    if !plaintext {
+        anyhow::bail!("Refusing to export an unencrypted key share without --plaintext");
+    }
+    let mut share = load_key_share(cli)?;
+    share.seal_integrity();
+    let json = serde_json::to_string_pretty(&share)?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, &json)?;
+            println!("Plaintext share written to {}", path.display());
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+/// List named wallets and this party's key shares in each
+///
+/// The unnamed layout at the root of the data directory is listed first
+/// as `(default)`. Key IDs are the leading bytes of each share's public
+/// key, usable with --key-id.
+fn run_wallets(cli: &Cli) -> Result<()> {
+    let default_dir = cli
+        .wallets_root
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| cli.dest.clone());
+    let mut wallets = vec![("(default)".to_string(), default_dir)];
+    if let Ok(entries) = std::fs::read_dir(&cli.wallets_root) {
+        let mut named: Vec<(String, PathBuf)> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| (entry.file_name().to_string_lossy().into_owned(), entry.path()))
+            .collect();
+        named.sort();
+        wallets.extend(named);
+    }
+
+    for (name, dir) in wallets {
+        let files = key_share_files(&dir, cli.party_id);
+        if files.is_empty() {
+            continue;
+        }
+        println!("{}", name);
+        for path in files {
+            let file = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            match load_key_share_from(cli, &path) {
+                Ok(share) => println!(
+                    "  {:<32} key id {}  {}-of-{}",
+                    file,
+                    hex::encode(&share.public_key[..8]),
+                    share.threshold,
+                    share.n_parties
+                ),
+                Err(e) => println!("  {:<32} unreadable: {}", file, e),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check a signature against the group key without running a ceremony
+///
+/// The signature format is inferred from its length: 64 bytes is compact
+/// r||s, 65 is Ethereum r||s||v, anything else must parse as strict DER.
+fn run_verify(cli: &Cli, message: &str, signature: &str, pubkey: Option<&str>) -> Result<()> {
+    let digest: [u8; 32] = hex::decode(message)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Message must be 32 bytes"))?;
+
+    // A supplied key lets an operator check another committee's output
+    // without holding a share for it
+    let public_key = match pubkey {
+        Some(hex_key) => hex::decode(hex_key)?,
+        None => load_key_share(cli)?.public_key.clone(),
+    };
+
+    let sig_bytes = hex::decode(signature)?;
+    let (sig, format) = match sig_bytes.len() {
+        64 => {
+            let r: [u8; 32] = sig_bytes[..32].try_into().expect("split length");
+            let s: [u8; 32] = sig_bytes[32..].try_into().expect("split length");
+            (dkls23_core::Signature::new(r, s, 0), "compact")
+        }
+        65 => {
+            let (sig, chain_id) = dkls23_core::Signature::from_eth(&sig_bytes)?;
+            if let Some(chain_id) = chain_id {
+                info!(chain_id, "EIP-155 chain ID decoded from v");
+            }
+            (sig, "eth")
+        }
+        _ => (dkls23_core::Signature::from_der_strict(&sig_bytes)?, "der"),
+    };
+
+    sig.verify(&public_key, &digest)?;
+    println!("Signature OK ({} format)", format);
+    println!("  Public key: {}", hex::encode(&public_key));
+    Ok(())
+}
+
+/// Reconstruct and print the private key from a quorum of share files
+///
+/// Break-glass path: the reconstructed key is no longer protected by the
+/// threshold, so this prints a loud warning alongside the key. The core
+/// reconstruction validates the quorum and zeroizes intermediates; the
+/// encoded output strings are zeroized here once printed.
+fn run_export_key(cli: &Cli, share_paths: &[PathBuf], format: &str) -> Result<()> {
+    let shares = share_paths
+        .iter()
+        .map(|path| load_key_share_from(cli, path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let secret = keygen::reconstruct_secret(&shares)?;
+
+    let encoded = zeroize::Zeroizing::new(match format {
+        "hex" => hex::encode(secret.as_slice()),
+        "wif" => encode_wif(&secret),
+        other => anyhow::bail!("Unknown export format '{}' (expected hex or wif)", other),
+    });
+
+    eprintln!("WARNING: the key below is the fully reconstructed private key.");
+    eprintln!("It is no longer threshold-protected; handle it as a hot wallet");
+    eprintln!("secret and destroy every copy once recovery is complete.");
+    println!("Public Key:  {}", hex::encode(&shares[0].public_key));
+    println!("Private Key: {}", *encoded);
+
+    Ok(())
+}
+
+/// Encode a private key in compressed-pubkey WIF (Base58Check, 0x80)
+fn encode_wif(secret: &[u8; 32]) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut payload = zeroize::Zeroizing::new(Vec::with_capacity(38));
+    payload.push(0x80);
+    payload.extend_from_slice(secret);
+    payload.push(0x01);
+
+    let checksum = Sha256::digest(Sha256::digest(&*payload));
+    payload.extend_from_slice(&checksum[..4]);
+    bs58::encode(&*payload).into_string()
+}
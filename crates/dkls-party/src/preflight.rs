@@ -0,0 +1,339 @@
+//! Fail-fast pre-flight checks run before committing to a ceremony
+//!
+//! A multi-party ceremony wastes every participant's time when one node
+//! joins with a broken setup, so `dkls-party preflight` probes the local
+//! and relay-side prerequisites first and reports a structured list of
+//! findings. Blockers mean the ceremony cannot succeed; warnings flag
+//! conditions worth fixing but not fatal on their own.
+
+use dkls23_core::KeyShare;
+use msg_relay_client::RelayClient;
+use serde::Serialize;
+use std::path::Path;
+
+/// How long a peer's announcement may be stale before it counts as absent
+const LIVENESS_WINDOW_SECS: i64 = 300;
+
+/// Clock skew against the relay above which a warning fires
+const CLOCK_SKEW_WARN_SECS: i64 = 30;
+
+/// Bytes the checkpoint write probe asks the data directory for
+const DISK_PROBE_BYTES: usize = 1024 * 1024;
+
+/// Severity of one pre-flight finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CheckStatus {
+    /// Prerequisite satisfied
+    Pass,
+    /// Degraded but the ceremony can still succeed
+    Warn,
+    /// The ceremony cannot succeed until this is fixed
+    Blocker,
+}
+
+/// One pre-flight finding
+#[derive(Debug, Serialize)]
+pub struct PreflightCheck {
+    /// Which prerequisite was probed
+    pub name: &'static str,
+    /// Severity of the finding
+    pub status: CheckStatus,
+    /// Human-readable explanation
+    pub detail: String,
+}
+
+/// Structured result of a pre-flight run
+#[derive(Debug, Default, Serialize)]
+pub struct PreflightReport {
+    /// Findings in the order the checks ran
+    pub checks: Vec<PreflightCheck>,
+}
+
+impl PreflightReport {
+    fn record(&mut self, name: &'static str, status: CheckStatus, detail: String) {
+        self.checks.push(PreflightCheck {
+            name,
+            status,
+            detail,
+        });
+    }
+
+    /// Number of findings that block the ceremony
+    pub fn blockers(&self) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| check.status == CheckStatus::Blocker)
+            .count()
+    }
+}
+
+/// Run every pre-flight check and collect the findings
+///
+/// `key_share` is the share the ceremony would use, if it loaded; a
+/// missing or invalid share is itself a finding, so the caller passes the
+/// load result rather than failing early.
+pub async fn run_preflight(
+    relay: &RelayClient,
+    party_id: usize,
+    dest: &Path,
+    key_share: Result<KeyShare, String>,
+) -> PreflightReport {
+    let mut report = PreflightReport::default();
+
+    let health = check_relay(&mut report, relay).await;
+    check_clock_skew(&mut report, health.as_ref());
+    check_disk_space(&mut report, dest);
+    check_presignatures(&mut report, dest, party_id);
+
+    match key_share {
+        Ok(key_share) => {
+            check_key_share(&mut report, party_id, &key_share);
+            if health.is_some() {
+                check_peer_liveness(&mut report, relay, party_id, &key_share).await;
+            }
+        }
+        Err(detail) => report.record("key share", CheckStatus::Blocker, detail),
+    }
+
+    report
+}
+
+/// Relay reachability: the health endpoint must answer
+async fn check_relay(
+    report: &mut PreflightReport,
+    relay: &RelayClient,
+) -> Option<msg_relay_client::RelayHealth> {
+    match relay.health().await {
+        Ok(health) => {
+            let version = health
+                .body
+                .get("version")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown");
+            report.record(
+                "relay",
+                CheckStatus::Pass,
+                format!("Relay reachable (version {})", version),
+            );
+            Some(health)
+        }
+        Err(e) => {
+            report.record("relay", CheckStatus::Blocker, format!("Relay unreachable: {}", e));
+            None
+        }
+    }
+}
+
+/// Clock skew: compare the relay's Date header against the local clock
+fn check_clock_skew(report: &mut PreflightReport, health: Option<&msg_relay_client::RelayHealth>) {
+    let Some(health) = health else {
+        return;
+    };
+    let Some(parsed) = health
+        .server_date
+        .as_deref()
+        .and_then(|date| chrono::DateTime::parse_from_rfc2822(date).ok())
+    else {
+        report.record(
+            "clock skew",
+            CheckStatus::Warn,
+            "Relay did not return a parseable Date header; skew unknown".to_string(),
+        );
+        return;
+    };
+
+    let skew = (chrono::Utc::now() - parsed.with_timezone(&chrono::Utc)).num_seconds();
+    if skew.abs() > CLOCK_SKEW_WARN_SECS {
+        report.record(
+            "clock skew",
+            CheckStatus::Warn,
+            format!(
+                "Local clock is {} s away from the relay; timestamped envelopes may be rejected",
+                skew.abs()
+            ),
+        );
+    } else {
+        report.record(
+            "clock skew",
+            CheckStatus::Pass,
+            format!("Within {} s of the relay clock", CLOCK_SKEW_WARN_SECS),
+        );
+    }
+}
+
+/// Disk space: the data directory must accept a checkpoint-sized write
+fn check_disk_space(report: &mut PreflightReport, dest: &Path) {
+    let probe_path = dest.join(".preflight-probe");
+    let result = std::fs::write(&probe_path, vec![0u8; DISK_PROBE_BYTES]);
+    let _ = std::fs::remove_file(&probe_path);
+    match result {
+        Ok(()) => report.record(
+            "disk space",
+            CheckStatus::Pass,
+            format!("Data directory accepted a {} KiB probe write", DISK_PROBE_BYTES / 1024),
+        ),
+        Err(e) => report.record(
+            "disk space",
+            CheckStatus::Blocker,
+            format!("Cannot write checkpoints to {}: {}", dest.display(), e),
+        ),
+    }
+}
+
+/// Key share validity: the secret share must match our published point
+fn check_key_share(report: &mut PreflightReport, party_id: usize, key_share: &KeyShare) {
+    if key_share.party_id != party_id {
+        report.record(
+            "key share",
+            CheckStatus::Blocker,
+            format!(
+                "Key share belongs to party {} but this node is party {}",
+                key_share.party_id, party_id
+            ),
+        );
+        return;
+    }
+
+    if let Err(e) = key_share.verify_consistency() {
+        report.record("key share", CheckStatus::Blocker, e.to_string());
+        return;
+    }
+
+    report.record(
+        "key share",
+        CheckStatus::Pass,
+        format!(
+            "{}-of-{} share for key {}",
+            key_share.threshold,
+            key_share.n_parties,
+            hex::encode(&key_share.public_key)
+        ),
+    );
+}
+
+/// Announcement payload posted into the per-key preflight session
+#[derive(Serialize)]
+struct AnnounceMessage {
+    party_id: usize,
+    protocol_version: u32,
+}
+
+/// The well-known session parties announce themselves into before a
+/// ceremony over the given key
+fn announce_session_id(public_key: &[u8]) -> dkls23_core::SessionId {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"dkls-party preflight announce v1");
+    hasher.update(public_key);
+    *hasher.finalize().as_bytes()
+}
+
+/// Peer liveness: announce ourselves, then see which peers have announced
+/// recently into the same per-key session
+async fn check_peer_liveness(
+    report: &mut PreflightReport,
+    relay: &RelayClient,
+    party_id: usize,
+    key_share: &KeyShare,
+) {
+    use dkls23_core::mpc::Relay;
+
+    let session_id = announce_session_id(&key_share.public_key);
+    let announce = AnnounceMessage {
+        party_id,
+        protocol_version: dkls23_core::PROTOCOL_VERSION,
+    };
+    if let Err(e) = relay.broadcast(&session_id, 0, &announce).await {
+        report.record(
+            "peer liveness",
+            CheckStatus::Warn,
+            format!("Could not post liveness announcement: {}", e),
+        );
+        return;
+    }
+
+    let stats = match relay.session_stats(&session_id).await {
+        Ok(Some(stats)) => stats,
+        Ok(None) => {
+            report.record(
+                "peer liveness",
+                CheckStatus::Warn,
+                "No announcements visible yet; peers may not have run preflight".to_string(),
+            );
+            return;
+        }
+        Err(e) => {
+            report.record(
+                "peer liveness",
+                CheckStatus::Warn,
+                format!("Could not read announcements: {}", e),
+            );
+            return;
+        }
+    };
+
+    let now = chrono::Utc::now();
+    let quiet: Vec<usize> = (0..key_share.n_parties)
+        .filter(|peer| *peer != party_id)
+        .filter(|peer| {
+            stats
+                .last_post_by_sender
+                .get(peer)
+                .map(|seen| (now - *seen).num_seconds() > LIVENESS_WINDOW_SECS)
+                .unwrap_or(true)
+        })
+        .collect();
+
+    if quiet.is_empty() {
+        report.record(
+            "peer liveness",
+            CheckStatus::Pass,
+            format!(
+                "All {} peers announced within the last {} s",
+                key_share.n_parties - 1,
+                LIVENESS_WINDOW_SECS
+            ),
+        );
+    } else {
+        report.record(
+            "peer liveness",
+            CheckStatus::Warn,
+            format!(
+                "No recent announcement from parties {:?}; they may not be online",
+                quiet
+            ),
+        );
+    }
+}
+
+/// Pre-signature availability: stored pre-signatures make signing a
+/// single round trip; their absence just means a full DSG
+fn check_presignatures(report: &mut PreflightReport, dest: &Path, party_id: usize) {
+    let prefix = format!("presig.{}.", party_id);
+    let count = std::fs::read_dir(dest)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| {
+                    entry
+                        .file_name()
+                        .to_str()
+                        .is_some_and(|name| name.starts_with(&prefix))
+                })
+                .count()
+        })
+        .unwrap_or(0);
+
+    if count > 0 {
+        report.record(
+            "pre-signatures",
+            CheckStatus::Pass,
+            format!("{} stored pre-signature(s) available", count),
+        );
+    } else {
+        report.record(
+            "pre-signatures",
+            CheckStatus::Warn,
+            "No stored pre-signatures; signing will run the full protocol".to_string(),
+        );
+    }
+}
@@ -0,0 +1,403 @@
+//! Minimal PSBT (BIP174) handling for the sign-psbt command
+//!
+//! Parses a v0 PSBT into its key-value maps, exposes just enough of the
+//! input fields to compute BIP143 sighashes for P2WPKH inputs the group
+//! key controls, and writes partial signatures back without disturbing
+//! any field it does not understand — other cosigners' data survives a
+//! round trip byte for byte.
+
+use anyhow::{bail, Context, Result};
+use dkls23_core::hashing::{write_compact_size, Transaction, TxIn, TxOut};
+
+/// PSBT magic: "psbt" followed by 0xff
+const PSBT_MAGIC: &[u8; 5] = b"psbt\xff";
+
+/// Global key type: the unsigned transaction
+const GLOBAL_UNSIGNED_TX: u8 = 0x00;
+/// Input key type: the witness UTXO being spent
+const INPUT_WITNESS_UTXO: u8 = 0x01;
+/// Input key type: a partial signature, keyed by public key
+const INPUT_PARTIAL_SIG: u8 = 0x02;
+/// Input key type: explicit sighash type
+const INPUT_SIGHASH_TYPE: u8 = 0x03;
+/// Input key type: finalized scriptSig
+const INPUT_FINAL_SCRIPTSIG: u8 = 0x07;
+/// Input key type: finalized witness stack
+const INPUT_FINAL_SCRIPTWITNESS: u8 = 0x08;
+
+/// One raw key-value pair; unknown types are carried through untouched
+#[derive(Debug, Clone)]
+pub struct KeyValue {
+    /// Type byte followed by key data
+    pub key: Vec<u8>,
+    /// Value bytes
+    pub value: Vec<u8>,
+}
+
+/// A parsed v0 PSBT: the unsigned transaction plus raw per-map pairs
+#[derive(Debug, Clone)]
+pub struct Psbt {
+    /// Global map, including the serialized unsigned transaction
+    pub global: Vec<KeyValue>,
+    /// One map per transaction input
+    pub inputs: Vec<Vec<KeyValue>>,
+    /// One map per transaction output
+    pub outputs: Vec<Vec<KeyValue>>,
+    /// The unsigned transaction, parsed for sighash computation
+    pub unsigned_tx: Transaction,
+}
+
+/// Byte cursor over the PSBT body
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.bytes.len())
+            .context("PSBT truncated")?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32_le(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64_le(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn compact_size(&mut self) -> Result<u64> {
+        Ok(match self.u8()? {
+            0xfd => u16::from_le_bytes(self.take(2)?.try_into().unwrap()) as u64,
+            0xfe => self.u32_le()? as u64,
+            0xff => self.u64_le()?,
+            small => small as u64,
+        })
+    }
+
+    /// A CompactSize-prefixed byte string
+    fn var_bytes(&mut self) -> Result<Vec<u8>> {
+        let len = self.compact_size()? as usize;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    fn done(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+}
+
+/// Read one key-value map, consuming its 0x00 terminator
+fn read_map(reader: &mut Reader) -> Result<Vec<KeyValue>> {
+    let mut pairs = Vec::new();
+    loop {
+        let key_len = reader.compact_size()? as usize;
+        if key_len == 0 {
+            return Ok(pairs);
+        }
+        let key = reader.take(key_len)?.to_vec();
+        let value = reader.var_bytes()?;
+        pairs.push(KeyValue { key, value });
+    }
+}
+
+/// Parse the legacy (witness-free) transaction a PSBT carries
+fn parse_unsigned_tx(bytes: &[u8]) -> Result<Transaction> {
+    let mut reader = Reader { bytes, pos: 0 };
+    let version = reader.u32_le()? as i32;
+
+    let n_inputs = reader.compact_size()? as usize;
+    let mut inputs = Vec::with_capacity(n_inputs);
+    for _ in 0..n_inputs {
+        let prev_txid: [u8; 32] = reader.take(32)?.try_into().unwrap();
+        let prev_vout = reader.u32_le()?;
+        let script_sig = reader.var_bytes()?;
+        if !script_sig.is_empty() {
+            bail!("PSBT unsigned transaction has a non-empty scriptSig");
+        }
+        let sequence = reader.u32_le()?;
+        inputs.push(TxIn {
+            prev_txid,
+            prev_vout,
+            sequence,
+        });
+    }
+
+    let n_outputs = reader.compact_size()? as usize;
+    let mut outputs = Vec::with_capacity(n_outputs);
+    for _ in 0..n_outputs {
+        let value = reader.u64_le()?;
+        let script_pubkey = reader.var_bytes()?;
+        outputs.push(TxOut {
+            value,
+            script_pubkey,
+        });
+    }
+
+    let lock_time = reader.u32_le()?;
+    if !reader.done() {
+        bail!("Trailing bytes after the unsigned transaction");
+    }
+    Ok(Transaction {
+        version,
+        inputs,
+        outputs,
+        lock_time,
+    })
+}
+
+/// Serialize an unsigned transaction in legacy framing (test fixture
+/// construction; parsing keeps the original bytes for round-tripping)
+#[cfg(test)]
+fn serialize_unsigned_tx(tx: &Transaction) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&(tx.version as u32).to_le_bytes());
+    write_compact_size(&mut out, tx.inputs.len() as u64);
+    for input in &tx.inputs {
+        out.extend_from_slice(&input.prev_txid);
+        out.extend_from_slice(&input.prev_vout.to_le_bytes());
+        out.push(0); // empty scriptSig
+        out.extend_from_slice(&input.sequence.to_le_bytes());
+    }
+    write_compact_size(&mut out, tx.outputs.len() as u64);
+    for output in &tx.outputs {
+        out.extend_from_slice(&output.value.to_le_bytes());
+        write_compact_size(&mut out, output.script_pubkey.len() as u64);
+        out.extend_from_slice(&output.script_pubkey);
+    }
+    out.extend_from_slice(&tx.lock_time.to_le_bytes());
+    out
+}
+
+impl Psbt {
+    /// Parse a binary PSBT
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader { bytes, pos: 0 };
+        if reader.take(PSBT_MAGIC.len())? != PSBT_MAGIC {
+            bail!("Not a PSBT (bad magic)");
+        }
+
+        let global = read_map(&mut reader)?;
+        let tx_bytes = global
+            .iter()
+            .find(|pair| pair.key == [GLOBAL_UNSIGNED_TX])
+            .map(|pair| pair.value.clone())
+            .context("PSBT has no unsigned transaction")?;
+        let unsigned_tx = parse_unsigned_tx(&tx_bytes)?;
+
+        let inputs = (0..unsigned_tx.inputs.len())
+            .map(|_| read_map(&mut reader))
+            .collect::<Result<Vec<_>>>()?;
+        let outputs = (0..unsigned_tx.outputs.len())
+            .map(|_| read_map(&mut reader))
+            .collect::<Result<Vec<_>>>()?;
+        if !reader.done() {
+            bail!("Trailing bytes after the PSBT maps");
+        }
+
+        Ok(Self {
+            global,
+            inputs,
+            outputs,
+            unsigned_tx,
+        })
+    }
+
+    /// Serialize back to the binary form, preserving all fields
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(PSBT_MAGIC);
+        for map in std::iter::once(&self.global)
+            .chain(&self.inputs)
+            .chain(&self.outputs)
+        {
+            for pair in map {
+                write_compact_size(&mut out, pair.key.len() as u64);
+                out.extend_from_slice(&pair.key);
+                write_compact_size(&mut out, pair.value.len() as u64);
+                out.extend_from_slice(&pair.value);
+            }
+            out.push(0);
+        }
+        out
+    }
+
+    /// The witness UTXO an input spends, if recorded
+    pub fn witness_utxo(&self, input_index: usize) -> Result<Option<TxOut>> {
+        let Some(pair) = self.inputs[input_index]
+            .iter()
+            .find(|pair| pair.key == [INPUT_WITNESS_UTXO])
+        else {
+            return Ok(None);
+        };
+        let mut reader = Reader {
+            bytes: &pair.value,
+            pos: 0,
+        };
+        let value = reader.u64_le()?;
+        let script_pubkey = reader.var_bytes()?;
+        if !reader.done() {
+            bail!("Malformed witness UTXO");
+        }
+        Ok(Some(TxOut {
+            value,
+            script_pubkey,
+        }))
+    }
+
+    /// The input's explicit sighash type, when one is requested
+    pub fn sighash_type(&self, input_index: usize) -> Result<Option<u8>> {
+        let Some(pair) = self.inputs[input_index]
+            .iter()
+            .find(|pair| pair.key == [INPUT_SIGHASH_TYPE])
+        else {
+            return Ok(None);
+        };
+        let raw: [u8; 4] = pair
+            .value
+            .as_slice()
+            .try_into()
+            .context("Malformed sighash type")?;
+        let raw = u32::from_le_bytes(raw);
+        u8::try_from(raw)
+            .map(Some)
+            .context("Sighash type out of range")
+    }
+
+    /// Whether an input already carries a final script or witness
+    pub fn is_finalized(&self, input_index: usize) -> bool {
+        self.inputs[input_index].iter().any(|pair| {
+            pair.key == [INPUT_FINAL_SCRIPTSIG] || pair.key == [INPUT_FINAL_SCRIPTWITNESS]
+        })
+    }
+
+    /// Whether an input holds a partial signature under this public key
+    pub fn has_partial_sig(&self, input_index: usize, public_key: &[u8]) -> bool {
+        self.inputs[input_index].iter().any(|pair| {
+            pair.key.first() == Some(&INPUT_PARTIAL_SIG) && pair.key[1..] == *public_key
+        })
+    }
+
+    /// Insert a partial signature (DER plus sighash byte) for a key
+    pub fn add_partial_sig(&mut self, input_index: usize, public_key: &[u8], sig: Vec<u8>) {
+        let mut key = vec![INPUT_PARTIAL_SIG];
+        key.extend_from_slice(public_key);
+        self.inputs[input_index].push(KeyValue { key, value: sig });
+    }
+}
+
+/// P2WPKH scriptPubKey: `OP_0 <20-byte key hash>`
+pub fn p2wpkh_script(pubkey_hash: &[u8; 20]) -> Vec<u8> {
+    let mut script = vec![0x00, 0x14];
+    script.extend_from_slice(pubkey_hash);
+    script
+}
+
+/// The BIP143 scriptCode for a P2WPKH input: the implied P2PKH script
+pub fn p2wpkh_script_code(pubkey_hash: &[u8; 20]) -> Vec<u8> {
+    let mut script = vec![0x76, 0xa9, 0x14];
+    script.extend_from_slice(pubkey_hash);
+    script.extend_from_slice(&[0x88, 0xac]);
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A one-input, one-output PSBT spending a P2WPKH output
+    fn sample_psbt_bytes(pubkey_hash: [u8; 20]) -> Vec<u8> {
+        let tx = Transaction {
+            version: 2,
+            inputs: vec![TxIn {
+                prev_txid: [0xab; 32],
+                prev_vout: 1,
+                sequence: 0xffff_fffd,
+            }],
+            outputs: vec![TxOut {
+                value: 90_000,
+                script_pubkey: p2wpkh_script(&[0x11; 20]),
+            }],
+            lock_time: 0,
+        };
+
+        let mut out = Vec::new();
+        out.extend_from_slice(PSBT_MAGIC);
+        // Global: unsigned tx, then terminator
+        out.push(1);
+        out.push(GLOBAL_UNSIGNED_TX);
+        let tx_bytes = serialize_unsigned_tx(&tx);
+        write_compact_size(&mut out, tx_bytes.len() as u64);
+        out.extend_from_slice(&tx_bytes);
+        out.push(0);
+        // Input: witness UTXO, then terminator
+        out.push(1);
+        out.push(INPUT_WITNESS_UTXO);
+        let mut utxo = Vec::new();
+        utxo.extend_from_slice(&100_000u64.to_le_bytes());
+        let script = p2wpkh_script(&pubkey_hash);
+        write_compact_size(&mut utxo, script.len() as u64);
+        utxo.extend_from_slice(&script);
+        write_compact_size(&mut out, utxo.len() as u64);
+        out.extend_from_slice(&utxo);
+        out.push(0);
+        // Output map: empty
+        out.push(0);
+        out
+    }
+
+    #[test]
+    fn test_parse_roundtrips_byte_for_byte() {
+        let bytes = sample_psbt_bytes([0x22; 20]);
+        let psbt = Psbt::parse(&bytes).unwrap();
+
+        assert_eq!(psbt.unsigned_tx.inputs.len(), 1);
+        assert_eq!(psbt.unsigned_tx.outputs[0].value, 90_000);
+        let utxo = psbt.witness_utxo(0).unwrap().unwrap();
+        assert_eq!(utxo.value, 100_000);
+        assert_eq!(utxo.script_pubkey, p2wpkh_script(&[0x22; 20]));
+        assert_eq!(psbt.sighash_type(0).unwrap(), None);
+        assert!(!psbt.is_finalized(0));
+
+        assert_eq!(psbt.serialize(), bytes);
+    }
+
+    #[test]
+    fn test_partial_sig_insertion_survives_reparse() {
+        let mut psbt = Psbt::parse(&sample_psbt_bytes([0x22; 20])).unwrap();
+        let pubkey = [0x02; 33];
+
+        assert!(!psbt.has_partial_sig(0, &pubkey));
+        psbt.add_partial_sig(0, &pubkey, vec![0x30, 0x01, 0xaa, 0x01]);
+
+        let reparsed = Psbt::parse(&psbt.serialize()).unwrap();
+        assert!(reparsed.has_partial_sig(0, &pubkey));
+        // The original witness UTXO is untouched
+        assert!(reparsed.witness_utxo(0).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_malformed_psbts_are_rejected() {
+        // Bad magic
+        assert!(Psbt::parse(b"psbt\x00rest").is_err());
+
+        // Truncated body
+        let bytes = sample_psbt_bytes([0x22; 20]);
+        assert!(Psbt::parse(&bytes[..bytes.len() - 2]).is_err());
+
+        // Trailing garbage
+        let mut trailing = bytes.clone();
+        trailing.push(0x42);
+        assert!(Psbt::parse(&trailing).is_err());
+    }
+}
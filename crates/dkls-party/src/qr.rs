@@ -0,0 +1,225 @@
+//! QR frame chunking for air-gapped transfer
+//!
+//! Splits a payload (an encrypted key share or captured round messages)
+//! into self-describing frames small enough to render as QR codes, and
+//! reassembles scanned frames back into the payload. Frames carry their
+//! position, the total count, and a digest of the full payload, so they can
+//! be scanned in any order across multiple passes of an animated sequence.
+//!
+//! Frame layout: `DKLS:<index>/<total>:<digest16>:<base64 chunk>` where
+//! `digest16` is the first 16 hex characters of the payload's BLAKE3 hash.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use rand::RngCore;
+
+/// Frame prefix identifying our payloads among scanned codes
+const FRAME_PREFIX: &str = "DKLS";
+
+/// Default chunk size in bytes; keeps each frame within a comfortably
+/// scannable QR version on ordinary laptop screens
+pub const DEFAULT_CHUNK_SIZE: usize = 400;
+
+/// Domain separator for passphrase key derivation
+const QR_KEY_CONTEXT: &str = "dkls-party qr export key v1";
+
+/// Encrypt a payload under a passphrase (random nonce prepended)
+pub fn encrypt_payload(data: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let key = blake3::derive_key(QR_KEY_CONTEXT, passphrase.as_bytes());
+    let cipher = ChaCha20Poly1305::new(&key.into());
+
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), data)
+        .map_err(|_| anyhow!("Encryption failed"))?;
+
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypt a payload produced by [`encrypt_payload`]
+pub fn decrypt_payload(data: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    if data.len() < 12 {
+        bail!("Encrypted payload too short");
+    }
+    let key = blake3::derive_key(QR_KEY_CONTEXT, passphrase.as_bytes());
+    let cipher = ChaCha20Poly1305::new(&key.into());
+
+    cipher
+        .decrypt(Nonce::from_slice(&data[..12]), &data[12..])
+        .map_err(|_| anyhow!("Decryption failed; wrong passphrase or corrupted frames"))
+}
+
+/// Short payload digest included in every frame
+fn payload_digest(data: &[u8]) -> String {
+    hex::encode(blake3::hash(data).as_bytes())[..16].to_string()
+}
+
+/// Split a payload into QR frame strings
+pub fn encode_frames(data: &[u8], chunk_size: usize) -> Result<Vec<String>> {
+    if chunk_size == 0 {
+        bail!("Chunk size must be positive");
+    }
+    let digest = payload_digest(data);
+    let total = data.len().div_ceil(chunk_size).max(1);
+
+    Ok(data
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(index, chunk)| {
+            format!(
+                "{}:{}/{}:{}:{}",
+                FRAME_PREFIX,
+                index + 1,
+                total,
+                digest,
+                STANDARD.encode(chunk)
+            )
+        })
+        .collect())
+}
+
+/// Reassemble scanned frames into the original payload
+///
+/// Frames may arrive in any order and with duplicates (multiple passes of
+/// an animated sequence); all must belong to the same payload.
+pub fn decode_frames(frames: &[String]) -> Result<Vec<u8>> {
+    if frames.is_empty() {
+        bail!("No frames to decode");
+    }
+
+    let mut total = 0usize;
+    let mut digest: Option<String> = None;
+    let mut chunks: Vec<Option<Vec<u8>>> = Vec::new();
+
+    for frame in frames {
+        let frame = frame.trim();
+        let mut parts = frame.splitn(4, ':');
+        let (prefix, position, frame_digest, chunk_b64) = (
+            parts.next().unwrap_or_default(),
+            parts.next().unwrap_or_default(),
+            parts.next().unwrap_or_default(),
+            parts.next().unwrap_or_default(),
+        );
+
+        if prefix != FRAME_PREFIX {
+            bail!("Unrecognized frame: {}", frame);
+        }
+
+        let (index, count) = position
+            .split_once('/')
+            .context("Malformed frame position")?;
+        let index: usize = index.parse().context("Malformed frame index")?;
+        let count: usize = count.parse().context("Malformed frame count")?;
+        if index == 0 || index > count {
+            bail!("Frame index {} out of range 1..={}", index, count);
+        }
+
+        match &digest {
+            None => {
+                digest = Some(frame_digest.to_string());
+                total = count;
+                chunks = vec![None; count];
+            }
+            Some(expected) => {
+                if frame_digest != expected || count != total {
+                    bail!("Frame belongs to a different payload");
+                }
+            }
+        }
+
+        let chunk = STANDARD
+            .decode(chunk_b64)
+            .context("Malformed frame chunk")?;
+        chunks[index - 1] = Some(chunk);
+    }
+
+    let mut missing = Vec::new();
+    let mut payload = Vec::new();
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        match chunk {
+            Some(chunk) => payload.extend_from_slice(&chunk),
+            None => missing.push((index + 1).to_string()),
+        }
+    }
+    if !missing.is_empty() {
+        bail!(
+            "Missing {} of {} frames: {}",
+            missing.len(),
+            total,
+            missing.join(", ")
+        );
+    }
+
+    let expected = digest.unwrap_or_default();
+    if payload_digest(&payload) != expected {
+        bail!("Reassembled payload digest mismatch");
+    }
+
+    Ok(payload)
+}
+
+/// Render one frame as a terminal-displayable QR code
+pub fn render_frame(frame: &str) -> Result<String> {
+    let code = qrcode::QrCode::new(frame.as_bytes())
+        .map_err(|e| anyhow!("QR encoding failed: {}", e))?;
+    Ok(code
+        .render::<qrcode::render::unicode::Dense1x2>()
+        .quiet_zone(true)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_roundtrip_out_of_order() {
+        let payload: Vec<u8> = (0..=255u8).cycle().take(1500).collect();
+        let mut frames = encode_frames(&payload, 400).unwrap();
+        assert_eq!(frames.len(), 4);
+
+        frames.reverse();
+        frames.push(frames[0].clone()); // duplicate scan
+        assert_eq!(decode_frames(&frames).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_decode_reports_missing_frames() {
+        let payload = vec![7u8; 1000];
+        let mut frames = encode_frames(&payload, 400).unwrap();
+        frames.remove(1);
+
+        let err = decode_frames(&frames).unwrap_err().to_string();
+        assert!(err.contains("Missing 1 of 3"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_decode_rejects_mixed_payloads() {
+        let frames_a = encode_frames(&[1u8; 800], 400).unwrap();
+        let frames_b = encode_frames(&[2u8; 800], 400).unwrap();
+
+        let mixed = vec![frames_a[0].clone(), frames_b[1].clone()];
+        assert!(decode_frames(&mixed).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let payload = b"secret share bytes";
+        let sealed = encrypt_payload(payload, "hunter2").unwrap();
+        assert_eq!(decrypt_payload(&sealed, "hunter2").unwrap(), payload);
+        assert!(decrypt_payload(&sealed, "wrong").is_err());
+    }
+
+    #[test]
+    fn test_render_frame_produces_qr() {
+        let frames = encode_frames(b"tiny", 400).unwrap();
+        let art = render_frame(&frames[0]).unwrap();
+        assert!(!art.is_empty());
+    }
+}
@@ -0,0 +1,284 @@
+//! JSON-RPC daemon frontend
+//!
+//! `dkls-party serve` keeps the key share loaded — decrypted once, at
+//! startup — and exposes the ceremonies over HTTP, so backend services
+//! call an API instead of shelling out to the binary per signature. One
+//! JSON-RPC 2.0 endpoint at `POST /rpc` carries the `keygen`, `sign`,
+//! `derive` and `info` methods; `GET /health` answers liveness probes.
+//!
+//! Concurrent `sign` calls go through the core [`SignScheduler`], so
+//! requests for one key queue fairly instead of contending over the
+//! relay. Each call builds its own relay client and runs the ceremony to
+//! completion before answering; callers needing async submission should
+//! front this with their own queue.
+
+use anyhow::Result;
+use axum::{
+    extract::State,
+    routing::{get, post},
+    Json, Router,
+};
+use dkls23_core::sign::{self, SchedulerLimits, SignScheduler};
+use dkls23_core::KeyShare;
+use msg_relay_client::RelayClient;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::Cli;
+
+/// Shared daemon state: the cached share and per-key signing queue
+struct ServerState {
+    cli: Cli,
+    /// Loaded at startup, replaced after a keygen; `None` until the
+    /// first keygen for a fresh data directory
+    key_share: RwLock<Option<KeyShare>>,
+    scheduler: SignScheduler,
+}
+
+/// JSON-RPC 2.0 request envelope
+#[derive(Deserialize)]
+struct RpcRequest {
+    #[allow(dead_code)]
+    jsonrpc: Option<String>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+    #[serde(default)]
+    id: Value,
+}
+
+/// JSON-RPC error with the standard numeric code
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self {
+            code: -32602,
+            message: message.into(),
+        }
+    }
+
+    fn server(error: impl std::fmt::Display) -> Self {
+        Self {
+            code: -32000,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Run the daemon until a shutdown signal arrives
+pub async fn run_serve(cli: &Cli, listen: &str) -> Result<()> {
+    // Decrypt the share once up front; a missing share is fine, keygen
+    // over RPC will create it
+    let key_share = crate::load_key_share(cli).ok();
+    if key_share.is_none() {
+        info!("No key share loaded; waiting for a keygen call");
+    }
+
+    let state = Arc::new(ServerState {
+        cli: cli.clone(),
+        key_share: RwLock::new(key_share),
+        scheduler: SignScheduler::new(SchedulerLimits::default()),
+    });
+
+    let app = Router::new()
+        .route("/health", get(|| async { "ok" }))
+        .route("/rpc", post(rpc))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(listen).await?;
+    info!(address = %listen, "Serving JSON-RPC");
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            msg_relay::service::shutdown_signal().await;
+            info!("Shutdown requested; draining connections");
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// The single JSON-RPC endpoint
+async fn rpc(State(state): State<Arc<ServerState>>, Json(req): Json<RpcRequest>) -> Json<Value> {
+    let response = match dispatch(&state, &req.method, req.params).await {
+        Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": req.id }),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "error": { "code": error.code, "message": error.message },
+            "id": req.id,
+        }),
+    };
+    Json(response)
+}
+
+async fn dispatch(state: &ServerState, method: &str, params: Value) -> Result<Value, RpcError> {
+    match method {
+        "info" => rpc_info(state).await,
+        "sign" => rpc_sign(state, params).await,
+        "keygen" => rpc_keygen(state, params).await,
+        "derive" => rpc_derive(state, params).await,
+        other => Err(RpcError {
+            code: -32601,
+            message: format!("Method not found: {}", other),
+        }),
+    }
+}
+
+/// Clone the cached key share, or fail with a uniform error
+async fn cached_share(state: &ServerState) -> Result<KeyShare, RpcError> {
+    state
+        .key_share
+        .read()
+        .await
+        .clone()
+        .ok_or_else(|| RpcError::server("No key share loaded; run keygen first"))
+}
+
+async fn rpc_info(state: &ServerState) -> Result<Value, RpcError> {
+    let share = cached_share(state).await?;
+    Ok(json!({
+        "public_key": hex::encode(&share.public_key),
+        "party_id": share.party_id,
+        "n_parties": share.n_parties,
+        "threshold": share.threshold,
+        "scheme": share.scheme.to_string(),
+    }))
+}
+
+#[derive(Deserialize)]
+struct SignParams {
+    /// Hex-encoded 32-byte message hash
+    message: String,
+    /// Participating party IDs
+    parties: Vec<usize>,
+    /// Caller identity for the scheduler's per-requester quota
+    #[serde(default)]
+    requester: Option<String>,
+}
+
+async fn rpc_sign(state: &ServerState, params: Value) -> Result<Value, RpcError> {
+    let params: SignParams =
+        serde_json::from_value(params).map_err(|e| RpcError::invalid_params(e.to_string()))?;
+    let digest: [u8; 32] = hex::decode(&params.message)
+        .map_err(|e| RpcError::invalid_params(e.to_string()))?
+        .try_into()
+        .map_err(|_| RpcError::invalid_params("message must be 32 bytes"))?;
+
+    let share = cached_share(state).await?;
+    let requester = params.requester.as_deref().unwrap_or("rpc");
+    let _permit = state
+        .scheduler
+        .acquire(&share.public_key, requester)
+        .await
+        .map_err(RpcError::server)?;
+
+    let trace_id = hex::encode(rand::random::<[u8; 8]>());
+    let relay = build_relay(state, Some(&params.parties), &trace_id)?;
+    let signature = sign::run_dsg(&share, &digest, &params.parties, &relay)
+        .await
+        .map_err(RpcError::server)?;
+    signature
+        .verify(&share.public_key, &digest)
+        .map_err(RpcError::server)?;
+
+    Ok(json!({
+        "r": hex::encode(signature.r),
+        "s": hex::encode(signature.s),
+        "recovery_id": signature.recovery_id,
+        "der": hex::encode(signature.to_der()),
+        "trace_id": trace_id,
+    }))
+}
+
+#[derive(Deserialize)]
+struct KeygenParams {
+    n: usize,
+    t: usize,
+}
+
+async fn rpc_keygen(state: &ServerState, params: Value) -> Result<Value, RpcError> {
+    let params: KeygenParams =
+        serde_json::from_value(params).map_err(|e| RpcError::invalid_params(e.to_string()))?;
+
+    let trace_id = hex::encode(rand::random::<[u8; 8]>());
+    let relay = build_relay(state, None, &trace_id)?;
+    crate::run_keygen(&state.cli, &relay, params.n, params.t, 1, None)
+        .await
+        .map_err(RpcError::server)?;
+
+    // Swap the fresh share into the cache so later calls use it
+    let share = crate::load_key_share(&state.cli).map_err(RpcError::server)?;
+    let public_key = hex::encode(&share.public_key);
+    *state.key_share.write().await = Some(share);
+
+    Ok(json!({ "public_key": public_key, "trace_id": trace_id }))
+}
+
+#[derive(Deserialize)]
+struct DeriveParams {
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    label: Option<String>,
+}
+
+async fn rpc_derive(state: &ServerState, params: Value) -> Result<Value, RpcError> {
+    let params: DeriveParams =
+        serde_json::from_value(params).map_err(|e| RpcError::invalid_params(e.to_string()))?;
+
+    let derived = crate::run_derive(&state.cli, params.path.as_deref(), params.label.as_deref())
+        .map_err(RpcError::server)?;
+    Ok(json!({ "public_key": hex::encode(&derived.public_key) }))
+}
+
+/// A relay client configured like the one-shot CLI builds
+fn build_relay(
+    state: &ServerState,
+    parties: Option<&[usize]>,
+    trace_id: &str,
+) -> Result<RelayClient, RpcError> {
+    let settings = crate::load_relay_settings(&state.cli).map_err(RpcError::server)?;
+    let mut relay = RelayClient::new(&settings.url, state.cli.party_id).with_trace_id(trace_id);
+    if let Some(secs) = state.cli.config.relay_timeout_secs {
+        relay = relay.with_timeout(std::time::Duration::from_secs(secs));
+    }
+    if let Some(ref token) = settings.token {
+        relay = relay.with_auth_token(token).map_err(RpcError::server)?;
+    }
+    if let Some(parties) = parties {
+        relay = relay.with_parties(parties);
+    }
+    Ok(relay)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_request_parses_with_defaults() {
+        let req: RpcRequest =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","method":"info","id":7}"#).unwrap();
+        assert_eq!(req.method, "info");
+        assert!(req.params.is_null());
+        assert_eq!(req.id, json!(7));
+    }
+
+    #[test]
+    fn test_sign_params_require_message_and_parties() {
+        assert!(serde_json::from_value::<SignParams>(json!({"message": "00"})).is_err());
+        let params: SignParams = serde_json::from_value(json!({
+            "message": "00".repeat(32),
+            "parties": [0, 1],
+        }))
+        .unwrap();
+        assert!(params.requester.is_none());
+    }
+}
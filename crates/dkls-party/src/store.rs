@@ -0,0 +1,144 @@
+//! Pluggable at-rest storage backends for the key share
+//!
+//! `KeyShareStore` abstracts where the share's stored form lives: a file
+//! under the destination directory (the default and historical
+//! behavior), or the operating system's credential store — the macOS
+//! Keychain, the DPAPI-protected Windows Credential Manager, or the
+//! Linux secret-service, whichever the platform provides. What is stored
+//! is exactly what would have gone in the file — a plaintext share, a
+//! passphrase envelope, or a hardware envelope — so the wrapping layers
+//! compose with either backend.
+//!
+//! Only the key share itself goes through a store; signatures, backup
+//! fragments and other working artifacts stay plain files.
+
+use anyhow::{anyhow, bail, Result};
+use std::path::{Path, PathBuf};
+
+/// Keychain service name all entries are filed under
+const SERVICE: &str = "dkls-party";
+
+/// Storage backend for key share content, addressed by entry name
+/// (the share's file name, so backends agree on naming)
+pub trait KeyShareStore {
+    /// Store `content` under the entry name, replacing what was there
+    fn save(&self, name: &str, content: &str) -> Result<()>;
+
+    /// Fetch the content stored under the entry name
+    fn load(&self, name: &str) -> Result<String>;
+
+    /// Where the entry lives, for logs and error messages
+    fn location(&self, name: &str) -> String;
+}
+
+/// Open the backend selected by `--share-store`
+pub fn open(backend: &str, root: &Path) -> Result<Box<dyn KeyShareStore>> {
+    match backend {
+        "file" => Ok(Box::new(FsStore::new(root))),
+        "keychain" => Ok(Box::new(KeychainStore)),
+        other => bail!(
+            "Unknown share store '{}' (expected 'file' or 'keychain')",
+            other
+        ),
+    }
+}
+
+/// Plain files under the destination directory
+pub struct FsStore {
+    root: PathBuf,
+}
+
+impl FsStore {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+        }
+    }
+}
+
+impl KeyShareStore for FsStore {
+    fn save(&self, name: &str, content: &str) -> Result<()> {
+        Ok(std::fs::write(self.root.join(name), content)?)
+    }
+
+    fn load(&self, name: &str) -> Result<String> {
+        let path = self.root.join(name);
+        std::fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Cannot read {}: {}", path.display(), e))
+    }
+
+    fn location(&self, name: &str) -> String {
+        self.root.join(name).display().to_string()
+    }
+}
+
+/// The platform credential store, via one portable backend
+///
+/// Entries are filed under the `dkls-party` service; the platform
+/// decides how they are protected (login keychain, DPAPI, the unlocked
+/// secret-service collection).
+pub struct KeychainStore;
+
+impl KeychainStore {
+    fn entry(name: &str) -> Result<keyring::Entry> {
+        Ok(keyring::Entry::new(SERVICE, name)?)
+    }
+}
+
+impl KeyShareStore for KeychainStore {
+    fn save(&self, name: &str, content: &str) -> Result<()> {
+        Ok(Self::entry(name)?.set_password(content)?)
+    }
+
+    fn load(&self, name: &str) -> Result<String> {
+        Self::entry(name)?
+            .get_password()
+            .map_err(|e| anyhow!("Cannot read {} from the OS keychain: {}", name, e))
+    }
+
+    fn location(&self, name: &str) -> String {
+        format!("{}/{} in the OS keychain", SERVICE, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("store-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_fs_store_roundtrip() {
+        let root = temp_root("roundtrip");
+        let store = open("file", &root).unwrap();
+
+        store.save("keyshare.0.json", "{\"party_id\":0}").unwrap();
+        assert_eq!(store.load("keyshare.0.json").unwrap(), "{\"party_id\":0}");
+
+        // The entry is the plain file the rest of the tooling expects
+        assert_eq!(
+            std::fs::read_to_string(root.join("keyshare.0.json")).unwrap(),
+            "{\"party_id\":0}"
+        );
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_fs_store_missing_entry_names_the_path() {
+        let root = temp_root("missing");
+        let store = open("file", &root).unwrap();
+        let err = store.load("keyshare.9.json").err().unwrap();
+        assert!(err.to_string().contains("keyshare.9.json"));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_backend_is_rejected() {
+        let err = open("floppy", Path::new(".")).err().unwrap();
+        assert!(err.to_string().contains("floppy"));
+    }
+}
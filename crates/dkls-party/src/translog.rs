@@ -0,0 +1,332 @@
+//! Append-only transparency log of ceremony outcomes
+//!
+//! Each completed keygen, refresh or onboarding ceremony can publish its
+//! public key and transcript digest as a leaf of a local Merkle tree
+//! whose head is signed under a dedicated log key. Rewriting or dropping
+//! a past entry changes the root, so it can never match the signed head
+//! again, and forging a replacement head requires the log key — auditors
+//! and later ceremonies re-verify the whole chain in one pass and detect
+//! retroactive tampering with ceremony records.
+
+use anyhow::{anyhow, bail, Result};
+use k256::ecdsa::signature::{Signer, Verifier};
+use k256::ecdsa::{Signature as EcdsaSignature, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Log format version
+const LOG_VERSION: u32 = 1;
+
+/// Domain prefix for leaf hashes (RFC 6962 style)
+const LEAF_PREFIX: u8 = 0x00;
+
+/// Domain prefix for interior node hashes
+const NODE_PREFIX: u8 = 0x01;
+
+/// Domain separating tree-head signatures from other uses of the key
+const HEAD_CONTEXT: &[u8] = b"dkls-party translog head v1";
+
+/// One published ceremony record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// Position in the log, starting at 0
+    pub index: u64,
+    /// RFC 3339 timestamp of when the record was appended
+    pub recorded_at: String,
+    /// Ceremony kind ("dkg", "refresh", "add-party", ...)
+    pub ceremony: String,
+    /// Resulting group public key (hex)
+    pub public_key: String,
+    /// Ceremony transcript digest (hex)
+    pub transcript_digest: String,
+}
+
+/// Merkle root over the whole log, signed under the log key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedTreeHead {
+    /// Log format version
+    pub version: u32,
+    /// Number of entries the root covers
+    pub tree_size: u64,
+    /// Merkle root over all entries (hex)
+    pub root: String,
+    /// DER signature over the head under the log key (hex)
+    pub signature: String,
+}
+
+/// The log file as stored on disk
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransparencyLog {
+    /// Compressed public half of the log key (hex)
+    pub public_key: String,
+    /// All published records, in append order
+    pub entries: Vec<LogEntry>,
+    /// Signed head covering every entry above
+    pub head: SignedTreeHead,
+}
+
+/// Hash one entry as a leaf
+fn leaf_hash(entry: &LogEntry) -> Result<[u8; 32]> {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(&serde_json::to_vec(entry)?);
+    Ok(*hasher.finalize().as_bytes())
+}
+
+/// Merkle root over leaf hashes, splitting at the largest power of two
+/// below the count (RFC 6962 tree shape)
+fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    match leaves {
+        [] => *blake3::Hasher::new().finalize().as_bytes(),
+        [leaf] => *leaf,
+        _ => {
+            let split = leaves.len().div_ceil(2).next_power_of_two();
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(&[NODE_PREFIX]);
+            hasher.update(&merkle_root(&leaves[..split]));
+            hasher.update(&merkle_root(&leaves[split..]));
+            *hasher.finalize().as_bytes()
+        }
+    }
+}
+
+/// The exact bytes a tree-head signature covers
+fn head_bytes(tree_size: u64, root: &[u8; 32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEAD_CONTEXT.len() + 8 + 32);
+    bytes.extend_from_slice(HEAD_CONTEXT);
+    bytes.extend_from_slice(&tree_size.to_be_bytes());
+    bytes.extend_from_slice(root);
+    bytes
+}
+
+/// Load the log's signing key, generating one on first use
+fn load_or_create_key(key_path: &Path) -> Result<SigningKey> {
+    if key_path.exists() {
+        let bytes = hex::decode(std::fs::read_to_string(key_path)?.trim())?;
+        SigningKey::from_slice(&bytes)
+            .map_err(|e| anyhow!("Invalid transparency log key: {}", e))
+    } else {
+        let key = SigningKey::random(&mut rand::rngs::OsRng);
+        std::fs::write(key_path, hex::encode(key.to_bytes()))?;
+        Ok(key)
+    }
+}
+
+/// Append one ceremony record and re-sign the tree head
+///
+/// The existing log is fully verified first, so a tampered log is
+/// refused rather than silently extended under a fresh head. Returns
+/// the new tree size.
+pub fn publish(
+    log_path: &Path,
+    key_path: &Path,
+    ceremony: &str,
+    public_key: &[u8],
+    transcript_digest: &[u8; 32],
+) -> Result<u64> {
+    let key = load_or_create_key(key_path)?;
+    let log_key = hex::encode(key.verifying_key().to_sec1_bytes());
+
+    let mut log = if log_path.exists() {
+        let log: TransparencyLog = serde_json::from_str(&std::fs::read_to_string(log_path)?)?;
+        verify_log(&log)?;
+        if log.public_key != log_key {
+            bail!("Transparency log was started under a different log key");
+        }
+        log
+    } else {
+        TransparencyLog {
+            public_key: log_key,
+            entries: Vec::new(),
+            head: SignedTreeHead {
+                version: LOG_VERSION,
+                tree_size: 0,
+                root: String::new(),
+                signature: String::new(),
+            },
+        }
+    };
+
+    log.entries.push(LogEntry {
+        index: log.entries.len() as u64,
+        recorded_at: chrono::Utc::now().to_rfc3339(),
+        ceremony: ceremony.to_string(),
+        public_key: hex::encode(public_key),
+        transcript_digest: hex::encode(transcript_digest),
+    });
+
+    let leaves = log
+        .entries
+        .iter()
+        .map(leaf_hash)
+        .collect::<Result<Vec<_>>>()?;
+    let root = merkle_root(&leaves);
+    let tree_size = log.entries.len() as u64;
+    let signature: EcdsaSignature = key.sign(&head_bytes(tree_size, &root));
+    log.head = SignedTreeHead {
+        version: LOG_VERSION,
+        tree_size,
+        root: hex::encode(root),
+        signature: hex::encode(signature.to_der()),
+    };
+
+    // Write atomically so a crash mid-write cannot corrupt the log
+    let tmp = log_path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(&log)?)?;
+    std::fs::rename(&tmp, log_path)?;
+    Ok(tree_size)
+}
+
+/// Verify every entry against the signed tree head
+pub fn verify_log(log: &TransparencyLog) -> Result<()> {
+    if log.head.version != LOG_VERSION {
+        bail!(
+            "Unsupported transparency log version {} (this build supports {})",
+            log.head.version,
+            LOG_VERSION
+        );
+    }
+    if log.head.tree_size != log.entries.len() as u64 {
+        bail!(
+            "Transparency log head covers {} entries but {} are present",
+            log.head.tree_size,
+            log.entries.len()
+        );
+    }
+    for (position, entry) in log.entries.iter().enumerate() {
+        if entry.index != position as u64 {
+            bail!(
+                "Transparency log entry at position {} claims index {}",
+                position,
+                entry.index
+            );
+        }
+    }
+
+    let leaves = log
+        .entries
+        .iter()
+        .map(leaf_hash)
+        .collect::<Result<Vec<_>>>()?;
+    let root = merkle_root(&leaves);
+    if hex::encode(root) != log.head.root {
+        bail!("Transparency log root mismatch; a past entry has been altered");
+    }
+
+    let verifying_key = VerifyingKey::from_sec1_bytes(&hex::decode(&log.public_key)?)
+        .map_err(|e| anyhow!("Invalid transparency log public key: {}", e))?;
+    let signature = EcdsaSignature::from_der(&hex::decode(&log.head.signature)?)
+        .map_err(|e| anyhow!("Invalid tree head signature: {}", e))?;
+    verifying_key
+        .verify(&head_bytes(log.head.tree_size, &root), &signature)
+        .map_err(|_| anyhow!("Tree head signature does not verify under the log key"))?;
+    Ok(())
+}
+
+/// Verify a log file and print its head for the auditor
+pub fn run_verify(log_path: &Path) -> Result<()> {
+    let log: TransparencyLog = serde_json::from_str(&std::fs::read_to_string(log_path)?)?;
+    verify_log(&log)?;
+    println!("Transparency log OK: {} entries", log.head.tree_size);
+    println!("Root:    {}", log.head.root);
+    println!("Log key: {}", log.public_key);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_paths(name: &str) -> (PathBuf, PathBuf) {
+        let dir = std::env::temp_dir();
+        let pid = std::process::id();
+        let log = dir.join(format!("translog-{}-{}.json", name, pid));
+        let key = dir.join(format!("translog-{}-{}.key", name, pid));
+        let _ = std::fs::remove_file(&log);
+        let _ = std::fs::remove_file(&key);
+        (log, key)
+    }
+
+    #[test]
+    fn test_publish_and_verify_roundtrip() {
+        let (log_path, key_path) = temp_paths("roundtrip");
+
+        assert_eq!(
+            publish(&log_path, &key_path, "dkg", b"\x02pk", &[0x11; 32]).unwrap(),
+            1
+        );
+        assert_eq!(
+            publish(&log_path, &key_path, "refresh", b"\x02pk", &[0x22; 32]).unwrap(),
+            2
+        );
+
+        let log: TransparencyLog =
+            serde_json::from_str(&std::fs::read_to_string(&log_path).unwrap()).unwrap();
+        verify_log(&log).unwrap();
+        assert_eq!(log.entries[0].ceremony, "dkg");
+        assert_eq!(log.entries[1].ceremony, "refresh");
+
+        std::fs::remove_file(&log_path).unwrap();
+        std::fs::remove_file(&key_path).unwrap();
+    }
+
+    #[test]
+    fn test_altered_entry_breaks_the_chain() {
+        let (log_path, key_path) = temp_paths("tamper");
+        publish(&log_path, &key_path, "dkg", b"\x02pk", &[0x11; 32]).unwrap();
+        publish(&log_path, &key_path, "refresh", b"\x02pk", &[0x22; 32]).unwrap();
+
+        let mut log: TransparencyLog =
+            serde_json::from_str(&std::fs::read_to_string(&log_path).unwrap()).unwrap();
+        log.entries[0].transcript_digest = hex::encode([0x99u8; 32]);
+        let error = verify_log(&log).unwrap_err();
+        assert!(error.to_string().contains("root mismatch"));
+
+        // A tampered log on disk must be refused, not extended
+        std::fs::write(&log_path, serde_json::to_string_pretty(&log).unwrap()).unwrap();
+        assert!(publish(&log_path, &key_path, "dkg", b"\x02pk", &[0x33; 32]).is_err());
+
+        std::fs::remove_file(&log_path).unwrap();
+        std::fs::remove_file(&key_path).unwrap();
+    }
+
+    #[test]
+    fn test_forged_head_needs_the_log_key() {
+        let (log_path, key_path) = temp_paths("forge");
+        publish(&log_path, &key_path, "dkg", b"\x02pk", &[0x11; 32]).unwrap();
+
+        // Recompute a consistent root over altered entries, but sign it
+        // under an attacker key
+        let mut log: TransparencyLog =
+            serde_json::from_str(&std::fs::read_to_string(&log_path).unwrap()).unwrap();
+        log.entries[0].public_key = hex::encode(b"\x02attacker");
+        let leaves = vec![leaf_hash(&log.entries[0]).unwrap()];
+        let root = merkle_root(&leaves);
+        let attacker = SigningKey::random(&mut rand::rngs::OsRng);
+        let signature: EcdsaSignature = attacker.sign(&head_bytes(1, &root));
+        log.head.root = hex::encode(root);
+        log.head.signature = hex::encode(signature.to_der());
+
+        let error = verify_log(&log).unwrap_err();
+        assert!(error.to_string().contains("does not verify"));
+
+        std::fs::remove_file(&log_path).unwrap();
+        std::fs::remove_file(&key_path).unwrap();
+    }
+
+    #[test]
+    fn test_merkle_split_matches_rfc6962_shape() {
+        // For 3 leaves the split is 2|1: hash(hash(l0,l1), l2)
+        let leaves: Vec<[u8; 32]> = (0u8..3).map(|i| [i; 32]).collect();
+        let mut left = blake3::Hasher::new();
+        left.update(&[NODE_PREFIX]);
+        left.update(&leaves[0]);
+        left.update(&leaves[1]);
+        let mut expected = blake3::Hasher::new();
+        expected.update(&[NODE_PREFIX]);
+        expected.update(left.finalize().as_bytes());
+        expected.update(&leaves[2]);
+        assert_eq!(merkle_root(&leaves), *expected.finalize().as_bytes());
+    }
+}
@@ -0,0 +1,359 @@
+//! Interactive operator console for human co-signers
+//!
+//! `dkls-party tui` gives the operator of one party a live view of the
+//! relay — sessions with pending traffic, which peers have been seen
+//! posting, this party's presignature inventory — and an approval flow
+//! for signing requests, which a 2-of-3 setup with a human co-signer
+//! otherwise lacks.
+//!
+//! Sign requests ride the relay under a well-known inbox session derived
+//! from the group public key alone: requesters broadcast a
+//! [`SignRequest`] on round 0, and the console broadcasts a
+//! [`SignVerdict`] on round 1 when the operator approves or denies one.
+//! Automation on the requesting side queries round 1 and proceeds only
+//! once the co-signers it needs have approved; the convention carries no
+//! secret material, only digests and verdicts.
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use msg_relay_client::RelayClient;
+
+/// How often the console re-polls the relay
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A signing request awaiting operator review
+///
+/// Broadcast by the requesting side on round 0 of the inbox session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignRequest {
+    /// Digest the requester wants signed (hex, 32 bytes)
+    pub message: String,
+    /// Party asking for the signature
+    pub requester: usize,
+    /// Free-form context for the human reviewing it
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// One operator's decision on a request, broadcast on round 1
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignVerdict {
+    /// Digest the verdict covers (hex)
+    pub message: String,
+    /// Party that reviewed the request
+    pub party_id: usize,
+    /// Whether this party agrees to co-sign
+    pub approve: bool,
+}
+
+/// Inbox session for a group key's signing requests
+///
+/// Derived from the public key alone, so requesters and every co-signer
+/// find the same inbox with nothing agreed out of band.
+pub fn inbox_id(public_key: &[u8]) -> dkls23_core::SessionId {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"dkls-party sign request inbox v1");
+    hasher.update(public_key);
+    *hasher.finalize().as_bytes()
+}
+
+/// Everything one refresh pulls off the relay
+struct Snapshot {
+    relay_ok: bool,
+    peers: std::collections::BTreeSet<usize>,
+    sessions: Vec<msg_relay::SessionSummary>,
+    presigs: Vec<String>,
+    requests: Vec<SignRequest>,
+    verdicts: BTreeMap<String, bool>,
+}
+
+impl Snapshot {
+    fn empty() -> Self {
+        Self {
+            relay_ok: false,
+            peers: Default::default(),
+            sessions: Vec::new(),
+            presigs: Vec::new(),
+            requests: Vec::new(),
+            verdicts: BTreeMap::new(),
+        }
+    }
+}
+
+/// Poll the relay for the console's next frame of data
+async fn refresh(
+    cli: &crate::Cli,
+    client: &RelayClient,
+    inbox: Option<&dkls23_core::SessionId>,
+) -> Result<Snapshot> {
+    let mut snapshot = Snapshot::empty();
+    snapshot.relay_ok = client.health().await.is_ok();
+    snapshot.presigs = crate::stored_presig_ids(cli);
+
+    if let Ok(sessions) = client.sessions().await {
+        for summary in &sessions {
+            snapshot.peers.extend(summary.senders.iter().copied());
+        }
+        snapshot.sessions = sessions;
+    }
+
+    if let Some(inbox) = inbox {
+        for message in client.query_messages(inbox, 0, None, None).await? {
+            if let Ok(request) = serde_json::from_slice::<SignRequest>(&message.payload) {
+                if !snapshot.requests.iter().any(|r| r.message == request.message) {
+                    snapshot.requests.push(request);
+                }
+            }
+        }
+        for message in client.query_messages(inbox, 1, None, None).await? {
+            if let Ok(verdict) = serde_json::from_slice::<SignVerdict>(&message.payload) {
+                if verdict.party_id == cli.party_id {
+                    snapshot.verdicts.insert(verdict.message, verdict.approve);
+                }
+            }
+        }
+    }
+    Ok(snapshot)
+}
+
+/// Broadcast this operator's verdict on the selected request
+async fn send_verdict(
+    cli: &crate::Cli,
+    client: &RelayClient,
+    inbox: &dkls23_core::SessionId,
+    request: &SignRequest,
+    approve: bool,
+) -> Result<()> {
+    use dkls23_core::mpc::Relay;
+    let verdict = SignVerdict {
+        message: request.message.clone(),
+        party_id: cli.party_id,
+        approve,
+    };
+    client.broadcast(inbox, 1, &verdict).await?;
+    Ok(())
+}
+
+/// Render one frame of the console
+fn draw(
+    frame: &mut ratatui::Frame,
+    cli: &crate::Cli,
+    snapshot: &Snapshot,
+    list_state: &mut ListState,
+    status: &str,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(6),
+            Constraint::Min(6),
+            Constraint::Length(2),
+        ])
+        .split(frame.size());
+
+    let relay = if snapshot.relay_ok { "up" } else { "DOWN" };
+    let peers: Vec<String> = snapshot.peers.iter().map(|p| p.to_string()).collect();
+    let header = Paragraph::new(format!(
+        "party {}  relay {}  peers seen [{}]  presignatures {}",
+        cli.party_id,
+        relay,
+        peers.join(", "),
+        snapshot.presigs.len()
+    ))
+    .block(Block::default().borders(Borders::ALL).title("dkls-party"));
+    frame.render_widget(header, chunks[0]);
+
+    let sessions: Vec<ListItem> = snapshot
+        .sessions
+        .iter()
+        .map(|s| {
+            ListItem::new(format!(
+                "{}…  round {}  {} msgs  senders {:?}  last {}",
+                &s.session_id[..16.min(s.session_id.len())],
+                s.latest_round,
+                s.message_count,
+                s.senders,
+                s.last_activity.format("%H:%M:%S")
+            ))
+        })
+        .collect();
+    frame.render_widget(
+        List::new(sessions).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Sessions on the relay"),
+        ),
+        chunks[1],
+    );
+
+    let requests: Vec<ListItem> = snapshot
+        .requests
+        .iter()
+        .map(|request| {
+            let verdict = match snapshot.verdicts.get(&request.message) {
+                Some(true) => "approved",
+                Some(false) => "denied",
+                None => "PENDING",
+            };
+            let reason = request.reason.as_deref().unwrap_or("-");
+            ListItem::new(format!(
+                "[{}] {}…  from party {}  ({})",
+                verdict,
+                &request.message[..16.min(request.message.len())],
+                request.requester,
+                reason
+            ))
+        })
+        .collect();
+    frame.render_stateful_widget(
+        List::new(requests)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Signing requests"),
+            ),
+        chunks[2],
+        list_state,
+    );
+
+    frame.render_widget(
+        Paragraph::new(Line::from(format!(
+            "↑/↓ select   a approve   d deny   r refresh   q quit   {}",
+            status
+        ))),
+        chunks[3],
+    );
+}
+
+/// Run the console until the operator quits
+pub async fn run(cli: &crate::Cli) -> Result<()> {
+    let client = crate::session_client(cli)?;
+
+    // The request inbox needs the group key; without a local share the
+    // console still shows relay activity and inventory
+    let inbox = crate::load_key_share(cli)
+        .ok()
+        .map(|share| inbox_id(&share.public_key));
+
+    crossterm::terminal::enable_raw_mode()?;
+    let mut stdout = std::io::stdout();
+    crossterm::execute!(stdout, crossterm::terminal::EnterAlternateScreen)?;
+    let backend = ratatui::backend::CrosstermBackend::new(stdout);
+    let mut terminal = ratatui::Terminal::new(backend)?;
+
+    let result = event_loop(cli, &client, inbox.as_ref(), &mut terminal).await;
+
+    crossterm::terminal::disable_raw_mode()?;
+    crossterm::execute!(
+        terminal.backend_mut(),
+        crossterm::terminal::LeaveAlternateScreen
+    )?;
+    terminal.show_cursor()?;
+    result
+}
+
+async fn event_loop(
+    cli: &crate::Cli,
+    client: &RelayClient,
+    inbox: Option<&dkls23_core::SessionId>,
+    terminal: &mut ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
+) -> Result<()> {
+    let mut snapshot = Snapshot::empty();
+    let mut list_state = ListState::default();
+    let mut last_refresh: Option<Instant> = None;
+    let mut status = if inbox.is_some() {
+        String::new()
+    } else {
+        "no key share loaded; requests hidden".to_string()
+    };
+
+    loop {
+        if last_refresh.is_none_or(|at| at.elapsed() >= REFRESH_INTERVAL) {
+            match refresh(cli, client, inbox).await {
+                Ok(fresh) => snapshot = fresh,
+                Err(e) => status = format!("refresh failed: {}", e),
+            }
+            last_refresh = Some(Instant::now());
+            if list_state.selected().is_none() && !snapshot.requests.is_empty() {
+                list_state.select(Some(0));
+            }
+        }
+
+        terminal.draw(|frame| draw(frame, cli, &snapshot, &mut list_state, &status))?;
+
+        while event::poll(Duration::from_millis(0))? {
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let selected = list_state
+                .selected()
+                .and_then(|i| snapshot.requests.get(i).cloned());
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('r') => last_refresh = None,
+                KeyCode::Down => {
+                    let next = match list_state.selected() {
+                        Some(i) if i + 1 < snapshot.requests.len() => i + 1,
+                        other => other.unwrap_or(0),
+                    };
+                    list_state.select(Some(next));
+                }
+                KeyCode::Up => {
+                    list_state.select(Some(list_state.selected().unwrap_or(0).saturating_sub(1)));
+                }
+                KeyCode::Char(choice @ ('a' | 'd')) => {
+                    if let (Some(inbox), Some(request)) = (inbox, selected) {
+                        let approve = choice == 'a';
+                        match send_verdict(cli, client, inbox, &request, approve).await {
+                            Ok(()) => {
+                                snapshot.verdicts.insert(request.message.clone(), approve);
+                                status = format!(
+                                    "{} {}…",
+                                    if approve { "approved" } else { "denied" },
+                                    &request.message[..16.min(request.message.len())]
+                                );
+                            }
+                            Err(e) => status = format!("verdict failed: {}", e),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inbox_depends_only_on_the_group_key() {
+        let key = [2u8; 33];
+        assert_eq!(inbox_id(&key), inbox_id(&key));
+        assert_ne!(inbox_id(&key), inbox_id(&[3u8; 33]));
+    }
+
+    #[test]
+    fn test_request_reason_is_optional_on_the_wire() {
+        // Requesters may post the minimal form; the console must accept it
+        let request: SignRequest =
+            serde_json::from_str(r#"{"message":"ab","requester":1}"#).unwrap();
+        assert_eq!(request.reason, None);
+    }
+}
@@ -0,0 +1,329 @@
+//! Memory-hard wrapping of key shares at rest
+//!
+//! Encrypts a key share under a passphrase with an Argon2id-derived key,
+//! so a stolen disk image costs an attacker real memory and time per
+//! guess rather than a BLAKE3 call. The KDF parameters ride inside the
+//! envelope, which lets stored shares be unwrapped with whatever they
+//! were wrapped under and re-wrapped automatically once the configured
+//! parameters are strengthened.
+
+use anyhow::{anyhow, bail, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Envelope format version
+const WRAP_VERSION: u32 = 1;
+
+/// Argon2id cost parameters for share wrapping
+///
+/// The defaults (64 MiB, 3 passes, 1 lane) follow current OWASP-style
+/// guidance with margin; `dkls-party kdf-benchmark` recommends stronger
+/// settings when the host can afford them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    /// Number of passes over the memory
+    pub iterations: u32,
+    /// Degree of parallelism (lanes)
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            memory_kib: 64 * 1024,
+            iterations: 3,
+            parallelism: 1,
+        }
+    }
+}
+
+impl KdfParams {
+    /// Whether any cost dimension falls below `other`'s
+    ///
+    /// Drives automatic re-wrapping: a share wrapped under weaker
+    /// parameters is re-wrapped the next time it is loaded.
+    pub fn is_weaker_than(&self, other: &KdfParams) -> bool {
+        self.memory_kib < other.memory_kib
+            || self.iterations < other.iterations
+            || self.parallelism < other.parallelism
+    }
+
+    /// Derive the 32-byte wrapping key for a passphrase and salt
+    fn derive(&self, passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+            .map_err(|e| anyhow!("Invalid KDF parameters: {}", e))?;
+        let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+        let mut key = [0u8; 32];
+        argon2
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| anyhow!("Key derivation failed: {}", e))?;
+        Ok(key)
+    }
+}
+
+/// An encrypted key share as stored on disk
+///
+/// Distinguished from a legacy plaintext share file by its fields, so
+/// loaders can accept both without a separate marker.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WrappedShare {
+    /// Envelope format version
+    pub version: u32,
+    /// Parameters the wrapping key was derived under
+    pub kdf: KdfParams,
+    /// Random per-share KDF salt (hex)
+    pub salt: String,
+    /// Random 96-bit nonce (hex)
+    pub nonce: String,
+    /// ChaCha20-Poly1305 ciphertext of the share JSON (hex)
+    pub ciphertext: String,
+}
+
+/// Encrypt a serialized share under a passphrase
+pub fn wrap(plaintext: &[u8], passphrase: &str, params: &KdfParams) -> Result<WrappedShare> {
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let key = params.derive(passphrase, &salt)?;
+    let cipher = ChaCha20Poly1305::new(&key.into());
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| anyhow!("Share encryption failed"))?;
+
+    Ok(WrappedShare {
+        version: WRAP_VERSION,
+        kdf: *params,
+        salt: hex::encode(salt),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+/// Decrypt a wrapped share with the parameters recorded in its envelope
+pub fn unwrap_share(wrapped: &WrappedShare, passphrase: &str) -> Result<Vec<u8>> {
+    if wrapped.version != WRAP_VERSION {
+        bail!(
+            "Unsupported share envelope version {} (this build supports {})",
+            wrapped.version,
+            WRAP_VERSION
+        );
+    }
+    let salt = hex::decode(&wrapped.salt)?;
+    let nonce = hex::decode(&wrapped.nonce)?;
+    if nonce.len() != 12 {
+        bail!("Invalid share envelope nonce length");
+    }
+    let ciphertext = hex::decode(&wrapped.ciphertext)?;
+
+    let key = wrapped.kdf.derive(passphrase, &salt)?;
+    let cipher = ChaCha20Poly1305::new(&key.into());
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("Share decryption failed; wrong passphrase or corrupted file"))
+}
+
+/// Passphrase-bound wrapper for stored key share content
+///
+/// Owns the wrap/unwrap plumbing so callers deal only in stored content
+/// strings and serialized share bytes, wherever the content lives — a
+/// file or an OS keychain entry. Decrypting reports what should be
+/// upgraded: a legacy plaintext share is re-saved encrypted on first
+/// contact, and an envelope wrapped under weaker KDF parameters than the
+/// vault's is re-wrapped, so a vault-managed share never stays as
+/// plaintext secret material at rest.
+pub struct KeyShareVault {
+    passphrase: String,
+    params: KdfParams,
+}
+
+impl KeyShareVault {
+    /// Bind a passphrase and KDF parameters into a vault
+    pub fn new(passphrase: impl Into<String>, params: KdfParams) -> Self {
+        Self {
+            passphrase: passphrase.into(),
+            params,
+        }
+    }
+
+    /// Encrypt a serialized share to envelope JSON, without touching disk
+    ///
+    /// For callers whose storage is not a file — the OS keychain backend
+    /// stores exactly what this returns.
+    pub fn encrypt_to_string(&self, plaintext: &[u8]) -> Result<String> {
+        let wrapped = wrap(plaintext, &self.passphrase, &self.params)?;
+        Ok(serde_json::to_string_pretty(&wrapped)?)
+    }
+
+    /// Decrypt share content from wherever it was stored
+    ///
+    /// Returns the plaintext and whether the caller should re-save it:
+    /// true for a legacy plaintext share (encrypt on first contact) or
+    /// an envelope wrapped under weaker KDF parameters than the vault's.
+    pub fn decrypt_content(&self, content: &str) -> Result<(Vec<u8>, bool)> {
+        match serde_json::from_str::<WrappedShare>(content) {
+            Ok(wrapped) => {
+                let plaintext = unwrap_share(&wrapped, &self.passphrase)?;
+                Ok((plaintext, wrapped.kdf.is_weaker_than(&self.params)))
+            }
+            Err(_) => Ok((content.as_bytes().to_vec(), true)),
+        }
+    }
+
+
+}
+
+/// One measured calibration point
+#[derive(Debug)]
+pub struct BenchmarkPoint {
+    /// Parameters measured
+    pub params: KdfParams,
+    /// Wall-clock time of one derivation on this host
+    pub elapsed: Duration,
+}
+
+/// Measure a ladder of parameter sets and recommend the strongest one
+/// that stays within `target` per derivation on this host
+///
+/// Every candidate meets the defaults as a floor, so the recommendation
+/// never weakens a share below them.
+pub fn calibrate(target: Duration) -> Result<(Vec<BenchmarkPoint>, KdfParams)> {
+    let candidates = [
+        KdfParams::default(),
+        KdfParams {
+            memory_kib: 128 * 1024,
+            ..KdfParams::default()
+        },
+        KdfParams {
+            memory_kib: 256 * 1024,
+            ..KdfParams::default()
+        },
+        KdfParams {
+            memory_kib: 256 * 1024,
+            iterations: 4,
+            ..KdfParams::default()
+        },
+    ];
+
+    let mut points = Vec::with_capacity(candidates.len());
+    let mut recommended = candidates[0];
+    for params in candidates {
+        let started = Instant::now();
+        params.derive("calibration passphrase", b"calibration-salt")?;
+        let elapsed = started.elapsed();
+        if elapsed <= target && !params.is_weaker_than(&recommended) {
+            recommended = params;
+        }
+        points.push(BenchmarkPoint { params, elapsed });
+        // Stop climbing once the host is already over budget
+        if elapsed > target {
+            break;
+        }
+    }
+
+    Ok((points, recommended))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap parameters so tests do not pay the production KDF cost
+    fn fast_params() -> KdfParams {
+        KdfParams {
+            memory_kib: 1024,
+            iterations: 1,
+            parallelism: 1,
+        }
+    }
+
+    #[test]
+    fn test_wrap_roundtrip_and_wrong_passphrase() {
+        let share = br#"{"party_id":0}"#;
+        let wrapped = wrap(share, "correct horse", &fast_params()).unwrap();
+        assert_eq!(wrapped.kdf, fast_params());
+
+        let plaintext = unwrap_share(&wrapped, "correct horse").unwrap();
+        assert_eq!(plaintext, share);
+
+        assert!(unwrap_share(&wrapped, "battery staple").is_err());
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_is_rejected() {
+        let mut wrapped = wrap(b"secret", "pass", &fast_params()).unwrap();
+        let mut raw = hex::decode(&wrapped.ciphertext).unwrap();
+        raw[0] ^= 0x01;
+        wrapped.ciphertext = hex::encode(raw);
+        assert!(unwrap_share(&wrapped, "pass").is_err());
+    }
+
+    #[test]
+    fn test_weaker_than_ordering() {
+        let base = fast_params();
+        let stronger_memory = KdfParams {
+            memory_kib: base.memory_kib * 2,
+            ..base
+        };
+        let stronger_iterations = KdfParams {
+            iterations: base.iterations + 1,
+            ..base
+        };
+
+        assert!(base.is_weaker_than(&stronger_memory));
+        assert!(base.is_weaker_than(&stronger_iterations));
+        assert!(!stronger_memory.is_weaker_than(&base));
+        assert!(!base.is_weaker_than(&base));
+    }
+
+    #[test]
+    fn test_vault_content_never_stores_plaintext() {
+        let vault = KeyShareVault::new("correct horse", fast_params());
+
+        let stored = vault.encrypt_to_string(br#"{"party_id":1}"#).unwrap();
+        serde_json::from_str::<WrappedShare>(&stored)
+            .expect("the stored content must be a wrapped envelope");
+        assert!(!stored.contains("party_id"));
+
+        let (plaintext, upgrade) = vault.decrypt_content(&stored).unwrap();
+        assert_eq!(plaintext, br#"{"party_id":1}"#);
+        assert!(!upgrade, "content wrapped under current parameters stays");
+    }
+
+    #[test]
+    fn test_legacy_and_weak_content_flagged_for_rewrap() {
+        let vault = KeyShareVault::new("pass", fast_params());
+
+        // Legacy plaintext loads, and must be re-saved encrypted
+        let (plaintext, upgrade) = vault.decrypt_content(r#"{"party_id":2}"#).unwrap();
+        assert_eq!(plaintext, br#"{"party_id":2}"#);
+        assert!(upgrade);
+
+        // So must an envelope under weaker parameters than the vault's
+        let strong_vault = KeyShareVault::new(
+            "pass",
+            KdfParams {
+                iterations: 2,
+                ..fast_params()
+            },
+        );
+        let weak = vault.encrypt_to_string(b"secret").unwrap();
+        let (plaintext, upgrade) = strong_vault.decrypt_content(&weak).unwrap();
+        assert_eq!(plaintext, b"secret");
+        assert!(upgrade);
+    }
+
+    #[test]
+    fn test_plaintext_share_does_not_parse_as_envelope() {
+        // Legacy share files must keep loading via the plaintext path
+        let legacy = r#"{"party_id":0,"n_parties":3,"threshold":2}"#;
+        assert!(serde_json::from_str::<WrappedShare>(legacy).is_err());
+    }
+}
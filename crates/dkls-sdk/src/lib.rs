@@ -0,0 +1,497 @@
+//! High-level facade over dkls23-core ceremonies
+//!
+//! Applications embedding the protocol want one call that either returns
+//! a result or a definitive error, not a session to babysit. The
+//! [`CeremonyRunner`] wraps key generation and signing with bounded
+//! ceremony-level retry: when a ceremony aborts on a transient fault — a
+//! relay outage, a peer timing out — the whole ceremony is re-run under a
+//! fresh session ID derived from the attempt counter, so stale messages
+//! from the failed run can never bleed into the new one. Every party
+//! derives the same per-attempt ID from the same base, so parties that
+//! fail together resume together without extra coordination.
+//!
+//! Cryptographic failures are never retried. A verification failure, a
+//! malicious-party report or an invalid signature means a corrupted share
+//! or an active adversary; re-running cannot fix either and would only
+//! hand an attacker more protocol transcripts to work with.
+
+use dkls23_core::curve::ThresholdCurve;
+use dkls23_core::mpc::Relay;
+use dkls23_core::sign::PreSignatureToken;
+use dkls23_core::{
+    keygen, sign, Error, KeyShare, PartyId, Result, SessionConfig, SessionId, Signature,
+};
+use std::time::Duration;
+use tracing::warn;
+
+/// Domain for deriving per-attempt session IDs from a base session
+const ATTEMPT_SESSION_CONTEXT: &str = "dkls-sdk attempt session v1";
+
+/// Domain for deriving the signing base session from the signing context
+const SIGN_BASE_CONTEXT: &str = "dkls-sdk sign base v1";
+
+/// Bounds on ceremony-level retry
+///
+/// Attempts are spaced by an exponential backoff starting at `backoff`
+/// and doubling per attempt, so a briefly partitioned relay is not
+/// hammered back into overload the moment it recovers.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts per ceremony, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry; later retries double it each time
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that runs every ceremony exactly once
+    pub fn no_retry() -> Self {
+        Self::default().with_max_attempts(1)
+    }
+
+    /// Set the total number of attempts per ceremony
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Set the delay before the first retry
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Backoff before the retry that follows the given failed attempt
+    fn backoff_for(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(attempt.min(16)))
+    }
+}
+
+/// Whether a ceremony abort is worth retrying under a fresh session
+///
+/// Only infrastructure faults qualify: a relay error or a timeout can
+/// resolve on its own. Everything else — verification failures, malicious
+/// party reports, malformed messages, configuration errors — is either a
+/// bug or an attack, and retrying would mask it rather than fix it.
+pub fn is_transient(error: &Error) -> bool {
+    matches!(error, Error::Relay(_) | Error::Timeout(_))
+}
+
+/// One-call ceremony orchestration with bounded retry
+///
+/// Owns a relay and a [`RetryPolicy`] and exposes whole ceremonies as
+/// single calls that retry transient aborts internally.
+pub struct CeremonyRunner<R> {
+    relay: R,
+    policy: RetryPolicy,
+}
+
+impl<R: Relay> CeremonyRunner<R> {
+    /// Wrap a relay with the default retry policy
+    pub fn new(relay: R) -> Self {
+        Self {
+            relay,
+            policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Replace the retry policy
+    pub fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The wrapped relay
+    pub fn relay(&self) -> &R {
+        &self.relay
+    }
+
+    /// Run distributed key generation, retrying transient aborts
+    ///
+    /// `base_session` plays the role the session ID plays in a direct
+    /// [`keygen::run_dkg`] call: all parties must agree on it out of
+    /// band. Each attempt runs under a distinct ID derived from it.
+    pub async fn generate_key(
+        &self,
+        base_session: &SessionId,
+        n_parties: usize,
+        threshold: usize,
+        party_id: PartyId,
+    ) -> Result<KeyShare> {
+        self.generate_key_for_curve(base_session, n_parties, threshold, party_id)
+            .await
+    }
+
+    /// [`Self::generate_key`] over a caller-chosen curve
+    pub async fn generate_key_for_curve<C: ThresholdCurve>(
+        &self,
+        base_session: &SessionId,
+        n_parties: usize,
+        threshold: usize,
+        party_id: PartyId,
+    ) -> Result<KeyShare<C>> {
+        let mut attempt = 0;
+        loop {
+            let config = SessionConfig {
+                session_id: attempt_session_id(base_session, b"dkg", attempt),
+                n_parties,
+                threshold,
+                party_id,
+                parties: (0..n_parties).collect(),
+            };
+            match keygen::run_dkg_for_curve::<C, R>(&config, &self.relay).await {
+                Ok(share) => return Ok(share),
+                Err(err) => self.handle_failure("dkg", &mut attempt, err).await?,
+            }
+        }
+    }
+
+    /// Sign a message hash, retrying transient aborts
+    ///
+    /// Each attempt derives a fresh session from the signing context and
+    /// the attempt counter, then runs the pre-signature phase and the
+    /// partial-signature round under it. Nonces from an aborted attempt
+    /// are abandoned with their session and never reused.
+    pub async fn sign<C: ThresholdCurve>(
+        &self,
+        key_share: &KeyShare<C>,
+        message: &[u8; 32],
+        parties: &[PartyId],
+    ) -> Result<Signature> {
+        if parties.len() < key_share.threshold {
+            return Err(Error::ThresholdNotMet {
+                required: key_share.threshold,
+                actual: parties.len(),
+            });
+        }
+        if !parties.contains(&key_share.party_id) {
+            return Err(Error::InvalidPartyId(key_share.party_id));
+        }
+
+        let base = sign_base_session(&key_share.public_key, parties, message);
+        let mut attempt = 0;
+        loop {
+            let config = SessionConfig {
+                session_id: attempt_session_id(&base, b"dsg", attempt),
+                n_parties: parties.len(),
+                threshold: key_share.threshold,
+                party_id: key_share.party_id,
+                parties: parties.to_vec(),
+            };
+            let result = async {
+                let pre_sig = sign::pre_signature::<C, R>(key_share, &config, &self.relay).await?;
+                let token = PreSignatureToken::new(pre_sig);
+                sign::sign_with_presignature(key_share, token, message, &self.relay).await
+            }
+            .await;
+            match result {
+                Ok(signature) => return Ok(signature),
+                Err(err) => self.handle_failure("dsg", &mut attempt, err).await?,
+            }
+        }
+    }
+
+    /// Decide the fate of a failed attempt
+    ///
+    /// Sleeps the backoff and bumps the attempt counter when the policy
+    /// allows another try, or hands the error back to the caller.
+    async fn handle_failure(&self, ceremony: &str, attempt: &mut u32, err: Error) -> Result<()> {
+        if *attempt + 1 >= self.policy.max_attempts || !is_transient(&err) {
+            return Err(err);
+        }
+        warn!(
+            ceremony,
+            attempt = *attempt,
+            error = %err,
+            "Ceremony aborted on a transient fault; retrying under a fresh session"
+        );
+        tokio::time::sleep(self.policy.backoff_for(*attempt)).await;
+        *attempt += 1;
+        Ok(())
+    }
+}
+
+/// Derive the session ID for one retry attempt
+///
+/// Folds a ceremony label and the attempt counter into the base session,
+/// so every attempt runs under a distinct ID while parties that agree on
+/// the base and the attempt number land on the same one.
+fn attempt_session_id(base: &SessionId, ceremony: &[u8], attempt: u32) -> SessionId {
+    let mut material = Vec::with_capacity(base.len() + ceremony.len() + 4);
+    material.extend_from_slice(base);
+    material.extend_from_slice(ceremony);
+    material.extend_from_slice(&attempt.to_be_bytes());
+    blake3::derive_key(ATTEMPT_SESSION_CONTEXT, &material)
+}
+
+/// Derive the signing base session from the signing context
+///
+/// Mirrors the derivation inside [`sign::run_dsg`] — public key, signing
+/// set and message — under an SDK-specific domain, so retried signings
+/// can never collide with a direct `run_dsg` session.
+fn sign_base_session(public_key: &[u8], parties: &[PartyId], message: &[u8; 32]) -> SessionId {
+    let mut material = Vec::with_capacity(public_key.len() + parties.len() * 8 + 32);
+    material.extend_from_slice(public_key);
+    for &party in parties {
+        material.extend_from_slice(&(party as u64).to_be_bytes());
+    }
+    material.extend_from_slice(message);
+    blake3::derive_key(SIGN_BASE_CONTEXT, &material)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dkls23_core::mpc::{async_trait, MemoryRelay};
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Relay whose first `failures_left` broadcasts fail transiently
+    struct FlakyRelay {
+        inner: Arc<MemoryRelay>,
+        failures_left: AtomicUsize,
+        broadcasts: AtomicUsize,
+    }
+
+    impl FlakyRelay {
+        fn new(inner: Arc<MemoryRelay>, failures: usize) -> Self {
+            Self {
+                inner,
+                failures_left: AtomicUsize::new(failures),
+                broadcasts: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Relay for FlakyRelay {
+        async fn broadcast<T: Serialize + Send + Sync>(
+            &self,
+            session_id: &SessionId,
+            round: u32,
+            message: &T,
+        ) -> Result<()> {
+            self.broadcasts.fetch_add(1, Ordering::SeqCst);
+            let fail = self
+                .failures_left
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if fail {
+                return Err(Error::Relay("simulated outage".to_string()));
+            }
+            self.inner.broadcast(session_id, round, message).await
+        }
+
+        async fn send_direct<T: Serialize + Send + Sync>(
+            &self,
+            session_id: &SessionId,
+            round: u32,
+            to: PartyId,
+            message: &T,
+        ) -> Result<()> {
+            self.inner.send_direct(session_id, round, to, message).await
+        }
+
+        async fn collect_broadcasts<T: DeserializeOwned + Send>(
+            &self,
+            session_id: &SessionId,
+            round: u32,
+            count: usize,
+        ) -> Result<Vec<T>> {
+            self.inner.collect_broadcasts(session_id, round, count).await
+        }
+
+        async fn collect_direct<T: DeserializeOwned + Send>(
+            &self,
+            session_id: &SessionId,
+            round: u32,
+            my_id: PartyId,
+            count: usize,
+        ) -> Result<Vec<T>> {
+            self.inner
+                .collect_direct(session_id, round, my_id, count)
+                .await
+        }
+    }
+
+    /// Relay that reports a malicious party on every broadcast
+    struct HostileRelay {
+        broadcasts: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Relay for HostileRelay {
+        async fn broadcast<T: Serialize + Send + Sync>(
+            &self,
+            _session_id: &SessionId,
+            _round: u32,
+            _message: &T,
+        ) -> Result<()> {
+            self.broadcasts.fetch_add(1, Ordering::SeqCst);
+            Err(Error::MaliciousParty(1))
+        }
+
+        async fn send_direct<T: Serialize + Send + Sync>(
+            &self,
+            _session_id: &SessionId,
+            _round: u32,
+            _to: PartyId,
+            _message: &T,
+        ) -> Result<()> {
+            Err(Error::MaliciousParty(1))
+        }
+
+        async fn collect_broadcasts<T: DeserializeOwned + Send>(
+            &self,
+            _session_id: &SessionId,
+            _round: u32,
+            _count: usize,
+        ) -> Result<Vec<T>> {
+            Err(Error::MaliciousParty(1))
+        }
+
+        async fn collect_direct<T: DeserializeOwned + Send>(
+            &self,
+            _session_id: &SessionId,
+            _round: u32,
+            _my_id: PartyId,
+            _count: usize,
+        ) -> Result<Vec<T>> {
+            Err(Error::MaliciousParty(1))
+        }
+    }
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy::default()
+            .with_max_attempts(max_attempts)
+            .with_backoff(Duration::from_millis(10))
+    }
+
+    #[test]
+    fn test_transient_classification() {
+        assert!(is_transient(&Error::Relay("connection reset".into())));
+        assert!(is_transient(&Error::Timeout("round 1".into())));
+
+        assert!(!is_transient(&Error::VerificationFailed("bad proof".into())));
+        assert!(!is_transient(&Error::MaliciousParty(2)));
+        assert!(!is_transient(&Error::InvalidSignature));
+        assert!(!is_transient(&Error::InvalidConfig("t > n".into())));
+    }
+
+    #[test]
+    fn test_backoff_doubles_per_attempt() {
+        let policy = RetryPolicy::default().with_backoff(Duration::from_millis(100));
+        assert_eq!(policy.backoff_for(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff_for(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff_for(2), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_attempt_sessions_are_distinct() {
+        let base = [0x11u8; 32];
+        let first = attempt_session_id(&base, b"dkg", 0);
+        assert_eq!(first, attempt_session_id(&base, b"dkg", 0));
+        assert_ne!(first, attempt_session_id(&base, b"dkg", 1));
+        assert_ne!(first, attempt_session_id(&base, b"dsg", 0));
+        assert_ne!(first, attempt_session_id(&[0x12u8; 32], b"dkg", 0));
+    }
+
+    #[tokio::test]
+    async fn test_keygen_retries_after_transient_abort() {
+        let shared = Arc::new(MemoryRelay::new());
+        let base_session = [0x21u8; 32];
+
+        // Both parties lose their first broadcast, abort attempt 0
+        // together and reconvene under the attempt-1 session
+        let mut handles = Vec::new();
+        for party_id in 0..2 {
+            let shared = shared.clone();
+            handles.push(tokio::spawn(async move {
+                let runner = CeremonyRunner::new(FlakyRelay::new(shared, 1))
+                    .with_policy(fast_policy(3));
+                runner.generate_key(&base_session, 2, 2, party_id).await
+            }));
+        }
+
+        let mut shares = Vec::new();
+        for handle in handles {
+            shares.push(handle.await.unwrap().unwrap());
+        }
+        assert_eq!(shares[0].public_key, shares[1].public_key);
+    }
+
+    #[tokio::test]
+    async fn test_sign_retries_after_transient_abort() {
+        let shared = Arc::new(MemoryRelay::new());
+        let base_session = [0x22u8; 32];
+        let message = [0x07u8; 32];
+
+        let mut handles = Vec::new();
+        for party_id in 0..2 {
+            let shared = shared.clone();
+            handles.push(tokio::spawn(async move {
+                let runner = CeremonyRunner::new(FlakyRelay::new(shared, 0));
+                let share = runner.generate_key(&base_session, 2, 2, party_id).await?;
+
+                // The outage hits after keygen, during the first signing
+                // attempt
+                runner
+                    .relay()
+                    .failures_left
+                    .store(1, Ordering::SeqCst);
+                let runner = runner.with_policy(fast_policy(3));
+                let signature = runner.sign(&share, &message, &[0, 1]).await?;
+                Ok::<_, Error>(signature)
+            }));
+        }
+
+        let mut signatures = Vec::new();
+        for handle in handles {
+            signatures.push(handle.await.unwrap().unwrap());
+        }
+        assert_eq!(signatures[0].r, signatures[1].r);
+        assert_eq!(signatures[0].s, signatures[1].s);
+    }
+
+    #[tokio::test]
+    async fn test_cryptographic_failures_are_never_retried() {
+        let runner = CeremonyRunner::new(HostileRelay {
+            broadcasts: AtomicUsize::new(0),
+        })
+        .with_policy(fast_policy(5));
+
+        let result = runner.generate_key(&[0x23u8; 32], 2, 2, 0).await;
+        assert!(matches!(result, Err(Error::MaliciousParty(1))));
+        assert_eq!(
+            runner.relay().broadcasts.load(Ordering::SeqCst),
+            1,
+            "a malicious-party abort must not be retried"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_is_bounded() {
+        let shared = Arc::new(MemoryRelay::new());
+        let runner = CeremonyRunner::new(FlakyRelay::new(shared, usize::MAX))
+            .with_policy(fast_policy(2));
+
+        let result = runner.generate_key(&[0x24u8; 32], 2, 2, 0).await;
+        assert!(matches!(result, Err(Error::Relay(_))));
+        assert_eq!(
+            runner.relay().broadcasts.load(Ordering::SeqCst),
+            2,
+            "attempts must stop at the policy bound"
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! Public key export: chain addresses and extended public keys
+//!
+//! Integrators keep re-deriving addresses from `public_key` and
+//! `chain_code` by hand, and address derivation is exactly the kind of
+//! code where a silent mistake sends funds into the void. This module is
+//! the audited counterpart to [`crate::hashing`]: Ethereum addresses
+//! (EIP-55 checksummed), Bitcoin P2WPKH and P2TR addresses, and the
+//! BIP32 xpub serialization, all checked against the published test
+//! vectors.
+
+use crate::hashing::{keccak256, sha256, sha256d, tagged_hash};
+use crate::{Error, Result};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+/// Bitcoin network an address is derived for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    /// Mainnet
+    Bitcoin,
+    /// Testnet (and signet, which shares its parameters)
+    Testnet,
+}
+
+impl Network {
+    /// Bech32 human-readable part for segwit addresses
+    fn hrp(&self) -> &'static str {
+        match self {
+            Network::Bitcoin => "bc",
+            Network::Testnet => "tb",
+        }
+    }
+
+    /// BIP32 version bytes for public extended keys
+    fn xpub_version(&self) -> [u8; 4] {
+        match self {
+            Network::Bitcoin => [0x04, 0x88, 0xb2, 0x1e],
+            Network::Testnet => [0x04, 0x35, 0x87, 0xcf],
+        }
+    }
+}
+
+impl std::str::FromStr for Network {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "bitcoin" | "mainnet" => Ok(Network::Bitcoin),
+            "testnet" | "signet" => Ok(Network::Testnet),
+            other => Err(Error::InvalidConfig(format!("Unknown network: {}", other))),
+        }
+    }
+}
+
+/// Parse a SEC1-encoded secp256k1 public key
+fn parse_public_key(public_key: &[u8]) -> Result<k256::PublicKey> {
+    k256::PublicKey::from_sec1_bytes(public_key)
+        .map_err(|e| Error::InvalidConfig(format!("Invalid public key: {}", e)))
+}
+
+/// Ethereum address: the last 20 bytes of the Keccak-256 of the
+/// uncompressed public key (without the 0x04 prefix)
+pub fn eth_address(public_key: &[u8]) -> Result<[u8; 20]> {
+    let point = parse_public_key(public_key)?.to_encoded_point(false);
+    let digest = keccak256(&point.as_bytes()[1..]);
+    Ok(digest[12..].try_into().expect("sliced to 20 bytes"))
+}
+
+/// Ethereum address as an EIP-55 checksummed `0x...` string
+///
+/// Each hex letter is uppercased when the corresponding nibble of the
+/// Keccak-256 of the lowercase address is 8 or above, so a typo is
+/// overwhelmingly likely to break the checksum.
+pub fn eth_address_checksummed(public_key: &[u8]) -> Result<String> {
+    let lower = hex::encode(eth_address(public_key)?);
+    let digest = keccak256(lower.as_bytes());
+
+    let mut out = String::with_capacity(42);
+    out.push_str("0x");
+    for (i, c) in lower.chars().enumerate() {
+        let nibble = (digest[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+        if c.is_ascii_alphabetic() && nibble >= 8 {
+            out.push(c.to_ascii_uppercase());
+        } else {
+            out.push(c);
+        }
+    }
+    Ok(out)
+}
+
+/// HASH160: RIPEMD-160 of the SHA-256, Bitcoin's address digest
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    use ripemd::{Digest, Ripemd160};
+    Ripemd160::digest(sha256(data)).into()
+}
+
+/// Native segwit v0 (P2WPKH) address for a compressed public key
+pub fn p2wpkh_address(public_key: &[u8], network: Network) -> Result<String> {
+    let compressed = parse_public_key(public_key)?.to_encoded_point(true);
+    Ok(bech32_encode(
+        network.hrp(),
+        0,
+        &hash160(compressed.as_bytes()),
+    ))
+}
+
+/// Taproot (P2TR) key-path address, treating the key as the BIP341
+/// internal key
+///
+/// The output key is the even-y lift of the internal key tweaked by
+/// `tagged_hash("TapTweak", x)`, i.e. a key-path-only output with no
+/// script tree, exactly as BIP86 derives addresses.
+pub fn p2tr_address(public_key: &[u8], network: Network) -> Result<String> {
+    use k256::elliptic_curve::{bigint::U256, ops::Reduce};
+    use k256::{ProjectivePoint, Scalar};
+
+    let point = parse_public_key(public_key)?.to_encoded_point(true);
+    let x_only: [u8; 32] = point.as_bytes()[1..].try_into().expect("32-byte x");
+
+    // Lift to the even-y point BIP340 keys imply
+    let even = k256::PublicKey::from_sec1_bytes(
+        &[&[0x02][..], &x_only[..]].concat(),
+    )
+    .map_err(|e| Error::InvalidConfig(format!("Invalid internal key: {}", e)))?;
+
+    let tweak = <Scalar as Reduce<U256>>::reduce_bytes(&tagged_hash("TapTweak", &x_only).into());
+    let output = ProjectivePoint::from(even.as_affine()) + ProjectivePoint::GENERATOR * tweak;
+    let output_x: [u8; 32] = output.to_affine().to_encoded_point(true).as_bytes()[1..]
+        .try_into()
+        .expect("32-byte x");
+
+    Ok(bech32_encode(network.hrp(), 1, &output_x))
+}
+
+/// BIP32 extended public key for a key at depth zero
+///
+/// Serializes `public_key` and `chain_code` as a master xpub (depth 0,
+/// no parent fingerprint, child number 0) in Base58Check.
+pub fn xpub(public_key: &[u8], chain_code: &[u8; 32], network: Network) -> Result<String> {
+    let compressed = parse_public_key(public_key)?.to_encoded_point(true);
+
+    let mut data = Vec::with_capacity(78);
+    data.extend_from_slice(&network.xpub_version());
+    data.push(0); // depth
+    data.extend_from_slice(&[0u8; 4]); // parent fingerprint
+    data.extend_from_slice(&[0u8; 4]); // child number
+    data.extend_from_slice(chain_code);
+    data.extend_from_slice(compressed.as_bytes());
+
+    let checksum = sha256d(&data);
+    data.extend_from_slice(&checksum[..4]);
+    Ok(bs58::encode(data).into_string())
+}
+
+/// Bech32 character set, per BIP173
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// BIP173 checksum polynomial
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut chk: u32 = 1;
+    for &value in values {
+        let top = (chk >> 25) as u8;
+        chk = (chk & 0x1ff_ffff) << 5 ^ value as u32;
+        for (i, coefficient) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= coefficient;
+            }
+        }
+    }
+    chk
+}
+
+/// Regroup bytes into 5-bit values, padding the tail with zero bits
+fn to_base32(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut accumulator: u32 = 0;
+    let mut bits = 0;
+    for &byte in bytes {
+        accumulator = accumulator << 8 | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(((accumulator >> bits) & 0x1f) as u8);
+        }
+    }
+    if bits > 0 {
+        out.push(((accumulator << (5 - bits)) & 0x1f) as u8);
+    }
+    out
+}
+
+/// Encode a segwit address: bech32 for witness v0, bech32m above
+fn bech32_encode(hrp: &str, witness_version: u8, program: &[u8]) -> String {
+    let mut data = vec![witness_version];
+    data.extend(to_base32(program));
+
+    // BIP350 changed only the checksum constant for v1+
+    let checksum_const: u32 = if witness_version == 0 { 1 } else { 0x2bc830a3 };
+    let mut values: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    values.push(0);
+    values.extend(hrp.bytes().map(|b| b & 0x1f));
+    values.extend_from_slice(&data);
+    values.extend_from_slice(&[0u8; 6]);
+    let polymod = bech32_polymod(&values) ^ checksum_const;
+
+    let mut out = String::with_capacity(hrp.len() + 1 + data.len() + 6);
+    out.push_str(hrp);
+    out.push('1');
+    for value in data {
+        out.push(BECH32_CHARSET[value as usize] as char);
+    }
+    for i in 0..6 {
+        out.push(BECH32_CHARSET[((polymod >> (5 * (5 - i))) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+impl crate::KeyShare {
+    /// EIP-55 checksummed Ethereum address of the group key
+    pub fn eth_address(&self) -> Result<String> {
+        eth_address_checksummed(&self.public_key)
+    }
+
+    /// Native segwit (P2WPKH) address of the group key
+    pub fn btc_p2wpkh_address(&self, network: Network) -> Result<String> {
+        p2wpkh_address(&self.public_key, network)
+    }
+
+    /// Taproot key-path address of the group key
+    pub fn btc_p2tr_address(&self, network: Network) -> Result<String> {
+        p2tr_address(&self.public_key, network)
+    }
+
+    /// Master xpub over the group key and this share's chain code
+    pub fn xpub(&self, network: Network) -> Result<String> {
+        xpub(&self.public_key, &self.chain_code, network)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The generator point: the public key of secret key 1, whose
+    /// addresses are well-known reference values
+    fn generator_key() -> Vec<u8> {
+        hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_eth_address_of_secret_key_one() {
+        assert_eq!(
+            eth_address_checksummed(&generator_key()).unwrap(),
+            "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+        );
+    }
+
+    /// The BIP173 reference address is the P2WPKH of the generator point
+    #[test]
+    fn test_p2wpkh_bip173_vector() {
+        assert_eq!(
+            p2wpkh_address(&generator_key(), Network::Bitcoin).unwrap(),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        assert_eq!(
+            p2wpkh_address(&generator_key(), Network::Testnet).unwrap(),
+            "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+        );
+    }
+
+    /// First receiving address of the BIP86 reference wallet
+    #[test]
+    fn test_p2tr_bip86_vector() {
+        let internal =
+            hex::decode("03cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115")
+                .unwrap();
+        assert_eq!(
+            p2tr_address(&internal, Network::Bitcoin).unwrap(),
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        );
+    }
+
+    /// Master key serialization from BIP32 test vector 1
+    #[test]
+    fn test_xpub_bip32_vector() {
+        let public_key =
+            hex::decode("0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2")
+                .unwrap();
+        let chain_code: [u8; 32] =
+            hex::decode("873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        assert_eq!(
+            xpub(&public_key, &chain_code, Network::Bitcoin).unwrap(),
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJo\
+             Cu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"
+        );
+    }
+
+    #[test]
+    fn test_invalid_key_and_network_are_rejected() {
+        assert!(eth_address(&[0u8; 33]).is_err());
+        assert!(p2wpkh_address(&[1, 2, 3], Network::Bitcoin).is_err());
+        assert!("litecoin".parse::<Network>().is_err());
+        assert_eq!("MAINNET".parse::<Network>().unwrap(), Network::Bitcoin);
+    }
+}
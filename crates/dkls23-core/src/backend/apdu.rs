@@ -0,0 +1,205 @@
+//! APDU bridge to a hardware wallet share holder (experimental)
+//!
+//! Speaks a minimal ISO 7816-4 command set to a companion app running on a
+//! Ledger/Trezor-class device, so the device holds the secret share and
+//! performs all scalar operations on it. The transport is abstracted behind
+//! [`ApduTransport`]; a real deployment plugs in a HID or TCP bridge, while
+//! [`LoopbackTransport`] emulates the companion app in software for tests.
+//!
+//! Command set (CLA `0xE0`):
+//! - INS `0x01` MUL_SHARE: data = 32-byte factor, reply = 32-byte product
+//! - INS `0x02` ADD_SHARE: data = 32-byte delta, no reply data
+//! - INS `0x03` GET_PUBLIC: no data, reply = 33-byte compressed point
+//!
+//! Replies end with the standard `SW1 SW2` status word; anything other than
+//! `0x9000` is surfaced as [`Error::Internal`].
+
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::{ProjectivePoint, Scalar, U256};
+
+use crate::{Error, Result};
+
+use super::SecretShareBackend;
+
+/// Instruction class used by the companion app
+pub const CLA: u8 = 0xE0;
+/// Multiply the on-device share by a 32-byte scalar
+pub const INS_MUL_SHARE: u8 = 0x01;
+/// Add a 32-byte delta to the on-device share
+pub const INS_ADD_SHARE: u8 = 0x02;
+/// Return the compressed public point of the on-device share
+pub const INS_GET_PUBLIC: u8 = 0x03;
+
+/// Success status word
+const SW_OK: [u8; 2] = [0x90, 0x00];
+
+/// Raw APDU exchange with a device
+///
+/// Implementations wrap whatever channel reaches the device (HID, TCP
+/// bridge, smartcard reader). The trait is synchronous because APDU
+/// exchanges are strictly request/response and the underlying OS APIs are
+/// blocking.
+pub trait ApduTransport: Send + Sync {
+    /// Send one command APDU and return the full response including SW1/SW2
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Secret share backend that delegates to a device over APDU
+pub struct ApduBackend<T: ApduTransport> {
+    transport: T,
+}
+
+impl<T: ApduTransport> ApduBackend<T> {
+    /// Create a backend over the given transport
+    pub fn new(transport: T) -> Self {
+        Self { transport }
+    }
+
+    /// Build a command APDU, run it, and return the reply data sans status
+    fn command(&self, ins: u8, data: &[u8]) -> Result<Vec<u8>> {
+        let mut apdu = vec![CLA, ins, 0x00, 0x00, data.len() as u8];
+        apdu.extend_from_slice(data);
+
+        let mut reply = self.transport.exchange(&apdu)?;
+        if reply.len() < 2 {
+            return Err(Error::Internal("APDU reply too short".into()));
+        }
+        let sw = reply.split_off(reply.len() - 2);
+        if sw != SW_OK {
+            return Err(Error::Internal(format!(
+                "APDU command 0x{:02x} failed with status {:02x}{:02x}",
+                ins, sw[0], sw[1]
+            )));
+        }
+        Ok(reply)
+    }
+}
+
+impl<T: ApduTransport> SecretShareBackend for ApduBackend<T> {
+    fn mul_share(&self, factor: &Scalar) -> Result<Scalar> {
+        let reply = self.command(INS_MUL_SHARE, &factor.to_bytes())?;
+        scalar_from_reply(&reply)
+    }
+
+    fn add_to_share(&mut self, delta: &Scalar) -> Result<()> {
+        self.command(INS_ADD_SHARE, &delta.to_bytes())?;
+        Ok(())
+    }
+
+    fn public_share(&self) -> Result<Vec<u8>> {
+        let reply = self.command(INS_GET_PUBLIC, &[])?;
+        if reply.len() != 33 {
+            return Err(Error::Internal(format!(
+                "Expected 33-byte compressed point, got {} bytes",
+                reply.len()
+            )));
+        }
+        Ok(reply)
+    }
+}
+
+/// Decode a 32-byte big-endian scalar reply
+fn scalar_from_reply(reply: &[u8]) -> Result<Scalar> {
+    let bytes: [u8; 32] = reply
+        .try_into()
+        .map_err(|_| Error::Internal(format!("Expected 32-byte scalar, got {} bytes", reply.len())))?;
+    Ok(<Scalar as Reduce<U256>>::reduce_bytes(&bytes.into()))
+}
+
+/// In-process emulation of the companion app
+///
+/// Holds the share in memory and answers the same command set a real device
+/// would, so the bridge can be exercised without hardware.
+pub struct LoopbackTransport {
+    share: std::sync::Mutex<Scalar>,
+}
+
+impl LoopbackTransport {
+    /// Create an emulated device seeded with a share
+    pub fn new(share: Scalar) -> Self {
+        Self {
+            share: std::sync::Mutex::new(share),
+        }
+    }
+
+    fn reply_ok(data: &[u8]) -> Vec<u8> {
+        let mut reply = data.to_vec();
+        reply.extend_from_slice(&SW_OK);
+        reply
+    }
+
+    fn reply_err() -> Vec<u8> {
+        vec![0x6A, 0x80] // wrong data
+    }
+}
+
+impl ApduTransport for LoopbackTransport {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>> {
+        if apdu.len() < 5 || apdu[0] != CLA {
+            return Ok(Self::reply_err());
+        }
+        let data = &apdu[5..];
+        let mut share = self.share.lock().unwrap();
+
+        match apdu[1] {
+            INS_MUL_SHARE => {
+                let Ok(factor) = scalar_from_reply(data) else {
+                    return Ok(Self::reply_err());
+                };
+                let product = *share * factor;
+                Ok(Self::reply_ok(&product.to_bytes()))
+            }
+            INS_ADD_SHARE => {
+                let Ok(delta) = scalar_from_reply(data) else {
+                    return Ok(Self::reply_err());
+                };
+                *share += delta;
+                Ok(Self::reply_ok(&[]))
+            }
+            INS_GET_PUBLIC => {
+                let point = ProjectivePoint::GENERATOR * *share;
+                Ok(Self::reply_ok(
+                    point.to_affine().to_encoded_point(true).as_bytes(),
+                ))
+            }
+            _ => Ok(vec![0x6D, 0x00]), // instruction not supported
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::SoftwareBackend;
+
+    #[test]
+    fn test_apdu_backend_matches_software() {
+        let share = Scalar::from(123456u64);
+        let hw = ApduBackend::new(LoopbackTransport::new(share));
+        let sw = SoftwareBackend::new(share);
+
+        let factor = Scalar::from(789u64);
+        assert_eq!(
+            hw.mul_share(&factor).unwrap(),
+            sw.mul_share(&factor).unwrap()
+        );
+        assert_eq!(hw.public_share().unwrap(), sw.public_share().unwrap());
+    }
+
+    #[test]
+    fn test_apdu_backend_refresh() {
+        let mut hw = ApduBackend::new(LoopbackTransport::new(Scalar::from(5u64)));
+        hw.add_to_share(&Scalar::from(7u64)).unwrap();
+        assert_eq!(
+            hw.mul_share(&Scalar::ONE).unwrap(),
+            Scalar::from(12u64)
+        );
+    }
+
+    #[test]
+    fn test_apdu_unknown_instruction() {
+        let hw = ApduBackend::new(LoopbackTransport::new(Scalar::ONE));
+        assert!(hw.command(0x7F, &[]).is_err());
+    }
+}
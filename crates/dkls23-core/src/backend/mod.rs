@@ -0,0 +1,78 @@
+//! Secret share backends
+//!
+//! Abstracts where a party's secret share lives and who performs the
+//! scalar operations on it. The default [`SoftwareBackend`] keeps the
+//! scalar in process memory (current behavior); the feature-gated
+//! [`apdu`] module delegates the operations to a hardware wallet app so
+//! one share can live in a consumer HSM while the protocol runs on the
+//! host.
+
+use k256::{elliptic_curve::sec1::ToEncodedPoint, ProjectivePoint, Scalar};
+
+use crate::Result;
+
+#[cfg(feature = "hw-wallet")]
+pub mod apdu;
+
+/// Operations the protocol needs from a secret share holder
+///
+/// These are exactly the scalar operations the signing and refresh flows
+/// perform on the share; a backend never has to reveal the share itself.
+pub trait SecretShareBackend: Send + Sync {
+    /// Multiply the secret share by a factor and return the product
+    fn mul_share(&self, factor: &Scalar) -> Result<Scalar>;
+
+    /// Add a delta to the stored secret share (used by key refresh)
+    fn add_to_share(&mut self, delta: &Scalar) -> Result<()>;
+
+    /// Compressed public point `share * G`
+    fn public_share(&self) -> Result<Vec<u8>>;
+}
+
+/// Backend holding the secret share in process memory
+pub struct SoftwareBackend {
+    share: Scalar,
+}
+
+impl SoftwareBackend {
+    /// Create a backend from a raw secret share
+    pub fn new(share: Scalar) -> Self {
+        Self { share }
+    }
+}
+
+impl SecretShareBackend for SoftwareBackend {
+    fn mul_share(&self, factor: &Scalar) -> Result<Scalar> {
+        Ok(self.share * factor)
+    }
+
+    fn add_to_share(&mut self, delta: &Scalar) -> Result<()> {
+        self.share += delta;
+        Ok(())
+    }
+
+    fn public_share(&self) -> Result<Vec<u8>> {
+        let point = ProjectivePoint::GENERATOR * self.share;
+        Ok(point.to_affine().to_encoded_point(true).as_bytes().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_software_backend_mul() {
+        let backend = SoftwareBackend::new(Scalar::from(6u64));
+        let product = backend.mul_share(&Scalar::from(7u64)).unwrap();
+        assert_eq!(product, Scalar::from(42u64));
+    }
+
+    #[test]
+    fn test_software_backend_refresh() {
+        let mut backend = SoftwareBackend::new(Scalar::from(1u64));
+        backend.add_to_share(&Scalar::from(2u64)).unwrap();
+        let product = backend.mul_share(&Scalar::ONE).unwrap();
+        assert_eq!(product, Scalar::from(3u64));
+    }
+}
@@ -0,0 +1,135 @@
+//! Canonical serialization for hashed and signed structures
+//!
+//! Transcript digests, broadcast-set hashes and envelope signatures all
+//! commit to serialized message bytes, so two honest parties must encode
+//! the same value to the same bytes — across builds, serde versions and
+//! field-attribute changes. This module pins down that encoding:
+//! compact JSON with object keys in lexicographic order and no
+//! insignificant whitespace.
+//!
+//! Determinism comes from routing every value through
+//! [`serde_json::Value`], whose map is backed by a `BTreeMap` and
+//! therefore iterates keys in sorted order regardless of how the source
+//! type orders its fields or what map type it uses internally.
+//! Floating-point numbers are rejected outright: protocol structures
+//! carry none, and floats are the one JSON type whose text form varies
+//! between formatters.
+//!
+//! Relay-side hashes (message content addressing, shipping manifests)
+//! operate on raw payload bytes rather than re-serialized structures,
+//! so they need no canonicalization.
+
+use crate::{Error, Result};
+use serde::Serialize;
+
+/// Serialize a value to its canonical byte representation
+///
+/// The output is compact JSON with lexicographically sorted object keys.
+/// Any structure containing a floating-point number or a map with
+/// non-string keys is rejected, since neither has a stable canonical
+/// form in JSON.
+pub fn to_vec<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    let value = serde_json::to_value(value).map_err(|e| Error::Serialization(e.to_string()))?;
+    reject_floats(&value)?;
+    // serde_json's Map is a BTreeMap (the preserve_order feature is not
+    // enabled anywhere in this workspace), so encoding the Value emits
+    // keys in sorted order
+    serde_json::to_vec(&value).map_err(|e| Error::Serialization(e.to_string()))
+}
+
+/// Canonical bytes fed through BLAKE3
+///
+/// Convenience for commitments over whole structures; transcript-style
+/// running hashes should fold [`to_vec`] output into their own framing.
+pub fn digest<T: Serialize>(value: &T) -> Result<[u8; 32]> {
+    Ok(*blake3::hash(&to_vec(value)?).as_bytes())
+}
+
+/// Refuse values whose text encoding is not canonical
+fn reject_floats(value: &serde_json::Value) -> Result<()> {
+    match value {
+        serde_json::Value::Number(n) if n.as_i64().is_none() && n.as_u64().is_none() => Err(
+            Error::Serialization("Floating-point values have no canonical encoding".into()),
+        ),
+        serde_json::Value::Array(items) => items.iter().try_for_each(reject_floats),
+        serde_json::Value::Object(map) => map.values().try_for_each(reject_floats),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+    use std::collections::HashMap;
+
+    #[derive(Serialize)]
+    struct Unsorted {
+        zeta: u32,
+        alpha: Vec<u8>,
+        mid: Option<String>,
+    }
+
+    #[test]
+    fn test_keys_are_sorted_and_output_is_compact() {
+        let bytes = to_vec(&Unsorted {
+            zeta: 7,
+            alpha: vec![1, 2],
+            mid: None,
+        })
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            r#"{"alpha":[1,2],"mid":null,"zeta":7}"#
+        );
+    }
+
+    #[test]
+    fn test_map_iteration_order_does_not_matter() {
+        // HashMap iteration order is randomized per process; canonical
+        // encoding must erase it
+        let mut a = HashMap::new();
+        let mut b = HashMap::new();
+        for key in ["x", "b", "m", "a", "z"] {
+            a.insert(key.to_string(), key.len() as u64);
+            b.insert(key.to_string(), key.len() as u64);
+        }
+        assert_eq!(to_vec(&a).unwrap(), to_vec(&b).unwrap());
+        assert_eq!(
+            String::from_utf8(to_vec(&a).unwrap()).unwrap(),
+            r#"{"a":1,"b":1,"m":1,"x":1,"z":1}"#
+        );
+    }
+
+    #[test]
+    fn test_floats_are_rejected() {
+        assert!(to_vec(&1.5f64).is_err());
+        assert!(to_vec(&vec![1.5f32]).is_err());
+        // Integral types are fine, including ones that exceed i64
+        assert!(to_vec(&u64::MAX).is_ok());
+    }
+
+    /// Golden vector: the canonical bytes of a representative round
+    /// message must never change across versions, or every recorded
+    /// transcript digest silently diverges. Update this only with a
+    /// protocol version bump.
+    #[test]
+    fn test_cross_version_stability() {
+        let message = crate::keygen::DkgRound1Message {
+            party_id: 2,
+            commitments: vec![vec![0xab, 0xcd], vec![0x01]],
+            capabilities: 3,
+            pok_point: Vec::new(),
+            pok_response: Vec::new(),
+        };
+        let bytes = to_vec(&message).unwrap();
+        assert_eq!(
+            String::from_utf8(bytes.clone()).unwrap(),
+            r#"{"capabilities":3,"commitments":[[171,205],[1]],"party_id":2}"#
+        );
+        assert_eq!(
+            hex::encode(digest(&message).unwrap()),
+            hex::encode(*blake3::hash(&bytes).as_bytes())
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Runtime capability discovery and negotiation
+//!
+//! Parties advertise a capability bitmap in their session-setup broadcasts;
+//! everyone computes the intersection and records it in the transcript, so
+//! mixed-version fleets agree up front on what the ceremony may use
+//! instead of failing mid-round when one node hits an unknown feature.
+
+use serde::{Deserialize, Serialize};
+
+/// Capability bitmap advertised during session setup
+///
+/// Bits may be added but never reassigned: an older node treats unknown
+/// bits as features it does not have, which drops them from the
+/// intersection automatically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities(pub u64);
+
+impl Capabilities {
+    /// ECDSA over secp256k1
+    pub const SCHEME_SECP256K1: u64 = 1 << 0;
+    /// ECDSA over P-256
+    pub const SCHEME_P256: u64 = 1 << 1;
+    /// Ed25519
+    pub const SCHEME_ED25519: u64 = 1 << 2;
+    /// JSON round-message codec
+    pub const CODEC_JSON: u64 = 1 << 3;
+    /// zstd compression of round messages
+    pub const COMPRESSION_ZSTD: u64 = 1 << 4;
+    /// End-to-end encrypted envelopes
+    pub const E2E_ENCRYPTION: u64 = 1 << 5;
+    /// Batched ceremonies over one transport session
+    pub const BATCH_SIGNING: u64 = 1 << 6;
+
+    /// What every release has supported since the capability bit existed;
+    /// also what a node advertising nothing (a pre-capability release) is
+    /// assumed to speak
+    pub const BASELINE: u64 = Self::SCHEME_SECP256K1 | Self::CODEC_JSON;
+
+    /// Capabilities of this build
+    pub fn local() -> Self {
+        let mut bits = Self::BASELINE | Self::E2E_ENCRYPTION | Self::BATCH_SIGNING;
+        if cfg!(feature = "scheme-p256") {
+            bits |= Self::SCHEME_P256;
+        }
+        if cfg!(feature = "scheme-ed25519") {
+            bits |= Self::SCHEME_ED25519;
+        }
+        Self(bits)
+    }
+
+    /// Interpret a wire value, mapping the pre-capability zero to baseline
+    pub fn from_wire(bits: u64) -> Self {
+        if bits == 0 {
+            Self(Self::BASELINE)
+        } else {
+            Self(bits)
+        }
+    }
+
+    /// Intersect with another party's capabilities
+    pub fn intersect(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Whether every bit in `required` is present
+    pub fn supports(self, required: u64) -> bool {
+        self.0 & required == required
+    }
+
+    /// Human-readable names of the set bits, for transcripts and logs
+    pub fn names(self) -> Vec<&'static str> {
+        const TABLE: [(u64, &str); 7] = [
+            (Capabilities::SCHEME_SECP256K1, "scheme-secp256k1"),
+            (Capabilities::SCHEME_P256, "scheme-p256"),
+            (Capabilities::SCHEME_ED25519, "scheme-ed25519"),
+            (Capabilities::CODEC_JSON, "codec-json"),
+            (Capabilities::COMPRESSION_ZSTD, "compression-zstd"),
+            (Capabilities::E2E_ENCRYPTION, "e2e-encryption"),
+            (Capabilities::BATCH_SIGNING, "batch-signing"),
+        ];
+        TABLE
+            .iter()
+            .filter(|(bit, _)| self.0 & bit != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+/// Intersect everyone's advertised capabilities and check the floor
+///
+/// Returns the negotiated set, or an error if the intersection is missing
+/// something the ceremony cannot run without.
+pub fn negotiate(advertised: &[u64], required: u64) -> crate::Result<Capabilities> {
+    let negotiated = advertised
+        .iter()
+        .map(|&bits| Capabilities::from_wire(bits))
+        .fold(Capabilities(u64::MAX), Capabilities::intersect);
+
+    if !negotiated.supports(required) {
+        let missing = Capabilities(required & !negotiated.0);
+        return Err(crate::Error::InvalidConfig(format!(
+            "Capability negotiation failed; fleet is missing: {}",
+            missing.names().join(", ")
+        )));
+    }
+
+    Ok(negotiated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiation_intersects_and_checks_floor() {
+        let full = Capabilities::local().0;
+        let old = Capabilities::BASELINE;
+
+        let negotiated = negotiate(&[full, old], Capabilities::BASELINE).unwrap();
+        assert_eq!(negotiated.0, Capabilities::BASELINE);
+
+        // A fleet member without the required scheme fails loudly up front
+        let err = negotiate(
+            &[full, Capabilities::CODEC_JSON],
+            Capabilities::BASELINE,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("scheme-secp256k1"));
+    }
+
+    #[test]
+    fn test_zero_wire_value_means_legacy_baseline() {
+        assert_eq!(Capabilities::from_wire(0).0, Capabilities::BASELINE);
+        let negotiated = negotiate(&[0, Capabilities::local().0], Capabilities::BASELINE).unwrap();
+        assert_eq!(negotiated.0, Capabilities::BASELINE);
+    }
+
+    #[test]
+    fn test_names_follow_bits() {
+        let caps = Capabilities(Capabilities::SCHEME_SECP256K1 | Capabilities::BATCH_SIGNING);
+        assert_eq!(caps.names(), vec!["scheme-secp256k1", "batch-signing"]);
+    }
+}
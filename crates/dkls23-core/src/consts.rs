@@ -0,0 +1,102 @@
+//! Protocol constants and domain-separation contexts
+//!
+//! Single home for the protocol's fixed parameters: the versioned
+//! protocol identifier, round numbering, security parameters, and every
+//! BLAKE3 key-derivation context used across the crate. Centralizing
+//! them keeps two modules from ever deriving under the same context by
+//! accident and gives auditors one file to diff against the spec.
+//!
+//! Transcript labels live with the hashing they feed in
+//! [`crate::transcript`]; relay wire tags live in the `msg-relay` crate
+//! that owns the wire contract.
+
+/// Stable versioned protocol identifier
+///
+/// Folded into every ceremony transcript, so digests produced by a
+/// future protocol revision can never collide with today's. Bump the
+/// trailing version only alongside [`crate::PROTOCOL_VERSION`].
+pub const PROTOCOL_IDENT: &str = "dkls23/1";
+
+/// Computational security parameter: base OTs per extension
+pub const KAPPA: usize = 128;
+
+/// Bits per scalar input to the MtA conversion
+pub const XI: usize = 256;
+
+/// Broadcast rounds in the DKG flow (commitments, shares, complaints)
+pub const DKG_BROADCAST_ROUNDS: u32 = 3;
+
+/// DKG round carrying justifications after a complaint
+pub const DKG_JUSTIFICATION_ROUND: u32 = 4;
+
+/// Broadcast rounds in the DSG flow (the key check is round 0)
+pub const DSG_BROADCAST_ROUNDS: u32 = 3;
+
+/// MtA flight 1: receiver's base-OT public keys
+pub const MTA_ROUND_1: u32 = 11;
+
+/// MtA flight 2: sender's base-OT key pairs
+pub const MTA_ROUND_2: u32 = 12;
+
+/// MtA flight 3: receiver's masked extension rows
+pub const MTA_ROUND_3: u32 = 13;
+
+/// MtA flight 4: sender's correction scalars
+pub const MTA_ROUND_4: u32 = 14;
+
+/// Default timeout for relay HTTP requests, in seconds
+pub const DEFAULT_RELAY_TIMEOUT_SECS: u64 = 30;
+
+/// DSG session IDs derived from the signing context
+pub const DSG_SESSION_CONTEXT: &str = "dkls23-core dsg session v1";
+
+/// Challenge derivation for the DKG constant-term proof of knowledge
+pub const DKG_POK_CONTEXT: &str = "dkls23-core dkg pok v1";
+
+/// Schnorr signing session IDs derived from the signing context
+pub const SCHNORR_SESSION_CONTEXT: &str = "dkls23-core schnorr session v1";
+
+/// Schnorr nonce commitment hashing
+pub const SCHNORR_NONCE_CONTEXT: &str = "dkls23-core schnorr nonce commitment v1";
+
+/// Ed25519 signing session IDs derived from the signing context
+pub const ED25519_SESSION_CONTEXT: &str = "dkls23-core ed25519 session v1";
+
+/// Ed25519 nonce commitment hashing
+pub const ED25519_NONCE_CONTEXT: &str = "dkls23-core ed25519 nonce commitment v1";
+
+/// Ed25519 key share fingerprints
+pub const ED25519_FINGERPRINT_CONTEXT: &str = "dkls23-core ed25519 key fingerprint v1";
+
+/// Integrity tags stamped on persisted key shares
+pub const KEY_SHARE_INTEGRITY_CONTEXT: &str = "dkls23-core key share integrity v1";
+
+/// SoftSpokenOT pseudorandom generator expansion
+pub const SOFT_SPOKEN_PRG_CONTEXT: &str = "dkls23-core soft spoken prg v1";
+
+/// SoftSpokenOT output string derivation
+pub const EXTENSION_OUTPUT_CONTEXT: &str = "dkls23-core soft spoken output v1";
+
+/// Endemic OT output string derivation
+pub const OT_OUTPUT_CONTEXT: &str = "dkls23-core endemic ot output v1";
+
+/// Sealing key for dealt shares during key import
+pub const IMPORT_SEAL_CONTEXT: &str = "dkls23-core import seal v1";
+
+/// At-rest encryption key for pooled pre-signatures
+pub const POOL_KEY_CONTEXT: &str = "dkls23-core presig pool key v1";
+
+/// Session IDs for pool refill ceremonies
+pub const POOL_SESSION_CONTEXT: &str = "dkls23-core presig pool session v1";
+
+/// Committee key agreement for sealed envelopes
+pub const COMMITTEE_KEY_CONTEXT: &str = "dkls23-core committee key v1";
+
+/// Pairwise subkeys for sealed direct messages
+pub const DIRECT_KEY_CONTEXT: &str = "dkls23-core direct subkey v1";
+
+/// Guardian approval signatures for escrowed backups
+pub const APPROVAL_CONTEXT: &[u8] = b"dkls23-core escrow approval v1";
+
+/// Party identity signatures over relay envelopes
+pub const IDENTITY_SIGN_CONTEXT: &[u8] = b"dkls23-core identity envelope v2";
@@ -0,0 +1,122 @@
+//! Curve abstraction for the threshold ECDSA flows
+//!
+//! The DKG and DSG protocols only need generic Weierstrass arithmetic:
+//! scalar field operations, generator multiplication and compressed SEC1
+//! point encoding. [`ThresholdCurve`] captures exactly that surface on
+//! top of the RustCrypto `elliptic_curve` traits, so the same protocol
+//! code runs over secp256k1 (the default, always compiled in) and NIST
+//! P-256 (behind the `scheme-p256` feature). All wire messages carry
+//! points and scalars as bytes, so the message types — and the relay
+//! stack above them — are shared across curves unchanged.
+//!
+//! Ed25519 is deliberately not expressed through this trait: EdDSA is a
+//! Schnorr-style protocol with its own flow, implemented separately in
+//! [`crate::eddsa`].
+
+use elliptic_curve::{
+    bigint::U256,
+    consts::U32,
+    group::Curve as _,
+    sec1::{EncodedPoint, FromEncodedPoint, ToEncodedPoint},
+    Curve, CurveArithmetic, PrimeCurve,
+};
+
+use crate::scheme::SchemeId;
+use crate::{Error, Result};
+
+/// A short-Weierstrass curve the DKG and DSG protocols can run over
+///
+/// Restricted to curves with a 256-bit scalar field so shares, nonces and
+/// signature components are 32 bytes on every instantiation and the MtA
+/// bit decomposition has a fixed width. Scalars must be zeroizable so
+/// key shares and nonces can be wiped on drop rather than lingering in
+/// freed memory.
+pub trait ThresholdCurve:
+    PrimeCurve
+    + Curve<Uint = U256, FieldBytesSize = U32>
+    + CurveArithmetic<
+        Scalar: zeroize::DefaultIsZeroes,
+        AffinePoint: FromEncodedPoint<Self> + ToEncodedPoint<Self>,
+    >
+{
+    /// Scheme identifier recorded on key shares for this curve
+    const SCHEME: SchemeId;
+    /// Transcript label for DKG ceremonies over this curve
+    const DKG_LABEL: &'static str;
+    /// Transcript label for DSG ceremonies over this curve
+    const DSG_LABEL: &'static str;
+}
+
+impl ThresholdCurve for k256::Secp256k1 {
+    const SCHEME: SchemeId = SchemeId::Secp256k1;
+    const DKG_LABEL: &'static str = crate::transcript::DKG_LABEL;
+    const DSG_LABEL: &'static str = crate::transcript::DSG_LABEL;
+}
+
+#[cfg(feature = "scheme-p256")]
+impl ThresholdCurve for p256::NistP256 {
+    const SCHEME: SchemeId = SchemeId::P256;
+    const DKG_LABEL: &'static str = crate::transcript::P256_DKG_LABEL;
+    const DSG_LABEL: &'static str = crate::transcript::P256_DSG_LABEL;
+}
+
+/// Encode a point as compressed SEC1 bytes
+pub fn encode_point<C: ThresholdCurve>(point: &C::ProjectivePoint) -> Vec<u8> {
+    point.to_affine().to_encoded_point(true).as_bytes().to_vec()
+}
+
+/// Decode a SEC1-encoded point, rejecting encodings not on the curve
+pub fn decode_point<C: ThresholdCurve>(bytes: &[u8]) -> Result<C::ProjectivePoint> {
+    let encoded =
+        EncodedPoint::<C>::from_bytes(bytes).map_err(|e| Error::Deserialization(e.to_string()))?;
+    let affine = Option::<C::AffinePoint>::from(C::AffinePoint::from_encoded_point(&encoded))
+        .ok_or_else(|| Error::VerificationFailed("Invalid curve point".into()))?;
+    Ok(C::ProjectivePoint::from(affine))
+}
+
+/// Reduce 32 big-endian bytes into a scalar mod the curve order
+pub fn reduce_scalar_bytes<C: ThresholdCurve>(bytes: &[u8; 32]) -> C::Scalar {
+    use elliptic_curve::ops::Reduce;
+    <C::Scalar as Reduce<U256>>::reduce_bytes(&(*bytes).into())
+}
+
+/// Canonical 32-byte big-endian encoding of a scalar
+pub fn scalar_to_bytes<C: ThresholdCurve>(scalar: &C::Scalar) -> [u8; 32] {
+    use elliptic_curve::PrimeField;
+    scalar.to_repr().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elliptic_curve::{Field, Group};
+
+    fn point_roundtrip<C: ThresholdCurve>() {
+        let scalar = C::Scalar::random(&mut rand::rngs::OsRng);
+        let point = C::ProjectivePoint::generator() * scalar;
+
+        let encoded = encode_point::<C>(&point);
+        assert_eq!(encoded.len(), 33, "compressed SEC1 point is 33 bytes");
+        assert_eq!(decode_point::<C>(&encoded).unwrap(), point);
+
+        let bytes = scalar_to_bytes::<C>(&scalar);
+        assert_eq!(reduce_scalar_bytes::<C>(&bytes), scalar);
+    }
+
+    #[test]
+    fn test_secp256k1_codec_roundtrip() {
+        point_roundtrip::<k256::Secp256k1>();
+    }
+
+    #[cfg(feature = "scheme-p256")]
+    #[test]
+    fn test_p256_codec_roundtrip() {
+        point_roundtrip::<p256::NistP256>();
+    }
+
+    #[test]
+    fn test_decode_point_rejects_garbage() {
+        assert!(decode_point::<k256::Secp256k1>(&[0u8; 33]).is_err());
+        assert!(decode_point::<k256::Secp256k1>(&[1, 2, 3]).is_err());
+    }
+}
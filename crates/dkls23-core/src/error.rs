@@ -32,6 +32,10 @@ pub enum Error {
     #[error("Deserialization error: {0}")]
     Deserialization(String),
 
+    /// A persisted key share failed its integrity check
+    #[error("Corrupt key share: {0}")]
+    CorruptKeyShare(String),
+
     /// Cryptographic operation failed
     #[error("Cryptographic error: {0}")]
     Crypto(String),
@@ -44,6 +48,18 @@ pub enum Error {
     #[error("Timeout waiting for {0}")]
     Timeout(String),
 
+    /// Message arrived for a round the state machine is not collecting
+    #[error("Unexpected message for round {actual}; session is in round {expected}")]
+    UnexpectedRound { expected: u32, actual: u32 },
+
+    /// A collect saw more than one message from the same sender
+    #[error("Round {round} collected a duplicate message from party {party}")]
+    DuplicateSender { round: u32, party: usize },
+
+    /// Signing queue rejected a request
+    #[error("Signing queue is full: {0}")]
+    QueueFull(String),
+
     /// Session not found
     #[error("Session not found: {0}")]
     SessionNotFound(String),
@@ -52,10 +68,38 @@ pub enum Error {
     #[error("Invalid signature")]
     InvalidSignature,
 
+    /// A party's partial signature did not match its commitments
+    #[error("Malicious party detected: {0}")]
+    MaliciousParty(usize),
+
+    /// Parties disagree on the key material they are signing under
+    #[error("Party {party} holds different key material (fingerprint {fingerprint})")]
+    KeyMismatch { party: usize, fingerprint: String },
+
+    /// An authenticated envelope's signed timestamp falls outside the
+    /// configured skew tolerance or freshness window
+    ///
+    /// `skew_ms` is the sender's timestamp minus local time: positive
+    /// means future-dated, negative means stale.
+    #[error("Message from party {party} is timestamped {skew_ms} ms relative to local time (limit {limit_ms} ms)")]
+    TimestampOutOfWindow {
+        party: usize,
+        skew_ms: i64,
+        limit_ms: u64,
+    },
+
     /// Key derivation error
     #[error("Key derivation error: {0}")]
     Derivation(String),
 
+    /// Scheme required by a key share is not compiled into this binary
+    #[error("Unsupported scheme: {0}")]
+    UnsupportedScheme(crate::scheme::SchemeId),
+
+    /// Protocol version is below the minimum the key requires
+    #[error("Protocol version {actual} is below the minimum {required} required by this key")]
+    ProtocolVersionTooOld { required: u32, actual: u32 },
+
     /// Internal error
     #[error("Internal error: {0}")]
     Internal(String),
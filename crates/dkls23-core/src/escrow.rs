@@ -0,0 +1,220 @@
+//! Guardian approval policy for key share escrow
+//!
+//! Restoring a key share from escrow is the one operation that puts a
+//! whole share back into a single pair of hands, so it must not be a
+//! unilateral act. A policy names k guardian keys and requires signed
+//! approvals from m of them over the exact backup being restored; the
+//! restoring side verifies the approvals before decrypting anything and
+//! records who approved in its audit trail.
+//!
+//! Guardians sign the digest of the escrowed backup, not the restore
+//! request, so an approval collected for one backup cannot be replayed
+//! to release a different one.
+
+use crate::{Error, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::consts::APPROVAL_CONTEXT;
+
+/// A guardian's signing key
+pub struct GuardianKey {
+    signing_key: SigningKey,
+}
+
+impl GuardianKey {
+    /// Generate a fresh guardian keypair
+    pub fn generate() -> Self {
+        Self {
+            signing_key: SigningKey::from_bytes(&rand::random::<[u8; 32]>()),
+        }
+    }
+
+    /// Load a guardian key from its 32-byte secret
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let secret: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| Error::Deserialization("Guardian key must be 32 bytes".into()))?;
+        Ok(Self {
+            signing_key: SigningKey::from_bytes(&secret),
+        })
+    }
+
+    /// The 32-byte secret, for storage at rest
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.signing_key.to_bytes()
+    }
+
+    /// The public key enrolled in escrow policies
+    pub fn public_key(&self) -> [u8; 32] {
+        self.signing_key.verifying_key().to_bytes()
+    }
+
+    /// Approve restoration of the given escrowed backup
+    pub fn approve(&self, backup: &[u8]) -> Approval {
+        let signature = self.signing_key.sign(&approval_frame(backup));
+        Approval {
+            guardian: self.public_key(),
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+}
+
+/// One guardian's signed approval of a restore
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Approval {
+    /// Approving guardian's public key
+    pub guardian: [u8; 32],
+    /// Ed25519 signature over the approval frame
+    pub signature: Vec<u8>,
+}
+
+/// m-of-k guardian policy governing restores from escrow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuardianPolicy {
+    /// Approvals required to release a backup
+    pub required: usize,
+    /// Enrolled guardian public keys
+    pub guardians: Vec<[u8; 32]>,
+}
+
+impl GuardianPolicy {
+    /// Create a policy requiring `required` of the given guardian keys
+    pub fn new(required: usize, guardians: Vec<[u8; 32]>) -> Result<Self> {
+        if required == 0 {
+            return Err(Error::InvalidConfig(
+                "Guardian policy must require at least one approval".into(),
+            ));
+        }
+        if required > guardians.len() {
+            return Err(Error::InvalidConfig(format!(
+                "Policy requires {} approvals but enrolls only {} guardians",
+                required,
+                guardians.len()
+            )));
+        }
+        Ok(Self { required, guardians })
+    }
+
+    /// Verify that the approvals release this backup under the policy
+    ///
+    /// Returns the approving guardians' public keys (for the audit
+    /// record). Approvals from unenrolled keys, invalid signatures and
+    /// duplicate guardians are all ignored rather than fatal, so a mixed
+    /// bag of approvals succeeds as long as m valid ones are among them.
+    pub fn verify_restore(&self, backup: &[u8], approvals: &[Approval]) -> Result<Vec<[u8; 32]>> {
+        let frame = approval_frame(backup);
+        let mut approved: Vec<[u8; 32]> = Vec::new();
+
+        for approval in approvals {
+            if !self.guardians.contains(&approval.guardian) {
+                continue;
+            }
+            if approved.contains(&approval.guardian) {
+                continue;
+            }
+            let Ok(key) = VerifyingKey::from_bytes(&approval.guardian) else {
+                continue;
+            };
+            let Ok(signature) = Signature::from_slice(&approval.signature) else {
+                continue;
+            };
+            if key.verify(&frame, &signature).is_ok() {
+                approved.push(approval.guardian);
+            }
+        }
+
+        if approved.len() < self.required {
+            return Err(Error::ThresholdNotMet {
+                required: self.required,
+                actual: approved.len(),
+            });
+        }
+        Ok(approved)
+    }
+}
+
+/// The exact bytes a guardian approval signs
+fn approval_frame(backup: &[u8]) -> Vec<u8> {
+    let digest = blake3::hash(backup);
+    let mut frame = Vec::with_capacity(APPROVAL_CONTEXT.len() + 32);
+    frame.extend_from_slice(APPROVAL_CONTEXT);
+    frame.extend_from_slice(digest.as_bytes());
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_m_of_k_approvals_release_backup() {
+        let guardians: Vec<GuardianKey> = (0..3).map(|_| GuardianKey::generate()).collect();
+        let policy = GuardianPolicy::new(
+            2,
+            guardians.iter().map(|g| g.public_key()).collect(),
+        )
+        .unwrap();
+        let backup = b"sealed key share bytes";
+
+        // One approval is not enough
+        let one = vec![guardians[0].approve(backup)];
+        assert!(matches!(
+            policy.verify_restore(backup, &one),
+            Err(Error::ThresholdNotMet {
+                required: 2,
+                actual: 1
+            })
+        ));
+
+        // Two distinct guardians release it; the approvers are reported
+        let two = vec![guardians[0].approve(backup), guardians[2].approve(backup)];
+        let approved = policy.verify_restore(backup, &two).unwrap();
+        assert_eq!(
+            approved,
+            vec![guardians[0].public_key(), guardians[2].public_key()]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_and_foreign_approvals_do_not_count() {
+        let guardians: Vec<GuardianKey> = (0..2).map(|_| GuardianKey::generate()).collect();
+        let outsider = GuardianKey::generate();
+        let policy = GuardianPolicy::new(
+            2,
+            guardians.iter().map(|g| g.public_key()).collect(),
+        )
+        .unwrap();
+        let backup = b"sealed key share bytes";
+
+        // The same guardian twice, plus an unenrolled key, is one approval
+        let approvals = vec![
+            guardians[0].approve(backup),
+            guardians[0].approve(backup),
+            outsider.approve(backup),
+        ];
+        assert!(policy.verify_restore(backup, &approvals).is_err());
+    }
+
+    #[test]
+    fn test_approval_is_bound_to_the_backup() {
+        let guardian = GuardianKey::generate();
+        let policy = GuardianPolicy::new(1, vec![guardian.public_key()]).unwrap();
+
+        // An approval for one backup cannot release another
+        let approval = guardian.approve(b"backup A");
+        assert!(policy.verify_restore(b"backup B", &[approval]).is_err());
+    }
+
+    #[test]
+    fn test_policy_validation() {
+        let key = GuardianKey::generate();
+        assert!(GuardianPolicy::new(0, vec![key.public_key()]).is_err());
+        assert!(GuardianPolicy::new(2, vec![key.public_key()]).is_err());
+
+        // Keys round-trip through their at-rest encoding
+        let restored = GuardianKey::from_bytes(&key.to_bytes()).unwrap();
+        assert_eq!(restored.public_key(), key.public_key());
+        assert!(GuardianKey::from_bytes(&[0u8; 16]).is_err());
+    }
+}
@@ -0,0 +1,490 @@
+//! Add-party protocol for onboarding a new device
+//!
+//! A quorum of existing parties provisions a share for one brand-new
+//! party — a user's new phone — without changing the public key or the
+//! threshold. This is the narrow special case of resharing where the
+//! original sharing polynomial is kept: the dealers jointly evaluate it
+//! at the new party's share index, so existing shares stay valid and the
+//! only output is one new share.
+//!
+//! Each dealer's raw contribution is its Lagrange-weighted share, which
+//! would let the new party solve for the dealer's share. The dealers
+//! therefore cancel pairwise random masks against each other first: the
+//! masked contributions still sum to the polynomial evaluation, but the
+//! individual summands reveal nothing. The new party verifies the summed
+//! share against the committee's public shares before accepting it.
+
+use crate::mpc::Relay;
+use crate::{Error, KeyShare, PartyId, Result, SessionId};
+use k256::{
+    elliptic_curve::{
+        bigint::U256,
+        ops::Reduce,
+        sec1::{FromEncodedPoint, ToEncodedPoint},
+        Field,
+    },
+    AffinePoint, ProjectivePoint, Scalar,
+};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use tracing::{debug, info, instrument};
+
+/// Configuration for an add-party ceremony
+#[derive(Debug, Clone)]
+pub struct AddPartyConfig {
+    /// Session identifier
+    pub session_id: SessionId,
+    /// Existing parties provisioning the share (at least the threshold)
+    pub dealers: Vec<PartyId>,
+    /// Relay ID of the joining party
+    pub new_party: PartyId,
+    /// This party's relay ID
+    pub party_id: PartyId,
+}
+
+/// Run the add-party protocol
+///
+/// Dealers pass their existing key share and get back an updated one
+/// whose committee includes the new member; the joining party passes
+/// `None` and gets its freshly provisioned share. Existing parties that
+/// sat out the ceremony keep working for signing, but their record of
+/// the committee is stale until they refresh it.
+#[instrument(skip(relay, existing_share), fields(party_id = config.party_id))]
+pub async fn run_add_party<R: Relay>(
+    config: &AddPartyConfig,
+    existing_share: Option<&KeyShare>,
+    relay: &R,
+) -> Result<KeyShare> {
+    run_add_party_with_rng(config, existing_share, relay, &mut OsRng).await
+}
+
+/// [`run_add_party`] drawing the pairwise masks from a caller-supplied
+/// entropy source
+pub async fn run_add_party_with_rng<R: Relay, G: RngCore + CryptoRng>(
+    config: &AddPartyConfig,
+    existing_share: Option<&KeyShare>,
+    relay: &R,
+    rng: &mut G,
+) -> Result<KeyShare> {
+    let is_dealer = config.dealers.contains(&config.party_id);
+    if !is_dealer && config.party_id != config.new_party {
+        return Err(Error::InvalidPartyId(config.party_id));
+    }
+    if config.dealers.contains(&config.new_party) {
+        return Err(Error::InvalidConfig(
+            "Joining party cannot also be a dealer".into(),
+        ));
+    }
+
+    info!(
+        dealers = config.dealers.len(),
+        new_party = config.new_party,
+        "Starting add-party ceremony"
+    );
+
+    if let Some(share) = existing_share.filter(|_| is_dealer) {
+        run_as_dealer(config, share, relay, rng).await
+    } else if is_dealer {
+        Err(Error::InvalidConfig(
+            "Dealer must provide its existing key share".into(),
+        ))
+    } else {
+        run_as_joiner(config, relay).await
+    }
+}
+
+async fn run_as_dealer<R: Relay>(
+    config: &AddPartyConfig,
+    share: &KeyShare,
+    relay: &R,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> Result<KeyShare> {
+    if share.party_id != config.party_id {
+        return Err(Error::InvalidPartyId(share.party_id));
+    }
+    if config.dealers.len() < share.threshold {
+        return Err(Error::ThresholdNotMet {
+            required: share.threshold,
+            actual: config.dealers.len(),
+        });
+    }
+
+    // The new member takes the next share index after the current committee
+    let new_id = share.n_parties;
+    let x_new = new_id as u64 + 1;
+
+    // Round 1: exchange pairwise masks with the other dealers. The lower
+    // ID generates; the generator adds the mask, the peer subtracts it.
+    debug!("Add-party Round 1: Pairwise masks");
+    let mut mask_sum = Scalar::ZERO;
+    for &peer in &config.dealers {
+        if peer <= config.party_id {
+            continue;
+        }
+        let mask = Scalar::random(&mut *rng);
+        mask_sum += mask;
+        let mask_msg = super::DkgRound2Message {
+            from: config.party_id,
+            to: peer,
+            share: mask.to_bytes().to_vec(),
+        };
+        relay
+            .send_direct(&config.session_id, 1, peer, &mask_msg)
+            .await?;
+    }
+    let peers_below = config
+        .dealers
+        .iter()
+        .filter(|&&p| p < config.party_id)
+        .count();
+    if peers_below > 0 {
+        let received = relay
+            .collect_direct::<super::DkgRound2Message>(
+                &config.session_id,
+                1,
+                config.party_id,
+                peers_below,
+            )
+            .await?;
+        for mask_msg in &received {
+            mask_sum -= decode_scalar(&mask_msg.share)?;
+        }
+    }
+
+    // Round 2: broadcast the committee's public data so the joiner can
+    // verify its share; every dealer must tell the same story
+    debug!("Add-party Round 2: Public data");
+    let public_msg = super::AddPartyPublicMessage {
+        dealer: config.party_id,
+        n_parties: share.n_parties,
+        threshold: share.threshold,
+        public_key: share.public_key.clone(),
+        public_shares: share.public_shares.clone(),
+        chain_code: share.chain_code,
+    };
+    relay
+        .broadcast(&config.session_id, 2, &public_msg)
+        .await?;
+    let all_public = relay
+        .collect_broadcasts::<super::AddPartyPublicMessage>(
+            &config.session_id,
+            2,
+            config.dealers.len(),
+        )
+        .await?;
+    verify_public_consistency(&all_public, config)?;
+
+    // Round 3: send the masked Lagrange-weighted contribution
+    debug!("Add-party Round 3: Masked contribution");
+    let contribution =
+        lagrange_at(config.party_id, x_new, &config.dealers) * share.secret_share + mask_sum;
+    let contribution_msg = super::DkgRound2Message {
+        from: config.party_id,
+        to: new_id,
+        share: contribution.to_bytes().to_vec(),
+    };
+    relay
+        .send_direct(&config.session_id, 3, config.new_party, &contribution_msg)
+        .await?;
+
+    // Record the enlarged committee locally
+    let new_public_share = interpolate_public_share(share, x_new, &config.dealers)?;
+    let mut public_shares = share.public_shares.clone();
+    public_shares.push(new_public_share);
+    let mut updated = share.clone();
+    updated.n_parties = share.n_parties + 1;
+    updated.public_shares = public_shares;
+
+    info!(new_member = new_id, "Add-party ceremony completed (dealer)");
+    Ok(updated)
+}
+
+async fn run_as_joiner<R: Relay>(config: &AddPartyConfig, relay: &R) -> Result<KeyShare> {
+    // Learn the committee's shape and public data from the dealers
+    let mut all_public = relay
+        .collect_broadcasts::<super::AddPartyPublicMessage>(
+            &config.session_id,
+            2,
+            config.dealers.len(),
+        )
+        .await?;
+    all_public.sort_by_key(|msg| msg.dealer);
+    verify_public_consistency(&all_public, config)?;
+    let committee = &all_public[0];
+    if config.dealers.len() < committee.threshold {
+        return Err(Error::ThresholdNotMet {
+            required: committee.threshold,
+            actual: config.dealers.len(),
+        });
+    }
+
+    let new_id = committee.n_parties;
+    let x_new = new_id as u64 + 1;
+
+    // Sum the masked contributions; the masks cancel, leaving the
+    // sharing polynomial evaluated at this party's share index
+    let contributions = relay
+        .collect_direct::<super::DkgRound2Message>(
+            &config.session_id,
+            3,
+            config.party_id,
+            config.dealers.len(),
+        )
+        .await?;
+    let mut secret_share = Scalar::ZERO;
+    for msg in &contributions {
+        secret_share += decode_scalar(&msg.share)?;
+    }
+
+    // Verify the share against the committee's public shares before
+    // accepting it: G·share must equal the public interpolation
+    let mut expected = ProjectivePoint::IDENTITY;
+    for &dealer in &config.dealers {
+        let public_share = committee
+            .public_shares
+            .get(dealer)
+            .ok_or(Error::InvalidPartyId(dealer))?;
+        expected += decode_point(public_share)? * lagrange_at(dealer, x_new, &config.dealers);
+    }
+    if ProjectivePoint::GENERATOR * secret_share != expected {
+        return Err(Error::VerificationFailed(
+            "Provisioned share does not match the committee's public shares".into(),
+        ));
+    }
+
+    let mut public_shares = committee.public_shares.clone();
+    public_shares.push(
+        expected
+            .to_affine()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec(),
+    );
+
+    let mut transcript = crate::transcript::Transcript::new(
+        crate::transcript::ADD_PARTY_LABEL,
+        &config.session_id,
+    );
+    for msg in &all_public {
+        transcript.append_message(2, msg.dealer, msg)?;
+    }
+
+    let key_share = KeyShare {
+        party_id: new_id,
+        n_parties: committee.n_parties + 1,
+        threshold: committee.threshold,
+        secret_share,
+        public_key: committee.public_key.clone(),
+        public_shares,
+        chain_code: committee.chain_code,
+        scheme: crate::scheme::SchemeId::Secp256k1,
+        min_protocol_version: crate::PROTOCOL_VERSION,
+        transcript_digest: transcript.digest(),
+        integrity_tag: [0u8; 32],
+    };
+
+    info!(party_id = new_id, "Add-party ceremony completed (joiner)");
+    Ok(key_share)
+}
+
+/// All dealers must present identical public data
+fn verify_public_consistency(
+    all_public: &[super::AddPartyPublicMessage],
+    config: &AddPartyConfig,
+) -> Result<()> {
+    let mut dealt: Vec<PartyId> = all_public.iter().map(|msg| msg.dealer).collect();
+    dealt.sort_unstable();
+    let mut expected = config.dealers.clone();
+    expected.sort_unstable();
+    if dealt != expected {
+        return Err(Error::VerificationFailed(format!(
+            "Public data from {:?}, expected {:?}",
+            dealt, expected
+        )));
+    }
+
+    let first = &all_public[0];
+    for msg in &all_public[1..] {
+        if msg.n_parties != first.n_parties
+            || msg.threshold != first.threshold
+            || msg.public_key != first.public_key
+            || msg.public_shares != first.public_shares
+        {
+            return Err(Error::VerificationFailed(format!(
+                "Dealer {} disagrees on the committee's public data",
+                msg.dealer
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Lagrange coefficient for party `i` evaluated at `x` over `parties`
+fn lagrange_at(i: PartyId, x: u64, parties: &[PartyId]) -> Scalar {
+    let xi = Scalar::from(i as u64 + 1);
+    let x = Scalar::from(x);
+    let mut coef = Scalar::ONE;
+    for &j in parties {
+        if j == i {
+            continue;
+        }
+        let xj = Scalar::from(j as u64 + 1);
+        coef *= (x - xj) * (xi - xj).invert().unwrap();
+    }
+    coef
+}
+
+/// The new member's public share, interpolated from the dealer set
+fn interpolate_public_share(
+    share: &KeyShare,
+    x_new: u64,
+    dealers: &[PartyId],
+) -> Result<Vec<u8>> {
+    let mut point = ProjectivePoint::IDENTITY;
+    for &dealer in dealers {
+        let public_share = share
+            .public_shares
+            .get(dealer)
+            .ok_or(Error::InvalidPartyId(dealer))?;
+        point += decode_point(public_share)? * lagrange_at(dealer, x_new, dealers);
+    }
+    Ok(point.to_affine().to_encoded_point(true).as_bytes().to_vec())
+}
+
+/// Decode a 32-byte scalar from a message
+fn decode_scalar(bytes: &[u8]) -> Result<Scalar> {
+    let share_bytes: [u8; 32] = bytes
+        .to_vec()
+        .try_into()
+        .map_err(|_| Error::Deserialization("Invalid scalar length".into()))?;
+    Ok(<Scalar as Reduce<U256>>::reduce_bytes(&share_bytes.into()))
+}
+
+/// Decode a compressed point
+fn decode_point(bytes: &[u8]) -> Result<ProjectivePoint> {
+    let point = k256::EncodedPoint::from_bytes(bytes)
+        .map_err(|e| Error::VerificationFailed(e.to_string()))?;
+    let affine_opt = AffinePoint::from_encoded_point(&point);
+    let affine: AffinePoint = Option::<AffinePoint>::from(affine_opt)
+        .ok_or_else(|| Error::VerificationFailed("Invalid commitment point".into()))?;
+    Ok(ProjectivePoint::from(affine))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keygen::run_dkg;
+    use crate::mpc::MemoryRelay;
+    use crate::sign::run_dsg;
+    use crate::SessionConfig;
+    use std::sync::Arc;
+
+    async fn dkg_shares(relay: &Arc<MemoryRelay>, session_id: SessionId, n: usize) -> Vec<KeyShare> {
+        let mut handles = Vec::new();
+        for party_id in 0..n {
+            let relay = relay.clone();
+            handles.push(tokio::spawn(async move {
+                let config = SessionConfig {
+                    session_id,
+                    n_parties: n,
+                    threshold: 2,
+                    party_id,
+                    parties: (0..n).collect(),
+                };
+                run_dkg(&config, &*relay).await.unwrap()
+            }));
+        }
+        let mut shares = Vec::new();
+        for handle in handles {
+            shares.push(handle.await.unwrap());
+        }
+        shares.sort_by_key(|share| share.party_id);
+        shares
+    }
+
+    #[tokio::test]
+    async fn test_onboarded_party_can_sign() {
+        let relay = Arc::new(MemoryRelay::new());
+        let shares = dkg_shares(&relay, [0x61u8; 32], 3).await;
+        let public_key = shares[0].public_key.clone();
+
+        // All three existing parties provision a share for new device 3
+        let add_config = |party_id| AddPartyConfig {
+            session_id: [0x62u8; 32],
+            dealers: vec![0, 1, 2],
+            new_party: 3,
+            party_id,
+        };
+        let mut handles = Vec::new();
+        for share in shares.iter().cloned() {
+            let relay = relay.clone();
+            let config = add_config(share.party_id);
+            handles.push(tokio::spawn(async move {
+                run_add_party(&config, Some(&share), &*relay).await.unwrap()
+            }));
+        }
+        {
+            let relay = relay.clone();
+            let config = add_config(3);
+            handles.push(tokio::spawn(async move {
+                run_add_party(&config, None, &*relay).await.unwrap()
+            }));
+        }
+
+        let mut updated = Vec::new();
+        for handle in handles {
+            updated.push(handle.await.unwrap());
+        }
+        updated.sort_by_key(|share| share.party_id);
+
+        // Public key unchanged, committee enlarged, old shares untouched
+        for share in &updated {
+            assert_eq!(share.public_key, public_key);
+            assert_eq!(share.n_parties, 4);
+            assert_eq!(share.threshold, 2);
+            assert_eq!(share.public_shares.len(), 4);
+        }
+        for (share, old) in updated.iter().zip(&shares) {
+            assert_eq!(share.secret_share, old.secret_share);
+        }
+
+        // The new device signs together with an original member
+        let message = [0x63u8; 32];
+        let signer0 = updated[0].clone();
+        let signer3 = updated[3].clone();
+        let relay0 = relay.clone();
+        let relay3 = relay.clone();
+        let (sig0, sig3) = tokio::join!(
+            run_dsg(&signer0, &message, &[0, 3], &*relay0),
+            run_dsg(&signer3, &message, &[0, 3], &*relay3),
+        );
+        let sig0 = sig0.unwrap();
+        let sig3 = sig3.unwrap();
+        assert_eq!(sig0.r, sig3.r);
+        assert_eq!(sig0.s, sig3.s);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_undersized_dealer_quorum() {
+        let relay = Arc::new(MemoryRelay::new());
+        let shares = dkg_shares(&relay, [0x64u8; 32], 3).await;
+
+        let config = AddPartyConfig {
+            session_id: [0x65u8; 32],
+            dealers: vec![0],
+            new_party: 3,
+            party_id: 0,
+        };
+        let err = match run_add_party(&config, Some(&shares[0]), &*relay).await {
+            Err(err) => err,
+            Ok(_) => panic!("single dealer must be rejected"),
+        };
+        assert!(matches!(
+            err,
+            Error::ThresholdNotMet {
+                required: 2,
+                actual: 1
+            }
+        ));
+    }
+}
@@ -1,15 +1,14 @@
 //! DKG protocol implementation
 
-use crate::mpc::Relay;
-use crate::{Error, KeyShare, Result, SessionConfig};
-use k256::{
-    elliptic_curve::{
-        bigint::U256, group::GroupEncoding, ops::Reduce, subtle::CtOption, Field, Group,
-        sec1::{FromEncodedPoint, ToEncodedPoint},
-    },
-    AffinePoint, ProjectivePoint, Scalar,
+use crate::curve::{
+    decode_point, encode_point, reduce_scalar_bytes, scalar_to_bytes, ThresholdCurve,
 };
+use crate::mpc::Relay;
+use crate::{Error, KeyShare, Result, SessionConfig, SessionId};
+use elliptic_curve::{Field, Group};
+use futures_util::stream::{FuturesUnordered, Stream};
 use rand::rngs::OsRng;
+use rand::{CryptoRng, Rng, RngCore};
 use tracing::{debug, info, instrument};
 
 /// Run the distributed key generation protocol
@@ -24,6 +23,44 @@ use tracing::{debug, info, instrument};
 /// The party's key share after successful DKG
 #[instrument(skip(relay))]
 pub async fn run_dkg<R: Relay>(config: &SessionConfig, relay: &R) -> Result<KeyShare> {
+    run_dkg_for_curve::<k256::Secp256k1, R>(config, relay).await
+}
+
+/// [`run_dkg`] drawing all secret material from a caller-supplied
+/// entropy source
+///
+/// The default entry point uses the operating system RNG; hosts with an
+/// approved hardware entropy source (HSMs, enclaves) and deterministic
+/// tests plug theirs in here instead.
+pub async fn run_dkg_with_rng<R: Relay, G: RngCore + CryptoRng>(
+    config: &SessionConfig,
+    relay: &R,
+    rng: &mut G,
+) -> Result<KeyShare> {
+    run_dkg_for_curve_with_rng::<k256::Secp256k1, R, G>(config, relay, rng).await
+}
+
+/// Run the DKG over an explicit curve
+///
+/// Same protocol as [`run_dkg`], parameterized by [`ThresholdCurve`]; the
+/// resulting share records the curve's scheme so signing refuses to mix
+/// key material across curves. All messages carry points and scalars as
+/// bytes, so parties on different curves can never confuse each other's
+/// ceremonies beyond failing to decode.
+pub async fn run_dkg_for_curve<C: ThresholdCurve, R: Relay>(
+    config: &SessionConfig,
+    relay: &R,
+) -> Result<KeyShare<C>> {
+    run_dkg_for_curve_with_rng::<C, R, _>(config, relay, &mut OsRng).await
+}
+
+/// [`run_dkg_for_curve`] drawing all secret material from a
+/// caller-supplied entropy source
+pub async fn run_dkg_for_curve_with_rng<C: ThresholdCurve, R: Relay, G: RngCore + CryptoRng>(
+    config: &SessionConfig,
+    relay: &R,
+    rng: &mut G,
+) -> Result<KeyShare<C>> {
     info!(
         party_id = config.party_id,
         n_parties = config.n_parties,
@@ -33,33 +70,75 @@ pub async fn run_dkg<R: Relay>(config: &SessionConfig, relay: &R) -> Result<KeyS
 
     // Round 1: Generate and commit to secret polynomial
     debug!("DKG Round 1: Commitment");
-    let (secret_poly, commitments) = generate_secret_polynomial(config)?;
-
-    // Broadcast commitment
+    let round_started = std::time::Instant::now();
+    let secret_poly = generate_secret_polynomial::<C>(config, rng);
+    let commitments = commit_polynomial::<C>(&secret_poly);
+
+    // Broadcast commitment and collect everyone's over echo broadcast, so
+    // an equivocating dealer cannot show different commitment sets to
+    // different parties; the result comes back sorted by sender
+    let (pok_point, pok_response) = prove_constant_term::<C>(
+        &config.session_id,
+        config.party_id,
+        &secret_poly[0],
+        rng,
+    );
     let commitment_msg = super::DkgRound1Message {
         party_id: config.party_id,
         commitments: commitments.clone(),
+        capabilities: crate::capabilities::Capabilities::local().0,
+        pok_point,
+        pok_response,
     };
-    relay
-        .broadcast(&config.session_id, 1, &commitment_msg)
-        .await?;
+    let all_commitments = crate::mpc::echo_broadcast(
+        config,
+        1,
+        &commitment_msg,
+        relay,
+        |msg: &super::DkgRound1Message| msg.party_id,
+    )
+    .await?;
+    crate::telemetry::round_complete("dkg", 1, round_started.elapsed(), all_commitments.len());
+
+    // A dealer that cannot prove knowledge of its constant term may be
+    // deriving it from others' commitments to steer the aggregate key
+    // (a rogue-key attack); reject before its contribution counts
+    for msg in &all_commitments {
+        if msg.party_id != config.party_id
+            && verify_constant_term_pok::<C>(&config.session_id, msg).is_err()
+        {
+            crate::telemetry::verification_failures("dkg", 1);
+            return Err(Error::MaliciousParty(msg.party_id));
+        }
+    }
 
-    // Collect commitments from all parties
-    let all_commitments = relay
-        .collect_broadcasts::<super::DkgRound1Message>(&config.session_id, 1, config.n_parties)
-        .await?;
+    // Fold every accepted broadcast into the running transcript, in sorted
+    // order so all honest parties compute the same digest
+    let mut transcript = crate::transcript::Transcript::new(C::DKG_LABEL, &config.session_id);
+    for msg in &all_commitments {
+        transcript.append_message(1, msg.party_id, msg)?;
+    }
+
+    // Negotiate the fleet's capability intersection up front and record it
+    let advertised: Vec<u64> = all_commitments.iter().map(|msg| msg.capabilities).collect();
+    let negotiated = crate::capabilities::negotiate(
+        &advertised,
+        crate::capabilities::Capabilities::BASELINE,
+    )?;
+    info!(capabilities = ?negotiated.names(), "Negotiated ceremony capabilities");
 
     // Round 2: Send secret shares to each party
     debug!("DKG Round 2: Secret sharing");
+    let round_started = std::time::Instant::now();
     for party_id in &config.parties {
         if *party_id == config.party_id {
             continue;
         }
-        let share = evaluate_polynomial(&secret_poly, *party_id as u64 + 1);
+        let share = evaluate_polynomial::<C>(&secret_poly, *party_id as u64 + 1);
         let share_msg = super::DkgRound2Message {
             from: config.party_id,
             to: *party_id,
-            share: share.to_bytes().to_vec(),
+            share: scalar_to_bytes::<C>(&share).to_vec(),
         };
         relay
             .send_direct(&config.session_id, 2, *party_id, &share_msg)
@@ -75,39 +154,80 @@ pub async fn run_dkg<R: Relay>(config: &SessionConfig, relay: &R) -> Result<KeyS
             config.n_parties - 1,
         )
         .await?;
+    crate::telemetry::round_complete("dkg", 2, round_started.elapsed(), received_shares.len());
+
+    // Round 3: Verify shares, then broadcast complaints (empty when all
+    // shares checked out) so a bad dealer is identified by everyone
+    // instead of each victim just erroring locally
+    debug!("DKG Round 3: Verification and complaints");
+    let round_started = std::time::Instant::now();
+
+    let mut accused: Vec<usize> = received_shares
+        .iter()
+        .filter(|share_msg| {
+            verify_share::<C>(
+                share_msg,
+                &all_commitments[share_msg.from].commitments,
+                config.party_id,
+            )
+            .is_err()
+        })
+        .map(|share_msg| share_msg.from)
+        .collect();
+    accused.sort_unstable();
+    crate::telemetry::verification_failures("dkg", accused.len());
+
+    let complaint_msg = super::DkgComplaintMessage {
+        party_id: config.party_id,
+        accused,
+    };
+    relay
+        .broadcast(&config.session_id, 3, &complaint_msg)
+        .await?;
 
-    // Round 3: Verify shares and compute final key share
-    debug!("DKG Round 3: Verification");
+    let complaints = relay
+        .collect_broadcasts::<super::DkgComplaintMessage>(
+            &config.session_id,
+            3,
+            config.n_parties,
+        )
+        .await?;
+    crate::telemetry::round_complete("dkg", 3, round_started.elapsed(), complaints.len());
 
-    // Verify received shares against commitments
-    for share_msg in &received_shares {
-        verify_share(
-            share_msg,
-            &all_commitments[share_msg.from].commitments,
-            config.party_id,
-        )?;
+    for complaint in &complaints {
+        transcript.append_message(3, complaint.party_id, complaint)?;
+    }
+
+    if complaints.iter().any(|complaint| !complaint.accused.is_empty()) {
+        return resolve_complaints::<C, R>(
+            config,
+            &secret_poly,
+            &all_commitments,
+            &complaints,
+            relay,
+        )
+        .await;
     }
 
     // Compute final secret share
-    let mut final_secret = evaluate_polynomial(&secret_poly, config.party_id as u64 + 1);
+    let mut final_secret = evaluate_polynomial::<C>(&secret_poly, config.party_id as u64 + 1);
     for share_msg in &received_shares {
         let share_bytes: [u8; 32] = share_msg
             .share
             .clone()
             .try_into()
             .map_err(|_| Error::Deserialization("Invalid share length".into()))?;
-        let share = <Scalar as Reduce<U256>>::reduce_bytes(&share_bytes.into());
-        final_secret = final_secret + share;
+        final_secret += reduce_scalar_bytes::<C>(&share_bytes);
     }
 
     // Compute public key
-    let public_key = compute_public_key(&all_commitments)?;
+    let public_key = compute_public_key::<C>(&all_commitments)?;
 
     // Compute public shares
-    let public_shares = compute_public_shares(&all_commitments, config.n_parties)?;
+    let public_shares = compute_public_shares::<C>(&all_commitments, config.n_parties)?;
 
     // Generate chain code for BIP32
-    let chain_code: [u8; 32] = rand::random();
+    let chain_code: [u8; 32] = rng.gen();
 
     let key_share = KeyShare {
         party_id: config.party_id,
@@ -117,6 +237,10 @@ pub async fn run_dkg<R: Relay>(config: &SessionConfig, relay: &R) -> Result<KeyS
         public_key,
         public_shares,
         chain_code,
+        scheme: C::SCHEME,
+        min_protocol_version: crate::PROTOCOL_VERSION,
+        transcript_digest: transcript.digest(),
+        integrity_tag: [0u8; 32],
     };
 
     info!(
@@ -128,106 +252,339 @@ pub async fn run_dkg<R: Relay>(config: &SessionConfig, relay: &R) -> Result<KeyS
     Ok(key_share)
 }
 
-/// Generate a random secret polynomial of degree t-1
-fn generate_secret_polynomial(config: &SessionConfig) -> Result<(Vec<Scalar>, Vec<Vec<u8>>)> {
-    let mut rng = OsRng;
-    let mut coefficients = Vec::with_capacity(config.threshold);
-    let mut commitments = Vec::with_capacity(config.threshold);
+/// Run many independent DKG instances over one session
+///
+/// Each instance runs the full protocol with a session ID derived from the
+/// base session ID and the instance index, so all parties agree on the
+/// per-instance IDs without extra coordination. The transport and identity
+/// setup are shared; completed key shares are yielded as they finish, in
+/// completion order, tagged with their instance index so parties can match
+/// keys across the ceremony.
+///
+/// # Arguments
+/// * `config` - Base session configuration shared by all instances
+/// * `count` - Number of independent keys to generate
+/// * `relay` - Message relay for communication
+pub fn run_dkg_batch<'a, R: Relay>(
+    config: &'a SessionConfig,
+    count: usize,
+    relay: &'a R,
+) -> impl Stream<Item = Result<(usize, KeyShare)>> + 'a {
+    info!(
+        party_id = config.party_id,
+        count, "Starting batch DKG"
+    );
 
-    for _ in 0..config.threshold {
-        let coef = Scalar::random(&mut rng);
-        let commitment = (ProjectivePoint::GENERATOR * coef).to_affine();
+    let instances = FuturesUnordered::new();
+    for index in 0..count {
+        let mut instance_config = config.clone();
+        instance_config.session_id = batch_session_id(&config.session_id, index);
+        instances.push(async move {
+            run_dkg(&instance_config, relay)
+                .await
+                .map(|key_share| (index, key_share))
+        });
+    }
+    instances
+}
+
+/// Derive the session ID for one instance of a batch DKG
+fn batch_session_id(base: &SessionId, index: usize) -> SessionId {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(base);
+    hasher.update(&(index as u64).to_be_bytes());
+    *hasher.finalize().as_bytes()
+}
 
-        coefficients.push(coef);
-        commitments.push(commitment.to_encoded_point(true).as_bytes().to_vec());
+/// Run the justification round and adjudicate outstanding complaints
+///
+/// Every accused dealer reveals the disputed shares publicly; all parties
+/// re-run the commitment check on the revealed values and agree on the
+/// culprit. A complaint always aborts the ceremony -- either the dealer
+/// dealt a bad share (or refused to justify), or the complainer accused an
+/// honest dealer -- and the error names the cheating party.
+async fn resolve_complaints<C: ThresholdCurve, R: Relay>(
+    config: &SessionConfig,
+    secret_poly: &[C::Scalar],
+    all_commitments: &[super::DkgRound1Message],
+    complaints: &[super::DkgComplaintMessage],
+    relay: &R,
+) -> Result<KeyShare<C>> {
+    let accused_set: std::collections::BTreeSet<usize> = complaints
+        .iter()
+        .flat_map(|complaint| complaint.accused.iter().copied())
+        .collect();
+
+    // If this party is accused, reveal the shares it dealt to each
+    // complainer so everyone can re-check them against the commitments
+    if accused_set.contains(&config.party_id) {
+        let revealed = complaints
+            .iter()
+            .filter(|complaint| complaint.accused.contains(&config.party_id))
+            .map(|complaint| {
+                let share = evaluate_polynomial::<C>(secret_poly, complaint.party_id as u64 + 1);
+                (complaint.party_id, scalar_to_bytes::<C>(&share).to_vec())
+            })
+            .collect();
+        let justification = super::DkgJustificationMessage {
+            party_id: config.party_id,
+            revealed,
+        };
+        relay
+            .broadcast(&config.session_id, crate::consts::DKG_JUSTIFICATION_ROUND, &justification)
+            .await?;
     }
 
-    Ok((coefficients, commitments))
+    let justifications = relay
+        .collect_broadcasts::<super::DkgJustificationMessage>(
+            &config.session_id,
+            crate::consts::DKG_JUSTIFICATION_ROUND,
+            accused_set.len(),
+        )
+        .await?;
+
+    // Deterministic adjudication: walk accusations in sorted order so all
+    // honest parties abort naming the same culprit
+    for accused in &accused_set {
+        let justification = justifications
+            .iter()
+            .find(|justification| justification.party_id == *accused)
+            .ok_or(Error::MaliciousParty(*accused))?;
+
+        let mut complainers: Vec<usize> = complaints
+            .iter()
+            .filter(|complaint| complaint.accused.contains(accused))
+            .map(|complaint| complaint.party_id)
+            .collect();
+        complainers.sort_unstable();
+
+        // A complaint always aborts, so only the lowest-numbered complaint
+        // against this dealer needs adjudicating
+        if let Some(complainer) = complainers.first().copied() {
+            let revealed = justification
+                .revealed
+                .iter()
+                .find(|(party_id, _)| *party_id == complainer)
+                .map(|(_, share)| share)
+                .ok_or(Error::MaliciousParty(*accused))?;
+
+            match verify_commitment_set_for_curve::<C>(
+                &all_commitments[*accused].commitments,
+                revealed,
+                complainer,
+            ) {
+                // The revealed share is bad: the dealer cheated
+                Err(_) => return Err(Error::MaliciousParty(*accused)),
+                // The revealed share verifies: the complaint was false
+                Ok(()) => return Err(Error::MaliciousParty(complainer)),
+            }
+        }
+    }
+
+    Err(Error::Internal(
+        "Complaint round ran without any accusations".into(),
+    ))
+}
+
+/// Generate a random secret polynomial of degree t-1
+fn generate_secret_polynomial<C: ThresholdCurve>(
+    config: &SessionConfig,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> Vec<C::Scalar> {
+    (0..config.threshold)
+        .map(|_| C::Scalar::random(&mut *rng))
+        .collect()
+}
+
+/// Feldman commitments to a polynomial: one curve point per coefficient
+fn commit_polynomial<C: ThresholdCurve>(coefficients: &[C::Scalar]) -> Vec<Vec<u8>> {
+    coefficients
+        .iter()
+        .map(|coef| encode_point::<C>(&(C::ProjectivePoint::generator() * coef)))
+        .collect()
+}
+
+/// Fiat-Shamir challenge for the constant-term proof of knowledge
+///
+/// Bound to the session, the dealer and both commitment points, so a
+/// proof can never be replayed for another party or ceremony.
+fn pok_challenge<C: ThresholdCurve>(
+    session_id: &crate::SessionId,
+    party_id: crate::PartyId,
+    commitment: &[u8],
+    pok_point: &[u8],
+) -> C::Scalar {
+    let mut material =
+        Vec::with_capacity(session_id.len() + 8 + commitment.len() + pok_point.len());
+    material.extend_from_slice(session_id);
+    material.extend_from_slice(&(party_id as u64).to_be_bytes());
+    material.extend_from_slice(commitment);
+    material.extend_from_slice(pok_point);
+    reduce_scalar_bytes::<C>(&blake3::derive_key(crate::consts::DKG_POK_CONTEXT, &material))
+}
+
+/// Schnorr proof of knowledge of the constant-term discrete log
+///
+/// Without it, round 1 accepts Feldman commitments whose constant term a
+/// rogue dealer derived from everyone else's, letting it bias the
+/// aggregate public key toward one it controls.
+pub(super) fn prove_constant_term<C: ThresholdCurve>(
+    session_id: &crate::SessionId,
+    party_id: crate::PartyId,
+    constant: &C::Scalar,
+    rng: &mut (impl rand::RngCore + rand::CryptoRng),
+) -> (Vec<u8>, Vec<u8>) {
+    let commitment = encode_point::<C>(&(C::ProjectivePoint::generator() * constant));
+    let k = C::Scalar::random(&mut *rng);
+    let pok_point = encode_point::<C>(&(C::ProjectivePoint::generator() * k));
+    let challenge = pok_challenge::<C>(session_id, party_id, &commitment, &pok_point);
+    let response = k + challenge * constant;
+    (pok_point, scalar_to_bytes::<C>(&response).to_vec())
+}
+
+/// Verify a dealer's constant-term proof of knowledge
+pub(super) fn verify_constant_term_pok<C: ThresholdCurve>(
+    session_id: &crate::SessionId,
+    msg: &super::DkgRound1Message,
+) -> Result<()> {
+    let commitment = msg.commitments.first().ok_or_else(|| {
+        Error::VerificationFailed("Commitment set is empty".into())
+    })?;
+    let constant_point = decode_point::<C>(commitment)?;
+    let pok_point = decode_point::<C>(&msg.pok_point)?;
+    let response_bytes: [u8; 32] = msg
+        .pok_response
+        .clone()
+        .try_into()
+        .map_err(|_| Error::Deserialization("Invalid PoK response length".into()))?;
+    let response = reduce_scalar_bytes::<C>(&response_bytes);
+    let challenge = pok_challenge::<C>(session_id, msg.party_id, commitment, &msg.pok_point);
+
+    if C::ProjectivePoint::generator() * response == pok_point + constant_point * challenge {
+        Ok(())
+    } else {
+        Err(Error::VerificationFailed(format!(
+            "Invalid constant-term proof of knowledge from party {}",
+            msg.party_id
+        )))
+    }
 }
 
 /// Evaluate polynomial at a point
-fn evaluate_polynomial(coefficients: &[Scalar], x: u64) -> Scalar {
-    let x_scalar = Scalar::from(x);
-    let mut result = Scalar::ZERO;
-    let mut x_power = Scalar::ONE;
+fn evaluate_polynomial<C: ThresholdCurve>(coefficients: &[C::Scalar], x: u64) -> C::Scalar {
+    let x_scalar = C::Scalar::from(x);
+    let mut result = C::Scalar::ZERO;
+    let mut x_power = C::Scalar::ONE;
 
     for coef in coefficients {
-        result = result + (*coef * x_power);
-        x_power = x_power * x_scalar;
+        result += *coef * x_power;
+        x_power *= x_scalar;
     }
 
     result
 }
 
 /// Verify a share against commitments
-fn verify_share(
+fn verify_share<C: ThresholdCurve>(
     share_msg: &super::DkgRound2Message,
     commitments: &[Vec<u8>],
     my_id: usize,
 ) -> Result<()> {
-    let share_bytes: [u8; 32] = share_msg
-        .share
-        .clone()
+    verify_commitment_set_for_curve::<C>(commitments, &share_msg.share, my_id).map_err(|_| {
+        Error::VerificationFailed(format!(
+            "Share from party {} does not match commitment",
+            share_msg.from
+        ))
+    })
+}
+
+/// Verify a raw secret share against a dealer's Feldman commitment set
+///
+/// Checks that `share`, dealt to the party at `index`, lies on the
+/// polynomial the dealer committed to: `share * G` must equal the
+/// commitment set evaluated at `index + 1`. This is exactly the check the
+/// DKG runs internally; it is public so external auditors, watch-only
+/// verifiers and relay-side validators can reuse the same math.
+pub fn verify_commitment_set(commitments: &[Vec<u8>], share: &[u8], index: usize) -> Result<()> {
+    verify_commitment_set_for_curve::<k256::Secp256k1>(commitments, share, index)
+}
+
+/// Curve-generic form of [`verify_commitment_set`]
+pub fn verify_commitment_set_for_curve<C: ThresholdCurve>(
+    commitments: &[Vec<u8>],
+    share: &[u8],
+    index: usize,
+) -> Result<()> {
+    let share_bytes: [u8; 32] = share
+        .to_vec()
         .try_into()
         .map_err(|_| Error::Deserialization("Invalid share length".into()))?;
-    let share = <Scalar as Reduce<U256>>::reduce_bytes(&share_bytes.into());
+    let share = reduce_scalar_bytes::<C>(&share_bytes);
 
     // Compute expected commitment
-    let expected = ProjectivePoint::GENERATOR * share;
+    let expected = C::ProjectivePoint::generator() * share;
 
-    // Compute actual commitment from Lagrange interpolation of commitments
-    let x = (my_id + 1) as u64;
-    let mut actual = ProjectivePoint::IDENTITY;
-    let mut x_power = Scalar::ONE;
-    let x_scalar = Scalar::from(x);
+    // Compute actual commitment from the polynomial commitments
+    let x = (index + 1) as u64;
+    let mut actual = C::ProjectivePoint::identity();
+    let mut x_power = C::Scalar::ONE;
+    let x_scalar = C::Scalar::from(x);
 
     for commitment_bytes in commitments {
-        let point = k256::EncodedPoint::from_bytes(commitment_bytes)
-            .map_err(|e| Error::VerificationFailed(e.to_string()))?;
-        let affine_opt = AffinePoint::from_encoded_point(&point);
-        let affine: AffinePoint = Option::<AffinePoint>::from(affine_opt)
-            .ok_or_else(|| Error::VerificationFailed("Invalid commitment point".into()))?;
-        let commitment = ProjectivePoint::from(affine);
-
-        actual = actual + (commitment * x_power);
-        x_power = x_power * x_scalar;
+        let commitment = decode_point::<C>(commitment_bytes)?;
+        actual += commitment * x_power;
+        x_power *= x_scalar;
     }
 
     if expected != actual {
-        return Err(Error::VerificationFailed(format!(
-            "Share from party {} does not match commitment",
-            share_msg.from
-        )));
+        return Err(Error::VerificationFailed(
+            "Share does not match commitment".into(),
+        ));
     }
 
     Ok(())
 }
 
 /// Compute the public key from commitments
-fn compute_public_key(all_commitments: &[super::DkgRound1Message]) -> Result<Vec<u8>> {
-    let mut public_key = ProjectivePoint::IDENTITY;
+fn compute_public_key<C: ThresholdCurve>(
+    all_commitments: &[super::DkgRound1Message],
+) -> Result<Vec<u8>> {
+    let sets: Vec<Vec<Vec<u8>>> = all_commitments
+        .iter()
+        .map(|msg| msg.commitments.clone())
+        .collect();
+    interpolate_public_key_for_curve::<C>(&sets)
+}
+
+/// Combine every dealer's commitment set into the group public key
+///
+/// The group key is the sum of the constant terms (the commitments to each
+/// dealer's secret), encoded as a compressed SEC1 point. Public for the
+/// same reason as [`verify_commitment_set`]: auditors and validators can
+/// recompute the key from a transcript without running the protocol.
+pub fn interpolate_public_key(commitment_sets: &[Vec<Vec<u8>>]) -> Result<Vec<u8>> {
+    interpolate_public_key_for_curve::<k256::Secp256k1>(commitment_sets)
+}
 
-    for commitment_msg in all_commitments {
-        if commitment_msg.commitments.is_empty() {
+/// Curve-generic form of [`interpolate_public_key`]
+pub fn interpolate_public_key_for_curve<C: ThresholdCurve>(
+    commitment_sets: &[Vec<Vec<u8>>],
+) -> Result<Vec<u8>> {
+    let mut public_key = C::ProjectivePoint::identity();
+
+    for commitments in commitment_sets {
+        if commitments.is_empty() {
             return Err(Error::VerificationFailed("Empty commitments".into()));
         }
 
-        let point = k256::EncodedPoint::from_bytes(&commitment_msg.commitments[0])
-            .map_err(|e| Error::VerificationFailed(e.to_string()))?;
-        let affine_opt = AffinePoint::from_encoded_point(&point);
-        let affine: AffinePoint = Option::<AffinePoint>::from(affine_opt)
-            .ok_or_else(|| Error::VerificationFailed("Invalid commitment point".into()))?;
-        let commitment = ProjectivePoint::from(affine);
-
-        public_key = public_key + commitment;
+        public_key += decode_point::<C>(&commitments[0])?;
     }
 
-    let encoded = public_key.to_affine().to_encoded_point(true);
-    Ok(encoded.as_bytes().to_vec())
+    Ok(encode_point::<C>(&public_key))
 }
 
 /// Compute public shares for all parties
-fn compute_public_shares(
+fn compute_public_shares<C: ThresholdCurve>(
     all_commitments: &[super::DkgRound1Message],
     n_parties: usize,
 ) -> Result<Vec<Vec<u8>>> {
@@ -235,28 +592,356 @@ fn compute_public_shares(
 
     for party_id in 0..n_parties {
         let x = (party_id + 1) as u64;
-        let mut public_share = ProjectivePoint::IDENTITY;
+        let mut public_share = C::ProjectivePoint::identity();
 
         for commitment_msg in all_commitments {
-            let mut x_power = Scalar::ONE;
-            let x_scalar = Scalar::from(x);
+            let mut x_power = C::Scalar::ONE;
+            let x_scalar = C::Scalar::from(x);
 
             for commitment_bytes in &commitment_msg.commitments {
-                let point = k256::EncodedPoint::from_bytes(commitment_bytes)
-                    .map_err(|e| Error::VerificationFailed(e.to_string()))?;
-                let affine_opt = AffinePoint::from_encoded_point(&point);
-                let affine: AffinePoint = Option::<AffinePoint>::from(affine_opt)
-                    .ok_or_else(|| Error::VerificationFailed("Invalid commitment point".into()))?;
-                let commitment = ProjectivePoint::from(affine);
-
-                public_share = public_share + (commitment * x_power);
-                x_power = x_power * x_scalar;
+                let commitment = decode_point::<C>(commitment_bytes)?;
+                public_share += commitment * x_power;
+                x_power *= x_scalar;
             }
         }
 
-        let encoded = public_share.to_affine().to_encoded_point(true);
-        public_shares.push(encoded.as_bytes().to_vec());
+        public_shares.push(encode_point::<C>(&public_share));
     }
 
     Ok(public_shares)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::{Scalar, Secp256k1};
+
+    #[test]
+    fn test_verify_commitment_set_standalone() {
+        let config = SessionConfig::new(3, 2, 0).unwrap();
+        let poly = generate_secret_polynomial::<Secp256k1>(&config, &mut OsRng);
+        let commitments = commit_polynomial::<Secp256k1>(&poly);
+
+        for recipient in 0..3usize {
+            let share = evaluate_polynomial::<Secp256k1>(&poly, (recipient + 1) as u64);
+            let share_bytes = share.to_bytes().to_vec();
+            assert!(verify_commitment_set(&commitments, &share_bytes, recipient).is_ok());
+            // The same share under a different index evaluates elsewhere
+            assert!(verify_commitment_set(&commitments, &share_bytes, recipient + 1).is_err());
+        }
+
+        // A tampered share no longer lies on the committed polynomial
+        let mut tampered = evaluate_polynomial::<Secp256k1>(&poly, 1).to_bytes().to_vec();
+        tampered[0] ^= 0x01;
+        assert!(verify_commitment_set(&commitments, &tampered, 0).is_err());
+
+        // Shares must be exactly 32 bytes
+        assert!(verify_commitment_set(&commitments, &[1, 2, 3], 0).is_err());
+    }
+
+    #[test]
+    fn test_interpolate_public_key_sums_dealer_secrets() {
+        let config = SessionConfig::new(3, 2, 0).unwrap();
+        let mut sets = Vec::new();
+        let mut combined_secret = Scalar::ZERO;
+        for _ in 0..3 {
+            let poly = generate_secret_polynomial::<Secp256k1>(&config, &mut OsRng);
+            combined_secret += poly[0];
+            sets.push(commit_polynomial::<Secp256k1>(&poly));
+        }
+
+        let expected = encode_point::<Secp256k1>(
+            &(k256::ProjectivePoint::GENERATOR * combined_secret),
+        );
+        assert_eq!(interpolate_public_key(&sets).unwrap(), expected);
+
+        // A dealer with an empty commitment set is rejected
+        sets.push(Vec::new());
+        assert!(interpolate_public_key(&sets).is_err());
+    }
+
+    #[test]
+    fn test_batch_session_id_deterministic_and_unique() {
+        let base: SessionId = [7u8; 32];
+
+        assert_eq!(batch_session_id(&base, 0), batch_session_id(&base, 0));
+        assert_ne!(batch_session_id(&base, 0), batch_session_id(&base, 1));
+        assert_ne!(batch_session_id(&base, 0), base);
+    }
+
+    #[tokio::test]
+    async fn test_complaint_round_names_bad_dealer() {
+        use crate::mpc::{MemoryRelay, Relay};
+        use std::sync::Arc;
+
+        let relay = Arc::new(MemoryRelay::new());
+        let session_id: SessionId = [0x33u8; 32];
+        let n = 3;
+
+        // Parties 0 and 1 run the honest protocol
+        let mut handles = Vec::new();
+        for party_id in 0..2 {
+            let relay = relay.clone();
+            let config = SessionConfig {
+                session_id,
+                n_parties: n,
+                threshold: 2,
+                party_id,
+                parties: (0..n).collect(),
+            };
+            handles.push(tokio::spawn(
+                async move { run_dkg(&config, &*relay).await },
+            ));
+        }
+
+        // Party 2 deals a corrupted share to party 0, then tries (and
+        // fails) to justify itself by revealing the same bad share
+        let malicious = tokio::spawn({
+            let relay = relay.clone();
+            async move {
+                let config = SessionConfig {
+                    session_id,
+                    n_parties: n,
+                    threshold: 2,
+                    party_id: 2,
+                    parties: (0..n).collect(),
+                };
+                let secret_poly = generate_secret_polynomial::<Secp256k1>(&config, &mut OsRng);
+                let commitments = commit_polynomial::<Secp256k1>(&secret_poly);
+                let bad_share = evaluate_polynomial::<Secp256k1>(&secret_poly, 1) + Scalar::ONE;
+                let (pok_point, pok_response) = prove_constant_term::<Secp256k1>(
+                    &session_id,
+                    2,
+                    &secret_poly[0],
+                    &mut OsRng,
+                );
+
+                crate::mpc::echo_broadcast(
+                    &config,
+                    1,
+                    &super::super::DkgRound1Message {
+                        party_id: 2,
+                        commitments,
+                        capabilities: 0,
+                        pok_point,
+                        pok_response,
+                    },
+                    &*relay,
+                    |msg: &super::super::DkgRound1Message| msg.party_id,
+                )
+                .await
+                .unwrap();
+
+                for (to, share) in [
+                    (0usize, bad_share),
+                    (1, evaluate_polynomial::<Secp256k1>(&secret_poly, 2)),
+                ] {
+                    relay
+                        .send_direct(
+                            &session_id,
+                            2,
+                            to,
+                            &super::super::DkgRound2Message {
+                                from: 2,
+                                to,
+                                share: share.to_bytes().to_vec(),
+                            },
+                        )
+                        .await
+                        .unwrap();
+                }
+
+                relay
+                    .broadcast(
+                        &session_id,
+                        3,
+                        &super::super::DkgComplaintMessage {
+                            party_id: 2,
+                            accused: Vec::new(),
+                        },
+                    )
+                    .await
+                    .unwrap();
+
+                relay
+                    .broadcast(
+                        &session_id,
+                        4,
+                        &super::super::DkgJustificationMessage {
+                            party_id: 2,
+                            revealed: vec![(0, bad_share.to_bytes().to_vec())],
+                        },
+                    )
+                    .await
+                    .unwrap();
+            }
+        });
+
+        malicious.await.unwrap();
+        for handle in handles {
+            match handle.await.unwrap() {
+                Ok(_) => panic!("honest party accepted a bad share"),
+                Err(err) => assert!(matches!(err, Error::MaliciousParty(2)), "got {:?}", err),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dkg_batch_all_parties_agree() {
+        use crate::mpc::MemoryRelay;
+        use futures_util::StreamExt;
+        use std::sync::Arc;
+
+        let relay = Arc::new(MemoryRelay::new());
+        let session_id: SessionId = rand::random();
+        let count = 3;
+
+        let mut handles = Vec::new();
+        for party_id in 0..2 {
+            let relay = relay.clone();
+            let config = SessionConfig {
+                session_id,
+                n_parties: 2,
+                threshold: 2,
+                party_id,
+                parties: vec![0, 1],
+            };
+            handles.push(tokio::spawn(async move {
+                let mut shares = Vec::new();
+                let mut instances = std::pin::pin!(run_dkg_batch(&config, count, &*relay));
+                while let Some(result) = instances.next().await {
+                    shares.push(result.unwrap());
+                }
+                shares.sort_by_key(|(index, _)| *index);
+                shares
+            }));
+        }
+
+        let shares0 = handles.remove(0).await.unwrap();
+        let shares1 = handles.remove(0).await.unwrap();
+
+        assert_eq!(shares0.len(), count);
+        assert_eq!(shares1.len(), count);
+        for ((i0, ks0), (i1, ks1)) in shares0.iter().zip(&shares1) {
+            assert_eq!(i0, i1);
+            assert_eq!(ks0.public_key, ks1.public_key);
+            // Both parties saw the same broadcast flow
+            assert_eq!(ks0.transcript_digest, ks1.transcript_digest);
+            assert_ne!(ks0.transcript_digest, [0u8; 32]);
+        }
+        // Independent instances must yield independent keys
+        assert_ne!(shares0[0].1.public_key, shares0[1].1.public_key);
+    }
+
+    #[tokio::test]
+    async fn test_dkg_with_seeded_rng_is_deterministic() {
+        use crate::mpc::MemoryRelay;
+        use rand::SeedableRng;
+        use std::sync::Arc;
+
+        async fn ceremony() -> (KeyShare, KeyShare) {
+            let relay = Arc::new(MemoryRelay::new());
+            let session_id: SessionId = [7u8; 32];
+
+            let mut handles = Vec::new();
+            for party_id in 0..2 {
+                let relay = relay.clone();
+                let config = SessionConfig {
+                    session_id,
+                    n_parties: 2,
+                    threshold: 2,
+                    party_id,
+                    parties: vec![0, 1],
+                };
+                handles.push(tokio::spawn(async move {
+                    let mut rng = rand::rngs::StdRng::seed_from_u64(party_id as u64);
+                    run_dkg_with_rng(&config, &*relay, &mut rng).await.unwrap()
+                }));
+            }
+            (handles.remove(0).await.unwrap(), handles.remove(0).await.unwrap())
+        }
+
+        let (first0, first1) = ceremony().await;
+        let (second0, second1) = ceremony().await;
+
+        // The same seeds reproduce the same key material exactly
+        assert_eq!(first0.public_key, second0.public_key);
+        assert_eq!(first0.secret_share, second0.secret_share);
+        assert_eq!(first0.chain_code, second0.chain_code);
+        assert_eq!(first1.secret_share, second1.secret_share);
+        assert_eq!(first0.transcript_digest, second0.transcript_digest);
+    }
+
+    #[test]
+    fn test_constant_term_pok_rejects_forgery_and_replay() {
+        let session_id: SessionId = rand::random();
+        let constant = Scalar::random(&mut OsRng);
+        let commitments =
+            commit_polynomial::<Secp256k1>(&[constant, Scalar::random(&mut OsRng)]);
+        let (pok_point, pok_response) =
+            prove_constant_term::<Secp256k1>(&session_id, 0, &constant, &mut OsRng);
+
+        let msg = super::super::DkgRound1Message {
+            party_id: 0,
+            commitments,
+            capabilities: 0,
+            pok_point,
+            pok_response,
+        };
+        assert!(verify_constant_term_pok::<Secp256k1>(&session_id, &msg).is_ok());
+
+        // A missing proof (pre-PoK sender) is rejected
+        let mut missing = msg.clone();
+        missing.pok_point = Vec::new();
+        missing.pok_response = Vec::new();
+        assert!(verify_constant_term_pok::<Secp256k1>(&session_id, &missing).is_err());
+
+        // A tampered response scalar is rejected
+        let mut tampered = msg.clone();
+        tampered.pok_response[0] ^= 1;
+        assert!(verify_constant_term_pok::<Secp256k1>(&session_id, &tampered).is_err());
+
+        // An honest proof replayed under another identity or session fails
+        let mut replayed = msg.clone();
+        replayed.party_id = 1;
+        assert!(verify_constant_term_pok::<Secp256k1>(&session_id, &replayed).is_err());
+        assert!(verify_constant_term_pok::<Secp256k1>(&rand::random(), &msg).is_err());
+    }
+
+    #[cfg(feature = "scheme-p256")]
+    #[tokio::test]
+    async fn test_p256_dkg_all_parties_agree() {
+        use crate::mpc::MemoryRelay;
+        use std::sync::Arc;
+
+        let relay = Arc::new(MemoryRelay::new());
+        let session_id: SessionId = rand::random();
+
+        let mut handles = Vec::new();
+        for party_id in 0..2 {
+            let relay = relay.clone();
+            let config = SessionConfig {
+                session_id,
+                n_parties: 2,
+                threshold: 2,
+                party_id,
+                parties: vec![0, 1],
+            };
+            handles.push(tokio::spawn(async move {
+                run_dkg_for_curve::<p256::NistP256, _>(&config, &*relay)
+                    .await
+                    .unwrap()
+            }));
+        }
+
+        let share0 = handles.remove(0).await.unwrap();
+        let share1 = handles.remove(0).await.unwrap();
+
+        assert_eq!(share0.public_key, share1.public_key);
+        assert_eq!(share0.transcript_digest, share1.transcript_digest);
+        assert_eq!(share0.scheme, crate::scheme::SchemeId::P256);
+        // Each secret share must match its published public share
+        share0.verify_consistency().unwrap();
+        share1.verify_consistency().unwrap();
+    }
+}
@@ -0,0 +1,177 @@
+//! Break-glass secret reconstruction from a quorum of key shares
+//!
+//! Reconstructing the private key defeats the point of threshold
+//! signing, but organizations still need a documented disaster-recovery
+//! path — migrating off the protocol, satisfying an estate process, or
+//! salvaging funds after losing the signing infrastructure. Doing it
+//! here, with full validation and an audit log line, beats every team
+//! improvising the Lagrange math against raw share files.
+
+use crate::{Error, KeyShare, Result};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::{ProjectivePoint, Scalar};
+use tracing::warn;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Reconstruct the group private key from a threshold quorum of shares
+///
+/// Requires at least `threshold` shares that agree on the key material
+/// (same public key, parameters and per-party public shares) and come
+/// from distinct parties. Each share is checked against its published
+/// public share before use, and the result is verified against the group
+/// public key, so a corrupted or mismatched share file fails loudly
+/// instead of yielding a wrong key. Intermediates are zeroized; the
+/// returned key is zeroized when dropped.
+///
+/// This is a break-glass operation: once it runs, the key is no longer
+/// threshold-protected. A warning is logged so the event lands in audit
+/// trails.
+pub fn reconstruct_secret(shares: &[KeyShare]) -> Result<Zeroizing<[u8; 32]>> {
+    let first = shares
+        .first()
+        .ok_or_else(|| Error::InvalidConfig("No shares provided".into()))?;
+
+    if shares.len() < first.threshold {
+        return Err(Error::ThresholdNotMet {
+            required: first.threshold,
+            actual: shares.len(),
+        });
+    }
+
+    let fingerprint = first.key_fingerprint();
+    let mut seen = std::collections::BTreeSet::new();
+    for share in shares {
+        if share.party_id >= first.n_parties {
+            return Err(Error::InvalidPartyId(share.party_id));
+        }
+        if !seen.insert(share.party_id) {
+            return Err(Error::VerificationFailed(format!(
+                "Duplicate share for party {}",
+                share.party_id
+            )));
+        }
+        if share.key_fingerprint() != fingerprint {
+            return Err(Error::KeyMismatch {
+                party: share.party_id,
+                fingerprint: hex::encode(share.key_fingerprint()),
+            });
+        }
+        share.verify_consistency()?;
+    }
+
+    // Lagrange interpolation at zero over x-coordinates party_id + 1
+    let mut secret = Scalar::ZERO;
+    for share in shares {
+        let xi = Scalar::from(share.party_id as u64 + 1);
+        let mut coefficient = Scalar::ONE;
+        for other in shares {
+            if other.party_id == share.party_id {
+                continue;
+            }
+            let xj = Scalar::from(other.party_id as u64 + 1);
+            coefficient *= xj * (xj - xi).invert().unwrap();
+        }
+        secret += coefficient * share.secret_share;
+    }
+
+    // The reconstruction must land on the group key the shares claim
+    let expected = (ProjectivePoint::GENERATOR * secret)
+        .to_affine()
+        .to_encoded_point(true);
+    if expected.as_bytes() != first.public_key.as_slice() {
+        return Err(Error::VerificationFailed(
+            "Reconstructed key does not match the group public key".into(),
+        ));
+    }
+
+    warn!(
+        public_key = hex::encode(&first.public_key),
+        parties = ?seen,
+        "Private key reconstructed from a share quorum; threshold protection is void for this key"
+    );
+
+    let bytes = Zeroizing::new(secret.to_bytes().into());
+    secret.zeroize();
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keygen::import_key;
+    use crate::SessionConfig;
+
+    fn shares_for_secret(value: u64) -> Vec<KeyShare> {
+        let mut secret_bytes = [0u8; 32];
+        secret_bytes[24..].copy_from_slice(&value.to_be_bytes());
+        let config = SessionConfig {
+            session_id: [0x31u8; 32],
+            n_parties: 3,
+            threshold: 2,
+            party_id: 0,
+            parties: (0..3).collect(),
+        };
+        import_key(&secret_bytes, &config).unwrap()
+    }
+
+    #[test]
+    fn test_reconstruct_recovers_imported_secret() {
+        let shares = shares_for_secret(0x5eed);
+        let mut expected = [0u8; 32];
+        expected[24..].copy_from_slice(&0x5eedu64.to_be_bytes());
+
+        // Any threshold subset works, not just a prefix
+        let secret = reconstruct_secret(&[shares[0].clone(), shares[2].clone()]).unwrap();
+        assert_eq!(*secret, expected);
+
+        // An oversized quorum agrees
+        let secret = reconstruct_secret(&shares).unwrap();
+        assert_eq!(*secret, expected);
+    }
+
+    #[test]
+    fn test_reconstruct_rejects_undersized_or_duplicate_quorums() {
+        let shares = shares_for_secret(11);
+
+        match reconstruct_secret(&shares[..1]) {
+            Err(Error::ThresholdNotMet { required, actual }) => {
+                assert_eq!(required, 2);
+                assert_eq!(actual, 1);
+            }
+            _ => panic!("undersized quorum must be rejected"),
+        }
+
+        // A duplicated share does not count toward the threshold
+        let duplicated = [shares[0].clone(), shares[0].clone()];
+        assert!(matches!(
+            reconstruct_secret(&duplicated),
+            Err(Error::VerificationFailed(_))
+        ));
+
+        assert!(matches!(
+            reconstruct_secret(&[]),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_reconstruct_rejects_mismatched_and_corrupted_shares() {
+        let shares = shares_for_secret(21);
+        let foreign = shares_for_secret(22);
+
+        // A share from a different key is caught by its fingerprint
+        let mixed = [shares[0].clone(), foreign[1].clone()];
+        assert!(matches!(
+            reconstruct_secret(&mixed),
+            Err(Error::KeyMismatch { party: 1, .. })
+        ));
+
+        // A tampered secret share no longer matches its public share
+        let mut corrupted = shares[1].clone();
+        corrupted.secret_share += Scalar::ONE;
+        assert!(matches!(
+            reconstruct_secret(&[shares[0].clone(), corrupted]),
+            Err(Error::VerificationFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,521 @@
+//! Key import via trusted-dealer split
+//!
+//! Converts an existing secp256k1 private key into DKLs23 shares with the
+//! same public key, so a single-key wallet can migrate to threshold
+//! signing without changing its address. [`import_key`] performs the
+//! split locally when the dealer provisions every device itself;
+//! [`run_import_dealer`] and [`run_import_receiver`] run the same split
+//! over a relay, with each share sealed to the receiving device under an
+//! ephemeral x25519 key so the relay never sees share material.
+//!
+//! Either way the dealer briefly holds the whole key — that is inherent
+//! to importing. A fresh DKG is preferable whenever the key does not
+//! already exist.
+
+use crate::mpc::Relay;
+use crate::{Error, KeyShare, PartyId, Result, SessionConfig};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use k256::elliptic_curve::{
+    bigint::U256, ops::Reduce, sec1::ToEncodedPoint, PrimeField,
+};
+use k256::{ProjectivePoint, Scalar};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use tracing::{debug, info, instrument};
+use x25519_dalek::{PublicKey, ReusableSecret};
+
+use super::reshare::{deal_polynomial, decode_point, evaluate_polynomial};
+
+use crate::consts::IMPORT_SEAL_CONTEXT;
+
+/// Split an existing private key into threshold shares locally
+///
+/// The dealer machine sees every share, so this fits the case where one
+/// operator provisions all devices before distributing them. Shares are
+/// returned in party-ID order; `config.party_id` is ignored. The group
+/// public key equals the imported key's public key, so existing addresses
+/// keep working.
+pub fn import_key(secret_key: &[u8; 32], config: &SessionConfig) -> Result<Vec<KeyShare>> {
+    let secret = parse_secret_key(secret_key)?;
+    let (coefficients, commitments) = deal_polynomial(secret, config.threshold, &mut OsRng);
+
+    let public_key = (ProjectivePoint::GENERATOR * secret)
+        .to_affine()
+        .to_encoded_point(true)
+        .as_bytes()
+        .to_vec();
+
+    // Imported keys have no DKG transcript; commit to the split itself
+    let mut transcript =
+        crate::transcript::Transcript::new(crate::transcript::IMPORT_LABEL, &config.session_id);
+    let chain_code: [u8; 32] = rand::random();
+    for commitment in &commitments {
+        transcript.append(1, 0, commitment);
+    }
+    transcript.append(1, 0, &chain_code);
+    let transcript_digest = transcript.digest();
+
+    let public_shares: Vec<Vec<u8>> = (0..config.n_parties)
+        .map(|party_id| {
+            let share = evaluate_polynomial(&coefficients, party_id as u64 + 1);
+            (ProjectivePoint::GENERATOR * share)
+                .to_affine()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec()
+        })
+        .collect();
+
+    let shares = (0..config.n_parties)
+        .map(|party_id| KeyShare {
+            party_id,
+            n_parties: config.n_parties,
+            threshold: config.threshold,
+            secret_share: evaluate_polynomial(&coefficients, party_id as u64 + 1),
+            public_key: public_key.clone(),
+            public_shares: public_shares.clone(),
+            chain_code,
+            scheme: crate::scheme::SchemeId::Secp256k1,
+            min_protocol_version: crate::PROTOCOL_VERSION,
+            transcript_digest,
+            integrity_tag: [0u8; 32],
+        })
+        .collect();
+
+    info!(
+        n_parties = config.n_parties,
+        threshold = config.threshold,
+        "Imported key split into shares"
+    );
+    Ok(shares)
+}
+
+/// Run the dealer's side of a distributed key import
+///
+/// The dealer (this party) splits `secret_key` and sends each receiver
+/// its share sealed under that receiver's ephemeral x25519 key, so only
+/// commitments and ciphertexts cross the relay. Returns the dealer's own
+/// share of the imported key.
+#[instrument(skip(config, secret_key, relay))]
+pub async fn run_import_dealer<R: Relay>(
+    config: &SessionConfig,
+    secret_key: &[u8; 32],
+    relay: &R,
+) -> Result<KeyShare> {
+    let secret = parse_secret_key(secret_key)?;
+    let receivers: Vec<PartyId> = config
+        .parties
+        .iter()
+        .copied()
+        .filter(|&party| party != config.party_id)
+        .collect();
+
+    info!(party_id = config.party_id, "Starting key import as dealer");
+
+    // Round 1: collect every receiver's ephemeral encryption key
+    debug!("Import Round 1: Receiver encryption keys");
+    let enc_keys = relay
+        .collect_broadcasts::<super::ImportRound1Message>(
+            &config.session_id,
+            1,
+            receivers.len(),
+        )
+        .await?;
+    let announced: Vec<PartyId> = enc_keys.iter().map(|msg| msg.party_id).collect();
+    if announced != receivers {
+        return Err(Error::VerificationFailed(
+            "Import round 1 keys do not match the receiver set".into(),
+        ));
+    }
+
+    // Round 2: commit to the split and announce the dealer's ephemeral key
+    debug!("Import Round 2: Commitment");
+    let (coefficients, commitments) = deal_polynomial(secret, config.threshold, &mut OsRng);
+    let ephemeral = ReusableSecret::random_from_rng(OsRng);
+    let round2_msg = super::ImportRound2Message {
+        dealer: config.party_id,
+        commitments,
+        chain_code: rand::random(),
+        enc_public_key: PublicKey::from(&ephemeral).to_bytes(),
+    };
+    relay
+        .broadcast(&config.session_id, 2, &round2_msg)
+        .await?;
+
+    // Round 3: seal each receiver's evaluation to its ephemeral key
+    debug!("Import Round 3: Share distribution");
+    for msg in &enc_keys {
+        let share = evaluate_polynomial(&coefficients, msg.party_id as u64 + 1);
+        let key = seal_key(
+            &ephemeral,
+            &PublicKey::from(msg.enc_public_key),
+            msg.party_id,
+        );
+
+        let cipher = ChaCha20Poly1305::new((&key).into());
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), share.to_bytes().as_slice())
+            .map_err(|_| Error::Crypto("Share encryption failed".into()))?;
+
+        let share_msg = super::ImportRound3Message {
+            from: config.party_id,
+            to: msg.party_id,
+            nonce: nonce.to_vec(),
+            ciphertext,
+        };
+        relay
+            .send_direct(&config.session_id, 3, msg.party_id, &share_msg)
+            .await?;
+    }
+
+    let key_share = assemble_key_share(config, config.party_id, &round2_msg, {
+        evaluate_polynomial(&coefficients, config.party_id as u64 + 1)
+    })?;
+
+    info!(party_id = config.party_id, "Key import completed");
+    Ok(key_share)
+}
+
+/// Run a receiver's side of a distributed key import
+///
+/// Announces an ephemeral encryption key, then verifies the sealed share
+/// the dealer sends back against the broadcast Feldman commitments before
+/// accepting it.
+#[instrument(skip(config, relay))]
+pub async fn run_import_receiver<R: Relay>(
+    config: &SessionConfig,
+    dealer: PartyId,
+    relay: &R,
+) -> Result<KeyShare> {
+    if dealer == config.party_id {
+        return Err(Error::InvalidConfig(
+            "The dealer runs run_import_dealer, not the receiver side".into(),
+        ));
+    }
+    if !config.parties.contains(&dealer) {
+        return Err(Error::InvalidPartyId(dealer));
+    }
+
+    info!(
+        party_id = config.party_id,
+        dealer, "Starting key import as receiver"
+    );
+
+    // Round 1: announce an ephemeral encryption key
+    debug!("Import Round 1: Receiver encryption keys");
+    let ephemeral = ReusableSecret::random_from_rng(OsRng);
+    let round1_msg = super::ImportRound1Message {
+        party_id: config.party_id,
+        enc_public_key: PublicKey::from(&ephemeral).to_bytes(),
+    };
+    relay
+        .broadcast(&config.session_id, 1, &round1_msg)
+        .await?;
+
+    // Round 2: the dealer's commitment to the split
+    debug!("Import Round 2: Commitment");
+    let round2 = relay
+        .collect_broadcasts::<super::ImportRound2Message>(&config.session_id, 2, 1)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::Timeout("Waiting for the import commitment".into()))?;
+    if round2.dealer != dealer {
+        return Err(Error::VerificationFailed(format!(
+            "Import commitment came from party {}, expected dealer {}",
+            round2.dealer, dealer
+        )));
+    }
+    if round2.commitments.len() != config.threshold {
+        return Err(Error::VerificationFailed(format!(
+            "Dealer committed to {} coefficients, expected {}",
+            round2.commitments.len(),
+            config.threshold
+        )));
+    }
+
+    // Round 3: open the sealed share and verify it against the commitments
+    debug!("Import Round 3: Share verification");
+    let share_msg = relay
+        .collect_direct::<super::ImportRound3Message>(
+            &config.session_id,
+            3,
+            config.party_id,
+            1,
+        )
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::Timeout("Waiting for the sealed share".into()))?;
+    if share_msg.from != dealer || share_msg.to != config.party_id {
+        return Err(Error::VerificationFailed(
+            "Sealed share is not addressed from the dealer to this party".into(),
+        ));
+    }
+    if share_msg.nonce.len() != 12 {
+        return Err(Error::Crypto("Invalid sealed share nonce length".into()));
+    }
+
+    let key = seal_key(
+        &ephemeral,
+        &PublicKey::from(round2.enc_public_key),
+        config.party_id,
+    );
+    let cipher = ChaCha20Poly1305::new((&key).into());
+    let plaintext = cipher
+        .decrypt(
+            Nonce::from_slice(&share_msg.nonce),
+            share_msg.ciphertext.as_slice(),
+        )
+        .map_err(|_| Error::Crypto("Sealed share decryption failed".into()))?;
+
+    let share_bytes: [u8; 32] = plaintext
+        .try_into()
+        .map_err(|_| Error::Deserialization("Invalid share length".into()))?;
+    let share = <Scalar as Reduce<U256>>::reduce_bytes(&share_bytes.into());
+
+    // The share must lie on the committed polynomial at this party's index
+    let expected = ProjectivePoint::GENERATOR * share;
+    let x_scalar = Scalar::from(config.party_id as u64 + 1);
+    let mut actual = ProjectivePoint::IDENTITY;
+    let mut x_power = Scalar::ONE;
+    for commitment_bytes in &round2.commitments {
+        actual += decode_point(commitment_bytes)? * x_power;
+        x_power *= x_scalar;
+    }
+    if expected != actual {
+        return Err(Error::VerificationFailed(
+            "Dealt share does not match the import commitments".into(),
+        ));
+    }
+
+    let key_share = assemble_key_share(config, config.party_id, &round2, share)?;
+
+    info!(party_id = config.party_id, "Key import completed");
+    Ok(key_share)
+}
+
+/// Parse and validate an imported private key as a non-zero scalar
+fn parse_secret_key(secret_key: &[u8; 32]) -> Result<Scalar> {
+    let secret = Option::<Scalar>::from(Scalar::from_repr((*secret_key).into()))
+        .ok_or_else(|| Error::Crypto("Imported key is not a valid secp256k1 scalar".into()))?;
+    if bool::from(secret.is_zero()) {
+        return Err(Error::Crypto("Imported key must be non-zero".into()));
+    }
+    Ok(secret)
+}
+
+/// Sealing key for one receiver's share, bound to both ephemeral keys
+/// and the receiver's identity
+fn seal_key(ephemeral: &ReusableSecret, peer: &PublicKey, receiver: PartyId) -> [u8; 32] {
+    let shared = ephemeral.diffie_hellman(peer);
+    let mut material = Vec::with_capacity(40);
+    material.extend_from_slice(shared.as_bytes());
+    material.extend_from_slice(&(receiver as u64).to_be_bytes());
+    blake3::derive_key(IMPORT_SEAL_CONTEXT, &material)
+}
+
+/// Build a [`KeyShare`] from the dealer's round 2 broadcast and a
+/// verified secret share
+///
+/// Every participant derives the public shares from the same commitment
+/// polynomial and hashes the same broadcast into the transcript, so all
+/// resulting shares agree on the public data and its digest.
+fn assemble_key_share(
+    config: &SessionConfig,
+    party_id: PartyId,
+    round2: &super::ImportRound2Message,
+    secret_share: Scalar,
+) -> Result<KeyShare> {
+    let public_key = decode_point(
+        round2
+            .commitments
+            .first()
+            .ok_or_else(|| Error::VerificationFailed("Empty import commitment".into()))?,
+    )?;
+    if public_key == ProjectivePoint::IDENTITY {
+        return Err(Error::VerificationFailed(
+            "Imported public key is the identity".into(),
+        ));
+    }
+
+    let mut public_shares = Vec::with_capacity(config.n_parties);
+    for member in 0..config.n_parties {
+        let x_scalar = Scalar::from(member as u64 + 1);
+        let mut public_share = ProjectivePoint::IDENTITY;
+        let mut x_power = Scalar::ONE;
+        for commitment_bytes in &round2.commitments {
+            public_share += decode_point(commitment_bytes)? * x_power;
+            x_power *= x_scalar;
+        }
+        public_shares.push(public_share.to_affine().to_encoded_point(true).as_bytes().to_vec());
+    }
+
+    let mut transcript =
+        crate::transcript::Transcript::new(crate::transcript::IMPORT_LABEL, &config.session_id);
+    transcript.append_message(2, round2.dealer, round2)?;
+
+    Ok(KeyShare {
+        party_id,
+        n_parties: config.n_parties,
+        threshold: config.threshold,
+        secret_share,
+        public_key: public_key.to_affine().to_encoded_point(true).as_bytes().to_vec(),
+        public_shares,
+        chain_code: round2.chain_code,
+        scheme: crate::scheme::SchemeId::Secp256k1,
+        min_protocol_version: crate::PROTOCOL_VERSION,
+        transcript_digest: transcript.digest(),
+        integrity_tag: [0u8; 32],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::MemoryRelay;
+    use crate::sign::run_dsg;
+    use std::sync::Arc;
+
+    fn config(n: usize, threshold: usize, party_id: usize) -> SessionConfig {
+        SessionConfig {
+            session_id: [0x1du8; 32],
+            n_parties: n,
+            threshold,
+            party_id,
+            parties: (0..n).collect(),
+        }
+    }
+
+    /// Interpolate a secret from (party_id, share) pairs at zero
+    fn reconstruct(shares: &[(usize, Scalar)]) -> Scalar {
+        let parties: Vec<usize> = shares.iter().map(|(id, _)| *id).collect();
+        shares
+            .iter()
+            .map(|(id, share)| {
+                let mut coef = Scalar::ONE;
+                let xi = Scalar::from(*id as u64 + 1);
+                for &j in &parties {
+                    if j == *id {
+                        continue;
+                    }
+                    let xj = Scalar::from(j as u64 + 1);
+                    coef *= xj * (xj - xi).invert().unwrap();
+                }
+                coef * share
+            })
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn test_local_import_preserves_public_key_and_signs() {
+        let secret_bytes: [u8; 32] = {
+            let mut bytes = [0u8; 32];
+            bytes[31] = 7;
+            bytes
+        };
+        let secret = Scalar::from(7u64);
+        let expected_key = (ProjectivePoint::GENERATOR * secret)
+            .to_affine()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+
+        let shares = import_key(&secret_bytes, &config(3, 2, 0)).unwrap();
+        assert_eq!(shares.len(), 3);
+        for share in &shares {
+            assert_eq!(share.public_key, expected_key);
+            assert_eq!(share.transcript_digest, shares[0].transcript_digest);
+        }
+
+        // Any threshold subset reconstructs the imported key
+        let subset = [
+            (0, shares[0].secret_share),
+            (2, shares[2].secret_share),
+        ];
+        assert_eq!(reconstruct(&subset), secret);
+
+        // The shares sign like any DKG output, under the imported key
+        let relay = Arc::new(MemoryRelay::new());
+        let message = [0x1au8; 32];
+        let signers = vec![0usize, 1];
+        let mut handles = Vec::new();
+        for share in shares.into_iter().take(2) {
+            let relay = relay.clone();
+            let signers = signers.clone();
+            handles.push(tokio::spawn(async move {
+                run_dsg(&share, &message, &signers, &*relay).await.unwrap()
+            }));
+        }
+        let first = handles.remove(0).await.unwrap();
+        let second = handles.remove(0).await.unwrap();
+        assert_eq!(first.r, second.r);
+        assert_eq!(first.s, second.s);
+    }
+
+    #[tokio::test]
+    async fn test_distributed_import_matches_local_split() {
+        let secret_bytes: [u8; 32] = {
+            let mut bytes = [0u8; 32];
+            bytes[31] = 42;
+            bytes
+        };
+        let secret = Scalar::from(42u64);
+        let expected_key = (ProjectivePoint::GENERATOR * secret)
+            .to_affine()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+        let relay = Arc::new(MemoryRelay::new());
+
+        let mut handles = Vec::new();
+        for party_id in 0..3usize {
+            let relay = relay.clone();
+            handles.push(tokio::spawn(async move {
+                let config = config(3, 2, party_id);
+                if party_id == 0 {
+                    run_import_dealer(&config, &secret_bytes, &*relay).await.unwrap()
+                } else {
+                    run_import_receiver(&config, 0, &*relay).await.unwrap()
+                }
+            }));
+        }
+
+        let mut shares = Vec::new();
+        for handle in handles {
+            shares.push(handle.await.unwrap());
+        }
+        shares.sort_by_key(|share| share.party_id);
+
+        for share in &shares {
+            assert_eq!(share.public_key, expected_key);
+            assert_eq!(share.chain_code, shares[0].chain_code);
+            assert_eq!(share.transcript_digest, shares[0].transcript_digest);
+            assert_eq!(share.public_shares, shares[0].public_shares);
+        }
+        let subset = [
+            (1, shares[1].secret_share),
+            (2, shares[2].secret_share),
+        ];
+        assert_eq!(reconstruct(&subset), secret);
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_secrets() {
+        let zero = [0u8; 32];
+        assert!(matches!(
+            import_key(&zero, &config(3, 2, 0)),
+            Err(Error::Crypto(_))
+        ));
+
+        // Larger than the curve order: not a canonical scalar encoding
+        let overflow = [0xffu8; 32];
+        assert!(matches!(
+            import_key(&overflow, &config(3, 2, 0)),
+            Err(Error::Crypto(_))
+        ));
+    }
+}
@@ -1,35 +1,372 @@
 //! Key refresh protocol
 
 use crate::mpc::Relay;
-use crate::{KeyShare, Result, SessionConfig};
-use tracing::{info, instrument};
+use crate::{Error, KeyShare, Result, SessionConfig};
+use k256::{
+    elliptic_curve::{
+        bigint::U256, ops::Reduce, Field,
+        sec1::{FromEncodedPoint, ToEncodedPoint},
+    },
+    AffinePoint, ProjectivePoint, Scalar,
+};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use tracing::{debug, info, instrument};
 
 /// Run the key refresh protocol
 ///
 /// This allows parties to refresh their shares without changing the public key.
 /// Useful for proactive security - regularly refreshing shares to limit the
 /// window of vulnerability if a share is compromised.
+///
+/// Each party deals a fresh degree-(t-1) polynomial with a zero constant
+/// term and distributes its evaluations exactly as in DKG; shares are
+/// verified against the Feldman commitments, and every party checks that
+/// each dealer's constant-term commitment is the identity so the refresh
+/// cannot shift the public key. Adding the zero-share evaluations to the
+/// old share yields a new sharing of the same secret, making old shares
+/// useless on their own.
 #[instrument(skip(relay, key_share))]
 pub async fn run_key_refresh<R: Relay>(
     config: &SessionConfig,
     key_share: &KeyShare,
     relay: &R,
+) -> Result<KeyShare> {
+    run_key_refresh_with_rng(config, key_share, relay, &mut OsRng).await
+}
+
+/// [`run_key_refresh`] drawing the zero-polynomial from a caller-supplied
+/// entropy source
+pub async fn run_key_refresh_with_rng<R: Relay, G: RngCore + CryptoRng>(
+    config: &SessionConfig,
+    key_share: &KeyShare,
+    relay: &R,
+    rng: &mut G,
 ) -> Result<KeyShare> {
     info!(
         party_id = config.party_id,
         "Starting key refresh"
     );
 
-    // Key refresh follows similar structure to DKG but with zero-sum shares
-    // This ensures the public key remains unchanged
+    // Round 1: Deal a zero-constant polynomial and commit to it
+    debug!("Refresh Round 1: Commitment");
+    let (zero_poly, commitments) = generate_zero_polynomial(config, rng);
+
+    let commitment_msg = super::DkgRound1Message {
+        party_id: config.party_id,
+        commitments,
+        capabilities: crate::capabilities::Capabilities::local().0,
+        pok_point: Vec::new(),
+        pok_response: Vec::new(),
+    };
+    relay
+        .broadcast(&config.session_id, 1, &commitment_msg)
+        .await?;
+
+    let all_commitments = relay
+        .collect_broadcasts::<super::DkgRound1Message>(&config.session_id, 1, config.n_parties)
+        .await?;
+
+    // Every dealer's constant term must commit to zero, otherwise the
+    // refresh would silently move the public key
+    for commitment_msg in &all_commitments {
+        verify_zero_constant_term(commitment_msg)?;
+    }
+
+    // Round 2: Send zero-share evaluations to each party
+    debug!("Refresh Round 2: Zero-share distribution");
+    for party_id in &config.parties {
+        if *party_id == config.party_id {
+            continue;
+        }
+        let share = evaluate_polynomial(&zero_poly, *party_id as u64 + 1);
+        let share_msg = super::DkgRound2Message {
+            from: config.party_id,
+            to: *party_id,
+            share: share.to_bytes().to_vec(),
+        };
+        relay
+            .send_direct(&config.session_id, 2, *party_id, &share_msg)
+            .await?;
+    }
+
+    let received_shares = relay
+        .collect_direct::<super::DkgRound2Message>(
+            &config.session_id,
+            2,
+            config.party_id,
+            config.n_parties - 1,
+        )
+        .await?;
+
+    // Round 3: Verify shares and fold them into the existing share
+    debug!("Refresh Round 3: Verification");
+    for share_msg in &received_shares {
+        verify_refresh_share(
+            share_msg,
+            &all_commitments[share_msg.from].commitments,
+            config.party_id,
+        )?;
+    }
+
+    let mut new_secret =
+        key_share.secret_share + evaluate_polynomial(&zero_poly, config.party_id as u64 + 1);
+    for share_msg in &received_shares {
+        let share_bytes: [u8; 32] = share_msg
+            .share
+            .clone()
+            .try_into()
+            .map_err(|_| Error::Deserialization("Invalid share length".into()))?;
+        let share = <Scalar as Reduce<U256>>::reduce_bytes(&share_bytes.into());
+        new_secret += share;
+    }
 
-    // For now, return a placeholder - full implementation would follow
-    // the key refresh protocol from the DKLs23 paper
+    // Shift every public share by the refresh deltas so they stay
+    // consistent with the new secret shares; the public key is untouched
+    let public_shares = refresh_public_shares(key_share, &all_commitments)?;
+
+    let mut new_key_share = key_share.clone();
+    new_key_share.secret_share = new_secret;
+    new_key_share.public_shares = public_shares;
 
     info!(
         party_id = config.party_id,
         "Key refresh completed"
     );
 
-    Ok(key_share.clone())
+    Ok(new_key_share)
+}
+
+/// Generate a random polynomial of degree t-1 with a zero constant term
+fn generate_zero_polynomial(
+    config: &SessionConfig,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> (Vec<Scalar>, Vec<Vec<u8>>) {
+    let mut coefficients = Vec::with_capacity(config.threshold);
+    let mut commitments = Vec::with_capacity(config.threshold);
+
+    for degree in 0..config.threshold {
+        let coef = if degree == 0 {
+            Scalar::ZERO
+        } else {
+            Scalar::random(&mut *rng)
+        };
+        let commitment = (ProjectivePoint::GENERATOR * coef).to_affine();
+
+        coefficients.push(coef);
+        commitments.push(commitment.to_encoded_point(true).as_bytes().to_vec());
+    }
+
+    (coefficients, commitments)
+}
+
+/// Evaluate polynomial at a point
+fn evaluate_polynomial(coefficients: &[Scalar], x: u64) -> Scalar {
+    let x_scalar = Scalar::from(x);
+    let mut result = Scalar::ZERO;
+    let mut x_power = Scalar::ONE;
+
+    for coef in coefficients {
+        result += *coef * x_power;
+        x_power *= x_scalar;
+    }
+
+    result
+}
+
+/// Decode a compressed commitment point
+fn decode_point(bytes: &[u8]) -> Result<ProjectivePoint> {
+    let point = k256::EncodedPoint::from_bytes(bytes)
+        .map_err(|e| Error::VerificationFailed(e.to_string()))?;
+    let affine_opt = AffinePoint::from_encoded_point(&point);
+    let affine: AffinePoint = Option::<AffinePoint>::from(affine_opt)
+        .ok_or_else(|| Error::VerificationFailed("Invalid commitment point".into()))?;
+    Ok(ProjectivePoint::from(affine))
+}
+
+/// Check that a dealer's constant-term commitment is the identity
+fn verify_zero_constant_term(commitment_msg: &super::DkgRound1Message) -> Result<()> {
+    let constant = commitment_msg
+        .commitments
+        .first()
+        .ok_or_else(|| Error::VerificationFailed("Empty commitments".into()))?;
+
+    if decode_point(constant)? != ProjectivePoint::IDENTITY {
+        return Err(Error::VerificationFailed(format!(
+            "Party {} dealt a non-zero constant term during refresh",
+            commitment_msg.party_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Verify a zero-share evaluation against the dealer's commitments
+fn verify_refresh_share(
+    share_msg: &super::DkgRound2Message,
+    commitments: &[Vec<u8>],
+    my_id: usize,
+) -> Result<()> {
+    let share_bytes: [u8; 32] = share_msg
+        .share
+        .clone()
+        .try_into()
+        .map_err(|_| Error::Deserialization("Invalid share length".into()))?;
+    let share = <Scalar as Reduce<U256>>::reduce_bytes(&share_bytes.into());
+
+    let expected = ProjectivePoint::GENERATOR * share;
+
+    let x_scalar = Scalar::from((my_id + 1) as u64);
+    let mut actual = ProjectivePoint::IDENTITY;
+    let mut x_power = Scalar::ONE;
+    for commitment_bytes in commitments {
+        actual += decode_point(commitment_bytes)? * x_power;
+        x_power *= x_scalar;
+    }
+
+    if expected != actual {
+        return Err(Error::VerificationFailed(format!(
+            "Refresh share from party {} does not match commitment",
+            share_msg.from
+        )));
+    }
+
+    Ok(())
+}
+
+/// Add every dealer's commitment evaluation to the stored public shares
+fn refresh_public_shares(
+    key_share: &KeyShare,
+    all_commitments: &[super::DkgRound1Message],
+) -> Result<Vec<Vec<u8>>> {
+    let mut public_shares = Vec::with_capacity(key_share.public_shares.len());
+
+    for (party_id, old_share) in key_share.public_shares.iter().enumerate() {
+        let mut public_share = decode_point(old_share)?;
+        let x_scalar = Scalar::from((party_id + 1) as u64);
+
+        for commitment_msg in all_commitments {
+            let mut x_power = Scalar::ONE;
+            for commitment_bytes in &commitment_msg.commitments {
+                public_share += decode_point(commitment_bytes)? * x_power;
+                x_power *= x_scalar;
+            }
+        }
+
+        let encoded = public_share.to_affine().to_encoded_point(true);
+        public_shares.push(encoded.as_bytes().to_vec());
+    }
+
+    Ok(public_shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keygen::run_dkg;
+    use crate::mpc::MemoryRelay;
+    use std::sync::Arc;
+
+    /// Lagrange coefficient at zero for party `i` within `parties`
+    fn lagrange_at_zero(i: usize, parties: &[usize]) -> Scalar {
+        let xi = Scalar::from((i + 1) as u64);
+        let mut coef = Scalar::ONE;
+        for &j in parties {
+            if j == i {
+                continue;
+            }
+            let xj = Scalar::from((j + 1) as u64);
+            coef *= xj * (xj - xi).invert().unwrap();
+        }
+        coef
+    }
+
+    /// Reconstruct the secret from a threshold subset of shares
+    fn reconstruct(shares: &[(usize, Scalar)]) -> Scalar {
+        let parties: Vec<usize> = shares.iter().map(|(id, _)| *id).collect();
+        shares
+            .iter()
+            .map(|(id, share)| lagrange_at_zero(*id, &parties) * share)
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn test_refresh_preserves_secret_and_replaces_shares() {
+        let relay = Arc::new(MemoryRelay::new());
+        let dkg_session = [0x11u8; 32];
+        let refresh_session = [0x22u8; 32];
+        let n = 3;
+
+        let mut handles = Vec::new();
+        for party_id in 0..n {
+            let relay = relay.clone();
+            handles.push(tokio::spawn(async move {
+                let mut config = SessionConfig {
+                    session_id: dkg_session,
+                    n_parties: n,
+                    threshold: 2,
+                    party_id,
+                    parties: (0..n).collect(),
+                };
+                let old_share = run_dkg(&config, &*relay).await.unwrap();
+                config.session_id = refresh_session;
+                let new_share = run_key_refresh(&config, &old_share, &*relay)
+                    .await
+                    .unwrap();
+                (old_share, new_share)
+            }));
+        }
+
+        let mut old_shares = Vec::new();
+        let mut new_shares = Vec::new();
+        for handle in handles {
+            let (old_share, new_share) = handle.await.unwrap();
+            old_shares.push(old_share);
+            new_shares.push(new_share);
+        }
+        old_shares.sort_by_key(|share| share.party_id);
+        new_shares.sort_by_key(|share| share.party_id);
+
+        for (old_share, new_share) in old_shares.iter().zip(&new_shares) {
+            // Public key untouched, secret shares replaced
+            assert_eq!(old_share.public_key, new_share.public_key);
+            assert_ne!(old_share.secret_share, new_share.secret_share);
+
+            // Public shares stay consistent with the new secret shares
+            let expected = (ProjectivePoint::GENERATOR * new_share.secret_share)
+                .to_affine()
+                .to_encoded_point(true);
+            assert_eq!(
+                new_share.public_shares[new_share.party_id],
+                expected.as_bytes()
+            );
+        }
+
+        // Any threshold subset of new shares reconstructs the same secret
+        let old_secret = reconstruct(&[
+            (0, old_shares[0].secret_share),
+            (1, old_shares[1].secret_share),
+        ]);
+        let new_secret = reconstruct(&[
+            (1, new_shares[1].secret_share),
+            (2, new_shares[2].secret_share),
+        ]);
+        assert_eq!(old_secret, new_secret);
+    }
+
+    #[test]
+    fn test_rejects_non_zero_constant_term() {
+        let commitment = (ProjectivePoint::GENERATOR * Scalar::from(5u64))
+            .to_affine()
+            .to_encoded_point(true);
+        let msg = super::super::DkgRound1Message {
+            party_id: 0,
+            commitments: vec![commitment.as_bytes().to_vec()],
+            capabilities: 0,
+            pok_point: Vec::new(),
+            pok_response: Vec::new(),
+        };
+
+        assert!(verify_zero_constant_term(&msg).is_err());
+    }
 }
@@ -10,6 +10,16 @@ pub struct DkgRound1Message {
     pub party_id: PartyId,
     /// Commitments to polynomial coefficients (Feldman VSS)
     pub commitments: Vec<Vec<u8>>,
+    /// Advertised capability bitmap (zero from pre-capability releases)
+    #[serde(default)]
+    pub capabilities: u64,
+    /// Schnorr proof-of-knowledge point for the constant-term commitment
+    /// (absent from pre-PoK releases, which DKG verification rejects)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pok_point: Vec<u8>,
+    /// Response scalar of the constant-term proof of knowledge
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pok_response: Vec<u8>,
 }
 
 /// Round 2 message: Secret share
@@ -23,6 +33,42 @@ pub struct DkgRound2Message {
     pub share: Vec<u8>,
 }
 
+/// Reshare round 1 message: a dealer's commitment to its fresh polynomial
+///
+/// The constant term commits to the dealer's Lagrange-adjusted share, so
+/// the constant terms of all dealers sum to the group public key. The
+/// chain code rides along because new committee members have no prior
+/// copy of it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReshareRound1Message {
+    /// Dealer's (old-committee) party ID
+    pub dealer: PartyId,
+    /// Commitments to polynomial coefficients (Feldman VSS)
+    pub commitments: Vec<Vec<u8>>,
+    /// BIP32 chain code of the key being reshared
+    pub chain_code: [u8; 32],
+}
+
+/// Add-party message: a dealer's view of the committee's public data
+///
+/// The joining party has none of this yet; every dealer broadcasts its
+/// copy and all copies must match before the joiner trusts any of it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddPartyPublicMessage {
+    /// Dealer's party ID
+    pub dealer: PartyId,
+    /// Current committee size
+    pub n_parties: usize,
+    /// Signing threshold
+    pub threshold: usize,
+    /// Group public key (SEC1 compressed)
+    pub public_key: Vec<u8>,
+    /// Per-party public shares
+    pub public_shares: Vec<Vec<u8>>,
+    /// BIP32 chain code
+    pub chain_code: [u8; 32],
+}
+
 /// Round 3 message: Completion acknowledgment
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DkgRound3Message {
@@ -31,3 +77,69 @@ pub struct DkgRound3Message {
     /// Public key share verification
     pub public_share: Vec<u8>,
 }
+
+/// Complaint round message: shares that failed verification
+///
+/// Broadcast by every party after share verification, with an empty
+/// accusation list when all received shares checked out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DkgComplaintMessage {
+    /// Sender party ID
+    pub party_id: PartyId,
+    /// Dealers whose shares failed verification against their commitments
+    pub accused: Vec<PartyId>,
+}
+
+/// Justification message: an accused dealer reveals the disputed shares
+///
+/// Revealing the share publicly lets every party re-run the commitment
+/// check and agree on whether the dealer or the complainer cheated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DkgJustificationMessage {
+    /// Accused dealer's party ID
+    pub party_id: PartyId,
+    /// For each complainer, the share that was dealt to them
+    pub revealed: Vec<(PartyId, Vec<u8>)>,
+}
+
+/// Import round 1 message: a receiver's ephemeral encryption key
+///
+/// Each receiver announces a fresh x25519 public key so the dealer can
+/// seal that receiver's share; the ephemeral secret never outlives the
+/// ceremony.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRound1Message {
+    /// Receiver party ID
+    pub party_id: PartyId,
+    /// Ephemeral x25519 public key
+    pub enc_public_key: [u8; 32],
+}
+
+/// Import round 2 message: the dealer's commitment to the split
+///
+/// The constant-term commitment is the imported key's public key, which
+/// receivers check against what they expect to be importing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRound2Message {
+    /// Dealer party ID
+    pub dealer: PartyId,
+    /// Commitments to polynomial coefficients (Feldman VSS)
+    pub commitments: Vec<Vec<u8>>,
+    /// BIP32 chain code chosen for the imported key
+    pub chain_code: [u8; 32],
+    /// The dealer's ephemeral x25519 public key for sealing shares
+    pub enc_public_key: [u8; 32],
+}
+
+/// Import round 3 message: one receiver's sealed share
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRound3Message {
+    /// Dealer party ID
+    pub from: PartyId,
+    /// Receiver party ID
+    pub to: PartyId,
+    /// Random 96-bit nonce
+    pub nonce: Vec<u8>,
+    /// ChaCha20-Poly1305 ciphertext of the 32-byte share
+    pub ciphertext: Vec<u8>,
+}
@@ -2,42 +2,282 @@
 //!
 //! Implements the DKG protocol from DKLs23 for generating threshold ECDSA keys.
 
+mod add_party;
 mod dkg;
+mod export;
+mod import;
 mod key_refresh;
 mod messages;
+mod reshare;
 
-pub use dkg::run_dkg;
-pub use key_refresh::run_key_refresh;
+pub use add_party::{run_add_party, run_add_party_with_rng, AddPartyConfig};
+pub use dkg::{
+    interpolate_public_key, interpolate_public_key_for_curve, run_dkg, run_dkg_batch,
+    run_dkg_for_curve, run_dkg_for_curve_with_rng, run_dkg_with_rng, verify_commitment_set,
+    verify_commitment_set_for_curve,
+};
+pub use export::reconstruct_secret;
+pub use import::{import_key, run_import_dealer, run_import_receiver};
+pub use key_refresh::{run_key_refresh, run_key_refresh_with_rng};
 pub use messages::*;
+pub use reshare::{run_reshare, run_reshare_with_rng, run_revoke_party, ReshareConfig};
 
-use crate::{KeyShare, Result, SessionConfig};
+use crate::{Error, PartyId, Result, SessionConfig};
+use std::collections::BTreeMap;
 
-/// DKG state machine
+/// A protocol message fed into a [`DkgSession`]
+#[derive(Debug, Clone)]
+pub enum DkgInput {
+    /// Round 1 commitment broadcast
+    Round1(DkgRound1Message),
+    /// Round 2 direct share
+    Round2(DkgRound2Message),
+    /// Round 3 public share broadcast
+    Round3(DkgRound3Message),
+}
+
+impl DkgInput {
+    fn round(&self) -> u32 {
+        match self {
+            DkgInput::Round1(_) => 1,
+            DkgInput::Round2(_) => 2,
+            DkgInput::Round3(_) => 3,
+        }
+    }
+
+    fn sender(&self) -> PartyId {
+        match self {
+            DkgInput::Round1(msg) => msg.party_id,
+            DkgInput::Round2(msg) => msg.from,
+            DkgInput::Round3(msg) => msg.party_id,
+        }
+    }
+}
+
+/// Sans-IO DKG state machine
+///
+/// Tracks which round the ceremony is in and which parties have delivered
+/// their messages, rejecting anything that does not fit the current state:
+/// wrong-round messages, unknown senders, duplicates, and shares addressed
+/// to another party. [`run_dkg`] performs the cryptographic checks; this
+/// machine accounts for message flow so drivers can validate ordering
+/// without a relay.
 pub struct DkgSession {
     config: SessionConfig,
     round: u32,
-    commitments: Vec<Vec<u8>>,
-    shares: Vec<Vec<u8>>,
+    commitments: BTreeMap<PartyId, Vec<Vec<u8>>>,
+    shares: BTreeMap<PartyId, Vec<u8>>,
+    public_shares: BTreeMap<PartyId, Vec<u8>>,
 }
 
 impl DkgSession {
-    /// Create a new DKG session
+    /// Create a new DKG session, starting in round 1
     pub fn new(config: SessionConfig) -> Self {
         Self {
             config,
-            round: 0,
-            commitments: Vec::new(),
-            shares: Vec::new(),
+            round: 1,
+            commitments: BTreeMap::new(),
+            shares: BTreeMap::new(),
+            public_shares: BTreeMap::new(),
         }
     }
 
-    /// Get current round
+    /// The round the session is currently collecting
     pub fn round(&self) -> u32 {
         self.round
     }
 
     /// Check if DKG is complete
     pub fn is_complete(&self) -> bool {
-        self.round >= 3
+        self.round > crate::consts::DKG_BROADCAST_ROUNDS
+    }
+
+    /// Commitments collected in round 1, by dealer
+    pub fn commitments(&self) -> &BTreeMap<PartyId, Vec<Vec<u8>>> {
+        &self.commitments
+    }
+
+    /// Shares dealt to this party in round 2, by dealer
+    pub fn shares(&self) -> &BTreeMap<PartyId, Vec<u8>> {
+        &self.shares
+    }
+
+    /// Public shares collected in round 3, by party
+    pub fn public_shares(&self) -> &BTreeMap<PartyId, Vec<u8>> {
+        &self.public_shares
+    }
+
+    /// Feed one message into the state machine
+    ///
+    /// Accepts the message if it belongs to the current round, comes from a
+    /// known sender that has not delivered yet, and (for round 2) is
+    /// addressed to this party; advances the round once every expected
+    /// message for it has arrived. Rejected messages leave the state
+    /// untouched.
+    pub fn handle_message(&mut self, input: DkgInput) -> Result<()> {
+        if input.round() != self.round {
+            return Err(Error::UnexpectedRound {
+                expected: self.round,
+                actual: input.round(),
+            });
+        }
+        let sender = input.sender();
+        if !self.config.parties.contains(&sender) {
+            return Err(Error::InvalidPartyId(sender));
+        }
+
+        match input {
+            DkgInput::Round1(msg) => {
+                if self.commitments.contains_key(&msg.party_id) {
+                    return Err(Error::VerificationFailed(format!(
+                        "Duplicate round 1 commitment from party {}",
+                        msg.party_id
+                    )));
+                }
+                self.commitments.insert(msg.party_id, msg.commitments);
+                if self.commitments.len() == self.config.n_parties {
+                    self.round = 2;
+                }
+            }
+            DkgInput::Round2(msg) => {
+                if msg.to != self.config.party_id {
+                    return Err(Error::VerificationFailed(format!(
+                        "Received a share addressed to party {}",
+                        msg.to
+                    )));
+                }
+                if msg.from == self.config.party_id {
+                    return Err(Error::VerificationFailed(
+                        "Own share is dealt locally, not via the relay".into(),
+                    ));
+                }
+                if self.shares.contains_key(&msg.from) {
+                    return Err(Error::VerificationFailed(format!(
+                        "Duplicate round 2 share from party {}",
+                        msg.from
+                    )));
+                }
+                self.shares.insert(msg.from, msg.share);
+                if self.shares.len() == self.config.n_parties - 1 {
+                    self.round = 3;
+                }
+            }
+            DkgInput::Round3(msg) => {
+                if self.public_shares.contains_key(&msg.party_id) {
+                    return Err(Error::VerificationFailed(format!(
+                        "Duplicate round 3 public share from party {}",
+                        msg.party_id
+                    )));
+                }
+                self.public_shares.insert(msg.party_id, msg.public_share);
+                if self.public_shares.len() == self.config.n_parties {
+                    self.round = 4;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn config() -> SessionConfig {
+        SessionConfig::new(3, 2, 0).unwrap()
+    }
+
+    fn round1(from: PartyId) -> DkgInput {
+        DkgInput::Round1(DkgRound1Message {
+            party_id: from,
+            commitments: vec![vec![from as u8]],
+            capabilities: 0,
+            pok_point: Vec::new(),
+            pok_response: Vec::new(),
+        })
+    }
+
+    fn round2(from: PartyId, to: PartyId) -> DkgInput {
+        DkgInput::Round2(DkgRound2Message {
+            from,
+            to,
+            share: vec![from as u8],
+        })
+    }
+
+    fn round3(from: PartyId) -> DkgInput {
+        DkgInput::Round3(DkgRound3Message {
+            party_id: from,
+            public_share: vec![from as u8],
+        })
+    }
+
+    /// Arbitrary messages, including unknown senders and wrong recipients
+    fn arb_input() -> impl Strategy<Value = DkgInput> {
+        (0u8..3, 0usize..5, 0usize..5).prop_map(|(variant, from, to)| match variant {
+            0 => round1(from),
+            1 => round2(from, to),
+            _ => round3(from),
+        })
+    }
+
+    proptest! {
+        /// Every (state, message) pair is either accepted or rejected with
+        /// one of the defined errors, and rejections never mutate state
+        #[test]
+        fn model_rejections_are_defined_and_state_preserving(
+            inputs in proptest::collection::vec(arb_input(), 0..64),
+        ) {
+            let mut session = DkgSession::new(config());
+            for input in inputs {
+                let before = session.round();
+                match session.handle_message(input) {
+                    Ok(()) => prop_assert!(session.round() >= before),
+                    Err(Error::UnexpectedRound { expected, .. }) => {
+                        prop_assert_eq!(expected, before);
+                        prop_assert_eq!(session.round(), before);
+                    }
+                    Err(Error::InvalidPartyId(_)) | Err(Error::VerificationFailed(_)) => {
+                        prop_assert_eq!(session.round(), before);
+                    }
+                    Err(other) => prop_assert!(false, "undefined rejection: {}", other),
+                }
+                prop_assert!(session.round() <= 4);
+            }
+        }
+
+        /// Any delivery order within each round reaches the same complete
+        /// terminal state, after which nothing further is accepted
+        #[test]
+        fn model_valid_sequences_reach_consistent_terminal_state(
+            order1 in Just(vec![0usize, 1, 2]).prop_shuffle(),
+            order2 in Just(vec![1usize, 2]).prop_shuffle(),
+            order3 in Just(vec![0usize, 1, 2]).prop_shuffle(),
+        ) {
+            let mut session = DkgSession::new(config());
+
+            for from in order1 {
+                prop_assert!(session.handle_message(round1(from)).is_ok());
+            }
+            prop_assert_eq!(session.round(), 2);
+            for from in order2 {
+                prop_assert!(session.handle_message(round2(from, 0)).is_ok());
+            }
+            prop_assert_eq!(session.round(), 3);
+            for from in order3 {
+                prop_assert!(session.handle_message(round3(from)).is_ok());
+            }
+
+            prop_assert!(session.is_complete());
+            prop_assert_eq!(session.commitments().len(), 3);
+            prop_assert_eq!(session.shares().len(), 2);
+            prop_assert_eq!(session.public_shares().len(), 3);
+            let rejected_after_completion = matches!(
+                session.handle_message(round3(0)),
+                Err(Error::UnexpectedRound { .. })
+            );
+            prop_assert!(rejected_after_completion);
+        }
     }
 }
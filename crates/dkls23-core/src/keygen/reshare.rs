@@ -0,0 +1,651 @@
+//! Key resharing protocol
+//!
+//! Redistributes an existing key to a new (t', n') committee without ever
+//! reconstructing the secret. A threshold subset of the old committee acts
+//! as dealers: each dealer Lagrange-adjusts its share into an additive
+//! share of the secret, deals it out under a fresh degree-(t'-1) Feldman
+//! polynomial, and the new committee sums the dealt evaluations into a
+//! fresh Shamir sharing of the same secret. The group public key is
+//! unchanged; the new sharing is independent of the old one, so once the
+//! old committee destroys its shares they are useless even in combination
+//! with any minority of new shares.
+
+use crate::mpc::Relay;
+use crate::{Error, KeyShare, PartyId, Result, SessionId};
+use k256::{
+    elliptic_curve::{
+        bigint::U256,
+        ops::Reduce,
+        sec1::{FromEncodedPoint, ToEncodedPoint},
+        Field,
+    },
+    AffinePoint, ProjectivePoint, Scalar,
+};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use tracing::{debug, info, instrument};
+
+/// Configuration for a resharing ceremony
+///
+/// Relay party IDs name the physical participants: old-committee members
+/// keep their DKG-era IDs, new members get fresh ones, and a party that
+/// serves on both committees appears in both lists under one ID. The new
+/// committee's share indices are positions in `receivers`, so the new key
+/// shares are numbered densely from zero regardless of relay IDs.
+#[derive(Debug, Clone)]
+pub struct ReshareConfig {
+    /// Session identifier
+    pub session_id: SessionId,
+    /// Old-committee members dealing their shares (their DKG party IDs);
+    /// must be at least the old threshold
+    pub dealers: Vec<PartyId>,
+    /// Relay IDs of the new committee; position in this list becomes the
+    /// member's new party ID
+    pub receivers: Vec<PartyId>,
+    /// Threshold of the new committee
+    pub new_threshold: usize,
+    /// This party's relay ID
+    pub party_id: PartyId,
+    /// Group public key new members expect to receive (SEC1 compressed);
+    /// dealers check against their share, so this may be `None` for them
+    pub expected_public_key: Option<Vec<u8>>,
+}
+
+/// Run the key resharing protocol
+///
+/// Dealers pass their old key share; receiver-only parties pass `None`.
+/// Returns the fresh key share for parties on the new committee and
+/// `None` for dealers who are retiring. Old shares are not touched —
+/// invalidating them means the old committee deleting them once the new
+/// committee has confirmed its shares.
+#[instrument(skip(relay, old_share), fields(party_id = config.party_id))]
+pub async fn run_reshare<R: Relay>(
+    config: &ReshareConfig,
+    old_share: Option<&KeyShare>,
+    relay: &R,
+) -> Result<Option<KeyShare>> {
+    run_reshare_with_rng(config, old_share, relay, &mut OsRng).await
+}
+
+/// [`run_reshare`] drawing the fresh polynomials from a caller-supplied
+/// entropy source
+pub async fn run_reshare_with_rng<R: Relay, G: RngCore + CryptoRng>(
+    config: &ReshareConfig,
+    old_share: Option<&KeyShare>,
+    relay: &R,
+    rng: &mut G,
+) -> Result<Option<KeyShare>> {
+    let is_dealer = config.dealers.contains(&config.party_id);
+    let new_id = config.receivers.iter().position(|&p| p == config.party_id);
+    if !is_dealer && new_id.is_none() {
+        return Err(Error::InvalidPartyId(config.party_id));
+    }
+    if config.new_threshold < 2 || config.new_threshold > config.receivers.len() {
+        return Err(Error::InvalidConfig(format!(
+            "New threshold {} invalid for {} receivers",
+            config.new_threshold,
+            config.receivers.len()
+        )));
+    }
+
+    info!(
+        dealers = config.dealers.len(),
+        receivers = config.receivers.len(),
+        new_threshold = config.new_threshold,
+        "Starting key resharing"
+    );
+
+    // Round 1: each dealer commits to a fresh polynomial whose constant
+    // term is its Lagrange-adjusted (additive) share of the secret
+    debug!("Reshare Round 1: Commitment");
+    let dealt_poly = if is_dealer {
+        let old_share = old_share.ok_or_else(|| {
+            Error::InvalidConfig("Dealer must provide its old key share".into())
+        })?;
+        if old_share.party_id != config.party_id {
+            return Err(Error::InvalidPartyId(old_share.party_id));
+        }
+        if config.dealers.len() < old_share.threshold {
+            return Err(Error::ThresholdNotMet {
+                required: old_share.threshold,
+                actual: config.dealers.len(),
+            });
+        }
+
+        let additive = lagrange_at_zero(config.party_id, &config.dealers) * old_share.secret_share;
+        let (poly, commitments) = deal_polynomial(additive, config.new_threshold, rng);
+
+        let commitment_msg = super::ReshareRound1Message {
+            dealer: config.party_id,
+            commitments,
+            chain_code: old_share.chain_code,
+        };
+        relay
+            .broadcast(&config.session_id, 1, &commitment_msg)
+            .await?;
+        Some(poly)
+    } else {
+        None
+    };
+
+    let mut all_commitments = relay
+        .collect_broadcasts::<super::ReshareRound1Message>(
+            &config.session_id,
+            1,
+            config.dealers.len(),
+        )
+        .await?;
+    all_commitments.sort_by_key(|msg| msg.dealer);
+
+    // Every configured dealer must have dealt exactly once and the
+    // constant terms must sum to the group key — a committee cannot be
+    // reshared onto a new secret
+    let dealt: Vec<PartyId> = all_commitments.iter().map(|msg| msg.dealer).collect();
+    let mut expected_dealers = config.dealers.clone();
+    expected_dealers.sort_unstable();
+    if dealt != expected_dealers {
+        return Err(Error::VerificationFailed(format!(
+            "Dealt commitments from {:?}, expected {:?}",
+            dealt, expected_dealers
+        )));
+    }
+    // New members have no prior copy of the chain code; adopt the lowest
+    // dealer's (DKG hands each party its own, so dealers may disagree)
+    let chain_code = all_commitments[0].chain_code;
+    for msg in &all_commitments {
+        if msg.commitments.len() != config.new_threshold {
+            return Err(Error::VerificationFailed(format!(
+                "Dealer {} committed to a degree-{} polynomial, expected {}",
+                msg.dealer,
+                msg.commitments.len(),
+                config.new_threshold
+            )));
+        }
+    }
+
+    let mut group_key = ProjectivePoint::IDENTITY;
+    for msg in &all_commitments {
+        group_key += decode_point(&msg.commitments[0])?;
+    }
+    let public_key = group_key.to_affine().to_encoded_point(true).as_bytes().to_vec();
+    let expected = old_share
+        .map(|share| &share.public_key)
+        .or(config.expected_public_key.as_ref());
+    if let Some(expected) = expected {
+        if *expected != public_key {
+            return Err(Error::VerificationFailed(
+                "Dealt commitments do not sum to the expected public key".into(),
+            ));
+        }
+    }
+
+    let mut transcript =
+        crate::transcript::Transcript::new(crate::transcript::RESHARE_LABEL, &config.session_id);
+    for msg in &all_commitments {
+        transcript.append_message(1, msg.dealer, msg)?;
+    }
+
+    // Round 2: dealers evaluate their polynomial at each new member's
+    // share index and send the evaluation directly
+    debug!("Reshare Round 2: Share distribution");
+    if let Some(poly) = &dealt_poly {
+        for (new_id, &relay_id) in config.receivers.iter().enumerate() {
+            let share = evaluate_polynomial(poly, new_id as u64 + 1);
+            let share_msg = super::DkgRound2Message {
+                from: config.party_id,
+                to: new_id,
+                share: share.to_bytes().to_vec(),
+            };
+            relay
+                .send_direct(&config.session_id, 2, relay_id, &share_msg)
+                .await?;
+        }
+    }
+
+    // Retiring dealers are done once they have dealt
+    let Some(new_id) = new_id else {
+        info!("Resharing completed (dealer only)");
+        return Ok(None);
+    };
+
+    let received = relay
+        .collect_direct::<super::DkgRound2Message>(
+            &config.session_id,
+            2,
+            config.party_id,
+            config.dealers.len(),
+        )
+        .await?;
+
+    // Round 3: verify every dealt evaluation against its dealer's
+    // commitments and sum them into this member's new share
+    debug!("Reshare Round 3: Verification");
+    let mut new_secret = Scalar::ZERO;
+    for share_msg in &received {
+        let commitments = all_commitments
+            .iter()
+            .find(|msg| msg.dealer == share_msg.from)
+            .map(|msg| &msg.commitments)
+            .ok_or(Error::InvalidPartyId(share_msg.from))?;
+        new_secret += verify_dealt_share(share_msg, commitments, new_id)?;
+    }
+
+    // Public shares for every new member, from the summed commitment
+    // polynomials evaluated at each share index
+    let mut public_shares = Vec::with_capacity(config.receivers.len());
+    for member in 0..config.receivers.len() {
+        let x_scalar = Scalar::from(member as u64 + 1);
+        let mut public_share = ProjectivePoint::IDENTITY;
+        for msg in &all_commitments {
+            let mut x_power = Scalar::ONE;
+            for commitment_bytes in &msg.commitments {
+                public_share += decode_point(commitment_bytes)? * x_power;
+                x_power *= x_scalar;
+            }
+        }
+        let encoded = public_share.to_affine().to_encoded_point(true);
+        public_shares.push(encoded.as_bytes().to_vec());
+    }
+
+    let key_share = KeyShare {
+        party_id: new_id,
+        n_parties: config.receivers.len(),
+        threshold: config.new_threshold,
+        secret_share: new_secret,
+        public_key,
+        public_shares,
+        chain_code,
+        scheme: crate::scheme::SchemeId::Secp256k1,
+        min_protocol_version: crate::PROTOCOL_VERSION,
+        transcript_digest: transcript.digest(),
+        integrity_tag: [0u8; 32],
+    };
+
+    info!(new_party_id = new_id, "Resharing completed");
+    Ok(Some(key_share))
+}
+
+/// Revoke a compromised party and refresh the survivors' shares
+///
+/// Every remaining committee member runs this with the same `session_id`
+/// and the ID of the device being revoked. The survivors reshare the key
+/// among themselves onto fresh polynomials: the public key is unchanged,
+/// the committee shrinks by one, survivors are renumbered densely from
+/// zero, and the revoked device's share lies on a polynomial nobody uses
+/// anymore — it contributes nothing toward the new threshold, alone or
+/// combined with any minority of refreshed shares.
+///
+/// Needs at least the old threshold of survivors; a committee that was
+/// already at its threshold cannot revoke below it.
+#[instrument(skip(relay, old_share), fields(party_id = old_share.party_id))]
+pub async fn run_revoke_party<R: Relay>(
+    session_id: SessionId,
+    old_share: &KeyShare,
+    revoked: PartyId,
+    relay: &R,
+) -> Result<KeyShare> {
+    if revoked >= old_share.n_parties {
+        return Err(Error::InvalidPartyId(revoked));
+    }
+    if old_share.party_id == revoked {
+        return Err(Error::InvalidConfig(
+            "The revoked party cannot run its own revocation".into(),
+        ));
+    }
+    let survivors: Vec<PartyId> = (0..old_share.n_parties)
+        .filter(|&p| p != revoked)
+        .collect();
+    if survivors.len() < old_share.threshold {
+        return Err(Error::ThresholdNotMet {
+            required: old_share.threshold,
+            actual: survivors.len(),
+        });
+    }
+
+    info!(revoked, survivors = survivors.len(), "Revoking party");
+
+    let config = ReshareConfig {
+        session_id,
+        dealers: survivors.clone(),
+        receivers: survivors,
+        new_threshold: old_share.threshold,
+        party_id: old_share.party_id,
+        expected_public_key: None,
+    };
+    let new_share = run_reshare(&config, Some(old_share), relay)
+        .await?
+        .expect("every survivor is on the new committee");
+    Ok(new_share)
+}
+
+/// Lagrange coefficient at zero for party `i` within `parties`
+fn lagrange_at_zero(i: PartyId, parties: &[PartyId]) -> Scalar {
+    let xi = Scalar::from(i as u64 + 1);
+    let mut coef = Scalar::ONE;
+    for &j in parties {
+        if j == i {
+            continue;
+        }
+        let xj = Scalar::from(j as u64 + 1);
+        coef *= xj * (xj - xi).invert().unwrap();
+    }
+    coef
+}
+
+/// Deal a fresh degree-(t'-1) polynomial with the given constant term
+pub(super) fn deal_polynomial(
+    constant: Scalar,
+    threshold: usize,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> (Vec<Scalar>, Vec<Vec<u8>>) {
+    let mut coefficients = Vec::with_capacity(threshold);
+    let mut commitments = Vec::with_capacity(threshold);
+
+    for degree in 0..threshold {
+        let coef = if degree == 0 {
+            constant
+        } else {
+            Scalar::random(&mut *rng)
+        };
+        let commitment = (ProjectivePoint::GENERATOR * coef).to_affine();
+
+        coefficients.push(coef);
+        commitments.push(commitment.to_encoded_point(true).as_bytes().to_vec());
+    }
+
+    (coefficients, commitments)
+}
+
+/// Evaluate polynomial at a point
+pub(super) fn evaluate_polynomial(coefficients: &[Scalar], x: u64) -> Scalar {
+    let x_scalar = Scalar::from(x);
+    let mut result = Scalar::ZERO;
+    let mut x_power = Scalar::ONE;
+
+    for coef in coefficients {
+        result += *coef * x_power;
+        x_power *= x_scalar;
+    }
+
+    result
+}
+
+/// Decode a compressed commitment point
+pub(super) fn decode_point(bytes: &[u8]) -> Result<ProjectivePoint> {
+    let point = k256::EncodedPoint::from_bytes(bytes)
+        .map_err(|e| Error::VerificationFailed(e.to_string()))?;
+    let affine_opt = AffinePoint::from_encoded_point(&point);
+    let affine: AffinePoint = Option::<AffinePoint>::from(affine_opt)
+        .ok_or_else(|| Error::VerificationFailed("Invalid commitment point".into()))?;
+    Ok(ProjectivePoint::from(affine))
+}
+
+/// Verify a dealt evaluation against the dealer's commitments and return it
+pub(super) fn verify_dealt_share(
+    share_msg: &super::DkgRound2Message,
+    commitments: &[Vec<u8>],
+    new_id: usize,
+) -> Result<Scalar> {
+    let share_bytes: [u8; 32] = share_msg
+        .share
+        .clone()
+        .try_into()
+        .map_err(|_| Error::Deserialization("Invalid share length".into()))?;
+    let share = <Scalar as Reduce<U256>>::reduce_bytes(&share_bytes.into());
+
+    let expected = ProjectivePoint::GENERATOR * share;
+
+    let x_scalar = Scalar::from(new_id as u64 + 1);
+    let mut actual = ProjectivePoint::IDENTITY;
+    let mut x_power = Scalar::ONE;
+    for commitment_bytes in commitments {
+        actual += decode_point(commitment_bytes)? * x_power;
+        x_power *= x_scalar;
+    }
+
+    if expected != actual {
+        return Err(Error::VerificationFailed(format!(
+            "Dealt share from party {} does not match commitment",
+            share_msg.from
+        )));
+    }
+
+    Ok(share)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keygen::run_dkg;
+    use crate::mpc::MemoryRelay;
+    use crate::SessionConfig;
+    use std::sync::Arc;
+
+    /// Reconstruct the secret from a threshold subset of shares
+    fn reconstruct(shares: &[(usize, Scalar)]) -> Scalar {
+        let parties: Vec<usize> = shares.iter().map(|(id, _)| *id).collect();
+        shares
+            .iter()
+            .map(|(id, share)| lagrange_at_zero(*id, &parties) * share)
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn test_reshare_two_of_three_to_three_of_five() {
+        let relay = Arc::new(MemoryRelay::new());
+        let dkg_session = [0x31u8; 32];
+        let reshare_session = [0x32u8; 32];
+
+        // Old committee: 2-of-3 on relay IDs {0, 1, 2}
+        let mut handles = Vec::new();
+        for party_id in 0..3 {
+            let relay = relay.clone();
+            handles.push(tokio::spawn(async move {
+                let config = SessionConfig {
+                    session_id: dkg_session,
+                    n_parties: 3,
+                    threshold: 2,
+                    party_id,
+                    parties: (0..3).collect(),
+                };
+                run_dkg(&config, &*relay).await.unwrap()
+            }));
+        }
+        let mut old_shares = Vec::new();
+        for handle in handles {
+            old_shares.push(handle.await.unwrap());
+        }
+        old_shares.sort_by_key(|share| share.party_id);
+        let public_key = old_shares[0].public_key.clone();
+
+        // New committee: 3-of-5 on relay IDs {0, 1, 3, 4, 5} — parties 0
+        // and 1 continue and act as dealers, party 2 retires, three new
+        // members join
+        let reshare_config = |party_id| ReshareConfig {
+            session_id: reshare_session,
+            dealers: vec![0, 1],
+            receivers: vec![0, 1, 3, 4, 5],
+            new_threshold: 3,
+            party_id,
+            expected_public_key: Some(public_key.clone()),
+        };
+
+        let mut handles = Vec::new();
+        for old_share in old_shares.iter().take(2).cloned() {
+            let relay = relay.clone();
+            let config = reshare_config(old_share.party_id);
+            handles.push(tokio::spawn(async move {
+                run_reshare(&config, Some(&old_share), &*relay).await.unwrap()
+            }));
+        }
+        for relay_id in [3usize, 4, 5] {
+            let relay = relay.clone();
+            let config = reshare_config(relay_id);
+            handles.push(tokio::spawn(async move {
+                run_reshare(&config, None, &*relay).await.unwrap()
+            }));
+        }
+
+        let mut new_shares = Vec::new();
+        for handle in handles {
+            new_shares.push(handle.await.unwrap().expect("receiver must get a share"));
+        }
+        new_shares.sort_by_key(|share| share.party_id);
+
+        // Same key, same chain code, new committee shape
+        for share in &new_shares {
+            assert_eq!(share.public_key, public_key);
+            assert_eq!(share.chain_code, old_shares[0].chain_code);
+            assert_eq!(share.n_parties, 5);
+            assert_eq!(share.threshold, 3);
+            let expected = (ProjectivePoint::GENERATOR * share.secret_share)
+                .to_affine()
+                .to_encoded_point(true);
+            assert_eq!(share.public_shares[share.party_id], expected.as_bytes());
+        }
+
+        // Any new-threshold subset reconstructs the original secret
+        let old_secret = reconstruct(&[
+            (0, old_shares[0].secret_share),
+            (1, old_shares[1].secret_share),
+        ]);
+        let new_secret = reconstruct(&[
+            (1, new_shares[1].secret_share),
+            (2, new_shares[2].secret_share),
+            (4, new_shares[4].secret_share),
+        ]);
+        assert_eq!(old_secret, new_secret);
+
+        // But no old-threshold-sized subset of the new sharing does
+        let undersized = reconstruct(&[
+            (0, new_shares[0].secret_share),
+            (1, new_shares[1].secret_share),
+        ]);
+        assert_ne!(undersized, old_secret);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_party_invalidates_old_share() {
+        let relay = Arc::new(MemoryRelay::new());
+        let dkg_session = [0x35u8; 32];
+        let revoke_session = [0x36u8; 32];
+
+        let mut handles = Vec::new();
+        for party_id in 0..3 {
+            let relay = relay.clone();
+            handles.push(tokio::spawn(async move {
+                let config = SessionConfig {
+                    session_id: dkg_session,
+                    n_parties: 3,
+                    threshold: 2,
+                    party_id,
+                    parties: (0..3).collect(),
+                };
+                run_dkg(&config, &*relay).await.unwrap()
+            }));
+        }
+        let mut old_shares = Vec::new();
+        for handle in handles {
+            old_shares.push(handle.await.unwrap());
+        }
+        old_shares.sort_by_key(|share| share.party_id);
+
+        // Party 1's device is compromised; parties 0 and 2 revoke it
+        let mut handles = Vec::new();
+        for old_share in [old_shares[0].clone(), old_shares[2].clone()] {
+            let relay = relay.clone();
+            handles.push(tokio::spawn(async move {
+                run_revoke_party(revoke_session, &old_share, 1, &*relay)
+                    .await
+                    .unwrap()
+            }));
+        }
+        let mut new_shares = Vec::new();
+        for handle in handles {
+            new_shares.push(handle.await.unwrap());
+        }
+        new_shares.sort_by_key(|share| share.party_id);
+
+        // Survivors are renumbered onto a 2-of-2 committee, same key
+        for share in &new_shares {
+            assert_eq!(share.public_key, old_shares[0].public_key);
+            assert_eq!(share.n_parties, 2);
+            assert_eq!(share.threshold, 2);
+        }
+
+        // The refreshed shares reconstruct the original secret...
+        let old_secret = reconstruct(&[
+            (0, old_shares[0].secret_share),
+            (1, old_shares[1].secret_share),
+        ]);
+        let new_secret = reconstruct(&[
+            (0, new_shares[0].secret_share),
+            (1, new_shares[1].secret_share),
+        ]);
+        assert_eq!(old_secret, new_secret);
+
+        // ...but the revoked share contributes nothing: combined with any
+        // single refreshed share it misses the secret
+        let stale_mix = reconstruct(&[
+            (1, old_shares[1].secret_share),
+            (0, new_shares[0].secret_share),
+        ]);
+        assert_ne!(stale_mix, old_secret);
+
+        // A 2-of-2 committee cannot revoke below its threshold
+        let err = match run_revoke_party([0x37u8; 32], &new_shares[0], 1, &*relay).await {
+            Err(err) => err,
+            Ok(_) => panic!("revoking below threshold must be rejected"),
+        };
+        assert!(matches!(err, Error::ThresholdNotMet { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_reshare_rejects_undersized_dealer_set() {
+        let relay = MemoryRelay::new();
+        let share = {
+            let relay = Arc::new(MemoryRelay::new());
+            let mut handles = Vec::new();
+            for party_id in 0..3 {
+                let relay = relay.clone();
+                handles.push(tokio::spawn(async move {
+                    let config = SessionConfig {
+                        session_id: [0x33u8; 32],
+                        n_parties: 3,
+                        threshold: 2,
+                        party_id,
+                        parties: (0..3).collect(),
+                    };
+                    run_dkg(&config, &*relay).await.unwrap()
+                }));
+            }
+            let mut shares = Vec::new();
+            for handle in handles {
+                shares.push(handle.await.unwrap());
+            }
+            shares.sort_by_key(|s| s.party_id);
+            shares.remove(0)
+        };
+
+        // A single dealer cannot redistribute a threshold-2 key
+        let config = ReshareConfig {
+            session_id: [0x34u8; 32],
+            dealers: vec![0],
+            receivers: vec![0, 1],
+            new_threshold: 2,
+            party_id: 0,
+            expected_public_key: None,
+        };
+        let err = match run_reshare(&config, Some(&share), &relay).await {
+            Err(err) => err,
+            Ok(_) => panic!("undersized dealer set must be rejected"),
+        };
+        assert!(matches!(
+            err,
+            Error::ThresholdNotMet {
+                required: 2,
+                actual: 1
+            }
+        ));
+    }
+}
@@ -0,0 +1,150 @@
+//! Deterministic key tree over a single master DKG share
+//!
+//! Instead of running a DKG per key, one master share plus non-hardened
+//! BIP32 derivation yields a labeled tree of child keys. The tree tracks
+//! which labels map to which derivation paths, allocates fresh child
+//! indices, and rejects label or path collisions, so every party derives
+//! the same children for the same labels.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{Error, KeyShare, Result};
+
+/// Labeled tree of derived child keys rooted at one master share
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyTree {
+    /// Compressed public key of the master share (hex), used to detect
+    /// a tree being used with the wrong master
+    pub master_public_key: String,
+
+    /// Next child index to allocate under `m/<index>`
+    pub next_index: u32,
+
+    /// Label -> derivation path
+    pub entries: BTreeMap<String, String>,
+}
+
+impl KeyTree {
+    /// Create an empty tree rooted at the given master share
+    pub fn new(master: &KeyShare) -> Self {
+        Self {
+            master_public_key: hex::encode(&master.public_key),
+            next_index: 0,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Allocate the next free child index for a label
+    ///
+    /// Returns the derivation path assigned to the label.
+    pub fn allocate(&mut self, label: &str) -> Result<String> {
+        let path = format!("m/{}", self.next_index);
+        self.insert(label, &path)?;
+        self.next_index += 1;
+        Ok(path)
+    }
+
+    /// Register a label for an explicit derivation path
+    pub fn insert(&mut self, label: &str, path: &str) -> Result<()> {
+        if label.is_empty() {
+            return Err(Error::InvalidConfig("Key label cannot be empty".into()));
+        }
+        if self.entries.contains_key(label) {
+            return Err(Error::InvalidConfig(format!(
+                "Key label already in use: {}",
+                label
+            )));
+        }
+        if let Some(existing) = self.entries.iter().find(|(_, p)| p.as_str() == path) {
+            return Err(Error::InvalidConfig(format!(
+                "Derivation path {} already assigned to label {}",
+                path, existing.0
+            )));
+        }
+
+        self.entries.insert(label.to_string(), path.to_string());
+        Ok(())
+    }
+
+    /// Look up the derivation path for a label
+    pub fn path(&self, label: &str) -> Result<&str> {
+        self.entries
+            .get(label)
+            .map(String::as_str)
+            .ok_or_else(|| Error::InvalidConfig(format!("Unknown key label: {}", label)))
+    }
+
+    /// Derive the child share for a label from the master share
+    pub fn derive(&self, master: &KeyShare, label: &str) -> Result<KeyShare> {
+        if hex::encode(&master.public_key) != self.master_public_key {
+            return Err(Error::Derivation(
+                "Key tree does not belong to this master share".into(),
+            ));
+        }
+        master.derive_child(self.path(label)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dummy_share() -> KeyShare {
+        KeyShare {
+            party_id: 0,
+            n_parties: 3,
+            threshold: 2,
+            secret_share: k256::Scalar::ONE,
+            public_key: k256::ProjectivePoint::GENERATOR
+                .to_affine()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+            public_shares: Vec::new(),
+            chain_code: [1u8; 32],
+            scheme: crate::scheme::SchemeId::Secp256k1,
+            min_protocol_version: crate::PROTOCOL_VERSION,
+            transcript_digest: [0u8; 32],
+            integrity_tag: [0u8; 32],
+        }
+    }
+
+    use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+    #[test]
+    fn test_allocate_assigns_sequential_paths() {
+        let master = dummy_share();
+        let mut tree = KeyTree::new(&master);
+
+        assert_eq!(tree.allocate("alice").unwrap(), "m/0");
+        assert_eq!(tree.allocate("bob").unwrap(), "m/1");
+        assert_eq!(tree.path("alice").unwrap(), "m/0");
+    }
+
+    #[test]
+    fn test_collisions_rejected() {
+        let master = dummy_share();
+        let mut tree = KeyTree::new(&master);
+
+        tree.allocate("alice").unwrap();
+        assert!(tree.allocate("alice").is_err());
+        assert!(tree.insert("eve", "m/0").is_err());
+    }
+
+    #[test]
+    fn test_derive_matches_direct_derivation() {
+        let master = dummy_share();
+        let mut tree = KeyTree::new(&master);
+        let path = tree.allocate("alice").unwrap();
+
+        let via_tree = tree.derive(&master, "alice").unwrap();
+        let direct = master.derive_child(&path).unwrap();
+
+        assert_eq!(
+            via_tree.secret_share.to_bytes(),
+            direct.secret_share.to_bytes()
+        );
+        assert_eq!(via_tree.chain_code, direct.chain_code);
+    }
+}
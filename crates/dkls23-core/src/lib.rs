@@ -26,19 +26,43 @@
 //! let signature = sign::run_dsg(&key_share, message, &relay).await?;
 //! ```
 
+pub mod address;
+pub mod backend;
+pub mod canonical;
+pub mod capabilities;
+pub mod consts;
+pub mod curve;
+#[cfg(feature = "scheme-ed25519")]
+pub mod eddsa;
 pub mod error;
+pub mod escrow;
+pub mod eth;
+pub mod hashing;
 pub mod keygen;
+pub mod keytree;
 pub mod mpc;
 pub mod oblivious;
+pub mod scheme;
 pub mod sign;
+pub(crate) mod telemetry;
+pub mod transcript;
 pub mod types;
 
+pub use curve::ThresholdCurve;
 pub use error::{Error, Result};
+pub use scheme::SchemeId;
 pub use types::{KeyShare, PartyId, PublicKey, SessionConfig, SessionId, Signature};
 
 /// Protocol version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Wire protocol version spoken by this build
+///
+/// Bumped whenever the message flow changes. Key shares record the version
+/// they were generated with as their `min_protocol_version`, and signing
+/// refuses to run an older flow for that key.
+pub const PROTOCOL_VERSION: u32 = 1;
+
 /// Default threshold for a 3-party setup
 pub const DEFAULT_THRESHOLD: usize = 2;
 
@@ -0,0 +1,197 @@
+//! Reliable (echo) broadcast over the [`Relay`] trait
+//!
+//! The relay delivers whatever each sender posts, so an equivocating party
+//! could hand different "broadcasts" to different receivers and drive the
+//! ceremony into an inconsistent state. This wrapper adds one confirmation
+//! sub-round: after collecting a round's broadcasts, every party publishes
+//! a digest of the full set it received, and the round is only accepted
+//! once all digests agree. Equivocation surfaces as a digest mismatch
+//! naming the party whose view diverged.
+
+use super::Relay;
+use crate::{Error, PartyId, Result, SessionConfig};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Echo confirmations travel on `round + ECHO_ROUND_OFFSET`, mirroring the
+/// sub-round convention the MtA flights use
+pub const ECHO_ROUND_OFFSET: u32 = 100;
+
+/// Digest of the broadcast set one party received for a round
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EchoMessage {
+    /// Sender party ID
+    pub party_id: PartyId,
+    /// Digest over the sorted broadcast set
+    pub digest: [u8; 32],
+}
+
+/// Broadcast a round message and collect everyone's, with echo checking
+///
+/// Returns the round's messages sorted by sender. `sender_of` extracts the
+/// sender ID from a message so the set can be ordered identically on every
+/// party before hashing.
+pub async fn echo_broadcast<R, T, F>(
+    config: &SessionConfig,
+    round: u32,
+    message: &T,
+    relay: &R,
+    sender_of: F,
+) -> Result<Vec<T>>
+where
+    R: Relay,
+    T: Serialize + DeserializeOwned + Send + Sync,
+    F: Fn(&T) -> PartyId,
+{
+    relay.broadcast(&config.session_id, round, message).await?;
+
+    let mut messages = relay
+        .collect_broadcasts::<T>(&config.session_id, round, config.n_parties)
+        .await?;
+    messages.sort_by_key(|msg| sender_of(msg));
+
+    let digest = digest_broadcast_set(round, &messages, &sender_of)?;
+    let echo = EchoMessage {
+        party_id: config.party_id,
+        digest,
+    };
+    relay
+        .broadcast(&config.session_id, round + ECHO_ROUND_OFFSET, &echo)
+        .await?;
+
+    let echoes = relay
+        .collect_broadcasts::<EchoMessage>(
+            &config.session_id,
+            round + ECHO_ROUND_OFFSET,
+            config.n_parties,
+        )
+        .await?;
+    for echo in &echoes {
+        if echo.digest != digest {
+            return Err(Error::VerificationFailed(format!(
+                "Party {} saw a different round-{} broadcast set; a sender equivocated",
+                echo.party_id, round
+            )));
+        }
+    }
+
+    Ok(messages)
+}
+
+/// Hash a sorted broadcast set with unambiguous field boundaries
+///
+/// Messages are encoded with [`crate::canonical`] so every party hashes
+/// identical bytes for identical values.
+fn digest_broadcast_set<T, F>(round: u32, messages: &[T], sender_of: &F) -> Result<[u8; 32]>
+where
+    T: Serialize,
+    F: Fn(&T) -> PartyId,
+{
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&round.to_be_bytes());
+    for msg in messages {
+        let payload = crate::canonical::to_vec(msg)?;
+        hasher.update(&(sender_of(msg) as u64).to_be_bytes());
+        hasher.update(&(payload.len() as u64).to_be_bytes());
+        hasher.update(&payload);
+    }
+    Ok(*hasher.finalize().as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::MemoryRelay;
+    use std::sync::Arc;
+
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    struct TestMessage {
+        party_id: PartyId,
+        value: u32,
+    }
+
+    fn config(party_id: PartyId, n: usize) -> SessionConfig {
+        SessionConfig {
+            session_id: [0x66u8; 32],
+            n_parties: n,
+            threshold: 2,
+            party_id,
+            parties: (0..n).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consistent_broadcasts_are_accepted() {
+        let relay = Arc::new(MemoryRelay::new());
+        let n = 3;
+
+        let mut handles = Vec::new();
+        for party_id in 0..n {
+            let relay = relay.clone();
+            handles.push(tokio::spawn(async move {
+                let msg = TestMessage {
+                    party_id,
+                    value: party_id as u32 * 10,
+                };
+                echo_broadcast(&config(party_id, n), 1, &msg, &*relay, |m: &TestMessage| {
+                    m.party_id
+                })
+                .await
+            }));
+        }
+
+        for handle in handles {
+            let messages = handle.await.unwrap().unwrap();
+            assert_eq!(messages.len(), n);
+            // Sorted by sender on every party
+            for (i, msg) in messages.iter().enumerate() {
+                assert_eq!(msg.party_id, i);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_divergent_echo_digest_aborts_round() {
+        let relay = Arc::new(MemoryRelay::new());
+        let n = 3;
+
+        // Parties 0 and 1 run the honest wrapper
+        let mut handles = Vec::new();
+        for party_id in 0..2 {
+            let relay = relay.clone();
+            handles.push(tokio::spawn(async move {
+                let msg = TestMessage { party_id, value: 7 };
+                echo_broadcast(&config(party_id, n), 1, &msg, &*relay, |m: &TestMessage| {
+                    m.party_id
+                })
+                .await
+            }));
+        }
+
+        // Party 2 posts its broadcast, then claims to have seen a
+        // different set (the receiver-side symptom of equivocation)
+        {
+            use crate::mpc::Relay;
+            let session_id = [0x66u8; 32];
+            relay
+                .broadcast(&session_id, 1, &TestMessage { party_id: 2, value: 7 })
+                .await
+                .unwrap();
+            relay
+                .broadcast(
+                    &session_id,
+                    1 + ECHO_ROUND_OFFSET,
+                    &EchoMessage {
+                        party_id: 2,
+                        digest: [0xaa; 32],
+                    },
+                )
+                .await
+                .unwrap();
+        }
+
+        for handle in handles {
+            let err = handle.await.unwrap().unwrap_err();
+            assert!(matches!(err, Error::VerificationFailed(_)), "got {:?}", err);
+        }
+    }
+}
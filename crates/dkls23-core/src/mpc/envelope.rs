@@ -0,0 +1,274 @@
+//! Encrypted relay envelope for private ceremonies
+//!
+//! Wraps any [`Relay`] so that payloads are encrypted before they reach the
+//! transport. Broadcasts are encrypted under a committee-wide key derived at
+//! setup, so even the relay operator cannot read round contents; direct
+//! messages are sealed under a per-recipient subkey so only the addressee can
+//! open them. The wrapper is transparent to the protocol code — it speaks the
+//! same `Relay` trait and carries a [`SealedEnvelope`] on the wire.
+
+use super::{async_trait, Relay};
+use crate::{Error, PartyId, Result, SessionId};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use rand::RngCore;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::consts::{COMMITTEE_KEY_CONTEXT, DIRECT_KEY_CONTEXT};
+
+/// Committee-wide symmetric key for a private ceremony
+///
+/// All parties derive the same key from a ceremony secret agreed during
+/// setup (distributed out of band or via the setup channel) bound to the
+/// session ID, so a key never outlives its ceremony.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct CommitteeKey {
+    key: [u8; 32],
+}
+
+impl CommitteeKey {
+    /// Derive the committee key for a session from the ceremony secret
+    pub fn derive(ceremony_secret: &[u8], session_id: &SessionId) -> Self {
+        let mut material = Vec::with_capacity(ceremony_secret.len() + session_id.len());
+        material.extend_from_slice(ceremony_secret);
+        material.extend_from_slice(session_id);
+        let key = blake3::derive_key(COMMITTEE_KEY_CONTEXT, &material);
+        material.zeroize();
+        Self { key }
+    }
+
+    /// Subkey for direct messages addressed to `recipient`
+    fn direct_subkey(&self, recipient: PartyId) -> [u8; 32] {
+        let mut material = Vec::with_capacity(40);
+        material.extend_from_slice(&self.key);
+        material.extend_from_slice(&(recipient as u64).to_be_bytes());
+        let key = blake3::derive_key(DIRECT_KEY_CONTEXT, &material);
+        material.zeroize();
+        key
+    }
+}
+
+/// Ciphertext envelope carried over the underlying relay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealedEnvelope {
+    /// Random 96-bit nonce
+    pub nonce: Vec<u8>,
+    /// ChaCha20-Poly1305 ciphertext of the serialized message
+    pub ciphertext: Vec<u8>,
+}
+
+fn seal(key: &[u8; 32], plaintext: &[u8]) -> Result<SealedEnvelope> {
+    let cipher = ChaCha20Poly1305::new(key.into());
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| Error::Crypto("Envelope encryption failed".into()))?;
+
+    Ok(SealedEnvelope {
+        nonce: nonce.to_vec(),
+        ciphertext,
+    })
+}
+
+fn open(key: &[u8; 32], envelope: &SealedEnvelope) -> Result<Vec<u8>> {
+    if envelope.nonce.len() != 12 {
+        return Err(Error::Crypto("Invalid envelope nonce length".into()));
+    }
+    let cipher = ChaCha20Poly1305::new(key.into());
+    cipher
+        .decrypt(Nonce::from_slice(&envelope.nonce), envelope.ciphertext.as_slice())
+        .map_err(|_| Error::Crypto("Envelope decryption failed (wrong committee key?)".into()))
+}
+
+/// Relay wrapper that encrypts every payload before forwarding it
+pub struct EncryptedRelay<R: Relay> {
+    inner: R,
+    key: CommitteeKey,
+}
+
+impl<R: Relay> EncryptedRelay<R> {
+    /// Wrap a relay with a committee key
+    pub fn new(inner: R, key: CommitteeKey) -> Self {
+        Self { inner, key }
+    }
+
+    /// Access the wrapped relay
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<R: Relay> Relay for EncryptedRelay<R> {
+    async fn broadcast<T: Serialize + Send + Sync>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        message: &T,
+    ) -> Result<()> {
+        let plaintext =
+            serde_json::to_vec(message).map_err(|e| Error::Serialization(e.to_string()))?;
+        let envelope = seal(&self.key.key, &plaintext)?;
+        self.inner.broadcast(session_id, round, &envelope).await
+    }
+
+    async fn send_direct<T: Serialize + Send + Sync>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        to: PartyId,
+        message: &T,
+    ) -> Result<()> {
+        let plaintext =
+            serde_json::to_vec(message).map_err(|e| Error::Serialization(e.to_string()))?;
+        let envelope = seal(&self.key.direct_subkey(to), &plaintext)?;
+        self.inner.send_direct(session_id, round, to, &envelope).await
+    }
+
+    async fn collect_broadcasts<T: DeserializeOwned + Send>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        count: usize,
+    ) -> Result<Vec<T>> {
+        let envelopes: Vec<SealedEnvelope> = self
+            .inner
+            .collect_broadcasts(session_id, round, count)
+            .await?;
+
+        // Sealed envelopes hide their sender from the inner relay, so
+        // the sender ordering contract is enforced on the plaintexts
+        let plaintexts = envelopes
+            .iter()
+            .map(|envelope| open(&self.key.key, envelope))
+            .collect::<Result<Vec<_>>>()?;
+        super::sort_collected(plaintexts, round)?
+            .iter()
+            .map(|plaintext| {
+                serde_json::from_slice(plaintext)
+                    .map_err(|e| Error::Deserialization(e.to_string()))
+            })
+            .collect()
+    }
+
+    async fn collect_direct<T: DeserializeOwned + Send>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        my_id: PartyId,
+        count: usize,
+    ) -> Result<Vec<T>> {
+        let envelopes: Vec<SealedEnvelope> = self
+            .inner
+            .collect_direct(session_id, round, my_id, count)
+            .await?;
+
+        let subkey = self.key.direct_subkey(my_id);
+        let plaintexts = envelopes
+            .iter()
+            .map(|envelope| open(&subkey, envelope))
+            .collect::<Result<Vec<_>>>()?;
+        super::sort_collected(plaintexts, round)?
+            .iter()
+            .map(|plaintext| {
+                serde_json::from_slice(plaintext)
+                    .map_err(|e| Error::Deserialization(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::MemoryRelay;
+
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    struct TestMessage {
+        value: u32,
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_broadcast_roundtrip() {
+        let session_id = [7u8; 32];
+        let relay = EncryptedRelay::new(
+            MemoryRelay::new(),
+            CommitteeKey::derive(b"ceremony secret", &session_id),
+        );
+
+        relay
+            .broadcast(&session_id, 1, &TestMessage { value: 42 })
+            .await
+            .unwrap();
+
+        // The transport only ever sees sealed envelopes
+        let raw: Vec<SealedEnvelope> = relay
+            .inner()
+            .collect_broadcasts(&session_id, 1, 1)
+            .await
+            .unwrap();
+        let plaintext = serde_json::to_vec(&TestMessage { value: 42 }).unwrap();
+        assert!(!raw[0]
+            .ciphertext
+            .windows(plaintext.len())
+            .any(|w| w == plaintext.as_slice()));
+
+        let messages: Vec<TestMessage> = relay.collect_broadcasts(&session_id, 1, 1).await.unwrap();
+        assert_eq!(messages[0].value, 42);
+    }
+
+    #[tokio::test]
+    async fn test_direct_sealed_per_recipient() {
+        let session_id = [8u8; 32];
+        let relay = EncryptedRelay::new(
+            MemoryRelay::new(),
+            CommitteeKey::derive(b"ceremony secret", &session_id),
+        );
+
+        relay
+            .send_direct(&session_id, 1, 1, &TestMessage { value: 9 })
+            .await
+            .unwrap();
+
+        let messages: Vec<TestMessage> =
+            relay.collect_direct(&session_id, 1, 1, 1).await.unwrap();
+        assert_eq!(messages[0].value, 9);
+
+        // A party with the committee key but the wrong recipient subkey
+        // cannot open the message
+        let raw: Vec<SealedEnvelope> = relay
+            .inner()
+            .collect_direct(&session_id, 1, 1, 1)
+            .await
+            .unwrap();
+        let key = CommitteeKey::derive(b"ceremony secret", &session_id);
+        assert!(open(&key.direct_subkey(2), &raw[0]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wrong_committee_key_fails() {
+        let session_id = [9u8; 32];
+        let relay = EncryptedRelay::new(
+            MemoryRelay::new(),
+            CommitteeKey::derive(b"secret A", &session_id),
+        );
+        relay
+            .broadcast(&session_id, 1, &TestMessage { value: 1 })
+            .await
+            .unwrap();
+
+        let envelope: SealedEnvelope = {
+            let raw: Vec<SealedEnvelope> = relay
+                .inner()
+                .collect_broadcasts(&session_id, 1, 1)
+                .await
+                .unwrap();
+            raw.into_iter().next().unwrap()
+        };
+        let wrong = CommitteeKey::derive(b"secret B", &session_id);
+        assert!(matches!(open(&wrong.key, &envelope), Err(Error::Crypto(_))));
+    }
+}
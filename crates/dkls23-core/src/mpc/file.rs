@@ -0,0 +1,296 @@
+//! File-based relay for air-gapped parties
+//!
+//! Writes outgoing round messages as JSON files into an outbox directory
+//! and reads peers' messages from an inbox directory, so a party can join a
+//! ceremony with no network stack at all: the operator moves the files
+//! between machines on removable media. With prompting enabled the relay
+//! tells the operator exactly how many files it is still missing and waits
+//! for Enter after each media transfer instead of silently polling.
+
+use super::{async_trait, Relay};
+use crate::{Error, PartyId, Result, SessionId};
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::{Path, PathBuf};
+
+/// How often the inbox is re-scanned when prompting is disabled
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Relay that exchanges round messages through directories
+pub struct FileRelay {
+    /// This party's ID (stamped into outgoing file names)
+    party_id: PartyId,
+    /// Directory for outgoing messages
+    outbox: PathBuf,
+    /// Directory scanned for incoming messages
+    inbox: PathBuf,
+    /// Prompt the operator and wait for Enter instead of polling
+    await_prompt: bool,
+}
+
+impl FileRelay {
+    /// Create a file relay over an outbox and inbox directory
+    pub fn new(party_id: PartyId, outbox: &Path, inbox: &Path) -> Result<Self> {
+        std::fs::create_dir_all(outbox)
+            .and_then(|_| std::fs::create_dir_all(inbox))
+            .map_err(|e| Error::Relay(format!("Cannot create relay directories: {}", e)))?;
+        Ok(Self {
+            party_id,
+            outbox: outbox.to_path_buf(),
+            inbox: inbox.to_path_buf(),
+            await_prompt: false,
+        })
+    }
+
+    /// Ask the operator to transfer media and press Enter when files are
+    /// missing, rather than silently re-scanning
+    pub fn with_await_prompt(mut self) -> Self {
+        self.await_prompt = true;
+        self
+    }
+
+    /// File name for one message; sortable so collection order is stable
+    fn file_name(session_id: &SessionId, round: u32, from: PartyId, to: Option<PartyId>) -> String {
+        let target = match to {
+            Some(to) => format!("to{:04}", to),
+            None => "bcast".to_string(),
+        };
+        format!(
+            "{}.r{:04}.p{:04}.{}.json",
+            &hex::encode(session_id)[..16],
+            round,
+            from,
+            target
+        )
+    }
+
+    fn write_message<T: Serialize>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        to: Option<PartyId>,
+        message: &T,
+    ) -> Result<()> {
+        let name = Self::file_name(session_id, round, self.party_id, to);
+        let bytes =
+            serde_json::to_vec_pretty(message).map_err(|e| Error::Serialization(e.to_string()))?;
+        // Write-then-rename so a half-copied file is never picked up
+        let tmp = self.outbox.join(format!("{}.tmp", name));
+        std::fs::write(&tmp, bytes)
+            .and_then(|_| std::fs::rename(&tmp, self.outbox.join(&name)))
+            .map_err(|e| Error::Relay(format!("Cannot write {}: {}", name, e)))?;
+        Ok(())
+    }
+
+    /// Collect matching files from the inbox and our own outbox
+    ///
+    /// Our own round messages live in the outbox, so both directories are
+    /// scanned; file names sort by round then party, keeping order stable
+    /// across re-scans.
+    fn scan(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        to: Option<PartyId>,
+    ) -> Result<Vec<Vec<u8>>> {
+        let session_prefix = &hex::encode(session_id)[..16];
+        let round_part = format!(".r{:04}.", round);
+        let target = match to {
+            Some(to) => format!(".to{:04}.", to),
+            None => ".bcast.".to_string(),
+        };
+
+        let mut paths = Vec::new();
+        for dir in [&self.inbox, &self.outbox] {
+            let entries = std::fs::read_dir(dir)
+                .map_err(|e| Error::Relay(format!("Cannot read {}: {}", dir.display(), e)))?;
+            for entry in entries {
+                let path = entry
+                    .map_err(|e| Error::Relay(e.to_string()))?
+                    .path();
+                let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                if name.starts_with(session_prefix)
+                    && name.contains(&round_part)
+                    && name.contains(&target)
+                    && name.ends_with(".json")
+                {
+                    paths.push(path);
+                }
+            }
+        }
+        paths.sort();
+        paths.dedup_by(|a, b| a.file_name() == b.file_name());
+
+        paths
+            .iter()
+            .map(|path| {
+                std::fs::read(path)
+                    .map_err(|e| Error::Relay(format!("Cannot read {}: {}", path.display(), e)))
+            })
+            .collect()
+    }
+
+    /// Wait for the operator (or just the filesystem) before re-scanning
+    async fn wait_for_files(&self, round: u32, have: usize, want: usize) -> Result<()> {
+        if self.await_prompt {
+            eprintln!(
+                "Round {}: have {}/{} messages. Copy incoming files into {} and press Enter...",
+                round,
+                have,
+                want,
+                self.inbox.display()
+            );
+            tokio::task::spawn_blocking(|| {
+                let mut line = String::new();
+                std::io::stdin().read_line(&mut line).map(|_| ())
+            })
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?
+            .map_err(|e| Error::Relay(format!("Cannot read prompt response: {}", e)))?;
+        } else {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Relay for FileRelay {
+    async fn broadcast<T: Serialize + Send + Sync>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        message: &T,
+    ) -> Result<()> {
+        self.write_message(session_id, round, None, message)
+    }
+
+    async fn send_direct<T: Serialize + Send + Sync>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        to: PartyId,
+        message: &T,
+    ) -> Result<()> {
+        self.write_message(session_id, round, Some(to), message)
+    }
+
+    async fn collect_broadcasts<T: DeserializeOwned + Send>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        count: usize,
+    ) -> Result<Vec<T>> {
+        loop {
+            let payloads = self.scan(session_id, round, None)?;
+            if payloads.len() >= count {
+                let taken: Vec<Vec<u8>> = payloads.into_iter().take(count).collect();
+                return super::sort_collected(taken, round)?
+                    .iter()
+                    .map(|bytes| {
+                        serde_json::from_slice(bytes)
+                            .map_err(|e| Error::Deserialization(e.to_string()))
+                    })
+                    .collect();
+            }
+            self.wait_for_files(round, payloads.len(), count).await?;
+        }
+    }
+
+    async fn collect_direct<T: DeserializeOwned + Send>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        my_id: PartyId,
+        count: usize,
+    ) -> Result<Vec<T>> {
+        loop {
+            let payloads = self.scan(session_id, round, Some(my_id))?;
+            if payloads.len() >= count {
+                let taken: Vec<Vec<u8>> = payloads.into_iter().take(count).collect();
+                return super::sort_collected(taken, round)?
+                    .iter()
+                    .map(|bytes| {
+                        serde_json::from_slice(bytes)
+                            .map_err(|e| Error::Deserialization(e.to_string()))
+                    })
+                    .collect();
+            }
+            self.wait_for_files(round, payloads.len(), count).await?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keygen::run_dkg;
+    use crate::SessionConfig;
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "dkls23-file-relay-{}-{}",
+            tag,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_dkg_over_file_relay() {
+        // Party 0's outbox is party 1's inbox and vice versa, emulating
+        // media that has already been carried across
+        let media_a = temp_dir("a");
+        let media_b = temp_dir("b");
+
+        let session_id = [5u8; 32];
+        let mut handles = Vec::new();
+        for party_id in 0..2 {
+            let (outbox, inbox) = if party_id == 0 {
+                (media_a.clone(), media_b.clone())
+            } else {
+                (media_b.clone(), media_a.clone())
+            };
+            handles.push(tokio::spawn(async move {
+                let relay = FileRelay::new(party_id, &outbox, &inbox).unwrap();
+                let config = SessionConfig {
+                    session_id,
+                    n_parties: 2,
+                    threshold: 2,
+                    party_id,
+                    parties: vec![0, 1],
+                };
+                run_dkg(&config, &relay).await.unwrap()
+            }));
+        }
+
+        let share0 = handles.remove(0).await.unwrap();
+        let share1 = handles.remove(0).await.unwrap();
+        assert_eq!(share0.public_key, share1.public_key);
+
+        let _ = std::fs::remove_dir_all(&media_a);
+        let _ = std::fs::remove_dir_all(&media_b);
+    }
+
+    #[tokio::test]
+    async fn test_partial_file_never_collected() {
+        let outbox = temp_dir("partial");
+        let relay = FileRelay::new(0, &outbox, &outbox).unwrap();
+        let session_id = [6u8; 32];
+
+        // A half-copied file must be invisible to collection
+        std::fs::write(
+            outbox.join(format!("{}.tmp", FileRelay::file_name(&session_id, 1, 1, None))),
+            b"garbage",
+        )
+        .unwrap();
+        relay.broadcast(&session_id, 1, &42u32).await.unwrap();
+
+        let collected: Vec<u32> = relay.collect_broadcasts(&session_id, 1, 1).await.unwrap();
+        assert_eq!(collected, vec![42]);
+
+        let _ = std::fs::remove_dir_all(&outbox);
+    }
+}
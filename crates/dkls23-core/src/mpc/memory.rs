@@ -7,12 +7,22 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::sync::Arc;
 use tokio::sync::broadcast;
 
+/// Stored message bytes keyed by (session_id, round)
+type BroadcastStore = Arc<DashMap<(SessionId, u32), Vec<Vec<u8>>>>;
+
+/// Stored message bytes keyed by (session_id, round, recipient)
+type DirectStore = Arc<DashMap<(SessionId, u32, PartyId), Vec<Vec<u8>>>>;
+
 /// In-memory message relay for local testing
+///
+/// Clones share the underlying message store, so parties in a test can
+/// each hold their own handle to one relay.
+#[derive(Clone)]
 pub struct MemoryRelay {
-    /// Broadcast messages: (session_id, round) -> Vec<message_bytes>
-    broadcasts: Arc<DashMap<(SessionId, u32), Vec<Vec<u8>>>>,
-    /// Direct messages: (session_id, round, to) -> Vec<message_bytes>
-    directs: Arc<DashMap<(SessionId, u32, PartyId), Vec<Vec<u8>>>>,
+    /// Broadcast messages
+    broadcasts: BroadcastStore,
+    /// Direct messages
+    directs: DirectStore,
     /// Notification channel
     notify: broadcast::Sender<()>,
 }
@@ -91,12 +101,12 @@ impl Relay for MemoryRelay {
         loop {
             if let Some(messages) = self.broadcasts.get(&(*session_id, round)) {
                 if messages.len() >= count {
-                    let result: Result<Vec<T>> = messages
+                    let payloads: Vec<Vec<u8>> = messages.iter().take(count).cloned().collect();
+                    drop(messages);
+                    return super::sort_collected(payloads, round)?
                         .iter()
-                        .take(count)
                         .map(|bytes| deserialize(bytes))
                         .collect();
-                    return result;
                 }
             }
 
@@ -120,12 +130,12 @@ impl Relay for MemoryRelay {
         loop {
             if let Some(messages) = self.directs.get(&(*session_id, round, my_id)) {
                 if messages.len() >= count {
-                    let result: Result<Vec<T>> = messages
+                    let payloads: Vec<Vec<u8>> = messages.iter().take(count).cloned().collect();
+                    drop(messages);
+                    return super::sort_collected(payloads, round)?
                         .iter()
-                        .take(count)
                         .map(|bytes| deserialize(bytes))
                         .collect();
-                    return result;
                 }
             }
 
@@ -163,6 +173,51 @@ mod tests {
         assert_eq!(messages[1].value, 43);
     }
 
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct SenderMessage {
+        party_id: usize,
+        value: u32,
+    }
+
+    #[tokio::test]
+    async fn test_collect_orders_by_sender_not_arrival() {
+        let relay = MemoryRelay::new();
+        let session_id = [1u8; 32];
+
+        for party_id in [2usize, 0, 1] {
+            relay
+                .broadcast(&session_id, 1, &SenderMessage { party_id, value: 0 })
+                .await
+                .unwrap();
+        }
+
+        let messages: Vec<SenderMessage> =
+            relay.collect_broadcasts(&session_id, 1, 3).await.unwrap();
+        let senders: Vec<usize> = messages.iter().map(|msg| msg.party_id).collect();
+        assert_eq!(senders, vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_sender_is_a_structured_error() {
+        let relay = MemoryRelay::new();
+        let session_id = [2u8; 32];
+
+        for party_id in [0usize, 1, 1] {
+            relay
+                .broadcast(&session_id, 1, &SenderMessage { party_id, value: 0 })
+                .await
+                .unwrap();
+        }
+
+        let result = relay
+            .collect_broadcasts::<SenderMessage>(&session_id, 1, 3)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::DuplicateSender { round: 1, party: 1 })
+        ));
+    }
+
     #[tokio::test]
     async fn test_direct() {
         let relay = MemoryRelay::new();
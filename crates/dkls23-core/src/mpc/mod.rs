@@ -26,6 +26,14 @@ pub trait Relay: Send + Sync {
     ) -> Result<()>;
 
     /// Collect broadcast messages from all parties
+    ///
+    /// Implementations must return exactly one message per sender,
+    /// sorted by ascending sender ID (the `party_id` — or envelope
+    /// `from` — field of the serialized message), and fail with
+    /// [`Error::DuplicateSender`](crate::Error::DuplicateSender) rather
+    /// than deliver the same sender twice. Protocol drivers rely on this
+    /// order when folding rounds into transcripts, so every honest party
+    /// sees one canonical sequence regardless of arrival order.
     async fn collect_broadcasts<T: DeserializeOwned + Send>(
         &self,
         session_id: &SessionId,
@@ -34,6 +42,10 @@ pub trait Relay: Send + Sync {
     ) -> Result<Vec<T>>;
 
     /// Collect direct messages sent to this party
+    ///
+    /// Subject to the same ordering contract as
+    /// [`collect_broadcasts`](Self::collect_broadcasts): one message per
+    /// sender, sorted by ascending sender ID.
     async fn collect_direct<T: DeserializeOwned + Send>(
         &self,
         session_id: &SessionId,
@@ -43,7 +55,60 @@ pub trait Relay: Send + Sync {
     ) -> Result<Vec<T>>;
 }
 
+/// Sender ID embedded in a serialized protocol message
+///
+/// Every protocol message serializes its sender as a `party_id` field
+/// (envelope layers use `from`); store-backed relays read it to order
+/// collected payloads. Payloads carrying neither parse as `None`.
+pub(crate) fn embedded_sender(bytes: &[u8]) -> Option<PartyId> {
+    let value: serde_json::Value = serde_json::from_slice(bytes).ok()?;
+    value
+        .get("party_id")
+        .or_else(|| value.get("from"))?
+        .as_u64()
+        .map(|id| id as PartyId)
+}
+
+/// Order collected payloads by their embedded sender
+///
+/// Implements the [`Relay`] collect contract for store-backed relays:
+/// payloads sort by ascending sender ID and a sender appearing twice is
+/// a structured error. Payloads without an embedded sender keep their
+/// arrival order behind the identified ones.
+pub(crate) fn sort_collected(payloads: Vec<Vec<u8>>, round: u32) -> Result<Vec<Vec<u8>>> {
+    let mut seen = std::collections::BTreeSet::new();
+    let mut keyed: Vec<(Option<PartyId>, Vec<u8>)> = Vec::with_capacity(payloads.len());
+    for payload in payloads {
+        let sender = embedded_sender(&payload);
+        if let Some(party) = sender {
+            if !seen.insert(party) {
+                return Err(crate::Error::DuplicateSender { round, party });
+            }
+        }
+        keyed.push((sender, payload));
+    }
+    keyed.sort_by_key(|(sender, _)| sender.map_or(usize::MAX, |party| party));
+    Ok(keyed.into_iter().map(|(_, payload)| payload).collect())
+}
+
 /// In-memory relay for testing
 pub mod memory;
+/// Reliable broadcast with echo confirmation
+pub mod echo;
+/// Encrypted envelope layer for private ceremonies
+pub mod envelope;
+/// File-based relay for air-gapped parties
+pub mod file;
+/// Party identity keys and message authentication
+pub mod identity;
+/// Bandwidth and timing accounting for relay traffic
+pub mod stats;
 
+pub use echo::echo_broadcast;
+pub use envelope::{CommitteeKey, EncryptedRelay};
+pub use file::FileRelay;
+pub use identity::{
+    AuthenticatedRelay, PartyIdentity, PartyRoster, SignedEnvelope, SystemTimeSource, TimeSource,
+};
 pub use memory::MemoryRelay;
+pub use stats::{MeteredRelay, RelayStats};
@@ -0,0 +1,327 @@
+//! Bandwidth and timing accounting for relay traffic
+//!
+//! Wraps any [`Relay`] so that every payload crossing it is measured.
+//! Mobile co-signers on metered connections care how many bytes a ceremony
+//! costs; operators care which round is slow. A [`RelayStats`] handle is
+//! shared between the wrapper and the driver, which reads the counters back
+//! once the ceremony finishes. Sizes are measured over the canonical JSON
+//! encoding, which is what the HTTP relay carries on the wire.
+
+use super::{async_trait, Relay};
+use crate::{Error, PartyId, Result, SessionId};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Fraction of the budget at which the approach warning fires
+const BUDGET_WARN_NUMERATOR: u64 = 4;
+const BUDGET_WARN_DENOMINATOR: u64 = 5;
+
+/// Counterparty of a measured transfer: the addressee of a direct send,
+/// or `None` for broadcasts and received traffic, where the relay layer
+/// does not see a single peer
+pub type Peer = Option<PartyId>;
+
+/// Message and byte counters for one (round, peer) pair
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TrafficCounters {
+    /// Messages sent
+    pub messages_sent: u64,
+    /// Bytes sent
+    pub bytes_sent: u64,
+    /// Messages received
+    pub messages_received: u64,
+    /// Bytes received
+    pub bytes_received: u64,
+}
+
+#[derive(Default)]
+struct StatsInner {
+    per_peer: BTreeMap<(u32, Peer), TrafficCounters>,
+    round_activity: BTreeMap<u32, (Instant, Instant)>,
+}
+
+/// Shared traffic accounting handle
+///
+/// Cloning yields another handle onto the same counters, so the driver
+/// keeps one clone and hands the other to a [`MeteredRelay`]. An optional
+/// bandwidth budget arms one-shot warnings when total traffic approaches
+/// or exceeds it.
+#[derive(Clone, Default)]
+pub struct RelayStats {
+    inner: Arc<Mutex<StatsInner>>,
+    budget_bytes: Option<u64>,
+    warned_approaching: Arc<AtomicBool>,
+    warned_exceeded: Arc<AtomicBool>,
+}
+
+impl RelayStats {
+    /// Create a fresh set of counters with no budget
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Arm budget warnings at `budget_bytes` total ceremony traffic
+    pub fn with_budget(mut self, budget_bytes: u64) -> Self {
+        self.budget_bytes = Some(budget_bytes);
+        self
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StatsInner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Record one sent message of `bytes` for `round`
+    pub fn record_sent(&self, round: u32, peer: Peer, bytes: u64) {
+        {
+            let mut inner = self.lock();
+            let counters = inner.per_peer.entry((round, peer)).or_default();
+            counters.messages_sent += 1;
+            counters.bytes_sent += bytes;
+            touch_round(&mut inner.round_activity, round);
+        }
+        self.check_budget();
+    }
+
+    /// Record one received message of `bytes` for `round`
+    pub fn record_received(&self, round: u32, peer: Peer, bytes: u64) {
+        {
+            let mut inner = self.lock();
+            let counters = inner.per_peer.entry((round, peer)).or_default();
+            counters.messages_received += 1;
+            counters.bytes_received += bytes;
+            touch_round(&mut inner.round_activity, round);
+        }
+        self.check_budget();
+    }
+
+    /// Counters summed over every round and peer
+    pub fn totals(&self) -> TrafficCounters {
+        let inner = self.lock();
+        let mut totals = TrafficCounters::default();
+        for counters in inner.per_peer.values() {
+            totals.messages_sent += counters.messages_sent;
+            totals.bytes_sent += counters.bytes_sent;
+            totals.messages_received += counters.messages_received;
+            totals.bytes_received += counters.bytes_received;
+        }
+        totals
+    }
+
+    /// Per-(round, peer) counters in round order
+    pub fn snapshot(&self) -> Vec<((u32, Peer), TrafficCounters)> {
+        self.lock()
+            .per_peer
+            .iter()
+            .map(|(key, counters)| (*key, *counters))
+            .collect()
+    }
+
+    /// Elapsed time between the first and last transfer of each round
+    pub fn round_durations(&self) -> Vec<(u32, Duration)> {
+        self.lock()
+            .round_activity
+            .iter()
+            .map(|(round, (first, last))| (*round, last.duration_since(*first)))
+            .collect()
+    }
+
+    /// The budget this handle was armed with, if any
+    pub fn budget_bytes(&self) -> Option<u64> {
+        self.budget_bytes
+    }
+
+    /// Whether total traffic has exceeded the configured budget
+    pub fn budget_exceeded(&self) -> bool {
+        match self.budget_bytes {
+            Some(budget) => self.total_bytes() > budget,
+            None => false,
+        }
+    }
+
+    fn total_bytes(&self) -> u64 {
+        let totals = self.totals();
+        totals.bytes_sent + totals.bytes_received
+    }
+
+    /// Warn once when approaching the budget and once when over it
+    fn check_budget(&self) {
+        let Some(budget) = self.budget_bytes else {
+            return;
+        };
+        let total = self.total_bytes();
+        if total > budget {
+            if !self.warned_exceeded.swap(true, Ordering::Relaxed) {
+                warn!(
+                    total_bytes = total,
+                    budget_bytes = budget,
+                    "Ceremony traffic exceeded the bandwidth budget"
+                );
+            }
+        } else if total * BUDGET_WARN_DENOMINATOR >= budget * BUDGET_WARN_NUMERATOR
+            && !self.warned_approaching.swap(true, Ordering::Relaxed)
+        {
+            warn!(
+                total_bytes = total,
+                budget_bytes = budget,
+                "Ceremony traffic is approaching the bandwidth budget"
+            );
+        }
+    }
+}
+
+fn touch_round(activity: &mut BTreeMap<u32, (Instant, Instant)>, round: u32) {
+    let now = Instant::now();
+    activity
+        .entry(round)
+        .and_modify(|(_, last)| *last = now)
+        .or_insert((now, now));
+}
+
+/// Relay wrapper that measures every payload it forwards
+pub struct MeteredRelay<R: Relay> {
+    inner: R,
+    stats: RelayStats,
+}
+
+impl<R: Relay> MeteredRelay<R> {
+    /// Wrap a relay, recording into `stats`
+    pub fn new(inner: R, stats: RelayStats) -> Self {
+        Self { inner, stats }
+    }
+
+    /// The accounting handle this wrapper records into
+    pub fn stats(&self) -> &RelayStats {
+        &self.stats
+    }
+
+    /// Access the wrapped relay
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+}
+
+fn encoded_len<T: Serialize>(message: &T) -> Result<u64> {
+    let bytes = serde_json::to_vec(message).map_err(|e| Error::Serialization(e.to_string()))?;
+    Ok(bytes.len() as u64)
+}
+
+#[async_trait]
+impl<R: Relay> Relay for MeteredRelay<R> {
+    async fn broadcast<T: Serialize + Send + Sync>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        message: &T,
+    ) -> Result<()> {
+        self.stats
+            .record_sent(round, None, encoded_len(message)?);
+        self.inner.broadcast(session_id, round, message).await
+    }
+
+    async fn send_direct<T: Serialize + Send + Sync>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        to: PartyId,
+        message: &T,
+    ) -> Result<()> {
+        self.stats
+            .record_sent(round, Some(to), encoded_len(message)?);
+        self.inner.send_direct(session_id, round, to, message).await
+    }
+
+    async fn collect_broadcasts<T: DeserializeOwned + Send>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        count: usize,
+    ) -> Result<Vec<T>> {
+        // Collect the raw JSON first so the size can be measured before
+        // the payload is handed to the caller's type
+        let values: Vec<serde_json::Value> = self
+            .inner
+            .collect_broadcasts(session_id, round, count)
+            .await?;
+
+        values
+            .into_iter()
+            .map(|value| {
+                self.stats.record_received(round, None, encoded_len(&value)?);
+                serde_json::from_value(value).map_err(|e| Error::Deserialization(e.to_string()))
+            })
+            .collect()
+    }
+
+    async fn collect_direct<T: DeserializeOwned + Send>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        my_id: PartyId,
+        count: usize,
+    ) -> Result<Vec<T>> {
+        let values: Vec<serde_json::Value> = self
+            .inner
+            .collect_direct(session_id, round, my_id, count)
+            .await?;
+
+        values
+            .into_iter()
+            .map(|value| {
+                self.stats.record_received(round, None, encoded_len(&value)?);
+                serde_json::from_value(value).map_err(|e| Error::Deserialization(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::MemoryRelay;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    struct TestMessage {
+        value: u32,
+    }
+
+    #[tokio::test]
+    async fn test_metered_relay_counts_both_directions() {
+        let session_id = [3u8; 32];
+        let relay = MeteredRelay::new(MemoryRelay::new(), RelayStats::new());
+        let message = TestMessage { value: 42 };
+        let wire_len = serde_json::to_vec(&message).unwrap().len() as u64;
+
+        relay.broadcast(&session_id, 1, &message).await.unwrap();
+        relay.send_direct(&session_id, 2, 1, &message).await.unwrap();
+        let received: Vec<TestMessage> =
+            relay.collect_broadcasts(&session_id, 1, 1).await.unwrap();
+        assert_eq!(received[0], message);
+
+        let totals = relay.stats().totals();
+        assert_eq!(totals.messages_sent, 2);
+        assert_eq!(totals.bytes_sent, 2 * wire_len);
+        assert_eq!(totals.messages_received, 1);
+        assert_eq!(totals.bytes_received, wire_len);
+
+        // Direct sends are attributed to their addressee
+        let snapshot = relay.stats().snapshot();
+        assert_eq!(snapshot[1].0, (2, Some(1)));
+        assert_eq!(snapshot[1].1.bytes_sent, wire_len);
+    }
+
+    #[test]
+    fn test_budget_warnings_track_total_traffic() {
+        let stats = RelayStats::new().with_budget(100);
+        stats.record_sent(1, None, 60);
+        assert!(!stats.budget_exceeded());
+
+        stats.record_received(1, None, 60);
+        assert!(stats.budget_exceeded());
+        assert_eq!(stats.budget_bytes(), Some(100));
+    }
+}
@@ -1,10 +1,42 @@
 //! Endemic OT implementation
 //!
 //! Base oblivious transfer protocol from https://eprint.iacr.org/2019/706.pdf
+//!
+//! One instance transfers `count` independent 1-out-of-2 random OTs: the
+//! sender ends with a pair of 32-byte strings per OT, the receiver with the
+//! string matching its choice bit. The receiver hides its choice by pairing
+//! its real X25519 key with a uniformly random one; it cannot compute the
+//! shared secret for the random key, so it only learns one string. The
+//! construction is honest-but-curious — the malicious-security consistency
+//! checks are tracked separately.
 
 use crate::{Error, Result};
 use rand::rngs::OsRng;
-use x25519_dalek::{EphemeralSecret, PublicKey};
+use rand::{CryptoRng, RngCore};
+use x25519_dalek::{PublicKey, ReusableSecret};
+
+use crate::consts::OT_OUTPUT_CONTEXT;
+
+/// Derive one OT output string from the transcript and shared secret
+fn derive_output(
+    sender_key: &PublicKey,
+    receiver_keys: &[[u8; 32]; 2],
+    index: usize,
+    position: u8,
+    shared: &[u8; 32],
+) -> [u8; 32] {
+    let mut material = Vec::with_capacity(32 * 4 + 9);
+    material.extend_from_slice(sender_key.as_bytes());
+    material.extend_from_slice(&receiver_keys[0]);
+    material.extend_from_slice(&receiver_keys[1]);
+    material.extend_from_slice(&(index as u64).to_be_bytes());
+    material.push(position);
+    material.extend_from_slice(shared);
+    blake3::derive_key(OT_OUTPUT_CONTEXT, &material)
+}
+
+/// Receiver round-1 output: per-OT output strings and key pairs
+type ReceiverRound1 = (Vec<[u8; 32]>, Vec<[[u8; 32]; 2]>);
 
 /// Endemic OT protocol state
 pub struct EndemicOT {
@@ -18,13 +50,22 @@ impl EndemicOT {
         Self { count }
     }
 
-    /// Sender's first message
-    pub fn sender_round1(&self) -> Result<(Vec<EphemeralSecret>, Vec<PublicKey>)> {
+    /// Sender's first message: one ephemeral public key per OT
+    pub fn sender_round1(&self) -> Result<(Vec<ReusableSecret>, Vec<PublicKey>)> {
+        self.sender_round1_with_rng(&mut OsRng)
+    }
+
+    /// [`sender_round1`](Self::sender_round1) drawing from a caller-supplied
+    /// entropy source
+    pub fn sender_round1_with_rng<G: RngCore + CryptoRng>(
+        &self,
+        rng: &mut G,
+    ) -> Result<(Vec<ReusableSecret>, Vec<PublicKey>)> {
         let mut secrets = Vec::with_capacity(self.count);
         let mut public_keys = Vec::with_capacity(self.count);
 
         for _ in 0..self.count {
-            let secret = EphemeralSecret::random_from_rng(OsRng);
+            let secret = ReusableSecret::random_from_rng(&mut *rng);
             let public = PublicKey::from(&secret);
             secrets.push(secret);
             public_keys.push(public);
@@ -34,11 +75,26 @@ impl EndemicOT {
     }
 
     /// Receiver's response given choice bits
+    ///
+    /// For each OT the receiver returns a pair of public keys: the one at
+    /// its choice position is a real key, the other is uniformly random.
+    /// It immediately learns the output string for its choice.
     pub fn receiver_round1(
         &self,
         sender_keys: &[PublicKey],
         choices: &[bool],
-    ) -> Result<(Vec<[u8; 32]>, Vec<PublicKey>)> {
+    ) -> Result<ReceiverRound1> {
+        self.receiver_round1_with_rng(&mut OsRng, sender_keys, choices)
+    }
+
+    /// [`receiver_round1`](Self::receiver_round1) drawing from a
+    /// caller-supplied entropy source
+    pub fn receiver_round1_with_rng<G: RngCore + CryptoRng>(
+        &self,
+        rng: &mut G,
+        sender_keys: &[PublicKey],
+        choices: &[bool],
+    ) -> Result<ReceiverRound1> {
         if sender_keys.len() != self.count || choices.len() != self.count {
             return Err(Error::InvalidConfig("Mismatched OT parameters".into()));
         }
@@ -47,36 +103,35 @@ impl EndemicOT {
         let mut receiver_keys = Vec::with_capacity(self.count);
 
         for i in 0..self.count {
-            let secret = EphemeralSecret::random_from_rng(OsRng);
+            let secret = ReusableSecret::random_from_rng(&mut *rng);
             let public = PublicKey::from(&secret);
 
-            // Compute shared secret
-            let shared = secret.diffie_hellman(&sender_keys[i]);
+            let mut decoy = [0u8; 32];
+            rng.fill_bytes(&mut decoy);
 
-            // Output depends on choice
-            let output = if choices[i] {
-                // XOR with sender's key
-                let mut out = *shared.as_bytes();
-                for (j, byte) in sender_keys[i].as_bytes().iter().enumerate() {
-                    out[j] ^= byte;
-                }
-                out
-            } else {
-                *shared.as_bytes()
-            };
+            let position = usize::from(choices[i]);
+            let mut pair = [decoy, decoy];
+            pair[position] = *public.as_bytes();
 
-            outputs.push(output);
-            receiver_keys.push(public);
+            let shared = secret.diffie_hellman(&sender_keys[i]);
+            outputs.push(derive_output(
+                &sender_keys[i],
+                &pair,
+                i,
+                position as u8,
+                shared.as_bytes(),
+            ));
+            receiver_keys.push(pair);
         }
 
         Ok((outputs, receiver_keys))
     }
 
-    /// Sender derives outputs
+    /// Sender derives both output strings per OT
     pub fn sender_derive(
         &self,
-        secrets: &[EphemeralSecret],
-        receiver_keys: &[PublicKey],
+        secrets: &[ReusableSecret],
+        receiver_keys: &[[[u8; 32]; 2]],
     ) -> Result<Vec<([u8; 32], [u8; 32])>> {
         if secrets.len() != self.count || receiver_keys.len() != self.count {
             return Err(Error::InvalidConfig("Mismatched OT parameters".into()));
@@ -85,10 +140,14 @@ impl EndemicOT {
         let mut outputs = Vec::with_capacity(self.count);
 
         for i in 0..self.count {
-            // This is a simplified version - real implementation would use
-            // proper key derivation
-            let out0 = [0u8; 32]; // Placeholder
-            let out1 = [0u8; 32]; // Placeholder
+            let sender_key = PublicKey::from(&secrets[i]);
+            let pair = &receiver_keys[i];
+
+            let shared0 = secrets[i].diffie_hellman(&PublicKey::from(pair[0]));
+            let shared1 = secrets[i].diffie_hellman(&PublicKey::from(pair[1]));
+
+            let out0 = derive_output(&sender_key, pair, i, 0, shared0.as_bytes());
+            let out1 = derive_output(&sender_key, pair, i, 1, shared1.as_bytes());
             outputs.push((out0, out1));
         }
 
@@ -108,4 +167,32 @@ mod tests {
         assert_eq!(secrets.len(), 10);
         assert_eq!(public_keys.len(), 10);
     }
+
+    #[test]
+    fn test_endemic_ot_correctness() {
+        let count = 16;
+        let ot = EndemicOT::new(count);
+        let choices: Vec<bool> = (0..count).map(|i| i % 3 == 0).collect();
+
+        let (secrets, sender_keys) = ot.sender_round1().unwrap();
+        let (receiver_outputs, receiver_keys) =
+            ot.receiver_round1(&sender_keys, &choices).unwrap();
+        let sender_outputs = ot.sender_derive(&secrets, &receiver_keys).unwrap();
+
+        for i in 0..count {
+            let expected = if choices[i] {
+                sender_outputs[i].1
+            } else {
+                sender_outputs[i].0
+            };
+            assert_eq!(receiver_outputs[i], expected);
+            // The unchosen string must differ from what the receiver got
+            let other = if choices[i] {
+                sender_outputs[i].0
+            } else {
+                sender_outputs[i].1
+            };
+            assert_ne!(receiver_outputs[i], other);
+        }
+    }
 }
@@ -1,42 +1,175 @@
 //! SoftSpokenOT implementation
 //!
 //! OT extension protocol from https://eprint.iacr.org/2022/192.pdf
+//!
+//! Instantiated with k = 1, which coincides with the classic IKNP extension:
+//! `KAPPA` base OTs in the reversed direction are stretched into `count`
+//! random OTs using a PRG and a bit-matrix transposition. The extension
+//! *sender* plays base-OT receiver with a random correlation vector Δ; the
+//! extension *receiver* plays base-OT sender and therefore knows both PRG
+//! seeds per base OT. The consistency check for malicious receivers is
+//! tracked separately; this is the honest-but-curious core.
+
+use crate::{Error, Result};
+
+pub use crate::consts::KAPPA;
+use crate::consts::{EXTENSION_OUTPUT_CONTEXT, SOFT_SPOKEN_PRG_CONTEXT};
 
-use crate::Result;
+/// Expand a base-OT seed into a `count`-bit pseudorandom row
+fn expand_seed(seed: &[u8; 32], count: usize) -> Vec<u8> {
+    let mut hasher = blake3::Hasher::new_derive_key(SOFT_SPOKEN_PRG_CONTEXT);
+    hasher.update(seed);
+    let mut row = vec![0u8; count.div_ceil(8)];
+    hasher.finalize_xof().fill(&mut row);
+    row
+}
+
+/// Read bit `j` of a packed bit row
+fn get_bit(row: &[u8], j: usize) -> bool {
+    (row[j / 8] >> (j % 8)) & 1 == 1
+}
+
+/// Hash one transposed column into a 32-byte OT output string
+fn hash_column(index: usize, column: &[u8; KAPPA / 8]) -> [u8; 32] {
+    let mut material = Vec::with_capacity(8 + KAPPA / 8);
+    material.extend_from_slice(&(index as u64).to_be_bytes());
+    material.extend_from_slice(column);
+    blake3::derive_key(EXTENSION_OUTPUT_CONTEXT, &material)
+}
+
+/// Masked rows `u` sent receiver -> sender, one packed bit row per base OT
+pub type MaskedRows = Vec<Vec<u8>>;
+
+/// One 32-byte OT output string per extended OT
+pub type OtStrings = Vec<[u8; 32]>;
 
 /// SoftSpokenOT protocol
 pub struct SoftSpokenOT {
-    /// Security parameter
-    security_param: usize,
     /// Number of OTs to extend
     count: usize,
 }
 
 impl SoftSpokenOT {
     /// Create a new SoftSpokenOT instance
-    pub fn new(security_param: usize, count: usize) -> Self {
-        Self {
-            security_param,
-            count,
-        }
+    pub fn new(count: usize) -> Self {
+        Self { count }
     }
 
-    /// Run the OT extension protocol as sender
-    pub fn extend_sender(&self, base_ots: &[([u8; 32], [u8; 32])]) -> Result<Vec<([u8; 32], [u8; 32])>> {
-        // Placeholder implementation
-        // Real implementation would use the SoftSpokenOT protocol
-        let outputs = vec![([0u8; 32], [0u8; 32]); self.count];
-        Ok(outputs)
-    }
-
-    /// Run the OT extension protocol as receiver
+    /// Run the extension as receiver
+    ///
+    /// The receiver holds both seeds of every base OT and its `count`
+    /// choice bits. Returns the masked rows `u` to send to the sender and
+    /// the receiver's OT output strings.
     pub fn extend_receiver(
         &self,
-        base_ots: &[[u8; 32]],
+        base_pairs: &[([u8; 32], [u8; 32])],
         choices: &[bool],
-    ) -> Result<Vec<[u8; 32]>> {
-        // Placeholder implementation
-        let outputs = vec![[0u8; 32]; self.count];
+    ) -> Result<(MaskedRows, OtStrings)> {
+        if base_pairs.len() != KAPPA {
+            return Err(Error::InvalidConfig(format!(
+                "Extension requires {} base OTs, got {}",
+                KAPPA,
+                base_pairs.len()
+            )));
+        }
+        if choices.len() != self.count {
+            return Err(Error::InvalidConfig("Mismatched choice count".into()));
+        }
+
+        let row_len = self.count.div_ceil(8);
+        let mut choice_row = vec![0u8; row_len];
+        for (j, &bit) in choices.iter().enumerate() {
+            if bit {
+                choice_row[j / 8] |= 1 << (j % 8);
+            }
+        }
+
+        let mut rows0 = Vec::with_capacity(KAPPA);
+        let mut u_rows = Vec::with_capacity(KAPPA);
+        for (seed0, seed1) in base_pairs {
+            let row0 = expand_seed(seed0, self.count);
+            let row1 = expand_seed(seed1, self.count);
+            let u: Vec<u8> = row0
+                .iter()
+                .zip(&row1)
+                .zip(&choice_row)
+                .map(|((a, b), c)| a ^ b ^ c)
+                .collect();
+            rows0.push(row0);
+            u_rows.push(u);
+        }
+
+        // Transpose: column j of the row0 matrix is the receiver's string
+        let mut outputs = Vec::with_capacity(self.count);
+        for j in 0..self.count {
+            let mut column = [0u8; KAPPA / 8];
+            for (i, row0) in rows0.iter().enumerate() {
+                if get_bit(row0, j) {
+                    column[i / 8] |= 1 << (i % 8);
+                }
+            }
+            outputs.push(hash_column(j, &column));
+        }
+
+        Ok((u_rows, outputs))
+    }
+
+    /// Run the extension as sender
+    ///
+    /// The sender holds its base-OT choice vector Δ, the seed it received
+    /// per base OT, and the receiver's masked rows. Returns a pair of
+    /// output strings per extended OT; the receiver knows the one matching
+    /// its choice bit.
+    pub fn extend_sender(
+        &self,
+        delta: &[bool],
+        base_outputs: &[[u8; 32]],
+        u_rows: &[Vec<u8>],
+    ) -> Result<Vec<([u8; 32], [u8; 32])>> {
+        if delta.len() != KAPPA || base_outputs.len() != KAPPA || u_rows.len() != KAPPA {
+            return Err(Error::InvalidConfig(format!(
+                "Extension requires {} base OTs",
+                KAPPA
+            )));
+        }
+
+        let row_len = self.count.div_ceil(8);
+        let mut q_rows = Vec::with_capacity(KAPPA);
+        for i in 0..KAPPA {
+            if u_rows[i].len() != row_len {
+                return Err(Error::InvalidConfig("Mismatched masked row length".into()));
+            }
+            let mut q = expand_seed(&base_outputs[i], self.count);
+            if delta[i] {
+                for (q_byte, u_byte) in q.iter_mut().zip(&u_rows[i]) {
+                    *q_byte ^= u_byte;
+                }
+            }
+            q_rows.push(q);
+        }
+
+        let mut delta_packed = [0u8; KAPPA / 8];
+        for (i, &bit) in delta.iter().enumerate() {
+            if bit {
+                delta_packed[i / 8] |= 1 << (i % 8);
+            }
+        }
+
+        let mut outputs = Vec::with_capacity(self.count);
+        for j in 0..self.count {
+            let mut column = [0u8; KAPPA / 8];
+            for (i, q_row) in q_rows.iter().enumerate() {
+                if get_bit(q_row, j) {
+                    column[i / 8] |= 1 << (i % 8);
+                }
+            }
+            let mut flipped = column;
+            for (byte, delta_byte) in flipped.iter_mut().zip(&delta_packed) {
+                *byte ^= delta_byte;
+            }
+            outputs.push((hash_column(j, &column), hash_column(j, &flipped)));
+        }
+
         Ok(outputs)
     }
 }
@@ -44,11 +177,44 @@ impl SoftSpokenOT {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_soft_spoken_extension_correctness() {
+        let mut rng = rand::thread_rng();
+        let count = 300;
+        let ot = SoftSpokenOT::new(count);
+
+        // Emulate the reversed-direction base OTs: the extension sender
+        // picked Δ and received one seed per base OT, the extension
+        // receiver knows both seeds.
+        let delta: Vec<bool> = (0..KAPPA).map(|_| rng.gen()).collect();
+        let base_pairs: Vec<([u8; 32], [u8; 32])> =
+            (0..KAPPA).map(|_| (rng.gen(), rng.gen())).collect();
+        let base_outputs: Vec<[u8; 32]> = base_pairs
+            .iter()
+            .zip(&delta)
+            .map(|((s0, s1), &d)| if d { *s1 } else { *s0 })
+            .collect();
+
+        let choices: Vec<bool> = (0..count).map(|_| rng.gen()).collect();
+        let (u_rows, receiver_outputs) = ot.extend_receiver(&base_pairs, &choices).unwrap();
+        let sender_outputs = ot.extend_sender(&delta, &base_outputs, &u_rows).unwrap();
+
+        for j in 0..count {
+            let expected = if choices[j] {
+                sender_outputs[j].1
+            } else {
+                sender_outputs[j].0
+            };
+            assert_eq!(receiver_outputs[j], expected);
+        }
+    }
 
     #[test]
-    fn test_soft_spoken_setup() {
-        let ot = SoftSpokenOT::new(128, 1000);
-        assert_eq!(ot.security_param, 128);
-        assert_eq!(ot.count, 1000);
+    fn test_soft_spoken_rejects_wrong_base_count() {
+        let ot = SoftSpokenOT::new(8);
+        let result = ot.extend_receiver(&[([0u8; 32], [1u8; 32]); 4], &[false; 8]);
+        assert!(result.is_err());
     }
 }
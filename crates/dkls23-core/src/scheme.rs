@@ -0,0 +1,99 @@
+//! Signature scheme registry
+//!
+//! Maps the `SchemeId` recorded in key metadata to the implementation
+//! compiled into this binary. Schemes other than secp256k1 ECDSA are
+//! gated behind feature flags so deployments can compile out what they
+//! don't need; loading a share that requires a missing scheme yields a
+//! clear [`Error::UnsupportedScheme`](crate::Error::UnsupportedScheme).
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// Identifier for a signature scheme / curve combination
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SchemeId {
+    /// ECDSA over secp256k1 (DKLs23)
+    #[default]
+    Secp256k1,
+    /// ECDSA over NIST P-256
+    P256,
+    /// EdDSA over Ed25519
+    Ed25519,
+}
+
+impl fmt::Display for SchemeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemeId::Secp256k1 => write!(f, "secp256k1"),
+            SchemeId::P256 => write!(f, "p256"),
+            SchemeId::Ed25519 => write!(f, "ed25519"),
+        }
+    }
+}
+
+impl FromStr for SchemeId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "secp256k1" => Ok(SchemeId::Secp256k1),
+            "p256" | "secp256r1" => Ok(SchemeId::P256),
+            "ed25519" => Ok(SchemeId::Ed25519),
+            other => Err(Error::InvalidConfig(format!("Unknown scheme: {}", other))),
+        }
+    }
+}
+
+/// Schemes compiled into this binary
+///
+/// secp256k1 is always available; the others depend on feature flags.
+pub fn supported_schemes() -> &'static [SchemeId] {
+    &[
+        SchemeId::Secp256k1,
+        #[cfg(feature = "scheme-p256")]
+        SchemeId::P256,
+        #[cfg(feature = "scheme-ed25519")]
+        SchemeId::Ed25519,
+    ]
+}
+
+/// Check whether a scheme is compiled into this binary
+pub fn is_supported(scheme: SchemeId) -> bool {
+    supported_schemes().contains(&scheme)
+}
+
+/// Return an error if the scheme is not compiled into this binary
+pub fn ensure_supported(scheme: SchemeId) -> Result<()> {
+    if is_supported(scheme) {
+        Ok(())
+    } else {
+        Err(Error::UnsupportedScheme(scheme))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secp256k1_always_supported() {
+        assert!(is_supported(SchemeId::Secp256k1));
+        assert!(ensure_supported(SchemeId::Secp256k1).is_ok());
+    }
+
+    #[test]
+    fn test_scheme_id_round_trip() {
+        for scheme in [SchemeId::Secp256k1, SchemeId::P256, SchemeId::Ed25519] {
+            let parsed: SchemeId = scheme.to_string().parse().unwrap();
+            assert_eq!(parsed, scheme);
+        }
+    }
+
+    #[test]
+    fn test_unknown_scheme_rejected() {
+        assert!("curve9000".parse::<SchemeId>().is_err());
+    }
+}
@@ -1,22 +1,27 @@
 //! DSG protocol implementation
 
+use crate::curve::{
+    decode_point, encode_point, reduce_scalar_bytes, scalar_to_bytes, ThresholdCurve,
+};
+use crate::consts::{MTA_ROUND_1, MTA_ROUND_2, MTA_ROUND_3, MTA_ROUND_4};
 use crate::mpc::Relay;
 use crate::{Error, KeyShare, PartyId, Result, SessionConfig, SessionId, Signature};
-use k256::{
-    elliptic_curve::{
-        bigint::U256, ops::Reduce, point::DecompressPoint, Field,
-        sec1::{FromEncodedPoint, ToEncodedPoint},
-    },
-    AffinePoint, ProjectivePoint, Scalar,
+use elliptic_curve::{
+    group::Curve as _, scalar::IsHigh, sec1::ToEncodedPoint, Field, Group,
 };
 use rand::rngs::OsRng;
-use tracing::{debug, info, instrument};
+use rand::{CryptoRng, RngCore};
+use tracing::{debug, error, info, instrument};
 
-use super::{PartialSignature, PreSignature};
+use super::{mta, PartialSignature, PreSignature, PreSignatureToken};
+use std::collections::HashMap;
 
 /// Run the distributed signature generation protocol
 ///
-/// This implements the 3-round signing protocol from DKLs23.
+/// Implements the DKLs23 signing flow: commitment broadcasts, pairwise
+/// OT-based MtA conversion, then a delta reveal and partial-signature
+/// combine. Broadcast rounds are numbered 1-3 as before; the MtA flights
+/// travel as direct messages on sub-rounds 11-14.
 ///
 /// # Arguments
 /// * `key_share` - This party's key share from DKG
@@ -27,11 +32,53 @@ use super::{PartialSignature, PreSignature};
 /// # Returns
 /// The ECDSA signature
 #[instrument(skip(key_share, relay))]
-pub async fn run_dsg<R: Relay>(
-    key_share: &KeyShare,
+pub async fn run_dsg<C: ThresholdCurve, R: Relay>(
+    key_share: &KeyShare<C>,
+    message: &[u8; 32],
+    parties: &[PartyId],
+    relay: &R,
+) -> Result<Signature> {
+    run_dsg_with_rng(key_share, message, parties, relay, &mut OsRng).await
+}
+
+/// [`run_dsg`] under a caller-chosen session ID
+///
+/// For parties that negotiated the session out of band (a session
+/// coordinator, or batch flows signing one message twice); everything
+/// else matches [`run_dsg`].
+#[instrument(skip(key_share, relay))]
+pub async fn run_dsg_with_session<C: ThresholdCurve, R: Relay>(
+    key_share: &KeyShare<C>,
+    message: &[u8; 32],
+    parties: &[PartyId],
+    relay: &R,
+    session_id: SessionId,
+) -> Result<Signature> {
+    run_dsg_inner(key_share, message, parties, relay, Some(session_id), &mut OsRng).await
+}
+
+/// [`run_dsg`] drawing nonces from a caller-supplied entropy source
+///
+/// The default entry point uses the operating system RNG; hosts with an
+/// approved hardware entropy source (HSMs, enclaves) and deterministic
+/// tests plug theirs in here instead.
+pub async fn run_dsg_with_rng<C: ThresholdCurve, R: Relay, G: RngCore + CryptoRng>(
+    key_share: &KeyShare<C>,
     message: &[u8; 32],
     parties: &[PartyId],
     relay: &R,
+    rng: &mut G,
+) -> Result<Signature> {
+    run_dsg_inner(key_share, message, parties, relay, None, rng).await
+}
+
+async fn run_dsg_inner<C: ThresholdCurve, R: Relay, G: RngCore + CryptoRng>(
+    key_share: &KeyShare<C>,
+    message: &[u8; 32],
+    parties: &[PartyId],
+    relay: &R,
+    session_id: Option<SessionId>,
+    rng: &mut G,
 ) -> Result<Signature> {
     info!(
         party_id = key_share.party_id,
@@ -39,6 +86,21 @@ pub async fn run_dsg<R: Relay>(
         "Starting DSG"
     );
 
+    // This protocol only produces ECDSA signatures over the share's own
+    // curve; a share generated on another curve must not enter this flow
+    crate::scheme::ensure_supported(key_share.scheme)?;
+    if key_share.scheme != C::SCHEME {
+        return Err(Error::UnsupportedScheme(key_share.scheme));
+    }
+
+    // Refuse to run a message flow older than the key's downgrade floor
+    if crate::PROTOCOL_VERSION < key_share.min_protocol_version {
+        return Err(Error::ProtocolVersionTooOld {
+            required: key_share.min_protocol_version,
+            actual: crate::PROTOCOL_VERSION,
+        });
+    }
+
     // Verify threshold
     if parties.len() < key_share.threshold {
         return Err(Error::ThresholdNotMet {
@@ -52,7 +114,13 @@ pub async fn run_dsg<R: Relay>(
         return Err(Error::InvalidPartyId(key_share.party_id));
     }
 
-    let session_id: SessionId = rand::random();
+    // All participants must agree on the session ID without extra
+    // coordination, so derive it from the signing context unless one was
+    // negotiated explicitly. Two concurrent signings of the same message
+    // under the same key would collide; batch callers disambiguate via
+    // distinct messages or explicit sessions.
+    let session_id =
+        session_id.unwrap_or_else(|| derive_session_id(&key_share.public_key, parties, message));
     let config = SessionConfig {
         session_id,
         n_parties: parties.len(),
@@ -61,8 +129,31 @@ pub async fn run_dsg<R: Relay>(
         parties: parties.to_vec(),
     };
 
+    // Parties holding divergent key material would only discover it as a
+    // garbage signature after three full rounds; compare fingerprints up
+    // front and abort immediately instead
+    let key_check = super::DsgKeyCheckMessage {
+        party_id: key_share.party_id,
+        key_fingerprint: key_share.key_fingerprint(),
+    };
+    let round_started = std::time::Instant::now();
+    relay.broadcast(&session_id, 0, &key_check).await?;
+    let checks = relay
+        .collect_broadcasts::<super::DsgKeyCheckMessage>(&session_id, 0, parties.len())
+        .await?;
+    crate::telemetry::round_complete("dsg", 0, round_started.elapsed(), checks.len());
+    for check in &checks {
+        if check.key_fingerprint != key_check.key_fingerprint {
+            crate::telemetry::verification_failures("dsg", 1);
+            return Err(Error::KeyMismatch {
+                party: check.party_id,
+                fingerprint: hex::encode(check.key_fingerprint),
+            });
+        }
+    }
+
     // Generate pre-signature
-    let pre_sig = pre_signature(key_share, &config, relay).await?;
+    let pre_sig = pre_signature_with_rng(key_share, &config, relay, rng).await?;
 
     // Create partial signature
     let partial = create_partial_signature(key_share, &pre_sig, message)?;
@@ -72,12 +163,14 @@ pub async fn run_dsg<R: Relay>(
         party_id: key_share.party_id,
         sigma_share: partial.sigma_share.clone(),
     };
+    let round_started = std::time::Instant::now();
     relay.broadcast(&session_id, 3, &partial_msg).await?;
 
-    // Collect partial signatures
+    // Collect partial signatures; the relay returns them in sender order
     let all_partials = relay
         .collect_broadcasts::<super::DsgPartialMessage>(&session_id, 3, parties.len())
         .await?;
+    crate::telemetry::round_complete("dsg", 3, round_started.elapsed(), all_partials.len());
 
     let partial_sigs: Vec<PartialSignature> = all_partials
         .into_iter()
@@ -87,110 +180,377 @@ pub async fn run_dsg<R: Relay>(
         })
         .collect();
 
-    // Combine partial signatures
-    let signature = combine_partial_signatures(&pre_sig, &partial_sigs, message)?;
+    let signature = finalize(key_share, &pre_sig, &partial_sigs, message)?;
 
     info!(
         party_id = key_share.party_id,
-        r = hex::encode(&signature.r),
-        s = hex::encode(&signature.s),
+        r = hex::encode(signature.r),
+        s = hex::encode(signature.s),
         "DSG completed successfully"
     );
 
     Ok(signature)
 }
 
+/// Sign a message using a stored pre-signature
+///
+/// Runs only the message-dependent phase: one partial-signature broadcast
+/// round on the pre-signature's session, then a non-interactive
+/// [`finalize`]. This is the latency advantage of DKLs23 — the expensive
+/// MtA phase ran earlier, before the message was known. The token is
+/// consumed because pre-signature nonces are strictly single-use.
+#[instrument(skip(key_share, token, relay))]
+pub async fn sign_with_presignature<C: ThresholdCurve, R: Relay>(
+    key_share: &KeyShare<C>,
+    token: PreSignatureToken,
+    message: &[u8; 32],
+    relay: &R,
+) -> Result<Signature> {
+    let (partial, pre_sig) = token.sign(key_share, message)?;
+    let session_id = pre_sig.session_id;
+    let n_parties = pre_sig.parties.len();
+
+    let partial_msg = super::DsgPartialMessage {
+        party_id: key_share.party_id,
+        sigma_share: partial.sigma_share.clone(),
+    };
+    relay.broadcast(&session_id, 3, &partial_msg).await?;
+
+    let all_partials = relay
+        .collect_broadcasts::<super::DsgPartialMessage>(&session_id, 3, n_parties)
+        .await?;
+    let partial_sigs: Vec<PartialSignature> = all_partials
+        .into_iter()
+        .map(|msg| PartialSignature {
+            party_id: msg.party_id,
+            sigma_share: msg.sigma_share,
+        })
+        .collect();
+
+    let signature = finalize(key_share, &pre_sig, &partial_sigs, message)?;
+
+    info!(
+        party_id = key_share.party_id,
+        r = hex::encode(signature.r),
+        "Signed from stored pre-signature"
+    );
+
+    Ok(signature)
+}
+
 /// Generate pre-signature (can be done before message is known)
+///
+/// Runs the commitment broadcast and the pairwise MtA conversions. Each
+/// ordered pair of parties runs one MtA instance per direction, converting
+/// `k_i * gamma_j` and `k_i * w_j` (with `w_j` the Lagrange-adjusted key
+/// share) into additive shares, so no party ever reveals `k_i` or its key
+/// share. The resulting `R = delta^-1 * sum(Gamma_j)` equals `k^-1 * G`.
 #[instrument(skip(key_share, relay))]
-pub async fn pre_signature<R: Relay>(
-    key_share: &KeyShare,
+pub async fn pre_signature<C: ThresholdCurve, R: Relay>(
+    key_share: &KeyShare<C>,
     config: &SessionConfig,
     relay: &R,
+) -> Result<PreSignature> {
+    pre_signature_with_rng(key_share, config, relay, &mut OsRng).await
+}
+
+/// [`pre_signature`] drawing nonces from a caller-supplied entropy source
+pub async fn pre_signature_with_rng<C: ThresholdCurve, R: Relay, G: RngCore + CryptoRng>(
+    key_share: &KeyShare<C>,
+    config: &SessionConfig,
+    relay: &R,
+    rng: &mut G,
 ) -> Result<PreSignature> {
     debug!("Generating pre-signature");
 
-    let mut rng = OsRng;
+    let session_id = &config.session_id;
+    let peers: Vec<PartyId> = config
+        .parties
+        .iter()
+        .copied()
+        .filter(|&p| p != config.party_id)
+        .collect();
 
-    // Round 1: Generate random k_i and broadcast commitment
-    let k_i = Scalar::random(&mut rng);
-    let gamma_i = Scalar::random(&mut rng);
+    // Round 1: generate nonce shares and broadcast commitments
+    let round_started = std::time::Instant::now();
+    let mut k_i = C::Scalar::random(&mut *rng);
+    let mut gamma_i = C::Scalar::random(&mut *rng);
 
-    let k_commitment = ProjectivePoint::GENERATOR * k_i;
-    let gamma_commitment = ProjectivePoint::GENERATOR * gamma_i;
+    let k_commitment = C::ProjectivePoint::generator() * k_i;
+    let gamma_commitment = C::ProjectivePoint::generator() * gamma_i;
 
     let round1_msg = super::DsgRound1Message {
         party_id: config.party_id,
-        k_commitment: k_commitment
-            .to_affine()
-            .to_encoded_point(true)
-            .as_bytes()
-            .to_vec(),
-        gamma_commitment: gamma_commitment
-            .to_affine()
-            .to_encoded_point(true)
-            .as_bytes()
-            .to_vec(),
+        protocol_version: crate::PROTOCOL_VERSION,
+        k_commitment: encode_point::<C>(&k_commitment),
+        gamma_commitment: encode_point::<C>(&gamma_commitment),
+        capabilities: crate::capabilities::Capabilities::local().0,
     };
-    relay.broadcast(&config.session_id, 1, &round1_msg).await?;
+    // MtA flight 1 (as receiver toward every peer) is independent of the
+    // round-1 broadcasts, so post it while still waiting for peers'
+    // commitments instead of serializing a full round-trip behind them.
+    // Round 1 runs over echo broadcast so an equivocating party cannot
+    // show different nonce commitments to different signers.
+    let mut receiver_states = HashMap::new();
+    let mut mta_round1 = Vec::with_capacity(peers.len());
+    for &peer in &peers {
+        let (state, base_keys) = mta::receiver_init(rng)?;
+        receiver_states.insert(peer, state);
+        mta_round1.push((
+            peer,
+            super::MtaRound1Message {
+                party_id: config.party_id,
+                base_keys,
+            },
+        ));
+    }
+    let (round1_msgs, _) = futures_util::try_join!(
+        crate::mpc::echo_broadcast(
+            config,
+            1,
+            &round1_msg,
+            relay,
+            |msg: &super::DsgRound1Message| msg.party_id,
+        ),
+        async {
+            for (peer, msg) in &mta_round1 {
+                relay.send_direct(session_id, MTA_ROUND_1, *peer, msg).await?;
+            }
+            Ok(())
+        },
+    )?;
+    crate::telemetry::round_complete("dsg", 1, round_started.elapsed(), round1_msgs.len());
+
+    // Fold the accepted broadcasts into the transcript in sorted order so
+    // all honest parties compute the same digest
+    let mut transcript = crate::transcript::Transcript::new(C::DSG_LABEL, session_id);
+    for msg in &round1_msgs {
+        transcript.append_message(1, msg.party_id, msg)?;
+    }
 
-    // Collect round 1 messages
-    let round1_msgs = relay
-        .collect_broadcasts::<super::DsgRound1Message>(&config.session_id, 1, config.parties.len())
+    // Every peer must advertise at least the key's minimum protocol
+    // version; otherwise one party could be tricked into a banned flow
+    for msg in &round1_msgs {
+        if msg.protocol_version < key_share.min_protocol_version {
+            return Err(Error::ProtocolVersionTooOld {
+                required: key_share.min_protocol_version,
+                actual: msg.protocol_version,
+            });
+        }
+    }
+
+    // Negotiate the signing set's capability intersection and record it
+    let advertised: Vec<u64> = round1_msgs.iter().map(|msg| msg.capabilities).collect();
+    let negotiated = crate::capabilities::negotiate(
+        &advertised,
+        crate::capabilities::Capabilities::BASELINE,
+    )?;
+    debug!(capabilities = ?negotiated.names(), "Negotiated signing capabilities");
+
+    // Lagrange-adjust the key share for this signing set
+    let lambda_i = compute_lagrange_coefficient::<C>(config.party_id, &config.parties);
+    let mut w_i = key_share.secret_share * lambda_i;
+
+    // MtA flight 2: answer every peer's base OTs (as sender)
+    let mut sender_states = HashMap::new();
+    let round_started = std::time::Instant::now();
+    let flight1 = relay
+        .collect_direct::<super::MtaRound1Message>(
+            session_id,
+            MTA_ROUND_1,
+            config.party_id,
+            peers.len(),
+        )
         .await?;
+    crate::telemetry::round_complete("dsg", MTA_ROUND_1, round_started.elapsed(), flight1.len());
+    for msg in &flight1 {
+        let (state, key_pairs) = mta::sender_respond(&mut *rng, &msg.base_keys)?;
+        sender_states.insert(msg.party_id, state);
+        let reply = super::MtaRound2Message {
+            party_id: config.party_id,
+            key_pairs,
+        };
+        relay.send_direct(session_id, MTA_ROUND_2, msg.party_id, &reply).await?;
+    }
 
-    // Round 2: Multiplicative-to-additive (MtA) protocol
-    debug!("DSG Round 2: MtA protocol");
+    // MtA flight 3: run the OT extension over gamma_i and w_i (as receiver)
+    let mut ready_states = HashMap::new();
+    let round_started = std::time::Instant::now();
+    let flight2 = relay
+        .collect_direct::<super::MtaRound2Message>(
+            session_id,
+            MTA_ROUND_2,
+            config.party_id,
+            peers.len(),
+        )
+        .await?;
+    crate::telemetry::round_complete("dsg", MTA_ROUND_2, round_started.elapsed(), flight2.len());
+    for msg in &flight2 {
+        let state = receiver_states
+            .remove(&msg.party_id)
+            .ok_or(Error::InvalidPartyId(msg.party_id))?;
+        let (ready, u_rows) = mta::receiver_extend::<C>(state, &gamma_i, &w_i, &msg.key_pairs)?;
+        ready_states.insert(msg.party_id, ready);
+        let reply = super::MtaRound3Message {
+            party_id: config.party_id,
+            u_rows,
+        };
+        crate::telemetry::ot_bytes(
+            "sent",
+            reply.u_rows.iter().map(|row| row.len()).sum(),
+        );
+        relay.send_direct(session_id, MTA_ROUND_3, msg.party_id, &reply).await?;
+    }
 
-    // Compute Lagrange coefficient
-    let lambda_i = compute_lagrange_coefficient(config.party_id, &config.parties);
+    // MtA flight 4: publish corrections and take the sender-side shares of
+    // k_i * gamma_j and k_i * w_j
+    let mut delta_i = k_i * gamma_i;
+    let mut sigma_i = k_i * w_i;
+    let round_started = std::time::Instant::now();
+    let flight3 = relay
+        .collect_direct::<super::MtaRound3Message>(
+            session_id,
+            MTA_ROUND_3,
+            config.party_id,
+            peers.len(),
+        )
+        .await?;
+    crate::telemetry::round_complete("dsg", MTA_ROUND_3, round_started.elapsed(), flight3.len());
+    for msg in &flight3 {
+        let state = sender_states
+            .remove(&msg.party_id)
+            .ok_or(Error::InvalidPartyId(msg.party_id))?;
+        crate::telemetry::ot_bytes(
+            "received",
+            msg.u_rows.iter().map(|row| row.len()).sum(),
+        );
+        let mta::MtaSenderOutput {
+            alpha_first: alpha_gamma,
+            alpha_second: alpha_w,
+            corrections_first: gamma_corrections,
+            corrections_second: w_corrections,
+        } = mta::sender_finish::<C>(state, &k_i, &msg.u_rows)?;
+        delta_i += alpha_gamma;
+        sigma_i += alpha_w;
+        let reply = super::MtaRound4Message {
+            party_id: config.party_id,
+            gamma_corrections,
+            w_corrections,
+        };
+        relay.send_direct(session_id, MTA_ROUND_4, msg.party_id, &reply).await?;
+    }
 
-    // Compute shares
-    let x_i = key_share.secret_share * lambda_i;
-    let k_inv_share = k_i; // Simplified - full protocol uses MtA
-    let chi_share = x_i * k_i; // Simplified
+    // Receiver-side shares of k_j * gamma_i and k_j * w_i
+    let round_started = std::time::Instant::now();
+    let flight4 = relay
+        .collect_direct::<super::MtaRound4Message>(
+            session_id,
+            MTA_ROUND_4,
+            config.party_id,
+            peers.len(),
+        )
+        .await?;
+    crate::telemetry::round_complete("dsg", MTA_ROUND_4, round_started.elapsed(), flight4.len());
+    for msg in &flight4 {
+        let ready = ready_states
+            .remove(&msg.party_id)
+            .ok_or(Error::InvalidPartyId(msg.party_id))?;
+        let (beta_gamma, beta_w) =
+            mta::receiver_finish::<C>(ready, &msg.gamma_corrections, &msg.w_corrections)?;
+        delta_i += beta_gamma;
+        sigma_i += beta_w;
+    }
 
-    // Broadcast round 2
+    // Round 2: reveal delta shares; delta = sum(delta_i) = k * gamma.
+    // The sigma commitment is published alongside so an invalid combined
+    // signature can later be blamed on a specific party.
+    let sigma_commitment = encode_point::<C>(&(C::ProjectivePoint::generator() * sigma_i));
     let round2_msg = super::DsgRound2Message {
         party_id: config.party_id,
-        delta_share: (k_i * gamma_i).to_bytes().to_vec(),
+        delta_share: scalar_to_bytes::<C>(&delta_i).to_vec(),
+        sigma_commitment,
     };
-    relay.broadcast(&config.session_id, 2, &round2_msg).await?;
+    let round_started = std::time::Instant::now();
+    relay.broadcast(session_id, 2, &round2_msg).await?;
 
-    // Collect round 2 messages
-    let _round2_msgs = relay
-        .collect_broadcasts::<super::DsgRound2Message>(&config.session_id, 2, config.parties.len())
+    let round2_msgs = relay
+        .collect_broadcasts::<super::DsgRound2Message>(session_id, 2, config.parties.len())
         .await?;
+    crate::telemetry::round_complete("dsg", 2, round_started.elapsed(), round2_msgs.len());
+
+    for msg in &round2_msgs {
+        transcript.append_message(2, msg.party_id, msg)?;
+    }
 
-    // Compute R = sum(k_i * G)
-    let mut r_point = ProjectivePoint::IDENTITY;
+    let mut delta = C::Scalar::ZERO;
+    for msg in &round2_msgs {
+        let bytes: [u8; 32] = msg
+            .delta_share
+            .clone()
+            .try_into()
+            .map_err(|_| Error::Deserialization("Invalid delta_share length".into()))?;
+        delta += reduce_scalar_bytes::<C>(&bytes);
+    }
+
+    let delta_inv = Option::<C::Scalar>::from(delta.invert())
+        .ok_or_else(|| Error::Crypto("Delta is zero; signing nonce was degenerate".into()))?;
+
+    // R = delta^-1 * sum(Gamma_j) = (k * gamma)^-1 * gamma * G = k^-1 * G
+    let mut gamma_point = C::ProjectivePoint::identity();
     for msg in &round1_msgs {
-        let point = k256::EncodedPoint::from_bytes(&msg.k_commitment)
-            .map_err(|e| Error::Deserialization(e.to_string()))?;
-        let affine_opt = AffinePoint::from_encoded_point(&point);
-        let affine: AffinePoint = Option::<AffinePoint>::from(affine_opt)
-            .ok_or_else(|| Error::VerificationFailed("Invalid K commitment".into()))?;
-        let commitment = ProjectivePoint::from(affine);
-        r_point = r_point + commitment;
-    }
-
-    let r_encoded = r_point.to_affine().to_encoded_point(true);
-    let r_bytes: [u8; 33] = r_encoded
-        .as_bytes()
+        gamma_point += decode_point::<C>(&msg.gamma_commitment)?;
+    }
+    let r_point = gamma_point * delta_inv;
+
+    let r_bytes: [u8; 33] = encode_point::<C>(&r_point)
         .try_into()
         .map_err(|_| Error::Internal("Invalid R point".into()))?;
 
-    Ok(PreSignature {
+    let k_commitments = round1_msgs
+        .iter()
+        .map(|msg| (msg.party_id, msg.k_commitment.clone()))
+        .collect();
+    let sigma_commitments = round2_msgs
+        .iter()
+        .map(|msg| (msg.party_id, msg.sigma_commitment.clone()))
+        .collect();
+
+    let pre_sig = PreSignature {
         session_id: config.session_id,
         parties: config.parties.clone(),
         r_point: r_bytes,
-        k_inv_share: k_inv_share.to_bytes().to_vec(),
-        chi_share: chi_share.to_bytes().to_vec(),
-    })
+        k_inv_share: scalar_to_bytes::<C>(&k_i).to_vec(),
+        chi_share: scalar_to_bytes::<C>(&sigma_i).to_vec(),
+        k_commitments,
+        sigma_commitments,
+        transcript_digest: transcript.digest(),
+    };
+
+    // The local nonce scalars now live only inside the pre-signature,
+    // which zeroizes on drop; wipe the stack copies as well
+    use zeroize::Zeroize;
+    k_i.zeroize();
+    gamma_i.zeroize();
+    w_i.zeroize();
+    sigma_i.zeroize();
+
+    Ok(pre_sig)
+}
+
+/// Derive the shared signing session ID from the signing context
+fn derive_session_id(public_key: &[u8], parties: &[PartyId], message: &[u8; 32]) -> SessionId {
+    let mut material = Vec::with_capacity(public_key.len() + parties.len() * 8 + 32);
+    material.extend_from_slice(public_key);
+    for &party in parties {
+        material.extend_from_slice(&(party as u64).to_be_bytes());
+    }
+    material.extend_from_slice(message);
+    blake3::derive_key(crate::consts::DSG_SESSION_CONTEXT, &material)
 }
 
 /// Create a partial signature
-pub fn create_partial_signature(
-    _key_share: &KeyShare,
+pub fn create_partial_signature<C: ThresholdCurve>(
+    _key_share: &KeyShare<C>,
     pre_sig: &PreSignature,
     message: &[u8; 32],
 ) -> Result<PartialSignature> {
@@ -200,105 +560,679 @@ pub fn create_partial_signature(
         .clone()
         .try_into()
         .map_err(|_| Error::Deserialization("Invalid k_inv_share length".into()))?;
-    let k_inv_share = <Scalar as Reduce<U256>>::reduce_bytes(&k_inv_bytes.into());
+    let k_inv_share = reduce_scalar_bytes::<C>(&k_inv_bytes);
 
     let chi_bytes: [u8; 32] = pre_sig
         .chi_share
         .clone()
         .try_into()
         .map_err(|_| Error::Deserialization("Invalid chi_share length".into()))?;
-    let chi_share = <Scalar as Reduce<U256>>::reduce_bytes(&chi_bytes.into());
-
-    // Get r value from R point
-    let r_point = k256::EncodedPoint::from_bytes(&pre_sig.r_point)
-        .map_err(|e| Error::Deserialization(e.to_string()))?;
-    let r_affine_opt = AffinePoint::from_encoded_point(&r_point);
-    let r_affine: AffinePoint = Option::<AffinePoint>::from(r_affine_opt)
-        .ok_or_else(|| Error::VerificationFailed("Invalid R point".into()))?;
+    let chi_share = reduce_scalar_bytes::<C>(&chi_bytes);
 
     // r = x-coordinate of R mod n
-    let r_bytes = r_affine.to_encoded_point(false);
-    let r_coord: [u8; 32] = r_bytes.as_bytes()[1..33]
-        .try_into()
-        .map_err(|_| Error::Internal("Invalid R coordinate".into()))?;
-    let r = <Scalar as Reduce<U256>>::reduce_bytes(&r_coord.into());
+    let r = r_scalar::<C>(&pre_sig.r_point)?;
 
     // m = message hash
-    let m = <Scalar as Reduce<U256>>::reduce_bytes(&(*message).into());
+    let m = reduce_scalar_bytes::<C>(message);
 
-    // sigma_i = k_i^-1 * (m + r * x_i)
-    // Simplified: sigma_i = k_inv_share * m + r * chi_share
+    // Partial signature share: s_i = m * k_i + r * sigma_i, which sums to
+    // s = k * (m + r * x) across parties -- a valid signature under the
+    // nonce k^-1 whose public point R the pre-signature carries.
     let sigma_share = k_inv_share * m + r * chi_share;
 
     Ok(PartialSignature {
         party_id: 0, // Will be set by caller
-        sigma_share: sigma_share.to_bytes().to_vec(),
+        sigma_share: scalar_to_bytes::<C>(&sigma_share).to_vec(),
     })
 }
 
+/// Reduce the x-coordinate of an encoded R point mod the curve order
+fn r_scalar<C: ThresholdCurve>(r_point: &[u8]) -> Result<C::Scalar> {
+    let r_affine = decode_point::<C>(r_point)?.to_affine();
+    let encoded = r_affine.to_encoded_point(false);
+    let r_coord: [u8; 32] = encoded.as_bytes()[1..33]
+        .try_into()
+        .map_err(|_| Error::Internal("Invalid R coordinate".into()))?;
+    Ok(reduce_scalar_bytes::<C>(&r_coord))
+}
+
 /// Combine partial signatures into final signature
+///
+/// The combined s is normalized to its low-s sibling, since Bitcoin and
+/// Ethereum both reject the high form.
+/// The recovery ID is computed by trial recovery against the group public
+/// key, so callers exporting to Ethereum get a `v` that actually recovers
+/// the signing address — including in the rare r-overflow case that parity
+/// alone cannot express.
 pub fn combine_partial_signatures(
     pre_sig: &PreSignature,
     partials: &[PartialSignature],
-    _message: &[u8; 32],
+    message: &[u8; 32],
+    public_key: &[u8],
+) -> Result<Signature> {
+    let mut signature = combine_partial_signatures_for_curve::<k256::Secp256k1>(pre_sig, partials)?;
+
+    // Upgrade the Y-parity heuristic to trial recovery against the group
+    // key, so the r-overflow case gets the ID parity alone cannot express
+    let r_affine = decode_point::<k256::Secp256k1>(&pre_sig.r_point)?.to_affine();
+    signature.recovery_id =
+        compute_recovery_id(message, &signature.r, &signature.s, public_key, &r_affine)?;
+
+    Ok(signature)
+}
+
+/// Combine partial signatures over an explicit curve
+///
+/// Curve-generic core of [`combine_partial_signatures`]: sums the sigma
+/// shares, extracts r and normalizes to the low-s sibling. The recovery ID
+/// is the Y-parity heuristic only — sufficient for curves without an
+/// ecosystem notion of recovery; secp256k1 callers go through the concrete
+/// wrapper, which refines it by trial recovery.
+pub fn combine_partial_signatures_for_curve<C: ThresholdCurve>(
+    pre_sig: &PreSignature,
+    partials: &[PartialSignature],
 ) -> Result<Signature> {
     // Sum all sigma shares
-    let mut s = Scalar::ZERO;
+    let mut s = C::Scalar::ZERO;
     for partial in partials {
         let sigma_bytes: [u8; 32] = partial
             .sigma_share
             .clone()
             .try_into()
             .map_err(|_| Error::Deserialization("Invalid sigma_share length".into()))?;
-        let sigma = <Scalar as Reduce<U256>>::reduce_bytes(&sigma_bytes.into());
-        s = s + sigma;
+        s += reduce_scalar_bytes::<C>(&sigma_bytes);
     }
 
     // Get r from R point
-    let r_point = k256::EncodedPoint::from_bytes(&pre_sig.r_point)
-        .map_err(|e| Error::Deserialization(e.to_string()))?;
-    let r_affine_opt = AffinePoint::from_encoded_point(&r_point);
-    let r_affine: AffinePoint = Option::<AffinePoint>::from(r_affine_opt)
-        .ok_or_else(|| Error::VerificationFailed("Invalid R point".into()))?;
-
+    let r_affine = decode_point::<C>(&pre_sig.r_point)?.to_affine();
     let r_bytes = r_affine.to_encoded_point(false);
     let r: [u8; 32] = r_bytes.as_bytes()[1..33]
         .try_into()
         .map_err(|_| Error::Internal("Invalid r length".into()))?;
 
-    // Normalize s to low-s form
-    let s_bytes = s.to_bytes();
-    let s_normalized: [u8; 32] = s_bytes
-        .as_slice()
-        .try_into()
-        .map_err(|_| Error::Internal("Invalid s length".into()))?;
+    // Y parity of R from the compressed tag byte; replacing s with its
+    // low sibling mirrors the nonce point across the x-axis, so the
+    // parity bit flips with it
+    let parity = u8::from(pre_sig.r_point[0] == 0x03);
+    let mut signature = Signature::new(r, scalar_to_bytes::<C>(&s), parity);
+    if bool::from(s.is_high()) {
+        signature.s = scalar_to_bytes::<C>(&(-s));
+        signature.recovery_id ^= 1;
+    }
+
+    Ok(signature)
+}
+
+/// Combine collected partial signatures into a verified signature
+///
+/// Non-interactive: sorts the partials, folds the final round into the
+/// transcript chained from the pre-signature digest, combines, then
+/// verifies against the group key — assigning blame via the commitment
+/// checks when verification fails.
+pub fn finalize<C: ThresholdCurve>(
+    key_share: &KeyShare<C>,
+    pre_sig: &PreSignature,
+    partials: &[PartialSignature],
+    message: &[u8; 32],
+) -> Result<Signature> {
+    let mut partials = partials.to_vec();
+    partials.sort_by_key(|partial| partial.party_id);
 
-    // Compute recovery ID from Y coordinate parity
-    // Check if Y is odd by looking at the compressed point prefix
-    let r_encoded = r_affine.to_encoded_point(true);
-    let recovery_id = if r_encoded.as_bytes()[0] == 0x03 { 1 } else { 0 };
+    // Complete the ceremony transcript with the final broadcast round,
+    // re-serializing each partial exactly as it traveled on the wire
+    let mut transcript = crate::transcript::Transcript::resume(&pre_sig.transcript_digest);
+    for partial in &partials {
+        let msg = super::DsgPartialMessage {
+            party_id: partial.party_id,
+            sigma_share: partial.sigma_share.clone(),
+        };
+        transcript.append_message(3, partial.party_id, &msg)?;
+    }
+
+    // secp256k1 signatures carry a trial-recovered ID for Ethereum export;
+    // other curves have no recovery convention and keep the parity bit
+    let mut signature = if C::SCHEME == crate::scheme::SchemeId::Secp256k1 {
+        combine_partial_signatures(pre_sig, &partials, message, &key_share.public_key)?
+    } else {
+        combine_partial_signatures_for_curve::<C>(pre_sig, &partials)?
+    };
+    signature.transcript_digest = transcript.digest();
+
+    // An invalid combined signature means someone contributed a bad share;
+    // check every partial against its commitments to name the culprit
+    if !verify_signature::<C>(&signature, message, &key_share.public_key_point()) {
+        identify_malicious_party::<C>(pre_sig, &partials, message)?;
+        // Every partial matched its commitments yet the combination does
+        // not verify; log what we combined so the ceremony can be audited
+        error!(
+            party_id = key_share.party_id,
+            r = hex::encode(signature.r),
+            s = hex::encode(signature.s),
+            public_key = hex::encode(&key_share.public_key),
+            message = hex::encode(message),
+            "Combined signature failed verification against the group key"
+        );
+        return Err(Error::InvalidSignature);
+    }
+
+    Ok(signature)
+}
 
-    Ok(Signature::new(r, s_normalized, recovery_id))
+/// Find the recovery ID by trial recovery against the group public key
+///
+/// Tries every candidate ID and keeps the one whose recovered key matches.
+/// If none matches — the combined signature is invalid and the caller's
+/// verification will name the culprit — fall back to the Y-parity
+/// heuristic so the blame path still gets a structurally complete value.
+fn compute_recovery_id(
+    message: &[u8; 32],
+    r: &[u8; 32],
+    s: &[u8; 32],
+    public_key: &[u8],
+    r_affine: &k256::AffinePoint,
+) -> Result<u8> {
+    use k256::ecdsa::{RecoveryId, Signature as EcdsaSignature, VerifyingKey};
+
+    let verifying_key = VerifyingKey::from_sec1_bytes(public_key)
+        .map_err(|e| Error::Deserialization(format!("Invalid public key: {}", e)))?;
+
+    // The wire signature carries the raw x-coordinate, but the scalar pair
+    // handed to the recovery machinery must be reduced mod n
+    let r_scalar = reduce_scalar_bytes::<k256::Secp256k1>(r);
+    let s_scalar = reduce_scalar_bytes::<k256::Secp256k1>(s);
+    let signature = EcdsaSignature::from_scalars(r_scalar.to_bytes(), s_scalar.to_bytes())
+        .map_err(|e| Error::Deserialization(format!("Invalid signature scalars: {}", e)))?;
+
+    match RecoveryId::trial_recovery_from_prehash(&verifying_key, message, &signature) {
+        Ok(recovery_id) => Ok(recovery_id.to_byte()),
+        Err(_) => {
+            debug!("No recovery ID reproduces the group key; falling back to parity");
+            let r_encoded = r_affine.to_encoded_point(true);
+            Ok(if r_encoded.as_bytes()[0] == 0x03 { 1 } else { 0 })
+        }
+    }
+}
+
+/// Check the combined signature against the group public key
+///
+/// Plain ECDSA verification over the raw (r, s) pair; used to decide
+/// whether a blame phase is needed before handing the signature back.
+fn verify_signature<C: ThresholdCurve>(
+    signature: &Signature,
+    message: &[u8; 32],
+    public_key: &C::ProjectivePoint,
+) -> bool {
+    let r = reduce_scalar_bytes::<C>(&signature.r);
+    let s = reduce_scalar_bytes::<C>(&signature.s);
+    let Some(s_inv) = Option::<C::Scalar>::from(s.invert()) else {
+        return false;
+    };
+    if bool::from(r.is_zero()) {
+        return false;
+    }
+
+    let m = reduce_scalar_bytes::<C>(message);
+    let point = C::ProjectivePoint::generator() * (m * s_inv) + *public_key * (r * s_inv);
+    if point == C::ProjectivePoint::identity() {
+        return false;
+    }
+
+    let encoded = point.to_affine().to_encoded_point(false);
+    let x_coord: [u8; 32] = match encoded.as_bytes()[1..33].try_into() {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+    reduce_scalar_bytes::<C>(&x_coord) == r
+}
+
+/// Blame phase: check each partial signature against its commitments
+///
+/// A correct partial satisfies `s_i * G = m * K_i + r * S_i` where `K_i`
+/// and `S_i` are the nonce and sigma commitments the party broadcast during
+/// pre-signing. Returns [`Error::MaliciousParty`] naming the first party
+/// whose partial breaks that relation; a party that lied consistently in
+/// both its commitments and its partial cannot be identified this way and
+/// falls through to the caller's generic failure.
+fn identify_malicious_party<C: ThresholdCurve>(
+    pre_sig: &PreSignature,
+    partials: &[PartialSignature],
+    message: &[u8; 32],
+) -> Result<()> {
+    let r = r_scalar::<C>(&pre_sig.r_point)?;
+    let m = reduce_scalar_bytes::<C>(message);
+
+    for partial in partials {
+        let k_commitment = pre_sig
+            .k_commitments
+            .iter()
+            .find(|(party_id, _)| *party_id == partial.party_id)
+            .map(|(_, bytes)| bytes)
+            .ok_or(Error::InvalidPartyId(partial.party_id))?;
+        let sigma_commitment = pre_sig
+            .sigma_commitments
+            .iter()
+            .find(|(party_id, _)| *party_id == partial.party_id)
+            .map(|(_, bytes)| bytes)
+            .ok_or(Error::InvalidPartyId(partial.party_id))?;
+
+        let sigma_bytes: [u8; 32] = partial
+            .sigma_share
+            .clone()
+            .try_into()
+            .map_err(|_| Error::MaliciousParty(partial.party_id))?;
+        let s_i = reduce_scalar_bytes::<C>(&sigma_bytes);
+
+        let expected =
+            decode_point::<C>(k_commitment)? * m + decode_point::<C>(sigma_commitment)? * r;
+        if C::ProjectivePoint::generator() * s_i != expected {
+            crate::telemetry::verification_failures("dsg", 1);
+            return Err(Error::MaliciousParty(partial.party_id));
+        }
+    }
+
+    Ok(())
 }
 
 /// Compute Lagrange coefficient for party i
-fn compute_lagrange_coefficient(party_id: PartyId, parties: &[PartyId]) -> Scalar {
+pub(super) fn compute_lagrange_coefficient<C: ThresholdCurve>(
+    party_id: PartyId,
+    parties: &[PartyId],
+) -> C::Scalar {
+    // Two-signer collapse: `λ_i = x_j / (x_j - x_i)`. For adjacent IDs —
+    // every 2-of-2 wallet, the dominant topology — the denominator is ±1
+    // and the coefficient needs no field inversion.
+    if let [a, b] = parties {
+        let other = if *a == party_id { *b } else { *a };
+        let x_j = other as u64 + 1;
+        if other == party_id + 1 {
+            return C::Scalar::from(x_j);
+        }
+        if party_id == other + 1 {
+            return -C::Scalar::from(x_j);
+        }
+    }
+
     let i = party_id as u64 + 1;
-    let mut numerator = Scalar::ONE;
-    let mut denominator = Scalar::ONE;
+    let mut numerator = C::Scalar::ONE;
+    let mut denominator = C::Scalar::ONE;
 
     for &j_id in parties {
         let j = j_id as u64 + 1;
         if j != i {
-            numerator = numerator * Scalar::from(j);
+            numerator *= C::Scalar::from(j);
             let diff = if j > i {
-                Scalar::from(j - i)
+                C::Scalar::from(j - i)
             } else {
-                -Scalar::from(i - j)
+                -C::Scalar::from(i - j)
             };
-            denominator = denominator * diff;
+            denominator *= diff;
         }
     }
 
-    numerator * denominator.invert().unwrap_or(Scalar::ONE)
+    numerator * denominator.invert().unwrap_or(C::Scalar::ONE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mpc::MemoryRelay;
+    use k256::elliptic_curve::{bigint::U256, ops::Reduce};
+    use k256::{ProjectivePoint, Scalar, Secp256k1};
+
+    fn dummy_share(min_protocol_version: u32) -> KeyShare {
+        KeyShare {
+            party_id: 0,
+            n_parties: 2,
+            threshold: 2,
+            secret_share: Scalar::ONE,
+            public_key: ProjectivePoint::GENERATOR
+                .to_affine()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+            public_shares: Vec::new(),
+            chain_code: [0u8; 32],
+            scheme: crate::scheme::SchemeId::Secp256k1,
+            min_protocol_version,
+            transcript_digest: [0u8; 32],
+            integrity_tag: [0u8; 32],
+        }
+    }
+
+    #[test]
+    fn test_presignature_serde_roundtrip_and_token_single_use() {
+        use crate::sign::PreSignatureToken;
+        use k256::elliptic_curve::Field;
+        let mut rng = rand::rngs::OsRng;
+
+        let r_point = (ProjectivePoint::GENERATOR * Scalar::random(&mut rng))
+            .to_affine()
+            .to_encoded_point(true);
+        let pre_sig = PreSignature {
+            session_id: [1u8; 32],
+            parties: vec![0, 1],
+            r_point: r_point.as_bytes().try_into().unwrap(),
+            k_inv_share: Scalar::random(&mut rng).to_bytes().to_vec(),
+            chi_share: Scalar::random(&mut rng).to_bytes().to_vec(),
+            k_commitments: Vec::new(),
+            sigma_commitments: Vec::new(),
+            transcript_digest: [2u8; 32],
+        };
+
+        // Stored pre-signatures survive a wire round-trip intact
+        let json = serde_json::to_string(&pre_sig).unwrap();
+        let restored: PreSignature = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.r_point, pre_sig.r_point);
+        assert_eq!(restored.k_inv_share, pre_sig.k_inv_share);
+        assert_eq!(restored.transcript_digest, pre_sig.transcript_digest);
+
+        // Consuming the token yields the partial and scrubs the nonce
+        // shares; a second use is a move of a consumed value and does
+        // not compile
+        let token = PreSignatureToken::new(restored);
+        let (partial, spent) = token.sign(&dummy_share(0), &[0x11u8; 32]).unwrap();
+        assert_eq!(partial.sigma_share.len(), 32);
+        assert!(spent.k_inv_share.is_empty());
+        assert!(spent.chi_share.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sign_with_presignature_is_one_round() {
+        use crate::sign::PreSignatureToken;
+        use k256::elliptic_curve::Field;
+        let mut rng = rand::rngs::OsRng;
+
+        // Synthetic single-party pre-signature: with R = K^-1 * G the
+        // partial K*(m + r*x) is a complete signature under nonce K^-1
+        let x = Scalar::random(&mut rng);
+        let public_key = (ProjectivePoint::GENERATOR * x)
+            .to_affine()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+        let big_k = Scalar::random(&mut rng);
+        let r_affine = (ProjectivePoint::GENERATOR * big_k.invert().unwrap()).to_affine();
+
+        let pre_sig = PreSignature {
+            session_id: [9u8; 32],
+            parties: vec![0],
+            r_point: r_affine.to_encoded_point(true).as_bytes().try_into().unwrap(),
+            k_inv_share: big_k.to_bytes().to_vec(),
+            chi_share: (big_k * x).to_bytes().to_vec(),
+            k_commitments: Vec::new(),
+            sigma_commitments: Vec::new(),
+            transcript_digest: [7u8; 32],
+        };
+        let key_share: KeyShare = KeyShare {
+            party_id: 0,
+            n_parties: 1,
+            threshold: 1,
+            secret_share: x,
+            public_key,
+            public_shares: Vec::new(),
+            chain_code: [0u8; 32],
+            scheme: crate::scheme::SchemeId::Secp256k1,
+            min_protocol_version: 0,
+            transcript_digest: [0u8; 32],
+            integrity_tag: [0u8; 32],
+        };
+
+        // The message-dependent phase is a single broadcast round
+        let relay = MemoryRelay::new();
+        let message = [0x55u8; 32];
+        let signature = sign_with_presignature(
+            &key_share,
+            PreSignatureToken::new(pre_sig),
+            &message,
+            &relay,
+        )
+        .await
+        .unwrap();
+
+        assert!(verify_signature::<Secp256k1>(
+            &signature,
+            &message,
+            &key_share.public_key_point()
+        ));
+        // The transcript digest chains from the pre-signature's
+        assert_ne!(signature.transcript_digest, [0u8; 32]);
+    }
+
+    #[test]
+    fn test_blame_identifies_tampered_partial() {
+        use k256::elliptic_curve::Field;
+        let mut rng = rand::rngs::OsRng;
+
+        // Two honest parties with known shares under the combine equation
+        let k = [Scalar::random(&mut rng), Scalar::random(&mut rng)];
+        let sigma = [Scalar::random(&mut rng), Scalar::random(&mut rng)];
+
+        let r_point = (ProjectivePoint::GENERATOR * Scalar::random(&mut rng))
+            .to_affine()
+            .to_encoded_point(true);
+        let r_coord: [u8; 32] = r_point.as_bytes()[1..33].try_into().unwrap_or([1u8; 32]);
+        let r = <Scalar as Reduce<U256>>::reduce_bytes(&r_coord.into());
+        let message = [0x5au8; 32];
+        let m = <Scalar as Reduce<U256>>::reduce_bytes(&message.into());
+
+        let commitment = |scalar: &Scalar| {
+            (ProjectivePoint::GENERATOR * scalar)
+                .to_affine()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec()
+        };
+        let pre_sig = PreSignature {
+            session_id: [0u8; 32],
+            parties: vec![0, 1],
+            r_point: r_point.as_bytes().try_into().unwrap(),
+            k_inv_share: k[0].to_bytes().to_vec(),
+            chi_share: sigma[0].to_bytes().to_vec(),
+            k_commitments: vec![(0, commitment(&k[0])), (1, commitment(&k[1]))],
+            sigma_commitments: vec![(0, commitment(&sigma[0])), (1, commitment(&sigma[1]))],
+            transcript_digest: [0u8; 32],
+        };
+
+        let honest = |i: usize| PartialSignature {
+            party_id: i,
+            sigma_share: (k[i] * m + r * sigma[i]).to_bytes().to_vec(),
+        };
+        let mut partials = vec![honest(0), honest(1)];
+
+        // All honest partials pass the blame check
+        assert!(identify_malicious_party::<Secp256k1>(&pre_sig, &partials, &message).is_ok());
+
+        // Party 1 tampers with its share and is named
+        partials[1].sigma_share = (k[1] * m + r * sigma[1] + Scalar::ONE).to_bytes().to_vec();
+        let err = identify_malicious_party::<Secp256k1>(&pre_sig, &partials, &message).unwrap_err();
+        assert!(matches!(err, Error::MaliciousParty(1)));
+    }
+
+    /// Run a synthetic single-party combine: with R = K^-1 * G the partial
+    /// K*(m + r*x) sums to a valid signature under nonce K^-1. The nonce is
+    /// negated until the raw (pre-normalization) s lands in the requested
+    /// half of the range.
+    fn combine_synthetic(want_high_s: bool) -> (Signature, Vec<u8>, [u8; 32]) {
+        use k256::elliptic_curve::scalar::IsHigh;
+        use k256::elliptic_curve::Field;
+        let mut rng = rand::rngs::OsRng;
+
+        let x = Scalar::random(&mut rng);
+        let public_key = (ProjectivePoint::GENERATOR * x)
+            .to_affine()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+        let message = [0x33u8; 32];
+        let m = <Scalar as Reduce<U256>>::reduce_bytes(&message.into());
+
+        let mut big_k = Scalar::random(&mut rng);
+        let (r_affine, r) = loop {
+            let r_affine = (ProjectivePoint::GENERATOR * big_k.invert().unwrap()).to_affine();
+            let r_coord: [u8; 32] = r_affine.to_encoded_point(false).as_bytes()[1..33]
+                .try_into()
+                .unwrap();
+            let r = <Scalar as Reduce<U256>>::reduce_bytes(&r_coord.into());
+            let s = big_k * (m + r * x);
+            // Negating the nonce flips s between halves without changing r
+            if bool::from(s.is_high()) == want_high_s {
+                break (r_affine, r);
+            }
+            big_k = -big_k;
+        };
+
+        let pre_sig = PreSignature {
+            session_id: [0u8; 32],
+            parties: vec![0],
+            r_point: r_affine.to_encoded_point(true).as_bytes().try_into().unwrap(),
+            k_inv_share: big_k.to_bytes().to_vec(),
+            chi_share: (big_k * x).to_bytes().to_vec(),
+            k_commitments: Vec::new(),
+            sigma_commitments: Vec::new(),
+            transcript_digest: [0u8; 32],
+        };
+        let partial = PartialSignature {
+            party_id: 0,
+            sigma_share: (big_k * m + r * big_k * x).to_bytes().to_vec(),
+        };
+
+        let signature =
+            combine_partial_signatures(&pre_sig, &[partial], &message, &public_key).unwrap();
+        (signature, public_key, message)
+    }
+
+    /// Assert that (r, s, v) alone reproduces the group key
+    fn assert_recovers(signature: &Signature, public_key: &[u8], message: &[u8; 32]) {
+        use k256::ecdsa::{RecoveryId, Signature as EcdsaSignature, VerifyingKey};
+
+        let ecdsa_sig = EcdsaSignature::from_scalars(signature.r, signature.s).unwrap();
+        let recovered = VerifyingKey::recover_from_prehash(
+            message,
+            &ecdsa_sig,
+            RecoveryId::from_byte(signature.recovery_id).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(recovered.to_encoded_point(true).as_bytes(), public_key);
+    }
+
+    #[test]
+    fn test_lagrange_coefficients_interpolate_at_zero() {
+        // Σ λ_i · f(x_i) must recover f(0) for a degree-1 polynomial,
+        // covering both the two-signer fast path (adjacent IDs) and the
+        // general loop (sparse and three-party sets)
+        let a = Scalar::from(123456789u64);
+        let b = Scalar::from(987654321u64);
+        for parties in [
+            vec![0usize, 1],
+            vec![2, 3],
+            vec![1, 0],
+            vec![0, 3],
+            vec![0, 2, 5],
+        ] {
+            let mut sum = Scalar::ZERO;
+            for &party_id in &parties {
+                let x = Scalar::from(party_id as u64 + 1);
+                sum += compute_lagrange_coefficient::<Secp256k1>(party_id, &parties) * (a + b * x);
+            }
+            assert_eq!(sum, a, "interpolation failed for {:?}", parties);
+        }
+
+        // The 2-of-2 closed forms: λ_0 = 2, λ_1 = -1 for signers {0, 1}
+        assert_eq!(
+            compute_lagrange_coefficient::<Secp256k1>(0, &[0, 1]),
+            Scalar::from(2u64)
+        );
+        assert_eq!(
+            compute_lagrange_coefficient::<Secp256k1>(1, &[0, 1]),
+            -Scalar::ONE
+        );
+    }
+
+    #[test]
+    fn test_recovery_id_recovers_group_key() {
+        let (signature, public_key, message) = combine_synthetic(false);
+        assert_recovers(&signature, &public_key, &message);
+    }
+
+    #[test]
+    fn test_combiner_emits_low_s_even_when_sum_is_high() {
+        use k256::elliptic_curve::scalar::IsHigh;
+
+        let (signature, public_key, message) = combine_synthetic(true);
+        let s = <Scalar as Reduce<U256>>::reduce_bytes(&signature.s.into());
+        assert!(!bool::from(s.is_high()));
+        assert_recovers(&signature, &public_key, &message);
+    }
+
+    #[tokio::test]
+    async fn test_key_mismatch_aborts_before_round_one() {
+        let relay = MemoryRelay::new();
+        let share0 = dummy_share(0);
+        let mut share1 = dummy_share(0);
+        share1.party_id = 1;
+        // Party 1 believes a different commitment set belongs to this key
+        share1.public_shares = vec![vec![0xAA]];
+
+        let message = [0x22u8; 32];
+        let (res0, res1) = tokio::join!(
+            run_dsg(&share0, &message, &[0, 1], &relay),
+            run_dsg(&share1, &message, &[0, 1], &relay),
+        );
+        for result in [res0, res1] {
+            match result {
+                Err(Error::KeyMismatch { .. }) => {}
+                Err(other) => panic!("unexpected error: {}", other),
+                Ok(_) => panic!("mismatched key material must not produce a signature"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejects_banned_protocol_version() {
+        let key_share = dummy_share(crate::PROTOCOL_VERSION + 1);
+        let relay = MemoryRelay::new();
+
+        let err = run_dsg(&key_share, &[0u8; 32], &[0, 1], &relay)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ProtocolVersionTooOld { .. }));
+    }
+
+    #[cfg(feature = "scheme-p256")]
+    #[tokio::test]
+    async fn test_p256_end_to_end_sign() {
+        use p256::ecdsa::signature::hazmat::PrehashVerifier;
+
+        // Full ceremony on the second curve: DKG, then a concurrent DSG,
+        // checked against a stock P-256 ECDSA verifier
+        let relay = MemoryRelay::new();
+        let config = |party_id| crate::SessionConfig {
+            session_id: [0x24u8; 32],
+            n_parties: 2,
+            threshold: 2,
+            party_id,
+            parties: vec![0, 1],
+        };
+        let (config0, config1) = (config(0), config(1));
+        let (share0, share1) = tokio::try_join!(
+            crate::keygen::run_dkg_for_curve::<p256::NistP256, _>(&config0, &relay),
+            crate::keygen::run_dkg_for_curve::<p256::NistP256, _>(&config1, &relay),
+        )
+        .unwrap();
+        assert_eq!(share0.scheme, crate::scheme::SchemeId::P256);
+
+        let relay = MemoryRelay::new();
+        let message = [0x42u8; 32];
+        let (sig0, sig1) = tokio::try_join!(
+            run_dsg(&share0, &message, &[0, 1], &relay),
+            run_dsg(&share1, &message, &[0, 1], &relay),
+        )
+        .unwrap();
+        assert_eq!(sig0.r, sig1.r);
+        assert_eq!(sig0.s, sig1.s);
+
+        let verifying_key = p256::ecdsa::VerifyingKey::from_sec1_bytes(&share0.public_key).unwrap();
+        let ecdsa_sig = p256::ecdsa::Signature::from_scalars(sig0.r, sig0.s).unwrap();
+        verifying_key.verify_prehash(&message, &ecdsa_sig).unwrap();
+    }
 }
@@ -3,15 +3,73 @@
 use crate::PartyId;
 use serde::{Deserialize, Serialize};
 
+/// Round 0 message: key consistency fingerprint
+///
+/// Exchanged before round 1 so parties holding divergent key material
+/// abort immediately instead of discovering the inconsistency as a
+/// garbage signature three rounds later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DsgKeyCheckMessage {
+    /// Sender party ID
+    pub party_id: PartyId,
+    /// Fingerprint over the sender's view of the key material
+    pub key_fingerprint: [u8; 32],
+}
+
 /// Round 1 message: Commitments
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DsgRound1Message {
     /// Sender party ID
     pub party_id: PartyId,
+    /// Wire protocol version the sender is speaking
+    #[serde(default)]
+    pub protocol_version: u32,
     /// Commitment to k_i
     pub k_commitment: Vec<u8>,
     /// Commitment to gamma_i
     pub gamma_commitment: Vec<u8>,
+    /// Advertised capability bitmap (zero from pre-capability releases)
+    #[serde(default)]
+    pub capabilities: u64,
+}
+
+/// MtA flight 1 (receiver -> sender): base-OT public keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtaRound1Message {
+    /// Sender party ID
+    pub party_id: PartyId,
+    /// One X25519 public key per base OT
+    pub base_keys: Vec<[u8; 32]>,
+}
+
+/// MtA flight 2 (sender -> receiver): base-OT response key pairs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtaRound2Message {
+    /// Sender party ID
+    pub party_id: PartyId,
+    /// One key pair per base OT; the position of the real key encodes the
+    /// sender's hidden correlation bit
+    pub key_pairs: Vec<[[u8; 32]; 2]>,
+}
+
+/// MtA flight 3 (receiver -> sender): masked OT-extension rows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtaRound3Message {
+    /// Sender party ID
+    pub party_id: PartyId,
+    /// One masked row per base OT
+    pub u_rows: Vec<Vec<u8>>,
+}
+
+/// MtA flight 4 (sender -> receiver): Gilboa correction scalars
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MtaRound4Message {
+    /// Sender party ID
+    pub party_id: PartyId,
+    /// Corrections for the gamma product, one scalar per bit
+    pub gamma_corrections: Vec<[u8; 32]>,
+    /// Corrections for the w product, one scalar per bit
+    pub w_corrections: Vec<[u8; 32]>,
 }
 
 /// Round 2 message: MtA protocol data
@@ -21,6 +79,9 @@ pub struct DsgRound2Message {
     pub party_id: PartyId,
     /// Delta share
     pub delta_share: Vec<u8>,
+    /// Commitment to this party's sigma share (sigma_i * G), kept for the
+    /// blame phase if the combined signature turns out invalid
+    pub sigma_commitment: Vec<u8>,
 }
 
 /// Round 3 message: Partial signature
@@ -31,3 +92,33 @@ pub struct DsgPartialMessage {
     /// Sigma share
     pub sigma_share: Vec<u8>,
 }
+
+/// Schnorr round 1 message: commitment to the nonce point
+///
+/// Committing before revealing stops any party from choosing its nonce
+/// as a function of the others' and biasing the aggregate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchnorrCommitMessage {
+    /// Sender party ID
+    pub party_id: PartyId,
+    /// Commitment to R_i
+    pub commitment: [u8; 32],
+}
+
+/// Schnorr round 2 message: nonce point reveal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchnorrRevealMessage {
+    /// Sender party ID
+    pub party_id: PartyId,
+    /// R_i = k_i * G (compressed SEC1)
+    pub r_point: Vec<u8>,
+}
+
+/// Schnorr round 3 message: partial signature scalar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchnorrPartialMessage {
+    /// Sender party ID
+    pub party_id: PartyId,
+    /// s_i = k_i + e * d_i
+    pub s_share: [u8; 32],
+}
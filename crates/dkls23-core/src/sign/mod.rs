@@ -4,25 +4,124 @@
 
 mod dsg;
 mod messages;
+mod mta;
+mod pool;
+mod scheduler;
+mod schnorr;
 
-pub use dsg::{create_partial_signature, pre_signature, run_dsg, combine_partial_signatures};
+pub use dsg::{
+    combine_partial_signatures, combine_partial_signatures_for_curve, create_partial_signature,
+    finalize, pre_signature, pre_signature_with_rng, run_dsg, run_dsg_with_rng,
+    run_dsg_with_session, sign_with_presignature,
+};
 pub use messages::*;
+pub use pool::{PreSignaturePool, Reservation};
+pub use schnorr::{
+    run_schnorr_dsg, run_schnorr_dsg_with_rng, verify_bip340, x_only_public_key, SchnorrSignature,
+};
+pub use scheduler::{KeyQueueMetrics, SchedulerLimits, SignPermit, SignScheduler};
 
-use crate::{KeyShare, PartyId, Result, SessionId, Signature};
+use crate::{Error, KeyShare, PartyId, Result, SessionConfig, SessionId};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// Pre-signature data (before message hash is known)
-#[derive(Clone)]
+///
+/// Serializable so pre-signatures can be stored for later low-latency
+/// signing. The nonce shares must be used for exactly one message —
+/// reusing them leaks the private key — so stored pre-signatures should
+/// be handled through a [`PreSignatureToken`].
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct PreSignature {
     /// Session ID
+    #[zeroize(skip)]
     pub session_id: SessionId,
     /// Participating parties
+    #[zeroize(skip)]
     pub parties: Vec<PartyId>,
     /// R point (compressed)
+    #[zeroize(skip)]
+    #[serde(with = "r_point_serde")]
     pub r_point: [u8; 33],
     /// Party's share of k^-1
     pub k_inv_share: Vec<u8>,
     /// Party's multiplicative share
     pub chi_share: Vec<u8>,
+    /// Each party's nonce commitment (k_i * G), for the blame phase
+    #[zeroize(skip)]
+    pub k_commitments: Vec<(PartyId, Vec<u8>)>,
+    /// Each party's sigma commitment (sigma_i * G), for the blame phase
+    #[zeroize(skip)]
+    pub sigma_commitments: Vec<(PartyId, Vec<u8>)>,
+    /// Digest of the transcript over the pre-signing broadcasts; signing
+    /// chains the final round onto it and the result lands on the signature
+    #[zeroize(skip)]
+    pub transcript_digest: [u8; 32],
+}
+
+/// Serde helper for the fixed-width compressed R point
+mod r_point_serde {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(bytes: &[u8; 33], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(bytes)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[u8; 33], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes: Vec<u8> = Vec::deserialize(deserializer)?;
+        bytes
+            .try_into()
+            .map_err(|_| serde::de::Error::custom("Invalid R point length"))
+    }
+}
+
+/// Single-use handle guarding a pre-signature's nonce shares
+///
+/// Using the same pre-signature for two different messages reveals the
+/// private key, so the shares only leave this wrapper once: it is not
+/// `Clone`, and [`sign`](Self::sign) consumes it. What comes back is the
+/// partial signature plus the public remainder of the pre-signature —
+/// R point and commitments for combining and blame — with the nonce
+/// shares already zeroized.
+pub struct PreSignatureToken {
+    pre_sig: PreSignature,
+}
+
+impl PreSignatureToken {
+    /// Take single-use custody of a pre-signature
+    pub fn new(pre_sig: PreSignature) -> Self {
+        Self { pre_sig }
+    }
+
+    /// The session the pre-signature was generated under
+    pub fn session_id(&self) -> &SessionId {
+        &self.pre_sig.session_id
+    }
+
+    /// The parties that participated in pre-signing
+    pub fn parties(&self) -> &[PartyId] {
+        &self.pre_sig.parties
+    }
+
+    /// Consume the token, producing this party's partial signature over
+    /// `message` and the spent public remainder of the pre-signature
+    pub fn sign<C: crate::curve::ThresholdCurve>(
+        mut self,
+        key_share: &KeyShare<C>,
+        message: &[u8; 32],
+    ) -> Result<(PartialSignature, PreSignature)> {
+        let partial = create_partial_signature(key_share, &self.pre_sig, message)?;
+        self.pre_sig.k_inv_share.zeroize();
+        self.pre_sig.chi_share.zeroize();
+        Ok((partial, self.pre_sig))
+    }
 }
 
 /// Partial signature from one party
@@ -33,3 +132,289 @@ pub struct PartialSignature {
     /// Sigma share
     pub sigma_share: Vec<u8>,
 }
+
+/// A protocol message fed into a [`SignSession`]
+#[derive(Debug, Clone)]
+pub enum DsgInput {
+    /// Round 0 key fingerprint broadcast
+    KeyCheck(DsgKeyCheckMessage),
+    /// Round 1 nonce commitment broadcast
+    Round1(DsgRound1Message),
+    /// Round 2 delta reveal broadcast
+    Round2(DsgRound2Message),
+    /// Round 3 partial signature broadcast
+    Partial(DsgPartialMessage),
+}
+
+impl DsgInput {
+    fn round(&self) -> u32 {
+        match self {
+            DsgInput::KeyCheck(_) => 0,
+            DsgInput::Round1(_) => 1,
+            DsgInput::Round2(_) => 2,
+            DsgInput::Partial(_) => 3,
+        }
+    }
+
+    fn sender(&self) -> PartyId {
+        match self {
+            DsgInput::KeyCheck(msg) => msg.party_id,
+            DsgInput::Round1(msg) => msg.party_id,
+            DsgInput::Round2(msg) => msg.party_id,
+            DsgInput::Partial(msg) => msg.party_id,
+        }
+    }
+}
+
+/// Sans-IO DSG state machine, the signing counterpart of
+/// [`DkgSession`](crate::keygen::DkgSession)
+///
+/// Tracks which broadcast round the ceremony is in and which parties have
+/// delivered, rejecting wrong-round messages, unknown senders and
+/// duplicates without touching the network. [`run_dsg`] performs the
+/// cryptographic checks; this machine accounts for message flow so
+/// drivers can validate ordering deterministically and non-tokio hosts
+/// can pump messages themselves. The pairwise MtA flights (sub-rounds
+/// 11-14) carry per-peer state inside the MtA module and are not modeled
+/// here.
+pub struct SignSession {
+    config: SessionConfig,
+    round: u32,
+    key_checks: BTreeMap<PartyId, [u8; 32]>,
+    commitments: BTreeMap<PartyId, DsgRound1Message>,
+    reveals: BTreeMap<PartyId, DsgRound2Message>,
+    partials: BTreeMap<PartyId, Vec<u8>>,
+}
+
+impl SignSession {
+    /// Create a new signing session, starting at the round 0 key check
+    pub fn new(config: SessionConfig) -> Self {
+        Self {
+            config,
+            round: 0,
+            key_checks: BTreeMap::new(),
+            commitments: BTreeMap::new(),
+            reveals: BTreeMap::new(),
+            partials: BTreeMap::new(),
+        }
+    }
+
+    /// The round the session is currently collecting
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    /// Check if the broadcast flow is complete
+    pub fn is_complete(&self) -> bool {
+        self.round > crate::consts::DSG_BROADCAST_ROUNDS
+    }
+
+    /// Key fingerprints collected in round 0, by party
+    pub fn key_checks(&self) -> &BTreeMap<PartyId, [u8; 32]> {
+        &self.key_checks
+    }
+
+    /// Nonce commitments collected in round 1, by party
+    pub fn commitments(&self) -> &BTreeMap<PartyId, DsgRound1Message> {
+        &self.commitments
+    }
+
+    /// Delta reveals collected in round 2, by party
+    pub fn reveals(&self) -> &BTreeMap<PartyId, DsgRound2Message> {
+        &self.reveals
+    }
+
+    /// Sigma shares collected in round 3, by party
+    pub fn partials(&self) -> &BTreeMap<PartyId, Vec<u8>> {
+        &self.partials
+    }
+
+    /// Feed one message into the state machine
+    ///
+    /// Accepts the message if it belongs to the current round and comes
+    /// from a known sender that has not delivered yet; advances the round
+    /// once all of the signing set has delivered. Rejected messages leave
+    /// the state untouched.
+    pub fn handle_message(&mut self, input: DsgInput) -> Result<()> {
+        if input.round() != self.round {
+            return Err(Error::UnexpectedRound {
+                expected: self.round,
+                actual: input.round(),
+            });
+        }
+        let sender = input.sender();
+        if !self.config.parties.contains(&sender) {
+            return Err(Error::InvalidPartyId(sender));
+        }
+
+        let expected = self.config.parties.len();
+        match input {
+            DsgInput::KeyCheck(msg) => {
+                if self.key_checks.contains_key(&msg.party_id) {
+                    return Err(Error::VerificationFailed(format!(
+                        "Duplicate round 0 key check from party {}",
+                        msg.party_id
+                    )));
+                }
+                self.key_checks.insert(msg.party_id, msg.key_fingerprint);
+                if self.key_checks.len() == expected {
+                    self.round = 1;
+                }
+            }
+            DsgInput::Round1(msg) => {
+                if self.commitments.contains_key(&msg.party_id) {
+                    return Err(Error::VerificationFailed(format!(
+                        "Duplicate round 1 commitment from party {}",
+                        msg.party_id
+                    )));
+                }
+                self.commitments.insert(msg.party_id, msg);
+                if self.commitments.len() == expected {
+                    self.round = 2;
+                }
+            }
+            DsgInput::Round2(msg) => {
+                if self.reveals.contains_key(&msg.party_id) {
+                    return Err(Error::VerificationFailed(format!(
+                        "Duplicate round 2 reveal from party {}",
+                        msg.party_id
+                    )));
+                }
+                self.reveals.insert(msg.party_id, msg);
+                if self.reveals.len() == expected {
+                    self.round = 3;
+                }
+            }
+            DsgInput::Partial(msg) => {
+                if self.partials.contains_key(&msg.party_id) {
+                    return Err(Error::VerificationFailed(format!(
+                        "Duplicate round 3 partial from party {}",
+                        msg.party_id
+                    )));
+                }
+                self.partials.insert(msg.party_id, msg.sigma_share);
+                if self.partials.len() == expected {
+                    self.round = 4;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn config() -> SessionConfig {
+        SessionConfig::new(3, 2, 0).unwrap()
+    }
+
+    fn key_check(from: PartyId) -> DsgInput {
+        DsgInput::KeyCheck(DsgKeyCheckMessage {
+            party_id: from,
+            key_fingerprint: [from as u8; 32],
+        })
+    }
+
+    fn round1(from: PartyId) -> DsgInput {
+        DsgInput::Round1(DsgRound1Message {
+            party_id: from,
+            protocol_version: 1,
+            k_commitment: vec![from as u8],
+            gamma_commitment: vec![from as u8],
+            capabilities: 0,
+        })
+    }
+
+    fn round2(from: PartyId) -> DsgInput {
+        DsgInput::Round2(DsgRound2Message {
+            party_id: from,
+            delta_share: vec![from as u8],
+            sigma_commitment: vec![from as u8],
+        })
+    }
+
+    fn partial(from: PartyId) -> DsgInput {
+        DsgInput::Partial(DsgPartialMessage {
+            party_id: from,
+            sigma_share: vec![from as u8],
+        })
+    }
+
+    /// Arbitrary messages, including unknown senders
+    fn arb_input() -> impl Strategy<Value = DsgInput> {
+        (0u8..4, 0usize..5).prop_map(|(variant, from)| match variant {
+            0 => key_check(from),
+            1 => round1(from),
+            2 => round2(from),
+            _ => partial(from),
+        })
+    }
+
+    proptest! {
+        /// Every (state, message) pair is either accepted or rejected with
+        /// one of the defined errors, and rejections never mutate state
+        #[test]
+        fn model_rejections_are_defined_and_state_preserving(
+            inputs in proptest::collection::vec(arb_input(), 0..64),
+        ) {
+            let mut session = SignSession::new(config());
+            for input in inputs {
+                let before = session.round();
+                match session.handle_message(input) {
+                    Ok(()) => prop_assert!(session.round() >= before),
+                    Err(Error::UnexpectedRound { expected, .. }) => {
+                        prop_assert_eq!(expected, before);
+                        prop_assert_eq!(session.round(), before);
+                    }
+                    Err(Error::InvalidPartyId(_)) | Err(Error::VerificationFailed(_)) => {
+                        prop_assert_eq!(session.round(), before);
+                    }
+                    Err(other) => prop_assert!(false, "undefined rejection: {}", other),
+                }
+                prop_assert!(session.round() <= 4);
+            }
+        }
+
+        /// Any delivery order within each round reaches the same complete
+        /// terminal state, after which nothing further is accepted
+        #[test]
+        fn model_valid_sequences_reach_consistent_terminal_state(
+            order0 in Just(vec![0usize, 1, 2]).prop_shuffle(),
+            order1 in Just(vec![0usize, 1, 2]).prop_shuffle(),
+            order2 in Just(vec![0usize, 1, 2]).prop_shuffle(),
+            order3 in Just(vec![0usize, 1, 2]).prop_shuffle(),
+        ) {
+            let mut session = SignSession::new(config());
+
+            for from in order0 {
+                prop_assert!(session.handle_message(key_check(from)).is_ok());
+            }
+            prop_assert_eq!(session.round(), 1);
+            for from in order1 {
+                prop_assert!(session.handle_message(round1(from)).is_ok());
+            }
+            prop_assert_eq!(session.round(), 2);
+            for from in order2 {
+                prop_assert!(session.handle_message(round2(from)).is_ok());
+            }
+            prop_assert_eq!(session.round(), 3);
+            for from in order3 {
+                prop_assert!(session.handle_message(partial(from)).is_ok());
+            }
+
+            prop_assert!(session.is_complete());
+            prop_assert_eq!(session.key_checks().len(), 3);
+            prop_assert_eq!(session.commitments().len(), 3);
+            prop_assert_eq!(session.reveals().len(), 3);
+            prop_assert_eq!(session.partials().len(), 3);
+            let rejected_after_completion = matches!(
+                session.handle_message(partial(0)),
+                Err(Error::UnexpectedRound { .. })
+            );
+            prop_assert!(rejected_after_completion);
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Multiplicative-to-additive (MtA) conversion for the DSG protocol
+//!
+//! Converts a product of two secret scalars held by different parties into
+//! additive shares: the sender inputs `a`, the receiver inputs `b`, and they
+//! end with `alpha` and `beta` such that `alpha + beta = a * b` without
+//! either side learning the other's input.
+//!
+//! Built as Gilboa's bit-decomposition multiplication over random OTs from
+//! the [`crate::oblivious`] module: `KAPPA` endemic base OTs run in the
+//! reversed direction are stretched by SoftSpokenOT into one random OT per
+//! bit of the receiver's input, and the sender then publishes one masked
+//! correction scalar per bit. Each instance converts two products that share
+//! the sender input `a` (the signing protocol needs `k_i * gamma_j` and
+//! `k_i * w_j` from the same pair), so the extension runs over `2 * XI`
+//! choice bits at once.
+//!
+//! Message flow, receiver initiating (four flights, two round trips):
+//! 1. receiver -> sender: base-OT public keys ([`receiver_init`])
+//! 2. sender -> receiver: base-OT key pairs ([`sender_respond`])
+//! 3. receiver -> sender: masked extension rows ([`receiver_extend`])
+//! 4. sender -> receiver: correction scalars ([`sender_finish`]), after
+//!    which [`receiver_finish`] yields the receiver's shares.
+
+use elliptic_curve::Field;
+use rand::{CryptoRng, Rng, RngCore};
+use x25519_dalek::{PublicKey, ReusableSecret};
+
+use crate::curve::{reduce_scalar_bytes, scalar_to_bytes, ThresholdCurve};
+use crate::oblivious::{soft_spoken::KAPPA, EndemicOT, SoftSpokenOT};
+use crate::{Error, Result};
+
+pub(crate) use crate::consts::XI;
+
+/// Hash one OT output string to a scalar
+fn string_to_scalar<C: ThresholdCurve>(out: &[u8; 32]) -> C::Scalar {
+    reduce_scalar_bytes::<C>(out)
+}
+
+/// Little-endian bit decomposition of a scalar (bit `j` has weight `2^j`)
+fn scalar_bits<C: ThresholdCurve>(value: &C::Scalar) -> Vec<bool> {
+    let bytes = scalar_to_bytes::<C>(value);
+    (0..XI)
+        .map(|j| (bytes[31 - j / 8] >> (j % 8)) & 1 == 1)
+        .collect()
+}
+
+/// Receiver state between flights 1 and 3
+pub(crate) struct MtaReceiverState {
+    base_secrets: Vec<ReusableSecret>,
+}
+
+/// Receiver state between flights 3 and 4
+pub(crate) struct MtaReceiverReady {
+    choices: Vec<bool>,
+    outputs: Vec<[u8; 32]>,
+}
+
+/// Sender state between flights 2 and 3
+pub(crate) struct MtaSenderState {
+    delta: Vec<bool>,
+    base_outputs: Vec<[u8; 32]>,
+}
+
+/// Sender output of flight 4
+///
+/// Each `alpha` is the sender's additive share of `a * b` for the
+/// corresponding receiver input; the correction scalars go back to the
+/// receiver, which needs them to derive its `beta` shares.
+pub(crate) struct MtaSenderOutput<C: ThresholdCurve> {
+    /// Additive share of `a * b_first`
+    pub(crate) alpha_first: C::Scalar,
+    /// Additive share of `a * b_second`
+    pub(crate) alpha_second: C::Scalar,
+    /// Masked correction scalars for the first product
+    pub(crate) corrections_first: Vec<[u8; 32]>,
+    /// Masked correction scalars for the second product
+    pub(crate) corrections_second: Vec<[u8; 32]>,
+}
+
+/// Receiver flight 1: generate base-OT sender keys
+///
+/// The MtA receiver plays base-OT *sender* so that it ends up knowing both
+/// extension seeds per base OT, as the reversed-direction extension needs.
+pub(crate) fn receiver_init<G: RngCore + CryptoRng>(
+    rng: &mut G,
+) -> Result<(MtaReceiverState, Vec<[u8; 32]>)> {
+    let ot = EndemicOT::new(KAPPA);
+    let (base_secrets, public_keys) = ot.sender_round1_with_rng(rng)?;
+    let keys = public_keys.iter().map(|k| *k.as_bytes()).collect();
+    Ok((MtaReceiverState { base_secrets }, keys))
+}
+
+/// Sender flight 2: answer the base OTs with a random correlation vector
+pub(crate) fn sender_respond<G: RngCore + CryptoRng>(
+    rng: &mut G,
+    receiver_base_keys: &[[u8; 32]],
+) -> Result<(MtaSenderState, Vec<[[u8; 32]; 2]>)> {
+    let delta: Vec<bool> = (0..KAPPA).map(|_| rng.gen()).collect();
+    let keys: Vec<PublicKey> = receiver_base_keys
+        .iter()
+        .map(|bytes| PublicKey::from(*bytes))
+        .collect();
+
+    let ot = EndemicOT::new(KAPPA);
+    let (base_outputs, key_pairs) = ot.receiver_round1_with_rng(rng, &keys, &delta)?;
+    Ok((
+        MtaSenderState {
+            delta,
+            base_outputs,
+        },
+        key_pairs,
+    ))
+}
+
+/// Receiver flight 3: run the OT extension over both input scalars
+///
+/// Choice bits are the bit decomposition of `b_first` followed by
+/// `b_second`; the masked rows go back to the sender.
+pub(crate) fn receiver_extend<C: ThresholdCurve>(
+    state: MtaReceiverState,
+    b_first: &C::Scalar,
+    b_second: &C::Scalar,
+    sender_key_pairs: &[[[u8; 32]; 2]],
+) -> Result<(MtaReceiverReady, Vec<Vec<u8>>)> {
+    let ot = EndemicOT::new(KAPPA);
+    let base_pairs = ot.sender_derive(&state.base_secrets, sender_key_pairs)?;
+
+    let mut choices = scalar_bits::<C>(b_first);
+    choices.extend(scalar_bits::<C>(b_second));
+
+    let extension = SoftSpokenOT::new(2 * XI);
+    let (u_rows, outputs) = extension.extend_receiver(&base_pairs, &choices)?;
+
+    Ok((MtaReceiverReady { choices, outputs }, u_rows))
+}
+
+/// Sender flight 4: derive shares and correction scalars for both products
+pub(crate) fn sender_finish<C: ThresholdCurve>(
+    state: MtaSenderState,
+    a: &C::Scalar,
+    u_rows: &[Vec<u8>],
+) -> Result<MtaSenderOutput<C>> {
+    let extension = SoftSpokenOT::new(2 * XI);
+    let pairs = extension.extend_sender(&state.delta, &state.base_outputs, u_rows)?;
+
+    let mut alphas = [C::Scalar::ZERO, C::Scalar::ZERO];
+    let mut corrections = [Vec::with_capacity(XI), Vec::with_capacity(XI)];
+
+    for half in 0..2 {
+        let mut power = *a;
+        for j in 0..XI {
+            let (s0, s1) = pairs[half * XI + j];
+            let r0 = string_to_scalar::<C>(&s0);
+            let r1 = string_to_scalar::<C>(&s1);
+
+            alphas[half] -= r0;
+            let correction = scalar_to_bytes::<C>(&(power + r0 - r1));
+            corrections[half].push(correction);
+            power += power;
+        }
+    }
+
+    let [corrections_first, corrections_second] = corrections;
+    Ok(MtaSenderOutput {
+        alpha_first: alphas[0],
+        alpha_second: alphas[1],
+        corrections_first,
+        corrections_second,
+    })
+}
+
+/// Receiver finish: apply the corrections to obtain both additive shares
+pub(crate) fn receiver_finish<C: ThresholdCurve>(
+    ready: MtaReceiverReady,
+    corrections_first: &[[u8; 32]],
+    corrections_second: &[[u8; 32]],
+) -> Result<(C::Scalar, C::Scalar)> {
+    if corrections_first.len() != XI || corrections_second.len() != XI {
+        return Err(Error::InvalidConfig(
+            "MtA correction vector has wrong length".into(),
+        ));
+    }
+
+    let mut betas = [C::Scalar::ZERO, C::Scalar::ZERO];
+    for (half, corrections) in [corrections_first, corrections_second].iter().enumerate() {
+        for j in 0..XI {
+            let index = half * XI + j;
+            let r = string_to_scalar::<C>(&ready.outputs[index]);
+            betas[half] += r;
+            if ready.choices[index] {
+                betas[half] += reduce_scalar_bytes::<C>(&corrections[j]);
+            }
+        }
+    }
+
+    Ok((betas[0], betas[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::Scalar;
+
+    /// Drive one full MtA instance locally and check both products convert
+    fn assert_mta_converts<C: ThresholdCurve>(
+        a: &C::Scalar,
+        b_first: &C::Scalar,
+        b_second: &C::Scalar,
+    ) {
+        let mut rng = rand::thread_rng();
+        let (receiver_state, base_keys) = receiver_init(&mut rng).unwrap();
+        let (sender_state, key_pairs) = sender_respond(&mut rng, &base_keys).unwrap();
+        let (ready, u_rows) =
+            receiver_extend::<C>(receiver_state, b_first, b_second, &key_pairs).unwrap();
+        let output = sender_finish::<C>(sender_state, a, &u_rows).unwrap();
+        let (beta_first, beta_second) =
+            receiver_finish::<C>(ready, &output.corrections_first, &output.corrections_second)
+                .unwrap();
+
+        assert_eq!(output.alpha_first + beta_first, *a * b_first);
+        assert_eq!(output.alpha_second + beta_second, *a * b_second);
+    }
+
+    #[test]
+    fn test_mta_converts_products() {
+        let mut rng = rand::thread_rng();
+        let a = Scalar::random(&mut rng);
+        let b_first = Scalar::random(&mut rng);
+        let b_second = Scalar::random(&mut rng);
+
+        assert_mta_converts::<k256::Secp256k1>(&a, &b_first, &b_second);
+    }
+
+    #[cfg(feature = "scheme-p256")]
+    #[test]
+    fn test_mta_converts_products_p256() {
+        let mut rng = rand::thread_rng();
+        let a = p256::Scalar::random(&mut rng);
+        let b_first = p256::Scalar::random(&mut rng);
+        let b_second = p256::Scalar::random(&mut rng);
+
+        assert_mta_converts::<p256::NistP256>(&a, &b_first, &b_second);
+    }
+
+    #[test]
+    fn test_mta_small_values() {
+        assert_mta_converts::<k256::Secp256k1>(
+            &Scalar::from(7u64),
+            &Scalar::from(6u64),
+            &Scalar::from(1u64),
+        );
+    }
+
+    #[test]
+    fn test_scalar_bits_roundtrip() {
+        let value = Scalar::from(0b1011u64);
+        let bits = scalar_bits::<k256::Secp256k1>(&value);
+        assert!(bits[0] && bits[1] && !bits[2] && bits[3]);
+
+        let mut reconstructed = Scalar::ZERO;
+        let mut power = Scalar::ONE;
+        for bit in bits {
+            if bit {
+                reconstructed += power;
+            }
+            power += power;
+        }
+        assert_eq!(reconstructed, value);
+    }
+}
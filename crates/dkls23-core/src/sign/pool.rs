@@ -0,0 +1,578 @@
+//! Pre-signature pool with background replenishment
+//!
+//! High-throughput signers cannot afford the MtA phase on every request,
+//! so the pool keeps a target number of pre-signatures on hand per key,
+//! refilling over a [`Relay`] in the background. Entries are persisted
+//! encrypted at rest — a pre-signature's nonce shares are as sensitive as
+//! the key share itself — and each is handed out exactly once, as a
+//! [`PreSignatureToken`], through a two-phase reservation: an entry is
+//! first journaled as reserved (an atomic rename), then consumed once the
+//! caller commits to signing. A crash in between leaves a dangling
+//! reservation the operator resolves explicitly, so a restart neither
+//! burns pre-signatures needlessly nor ever signs twice with one.
+//!
+//! Pre-signing is a multi-party ceremony, so every participant must run a
+//! pool over the same party set: session IDs are derived from the key, the
+//! party set and a monotonically increasing pool index, letting the
+//! parties' replenishers pair up without extra coordination. The index is
+//! persisted and never reused, since a session ID collision would replay a
+//! ceremony.
+
+use super::{pre_signature, PreSignature, PreSignatureToken};
+use crate::mpc::Relay;
+use crate::{Error, KeyShare, PartyId, Result, SessionConfig, SessionId};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::consts::{POOL_KEY_CONTEXT, POOL_SESSION_CONTEXT};
+
+/// Suffix marking an entry as reserved; the rename is the durable journal
+const RESERVED_SUFFIX: &str = ".reserved";
+
+/// A pre-signature pulled out of the pool but not yet committed to
+///
+/// Holds the only copy of the entry's token; resolve it through
+/// [`PreSignaturePool::consume`] or [`PreSignaturePool::release`]. If the
+/// process dies first, the on-disk journal entry survives as a dangling
+/// reservation for the operator to resolve.
+pub struct Reservation {
+    index: u64,
+    token: PreSignatureToken,
+}
+
+impl Reservation {
+    /// The pool index backing this reservation
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// The session the reserved pre-signature was generated under
+    pub fn session_id(&self) -> &SessionId {
+        self.token.session_id()
+    }
+}
+
+/// Persisted pool bookkeeping
+#[derive(Serialize, Deserialize, Default)]
+struct PoolState {
+    /// Next pool index; strictly increasing, never reused
+    next_index: u64,
+}
+
+/// Disk-backed pool of encrypted pre-signatures for one key
+pub struct PreSignaturePool {
+    dir: PathBuf,
+    /// At-rest encryption key derived from the pool secret and group key
+    cipher_key: [u8; 32],
+    /// Number of entries the replenisher keeps on hand
+    target: usize,
+    next_index: AtomicU64,
+    /// Serializes take/refill decisions about which files exist
+    guard: Mutex<()>,
+}
+
+impl PreSignaturePool {
+    /// Open (or initialize) the pool directory for a key
+    ///
+    /// The at-rest key is derived from `pool_secret` and the group public
+    /// key, so one secret can protect pools for many keys without letting
+    /// entries be swapped between them.
+    pub fn open(dir: &Path, pool_secret: &[u8], public_key: &[u8], target: usize) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| Error::Internal(format!("Cannot create pool directory: {}", e)))?;
+
+        let mut material = Vec::with_capacity(pool_secret.len() + public_key.len());
+        material.extend_from_slice(pool_secret);
+        material.extend_from_slice(public_key);
+        let cipher_key = blake3::derive_key(POOL_KEY_CONTEXT, &material);
+
+        let state = read_state(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            cipher_key,
+            target,
+            next_index: AtomicU64::new(state.next_index),
+            guard: Mutex::new(()),
+        })
+    }
+
+    /// Number of pre-signatures currently stored
+    pub fn len(&self) -> usize {
+        self.entry_paths().len()
+    }
+
+    /// Whether the pool has no pre-signatures stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of entries the replenisher aims to keep on hand
+    pub fn target(&self) -> usize {
+        self.target
+    }
+
+    /// Hand out the oldest stored pre-signature, exactly once
+    ///
+    /// Shorthand for [`reserve`](Self::reserve) immediately followed by
+    /// [`consume`](Self::consume); callers that may crash between picking
+    /// a pre-signature and finishing the online phase should use the
+    /// two-phase flow instead. Returns `None` when the pool is dry.
+    pub fn take(&self) -> Result<Option<PreSignatureToken>> {
+        match self.reserve()? {
+            Some(reservation) => self.consume(reservation).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Reserve the oldest stored pre-signature (phase one)
+    ///
+    /// The entry is atomically renamed to a reserved journal entry before
+    /// its shares are returned, so a crash leaves a durable record that
+    /// the pre-signature may have been touched. Follow up with
+    /// [`consume`](Self::consume) once committed to signing, or
+    /// [`release`](Self::release) if signing never started. Returns
+    /// `None` when the pool is dry.
+    pub fn reserve(&self) -> Result<Option<Reservation>> {
+        let _guard = self.lock();
+        let Some(path) = self.entry_paths().into_iter().next() else {
+            return Ok(None);
+        };
+        let Some(index) = entry_index(&path) else {
+            return Err(Error::Internal(format!(
+                "Unparseable pool entry name: {}",
+                path.display()
+            )));
+        };
+
+        let reserved = reserved_path(&path);
+        std::fs::rename(&path, &reserved)
+            .map_err(|e| Error::Internal(format!("Cannot reserve pool entry: {}", e)))?;
+
+        let sealed = std::fs::read(&reserved)
+            .map_err(|e| Error::Internal(format!("Cannot read pool entry: {}", e)))?;
+        let pre_sig = self.decrypt(&sealed)?;
+        Ok(Some(Reservation {
+            index,
+            token: PreSignatureToken::new(pre_sig),
+        }))
+    }
+
+    /// Commit to a reserved pre-signature (phase two)
+    ///
+    /// Deletes the journal entry and hands over the token; after this the
+    /// pre-signature can never be handed out again, so a crash later in
+    /// the online phase burns it rather than risking a second use.
+    pub fn consume(&self, reservation: Reservation) -> Result<PreSignatureToken> {
+        let _guard = self.lock();
+        let path = self.reserved_entry_path(reservation.index);
+        std::fs::remove_file(&path)
+            .map_err(|e| Error::Internal(format!("Cannot consume pool entry: {}", e)))?;
+        Ok(reservation.token)
+    }
+
+    /// Return a reserved pre-signature to the pool unused
+    ///
+    /// Only sound if no share of the reservation ever left this process —
+    /// the token is dropped (and its nonce shares zeroized) here, so the
+    /// caller cannot release and sign.
+    pub fn release(&self, reservation: Reservation) -> Result<()> {
+        let _guard = self.lock();
+        let reserved = self.reserved_entry_path(reservation.index);
+        std::fs::rename(&reserved, self.entry_path(reservation.index))
+            .map_err(|e| Error::Internal(format!("Cannot release pool entry: {}", e)))?;
+        Ok(())
+    }
+
+    /// Indices of reservations left dangling, e.g. by a crash
+    ///
+    /// The pool cannot know whether a dangling reservation's shares were
+    /// used before the crash, so it never resolves them on its own: the
+    /// operator decides per entry between [`release_outstanding`]
+    /// (Self::release_outstanding) (signing provably never started) and
+    /// [`discard_outstanding`](Self::discard_outstanding) (when in doubt).
+    pub fn outstanding(&self) -> Vec<u64> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut indices: Vec<u64> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.ends_with(RESERVED_SUFFIX))
+            })
+            .filter_map(|path| entry_index(&path))
+            .collect();
+        indices.sort_unstable();
+        indices
+    }
+
+    /// Return a dangling reservation to the pool
+    pub fn release_outstanding(&self, index: u64) -> Result<()> {
+        let _guard = self.lock();
+        std::fs::rename(self.reserved_entry_path(index), self.entry_path(index))
+            .map_err(|e| Error::Internal(format!("Cannot release pool entry: {}", e)))
+    }
+
+    /// Permanently discard a dangling reservation
+    pub fn discard_outstanding(&self, index: u64) -> Result<()> {
+        let _guard = self.lock();
+        std::fs::remove_file(self.reserved_entry_path(index))
+            .map_err(|e| Error::Internal(format!("Cannot discard pool entry: {}", e)))
+    }
+
+    /// Run pre-signing ceremonies until the pool holds `target` entries
+    ///
+    /// Every party in `parties` must be running its own refill over the
+    /// same key and party set; the derived session IDs pair the
+    /// ceremonies up. Returns how many entries were generated.
+    pub async fn refill<R: Relay>(
+        &self,
+        key_share: &KeyShare,
+        parties: &[PartyId],
+        relay: &R,
+    ) -> Result<usize> {
+        let mut generated = 0;
+        while self.len() < self.target {
+            let index = self.next_index.fetch_add(1, Ordering::SeqCst);
+            self.persist_state()?;
+
+            let session_id = pool_session_id(&key_share.public_key, parties, index);
+            let config = SessionConfig {
+                session_id,
+                n_parties: parties.len(),
+                threshold: key_share.threshold,
+                party_id: key_share.party_id,
+                parties: parties.to_vec(),
+            };
+
+            let pre_sig = pre_signature(key_share, &config, relay).await?;
+            self.store(index, &pre_sig)?;
+            generated += 1;
+        }
+
+        if generated > 0 {
+            info!(
+                generated,
+                stored = self.len(),
+                "Pre-signature pool replenished"
+            );
+        }
+        Ok(generated)
+    }
+
+    /// Keep the pool topped up from a background task
+    ///
+    /// Checks the level every `interval` and refills as needed; refill
+    /// errors are logged and retried on the next tick, since a peer being
+    /// offline is an expected condition.
+    pub fn spawn_replenisher<R: Relay + 'static>(
+        self: &Arc<Self>,
+        key_share: KeyShare,
+        parties: Vec<PartyId>,
+        relay: R,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let pool = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = pool.refill(&key_share, &parties, &relay).await {
+                    warn!("Pre-signature refill failed: {}", e);
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ()> {
+        self.guard.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Path of the stored entry for a pool index
+    fn entry_path(&self, index: u64) -> PathBuf {
+        self.dir.join(format!("presig-{:016x}.enc", index))
+    }
+
+    /// Path of the reserved journal entry for a pool index
+    fn reserved_entry_path(&self, index: u64) -> PathBuf {
+        reserved_path(&self.entry_path(index))
+    }
+
+    /// Paths of stored entries, oldest index first
+    fn entry_paths(&self) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("presig-") && name.ends_with(".enc"))
+            })
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    fn store(&self, index: u64, pre_sig: &PreSignature) -> Result<()> {
+        let _guard = self.lock();
+        let plaintext =
+            serde_json::to_vec(pre_sig).map_err(|e| Error::Serialization(e.to_string()))?;
+
+        let cipher = ChaCha20Poly1305::new((&self.cipher_key).into());
+        let mut nonce = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| Error::Crypto("Pool entry encryption failed".into()))?;
+
+        let mut sealed = Vec::with_capacity(12 + ciphertext.len());
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+
+        std::fs::write(self.entry_path(index), &sealed)
+            .map_err(|e| Error::Internal(format!("Cannot write pool entry: {}", e)))?;
+        Ok(())
+    }
+
+    fn decrypt(&self, sealed: &[u8]) -> Result<PreSignature> {
+        if sealed.len() < 12 {
+            return Err(Error::Crypto("Pool entry too short".into()));
+        }
+        let cipher = ChaCha20Poly1305::new((&self.cipher_key).into());
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&sealed[..12]), &sealed[12..])
+            .map_err(|_| Error::Crypto("Pool entry decryption failed (wrong pool secret?)".into()))?;
+        serde_json::from_slice(&plaintext).map_err(|e| Error::Deserialization(e.to_string()))
+    }
+
+    fn persist_state(&self) -> Result<()> {
+        let state = PoolState {
+            next_index: self.next_index.load(Ordering::SeqCst),
+        };
+        let json =
+            serde_json::to_string(&state).map_err(|e| Error::Serialization(e.to_string()))?;
+        std::fs::write(self.dir.join("pool.state.json"), json)
+            .map_err(|e| Error::Internal(format!("Cannot persist pool state: {}", e)))
+    }
+}
+
+/// Reserved journal path for a stored entry path
+fn reserved_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(RESERVED_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// Parse the pool index out of an entry or journal file name
+fn entry_index(path: &Path) -> Option<u64> {
+    let name = path.file_name()?.to_str()?;
+    let hex = name.strip_prefix("presig-")?.split('.').next()?;
+    u64::from_str_radix(hex, 16).ok()
+}
+
+fn read_state(dir: &Path) -> Result<PoolState> {
+    let path = dir.join("pool.state.json");
+    if !path.exists() {
+        return Ok(PoolState::default());
+    }
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| Error::Internal(format!("Cannot read pool state: {}", e)))?;
+    serde_json::from_str(&json).map_err(|e| Error::Deserialization(e.to_string()))
+}
+
+/// Session ID for pool entry `index` over the given key and party set
+fn pool_session_id(public_key: &[u8], parties: &[PartyId], index: u64) -> SessionId {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(POOL_SESSION_CONTEXT.as_bytes());
+    hasher.update(public_key);
+    for &party in parties {
+        hasher.update(&(party as u64).to_be_bytes());
+    }
+    hasher.update(&index.to_be_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keygen::run_dkg;
+    use crate::mpc::MemoryRelay;
+
+    /// Generate a 2-of-2 key over an in-memory relay
+    async fn two_party_shares(relay: &MemoryRelay) -> (KeyShare, KeyShare) {
+        let session_id = [0x99u8; 32];
+        let configs: Vec<SessionConfig> = (0..2)
+            .map(|party_id| SessionConfig {
+                session_id,
+                n_parties: 2,
+                threshold: 2,
+                party_id,
+                parties: vec![0, 1],
+            })
+            .collect();
+        let (share0, share1) = tokio::join!(
+            run_dkg(&configs[0], relay),
+            run_dkg(&configs[1], relay),
+        );
+        (share0.unwrap(), share1.unwrap())
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("presig-pool-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_paired_refill_and_exactly_once_handout() {
+        let dir0 = temp_dir("refill-0");
+        let dir1 = temp_dir("refill-1");
+        let relay = MemoryRelay::new();
+        let (share0, share1) = two_party_shares(&relay).await;
+
+        let pool0 = PreSignaturePool::open(&dir0, b"pool secret", &share0.public_key, 2).unwrap();
+        let pool1 = PreSignaturePool::open(&dir1, b"pool secret", &share1.public_key, 2).unwrap();
+
+        // Both parties refill concurrently; the derived session IDs pair
+        // their ceremonies up without any extra coordination
+        let (n0, n1) = tokio::join!(
+            pool0.refill(&share0, &[0, 1], &relay),
+            pool1.refill(&share1, &[0, 1], &relay),
+        );
+        assert_eq!(n0.unwrap(), 2);
+        assert_eq!(n1.unwrap(), 2);
+
+        // The oldest entry on each side belongs to the same ceremony
+        let token0 = pool0.take().unwrap().unwrap();
+        let token1 = pool1.take().unwrap().unwrap();
+        assert_eq!(token0.session_id(), token1.session_id());
+
+        // Each remaining entry comes out exactly once, is a distinct
+        // ceremony, and the pools then report dry
+        let next0 = pool0.take().unwrap().unwrap();
+        let next1 = pool1.take().unwrap().unwrap();
+        assert_ne!(next0.session_id(), token0.session_id());
+        assert_eq!(next0.session_id(), next1.session_id());
+        assert!(pool0.take().unwrap().is_none());
+        assert!(pool1.take().unwrap().is_none());
+
+        // Refilling after a drain never reuses consumed indices
+        let (r0, r1) = tokio::join!(
+            pool0.refill(&share0, &[0, 1], &relay),
+            pool1.refill(&share1, &[0, 1], &relay),
+        );
+        assert_eq!(r0.unwrap(), 2);
+        assert_eq!(r1.unwrap(), 2);
+        let fresh = pool0.take().unwrap().unwrap();
+        assert_ne!(fresh.session_id(), token0.session_id());
+        assert_ne!(fresh.session_id(), next0.session_id());
+
+        let _ = std::fs::remove_dir_all(&dir0);
+        let _ = std::fs::remove_dir_all(&dir1);
+    }
+
+    #[tokio::test]
+    async fn test_two_phase_reservation_and_crash_recovery() {
+        let dir0 = temp_dir("twophase-0");
+        let dir1 = temp_dir("twophase-1");
+        let relay = MemoryRelay::new();
+        let (share0, share1) = two_party_shares(&relay).await;
+
+        let pool = PreSignaturePool::open(&dir0, b"pool secret", &share0.public_key, 2).unwrap();
+        let peer = PreSignaturePool::open(&dir1, b"pool secret", &share1.public_key, 2).unwrap();
+        let (n0, n1) = tokio::join!(
+            pool.refill(&share0, &[0, 1], &relay),
+            peer.refill(&share1, &[0, 1], &relay),
+        );
+        n0.unwrap();
+        n1.unwrap();
+
+        // Reserving moves an entry out of the available set but keeps it
+        // journaled; releasing puts it back untouched
+        let reservation = pool.reserve().unwrap().unwrap();
+        let index = reservation.index();
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.outstanding(), vec![index]);
+        pool.release(reservation).unwrap();
+        assert_eq!(pool.len(), 2);
+        assert!(pool.outstanding().is_empty());
+
+        // Consuming is the point of no return
+        let reservation = pool.reserve().unwrap().unwrap();
+        let session = *reservation.session_id();
+        let token = pool.consume(reservation).unwrap();
+        assert_eq!(*token.session_id(), session);
+        assert_eq!(pool.len(), 1);
+        assert!(pool.outstanding().is_empty());
+
+        // A reservation dangling across a restart is surfaced, not
+        // silently resolved
+        let dangling = pool.reserve().unwrap().unwrap();
+        let dangling_index = dangling.index();
+        drop(dangling); // process dies holding the reservation
+        let reopened =
+            PreSignaturePool::open(&dir0, b"pool secret", &share0.public_key, 2).unwrap();
+        assert_eq!(reopened.len(), 0);
+        assert_eq!(reopened.outstanding(), vec![dangling_index]);
+
+        // The operator can put it back or discard it for good
+        reopened.release_outstanding(dangling_index).unwrap();
+        assert_eq!(reopened.len(), 1);
+        let reservation = reopened.reserve().unwrap().unwrap();
+        drop(reservation);
+        reopened.discard_outstanding(dangling_index).unwrap();
+        assert_eq!(reopened.len(), 0);
+        assert!(reopened.outstanding().is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir0);
+        let _ = std::fs::remove_dir_all(&dir1);
+    }
+
+    #[tokio::test]
+    async fn test_entries_are_encrypted_and_survive_reopen() {
+        let dir0 = temp_dir("reopen-0");
+        let dir1 = temp_dir("reopen-1");
+        let relay = MemoryRelay::new();
+        let (share0, share1) = two_party_shares(&relay).await;
+
+        {
+            let pool0 =
+                PreSignaturePool::open(&dir0, b"pool secret", &share0.public_key, 1).unwrap();
+            let pool1 =
+                PreSignaturePool::open(&dir1, b"pool secret", &share1.public_key, 1).unwrap();
+            let (n0, n1) = tokio::join!(
+                pool0.refill(&share0, &[0, 1], &relay),
+                pool1.refill(&share1, &[0, 1], &relay),
+            );
+            n0.unwrap();
+            n1.unwrap();
+        }
+
+        // The wrong pool secret cannot open persisted entries
+        let wrong = PreSignaturePool::open(&dir0, b"other secret", &share0.public_key, 1).unwrap();
+        assert_eq!(wrong.len(), 1);
+        assert!(matches!(wrong.take(), Err(Error::Crypto(_))));
+        // The failed attempt is journaled like any other reservation
+        assert_eq!(wrong.outstanding().len(), 1);
+
+        // The right secret reopens a persisted pool intact
+        let reopened =
+            PreSignaturePool::open(&dir1, b"pool secret", &share1.public_key, 1).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert!(reopened.take().unwrap().is_some());
+
+        let _ = std::fs::remove_dir_all(&dir0);
+        let _ = std::fs::remove_dir_all(&dir1);
+    }
+}
@@ -0,0 +1,286 @@
+//! Fair scheduling of concurrent signing ceremonies
+//!
+//! Concurrent [`run_dsg`](super::run_dsg) calls for the same key contend
+//! unpredictably over the relay and drain any presignature pool in bursts.
+//! The scheduler bounds how many ceremonies may run per key at once and
+//! queues the rest FIFO, with a per-requester quota so one caller cannot
+//! occupy the whole queue. Daemon frontends take a [`SignPermit`] before
+//! starting a ceremony and drop it when the ceremony finishes.
+
+use crate::{Error, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
+/// Limits applied per key by a [`SignScheduler`]
+#[derive(Debug, Clone, Copy)]
+pub struct SchedulerLimits {
+    /// Ceremonies allowed to run concurrently for one key
+    pub max_concurrent_per_key: usize,
+    /// Queued ceremonies allowed per requester for one key
+    pub max_pending_per_requester: usize,
+}
+
+impl Default for SchedulerLimits {
+    fn default() -> Self {
+        Self {
+            max_concurrent_per_key: 1,
+            max_pending_per_requester: 32,
+        }
+    }
+}
+
+/// Queue depth for one key, for metrics endpoints
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyQueueMetrics {
+    /// Public key (hex)
+    pub public_key: String,
+    /// Ceremonies currently running
+    pub running: usize,
+    /// Ceremonies waiting in the queue
+    pub queued: usize,
+}
+
+/// A ceremony waiting for its turn on a key
+struct Waiter {
+    requester: String,
+    ready: oneshot::Sender<()>,
+}
+
+#[derive(Default)]
+struct KeyState {
+    running: usize,
+    queue: VecDeque<Waiter>,
+    pending_by_requester: BTreeMap<String, usize>,
+}
+
+struct Inner {
+    limits: SchedulerLimits,
+    keys: Mutex<HashMap<Vec<u8>, KeyState>>,
+}
+
+/// FIFO scheduler bounding concurrent ceremonies per key
+#[derive(Clone)]
+pub struct SignScheduler {
+    inner: Arc<Inner>,
+}
+
+impl SignScheduler {
+    /// Create a scheduler with the given per-key limits
+    pub fn new(limits: SchedulerLimits) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                limits,
+                keys: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Wait for a slot to run a ceremony for `public_key`
+    ///
+    /// Returns immediately while the key is under its concurrency bound,
+    /// otherwise queues FIFO behind earlier requests. Fails with
+    /// [`Error::QueueFull`] when `requester` already has its quota of
+    /// ceremonies queued for this key.
+    pub async fn acquire(&self, public_key: &[u8], requester: &str) -> Result<SignPermit> {
+        let waiting = {
+            let mut keys = lock_keys(&self.inner);
+            let state = keys.entry(public_key.to_vec()).or_default();
+
+            if state.running < self.inner.limits.max_concurrent_per_key
+                && state.queue.is_empty()
+            {
+                state.running += 1;
+                None
+            } else {
+                let pending = state
+                    .pending_by_requester
+                    .entry(requester.to_string())
+                    .or_insert(0);
+                if *pending >= self.inner.limits.max_pending_per_requester {
+                    return Err(Error::QueueFull(format!(
+                        "Requester {} already has {} ceremonies queued for this key",
+                        requester, pending
+                    )));
+                }
+                *pending += 1;
+                let (ready, waiting) = oneshot::channel();
+                state.queue.push_back(Waiter {
+                    requester: requester.to_string(),
+                    ready,
+                });
+                Some(waiting)
+            }
+        };
+
+        if let Some(waiting) = waiting {
+            waiting
+                .await
+                .map_err(|_| Error::Internal("Scheduler dropped a queued ceremony".into()))?;
+        }
+
+        Ok(SignPermit {
+            inner: self.inner.clone(),
+            public_key: public_key.to_vec(),
+        })
+    }
+
+    /// Number of ceremonies queued (not running) for a key
+    pub fn queue_depth(&self, public_key: &[u8]) -> usize {
+        lock_keys(&self.inner)
+            .get(public_key)
+            .map(|state| state.queue.len())
+            .unwrap_or(0)
+    }
+
+    /// Per-key occupancy snapshot, sorted by key for stable output
+    pub fn metrics(&self) -> Vec<KeyQueueMetrics> {
+        let keys = lock_keys(&self.inner);
+        let mut metrics: Vec<KeyQueueMetrics> = keys
+            .iter()
+            .map(|(key, state)| KeyQueueMetrics {
+                public_key: hex::encode(key),
+                running: state.running,
+                queued: state.queue.len(),
+            })
+            .collect();
+        metrics.sort_by(|a, b| a.public_key.cmp(&b.public_key));
+        metrics
+    }
+}
+
+impl Default for SignScheduler {
+    fn default() -> Self {
+        Self::new(SchedulerLimits::default())
+    }
+}
+
+/// Permission to run one ceremony; dropping it hands the slot to the next
+/// queued request
+pub struct SignPermit {
+    inner: Arc<Inner>,
+    public_key: Vec<u8>,
+}
+
+impl Drop for SignPermit {
+    fn drop(&mut self) {
+        release(&self.inner, &self.public_key);
+    }
+}
+
+fn lock_keys(inner: &Inner) -> std::sync::MutexGuard<'_, HashMap<Vec<u8>, KeyState>> {
+    match inner.keys.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Free a slot and wake the next waiter in FIFO order
+fn release(inner: &Inner, public_key: &[u8]) {
+    let mut keys = lock_keys(inner);
+    let Some(state) = keys.get_mut(public_key) else {
+        return;
+    };
+    state.running = state.running.saturating_sub(1);
+
+    while let Some(waiter) = state.queue.pop_front() {
+        if let Some(pending) = state.pending_by_requester.get_mut(&waiter.requester) {
+            *pending -= 1;
+            if *pending == 0 {
+                state.pending_by_requester.remove(&waiter.requester);
+            }
+        }
+        // A closed channel means the waiter gave up; try the next in line
+        if waiter.ready.send(()).is_ok() {
+            state.running += 1;
+            break;
+        }
+    }
+
+    if state.running == 0 && state.queue.is_empty() {
+        keys.remove(public_key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_queued_ceremonies_run_fifo() {
+        let scheduler = SignScheduler::new(SchedulerLimits {
+            max_concurrent_per_key: 1,
+            max_pending_per_requester: 8,
+        });
+        let key = b"key".to_vec();
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let first = scheduler.acquire(&key, "holder").await.unwrap();
+
+        let mut handles = Vec::new();
+        for name in ["a", "b", "c"] {
+            let scheduler = scheduler.clone();
+            let key = key.clone();
+            let order = order.clone();
+            handles.push(tokio::spawn(async move {
+                let permit = scheduler.acquire(&key, name).await.unwrap();
+                order.lock().unwrap().push(name);
+                drop(permit);
+            }));
+            // Let each task enqueue before the next, so queue order is known
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        assert_eq!(scheduler.queue_depth(&key), 3);
+        drop(first);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(*order.lock().unwrap(), vec!["a", "b", "c"]);
+        // Fully drained queues are cleaned up
+        assert!(scheduler.metrics().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_per_requester_quota_rejects_excess() {
+        let scheduler = SignScheduler::new(SchedulerLimits {
+            max_concurrent_per_key: 1,
+            max_pending_per_requester: 1,
+        });
+        let key = b"key".to_vec();
+
+        let held = scheduler.acquire(&key, "alice").await.unwrap();
+
+        // First queued request for alice fits the quota...
+        let queued = tokio::spawn({
+            let scheduler = scheduler.clone();
+            let key = key.clone();
+            async move { scheduler.acquire(&key, "alice").await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        // ...the second is rejected up front instead of queueing forever
+        match scheduler.acquire(&key, "alice").await {
+            Ok(_) => panic!("quota was not enforced"),
+            Err(err) => assert!(matches!(err, Error::QueueFull(_))),
+        }
+
+        drop(held);
+        queued.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_distinct_keys_do_not_contend() {
+        let scheduler = SignScheduler::default();
+
+        let _first = scheduler.acquire(b"key1", "alice").await.unwrap();
+        // A different key has its own bound and proceeds immediately
+        let _second = scheduler.acquire(b"key2", "alice").await.unwrap();
+
+        let metrics = scheduler.metrics();
+        assert_eq!(metrics.len(), 2);
+        assert!(metrics.iter().all(|m| m.running == 1 && m.queued == 0));
+    }
+}
@@ -0,0 +1,472 @@
+//! BIP340 Schnorr signing from the same secp256k1 key shares
+//!
+//! Taproot key-path spends verify BIP340 Schnorr signatures, which the
+//! ECDSA flow cannot produce. This module signs with the shares a DKG
+//! (or import) already produced, over the same relay: a commit round for
+//! nonce points (so no party can pick its nonce as a function of the
+//! others' and bias the aggregate), a reveal round, then a partial
+//! signature round. Schnorr's linearity makes the combine a plain sum —
+//! no MtA conversion is needed, so this is both simpler and cheaper than
+//! the ECDSA ceremony.
+//!
+//! BIP340 fixes both the public key and the nonce point to even Y. The
+//! aggregate R and the group key P are visible to every signer, so when
+//! either has odd Y each party negates its local nonce or key share and
+//! all adjustments cancel in the sum.
+
+use crate::mpc::Relay;
+use crate::{Error, KeyShare, PartyId, Result, SessionId};
+use k256::{
+    elliptic_curve::{
+        bigint::U256,
+        ops::Reduce,
+        sec1::{FromEncodedPoint, ToEncodedPoint},
+        Field, Group, PrimeField,
+    },
+    AffinePoint, ProjectivePoint, Scalar,
+};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument};
+
+/// BIP340 Schnorr signature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchnorrSignature {
+    /// X coordinate of the (even-Y) nonce point
+    pub r: [u8; 32],
+    /// Signature scalar
+    pub s: [u8; 32],
+    /// Digest of the broadcast transcript that produced this signature;
+    /// zero for signatures assembled outside a ceremony
+    #[serde(default)]
+    pub transcript_digest: [u8; 32],
+}
+
+impl SchnorrSignature {
+    /// The 64-byte wire form Bitcoin consensus expects (r || s)
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..].copy_from_slice(&self.s);
+        bytes
+    }
+}
+
+/// The x-only form of a key share's public key, as Taproot outputs use
+pub fn x_only_public_key(key_share: &KeyShare) -> Result<[u8; 32]> {
+    let key: &[u8] = &key_share.public_key;
+    if key.len() != 33 {
+        return Err(Error::Crypto(format!(
+            "Expected a 33-byte compressed public key, got {} bytes",
+            key.len()
+        )));
+    }
+    Ok(key[1..].try_into().expect("checked length"))
+}
+
+/// Run the threshold BIP340 Schnorr signing protocol
+///
+/// Produces a signature over `message` (for Taproot, a BIP341 sighash)
+/// that verifies under the x-only form of the group public key. Rounds:
+/// a round-0 key-material check as in the ECDSA flow, nonce commitments
+/// on round 1, nonce reveals on round 2, partial scalars on round 3.
+#[instrument(skip(key_share, relay))]
+pub async fn run_schnorr_dsg<R: Relay>(
+    key_share: &KeyShare,
+    message: &[u8; 32],
+    parties: &[PartyId],
+    relay: &R,
+) -> Result<SchnorrSignature> {
+    run_schnorr_dsg_with_rng(key_share, message, parties, relay, &mut OsRng).await
+}
+
+/// [`run_schnorr_dsg`] drawing the nonce from a caller-supplied entropy
+/// source
+pub async fn run_schnorr_dsg_with_rng<R: Relay, G: RngCore + CryptoRng>(
+    key_share: &KeyShare,
+    message: &[u8; 32],
+    parties: &[PartyId],
+    relay: &R,
+    rng: &mut G,
+) -> Result<SchnorrSignature> {
+    info!(
+        party_id = key_share.party_id,
+        participants = ?parties,
+        "Starting Schnorr DSG"
+    );
+
+    // Schnorr mode reuses secp256k1 shares; other curves have their own
+    // signing flows
+    crate::scheme::ensure_supported(key_share.scheme)?;
+    if key_share.scheme != crate::scheme::SchemeId::Secp256k1 {
+        return Err(Error::UnsupportedScheme(key_share.scheme));
+    }
+    if crate::PROTOCOL_VERSION < key_share.min_protocol_version {
+        return Err(Error::ProtocolVersionTooOld {
+            required: key_share.min_protocol_version,
+            actual: crate::PROTOCOL_VERSION,
+        });
+    }
+    if parties.len() < key_share.threshold {
+        return Err(Error::ThresholdNotMet {
+            required: key_share.threshold,
+            actual: parties.len(),
+        });
+    }
+    if !parties.contains(&key_share.party_id) {
+        return Err(Error::InvalidPartyId(key_share.party_id));
+    }
+
+    // Domain-separated from the ECDSA session derivation so a concurrent
+    // ECDSA signing of the same message cannot collide
+    let session_id = derive_session_id(&key_share.public_key, parties, message);
+
+    // Round 0: abort immediately on divergent key material
+    let key_check = super::DsgKeyCheckMessage {
+        party_id: key_share.party_id,
+        key_fingerprint: key_share.key_fingerprint(),
+    };
+    relay.broadcast(&session_id, 0, &key_check).await?;
+    let checks = relay
+        .collect_broadcasts::<super::DsgKeyCheckMessage>(&session_id, 0, parties.len())
+        .await?;
+    for check in &checks {
+        if check.key_fingerprint != key_check.key_fingerprint {
+            return Err(Error::KeyMismatch {
+                party: check.party_id,
+                fingerprint: hex::encode(check.key_fingerprint),
+            });
+        }
+    }
+
+    let mut transcript =
+        crate::transcript::Transcript::new(crate::transcript::SCHNORR_LABEL, &session_id);
+
+    // Round 1: commit to the nonce point
+    let k_i = Scalar::random(&mut *rng);
+    let r_i = (ProjectivePoint::GENERATOR * k_i)
+        .to_affine()
+        .to_encoded_point(true)
+        .as_bytes()
+        .to_vec();
+    let commit_msg = super::SchnorrCommitMessage {
+        party_id: key_share.party_id,
+        commitment: nonce_commitment(&session_id, key_share.party_id, &r_i),
+    };
+    relay.broadcast(&session_id, 1, &commit_msg).await?;
+    let commits = relay
+        .collect_broadcasts::<super::SchnorrCommitMessage>(&session_id, 1, parties.len())
+        .await?;
+    for msg in &commits {
+        transcript.append_message(1, msg.party_id, msg)?;
+    }
+
+    // Round 2: reveal nonce points and check them against the commitments
+    let reveal_msg = super::SchnorrRevealMessage {
+        party_id: key_share.party_id,
+        r_point: r_i.clone(),
+    };
+    relay.broadcast(&session_id, 2, &reveal_msg).await?;
+    let reveals = relay
+        .collect_broadcasts::<super::SchnorrRevealMessage>(&session_id, 2, parties.len())
+        .await?;
+
+    let mut r_points = Vec::with_capacity(reveals.len());
+    for msg in &reveals {
+        let commit = commits
+            .iter()
+            .find(|c| c.party_id == msg.party_id)
+            .ok_or(Error::InvalidPartyId(msg.party_id))?;
+        if commit.commitment != nonce_commitment(&session_id, msg.party_id, &msg.r_point) {
+            return Err(Error::MaliciousParty(msg.party_id));
+        }
+        r_points.push((msg.party_id, decode_point(&msg.r_point)?));
+        transcript.append_message(2, msg.party_id, msg)?;
+    }
+
+    let r_agg: ProjectivePoint = r_points.iter().map(|(_, point)| point).sum();
+    let r_affine = r_agg.to_affine();
+    if bool::from(r_agg.is_identity()) {
+        return Err(Error::Crypto("Aggregate nonce is the identity".into()));
+    }
+
+    // BIP340 requires an even-Y nonce point; if the aggregate came out
+    // odd, every party negates its nonce share (and the per-party points
+    // used for blame) so the sum lands on the even lift
+    let r_is_odd = has_odd_y(&r_affine);
+    let k_adjusted = if r_is_odd { -k_i } else { k_i };
+    let r_x: [u8; 32] = r_affine.to_encoded_point(true).as_bytes()[1..]
+        .try_into()
+        .expect("compressed point is 33 bytes");
+
+    // Same treatment for the group key: an odd-Y public key means every
+    // party signs for the negated secret, whose key is the even lift
+    let p_x = x_only_public_key(key_share)?;
+    let p_is_odd = key_share.public_key[0] == 0x03;
+
+    let mut challenge_input = Vec::with_capacity(96);
+    challenge_input.extend_from_slice(&r_x);
+    challenge_input.extend_from_slice(&p_x);
+    challenge_input.extend_from_slice(message);
+    let e = <Scalar as Reduce<U256>>::reduce_bytes(
+        &crate::hashing::tagged_hash("BIP0340/challenge", &challenge_input).into(),
+    );
+
+    // Additive share of the secret for this signing set
+    let lambda_i =
+        super::dsg::compute_lagrange_coefficient::<k256::Secp256k1>(key_share.party_id, parties);
+    let mut d_i = key_share.secret_share * lambda_i;
+    if p_is_odd {
+        d_i = -d_i;
+    }
+    let s_i = k_adjusted + e * d_i;
+
+    // Round 3: exchange partial scalars and combine
+    let partial_msg = super::SchnorrPartialMessage {
+        party_id: key_share.party_id,
+        s_share: s_i.to_bytes().into(),
+    };
+    relay.broadcast(&session_id, 3, &partial_msg).await?;
+    let partials = relay
+        .collect_broadcasts::<super::SchnorrPartialMessage>(&session_id, 3, parties.len())
+        .await?;
+
+    let mut s = Scalar::ZERO;
+    for msg in &partials {
+        transcript.append_message(3, msg.party_id, msg)?;
+        s += <Scalar as Reduce<U256>>::reduce_bytes(&msg.s_share.into());
+    }
+
+    let signature = SchnorrSignature {
+        r: r_x,
+        s: s.to_bytes().into(),
+        transcript_digest: transcript.digest(),
+    };
+
+    // An invalid combination means someone sent a bogus partial; check
+    // each one against its public data and name the culprit
+    if verify_bip340(&p_x, message, &signature).is_err() {
+        for msg in &partials {
+            let s_j = <Scalar as Reduce<U256>>::reduce_bytes(&msg.s_share.into());
+            let (_, r_j) = r_points
+                .iter()
+                .find(|(party, _)| *party == msg.party_id)
+                .ok_or(Error::InvalidPartyId(msg.party_id))?;
+            let r_expected = if r_is_odd { -*r_j } else { *r_j };
+
+            let public_share = key_share
+                .public_shares
+                .get(msg.party_id)
+                .ok_or(Error::InvalidPartyId(msg.party_id))?;
+            let lambda_j =
+                super::dsg::compute_lagrange_coefficient::<k256::Secp256k1>(msg.party_id, parties);
+            let mut d_point = decode_point(public_share)? * lambda_j;
+            if p_is_odd {
+                d_point = -d_point;
+            }
+
+            if ProjectivePoint::GENERATOR * s_j != r_expected + d_point * e {
+                return Err(Error::MaliciousParty(msg.party_id));
+            }
+        }
+        return Err(Error::InvalidSignature);
+    }
+
+    debug!(r = hex::encode(signature.r), "Schnorr DSG completed");
+    Ok(signature)
+}
+
+/// Verify a BIP340 signature against an x-only public key
+///
+/// Implements the verification equation from the BIP: lift both x-only
+/// values to even-Y points, recompute the challenge and require
+/// `s*G - e*P` to land exactly on R.
+pub fn verify_bip340(
+    public_key_x: &[u8; 32],
+    message: &[u8; 32],
+    signature: &SchnorrSignature,
+) -> Result<()> {
+    let p = lift_x(public_key_x)?;
+    let s = Option::<Scalar>::from(Scalar::from_repr(signature.s.into()))
+        .ok_or(Error::InvalidSignature)?;
+
+    let mut challenge_input = Vec::with_capacity(96);
+    challenge_input.extend_from_slice(&signature.r);
+    challenge_input.extend_from_slice(public_key_x);
+    challenge_input.extend_from_slice(message);
+    let e = <Scalar as Reduce<U256>>::reduce_bytes(
+        &crate::hashing::tagged_hash("BIP0340/challenge", &challenge_input).into(),
+    );
+
+    let r = ProjectivePoint::GENERATOR * s - p * e;
+    if bool::from(r.is_identity()) {
+        return Err(Error::InvalidSignature);
+    }
+    let r_affine = r.to_affine();
+    if has_odd_y(&r_affine) {
+        return Err(Error::InvalidSignature);
+    }
+    if r_affine.to_encoded_point(true).as_bytes()[1..] != signature.r {
+        return Err(Error::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// Commitment binding a nonce point to its session and sender
+fn nonce_commitment(session_id: &SessionId, party_id: PartyId, r_point: &[u8]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new_derive_key(crate::consts::SCHNORR_NONCE_CONTEXT);
+    hasher.update(session_id);
+    hasher.update(&(party_id as u64).to_be_bytes());
+    hasher.update(r_point);
+    *hasher.finalize().as_bytes()
+}
+
+/// Derive the signing session ID from the public context
+fn derive_session_id(public_key: &[u8], parties: &[PartyId], message: &[u8; 32]) -> SessionId {
+    let mut material = Vec::with_capacity(public_key.len() + parties.len() * 8 + 32);
+    material.extend_from_slice(public_key);
+    for &party in parties {
+        material.extend_from_slice(&(party as u64).to_be_bytes());
+    }
+    material.extend_from_slice(message);
+    blake3::derive_key(crate::consts::SCHNORR_SESSION_CONTEXT, &material)
+}
+
+/// Decode a compressed SEC1 point
+fn decode_point(bytes: &[u8]) -> Result<ProjectivePoint> {
+    let encoded = k256::EncodedPoint::from_bytes(bytes)
+        .map_err(|e| Error::VerificationFailed(e.to_string()))?;
+    let affine = Option::<AffinePoint>::from(AffinePoint::from_encoded_point(&encoded))
+        .ok_or_else(|| Error::VerificationFailed("Invalid curve point".into()))?;
+    Ok(ProjectivePoint::from(affine))
+}
+
+/// Lift an x coordinate to the curve point with even Y
+fn lift_x(x: &[u8; 32]) -> Result<ProjectivePoint> {
+    let mut compressed = [0u8; 33];
+    compressed[0] = 0x02;
+    compressed[1..].copy_from_slice(x);
+    decode_point(&compressed)
+}
+
+/// Whether an affine point has an odd Y coordinate
+fn has_odd_y(point: &AffinePoint) -> bool {
+    point.to_encoded_point(true).as_bytes()[0] == 0x03
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keygen::import_key;
+    use crate::mpc::MemoryRelay;
+    use crate::SessionConfig;
+    use std::sync::Arc;
+
+    /// Vector 0 from the BIP340 reference test file
+    #[test]
+    fn test_verify_bip340_reference_vector() {
+        let mut public_key_x = [0u8; 32];
+        hex::decode_to_slice(
+            "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            &mut public_key_x,
+        )
+        .unwrap();
+        let sig_bytes = hex::decode(
+            "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215\
+             25f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0",
+        )
+        .unwrap();
+        let signature = SchnorrSignature {
+            r: sig_bytes[..32].try_into().unwrap(),
+            s: sig_bytes[32..].try_into().unwrap(),
+            transcript_digest: [0u8; 32],
+        };
+        let message = [0u8; 32];
+
+        assert!(verify_bip340(&public_key_x, &message, &signature).is_ok());
+
+        // Any bit flipped in the message, key or signature must fail
+        let mut wrong_message = message;
+        wrong_message[0] ^= 1;
+        assert!(verify_bip340(&public_key_x, &wrong_message, &signature).is_err());
+
+        let mut wrong_key = public_key_x;
+        wrong_key[31] ^= 1;
+        assert!(verify_bip340(&wrong_key, &message, &signature).is_err());
+
+        let mut wrong_sig = signature.clone();
+        wrong_sig.s[31] ^= 1;
+        assert!(verify_bip340(&public_key_x, &message, &wrong_sig).is_err());
+    }
+
+    fn shares_for_secret(value: u64) -> Vec<KeyShare> {
+        let mut secret_bytes = [0u8; 32];
+        secret_bytes[24..].copy_from_slice(&value.to_be_bytes());
+        let config = SessionConfig {
+            session_id: [0x44u8; 32],
+            n_parties: 3,
+            threshold: 2,
+            party_id: 0,
+            parties: (0..3).collect(),
+        };
+        import_key(&secret_bytes, &config).unwrap()
+    }
+
+    async fn sign_with(shares: &[KeyShare], signers: &[PartyId]) -> SchnorrSignature {
+        let relay = Arc::new(MemoryRelay::new());
+        let message = [0x7au8; 32];
+
+        let mut handles = Vec::new();
+        for &signer in signers {
+            let share = shares[signer].clone();
+            let relay = Arc::clone(&relay);
+            let signers = signers.to_vec();
+            handles.push(tokio::spawn(async move {
+                run_schnorr_dsg(&share, &message, &signers, &*relay).await
+            }));
+        }
+
+        let mut signatures = Vec::new();
+        for handle in handles {
+            signatures.push(handle.await.unwrap().unwrap());
+        }
+        for signature in &signatures {
+            assert_eq!(signature.r, signatures[0].r);
+            assert_eq!(signature.s, signatures[0].s);
+            assert_eq!(signature.transcript_digest, signatures[0].transcript_digest);
+        }
+
+        let p_x = x_only_public_key(&shares[signers[0]]).unwrap();
+        let signature = signatures.pop().unwrap();
+        assert!(verify_bip340(&p_x, &message, &signature).is_ok());
+        signature
+    }
+
+    #[tokio::test]
+    async fn test_schnorr_dsg_even_and_odd_group_keys() {
+        // Both Y parities of the group key must be handled: the protocol
+        // negates key shares for the odd case so the signature verifies
+        // under the even lift of P's x coordinate
+        // 5*G has even Y, 6*G odd
+        let mut parities_seen = [false, false];
+        for secret in 5u64..=6 {
+            let shares = shares_for_secret(secret);
+            parities_seen[(shares[0].public_key[0] == 0x03) as usize] = true;
+            sign_with(&shares, &[0, 1]).await;
+        }
+        assert!(
+            parities_seen[0] && parities_seen[1],
+            "test secrets must cover both key parities"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_schnorr_dsg_any_quorum_and_distinct_nonces() {
+        let shares = shares_for_secret(777);
+        let a = sign_with(&shares, &[0, 2]).await;
+        let b = sign_with(&shares, &[0, 1, 2]).await;
+        // Fresh nonces every ceremony: same message, different R
+        assert_ne!(a.r, b.r);
+    }
+}
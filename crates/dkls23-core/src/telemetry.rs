@@ -0,0 +1,84 @@
+//! Embedded protocol metrics
+//!
+//! Behind the `metrics` feature the helpers here emit counters and
+//! histograms through the `metrics` facade; the embedding binary picks
+//! the exporter (Prometheus, statsd, logs) by installing a recorder, so
+//! the CLI and the daemon get the same instrumentation without any
+//! caller-side plumbing. Without the feature every helper compiles to
+//! nothing and the hot path pays no cost.
+//!
+//! Emitted series:
+//! - `dkls23_round_duration_seconds{protocol, round}` — histogram of
+//!   wall-clock time per protocol round, including relay waits
+//! - `dkls23_messages_processed_total{protocol}` — counter of round
+//!   messages accepted from peers
+//! - `dkls23_verification_failures_total{protocol}` — counter of checks
+//!   that implicated a peer (bad shares, invalid partial signatures)
+//! - `dkls23_ot_bytes_total{direction}` — counter of OT-extension
+//!   payload bytes moved during MtA, split by `sent`/`received`
+
+use std::time::Duration;
+
+/// Record one completed protocol round: its duration and how many peer
+/// messages it processed
+pub(crate) fn round_complete(
+    protocol: &'static str,
+    round: u32,
+    elapsed: Duration,
+    messages: usize,
+) {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::histogram!(
+            "dkls23_round_duration_seconds",
+            "protocol" => protocol,
+            "round" => round.to_string(),
+        )
+        .record(elapsed.as_secs_f64());
+        metrics::counter!(
+            "dkls23_messages_processed_total",
+            "protocol" => protocol,
+        )
+        .increment(messages as u64);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (protocol, round, elapsed, messages);
+}
+
+/// Count checks that implicated a peer
+pub(crate) fn verification_failures(protocol: &'static str, count: usize) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        "dkls23_verification_failures_total",
+        "protocol" => protocol,
+    )
+    .increment(count as u64);
+    #[cfg(not(feature = "metrics"))]
+    let _ = (protocol, count);
+}
+
+/// Count OT-extension payload bytes moved during MtA
+pub(crate) fn ot_bytes(direction: &'static str, bytes: usize) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        "dkls23_ot_bytes_total",
+        "direction" => direction,
+    )
+    .increment(bytes as u64);
+    #[cfg(not(feature = "metrics"))]
+    let _ = (direction, bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The facade is a no-op without an installed recorder, so the
+    /// helpers must be safe to call unconditionally in either build
+    #[test]
+    fn test_helpers_are_safe_without_a_recorder() {
+        round_complete("dkg", 1, Duration::from_millis(5), 3);
+        verification_failures("dsg", 1);
+        ot_bytes("sent", 4096);
+    }
+}
@@ -0,0 +1,193 @@
+//! Session transcript hashing
+//!
+//! Every ceremony maintains a running hash of the broadcast messages it
+//! accepts. Each entry binds the session ID (via the transcript label),
+//! round number, sender and payload bytes, so a message spliced in from
+//! another session or round changes the digest and honest parties diverge
+//! immediately instead of producing a subtly wrong result. Direct messages
+//! are excluded: each party sees a different set, so folding them in would
+//! make honest parties' digests differ by construction.
+//!
+//! The final digest is recorded on [`KeyShare`](crate::KeyShare) and
+//! [`Signature`](crate::Signature) results, giving auditors a compact
+//! commitment to the exact message flow that produced them.
+
+use crate::{PartyId, Result, SessionId};
+use serde::Serialize;
+
+/// Transcript label for DKG ceremonies
+pub const DKG_LABEL: &str = "dkls23-core dkg transcript v1";
+
+/// Transcript label for DSG ceremonies
+pub const DSG_LABEL: &str = "dkls23-core dsg transcript v1";
+
+/// Transcript label for resharing ceremonies
+pub const RESHARE_LABEL: &str = "dkls23-core reshare transcript v1";
+
+/// Transcript label for add-party ceremonies
+pub const ADD_PARTY_LABEL: &str = "dkls23-core add-party transcript v1";
+
+/// Transcript label for key import ceremonies
+pub const IMPORT_LABEL: &str = "dkls23-core import transcript v1";
+
+/// Transcript label for BIP340 Schnorr signing ceremonies
+pub const SCHNORR_LABEL: &str = "dkls23-core schnorr transcript v1";
+
+/// Transcript label for P-256 DKG ceremonies
+pub const P256_DKG_LABEL: &str = "dkls23-core p256 dkg transcript v1";
+
+/// Transcript label for P-256 DSG ceremonies
+pub const P256_DSG_LABEL: &str = "dkls23-core p256 dsg transcript v1";
+
+/// Transcript label for Ed25519 DKG ceremonies
+pub const ED25519_DKG_LABEL: &str = "dkls23-core ed25519 dkg transcript v1";
+
+/// Transcript label for Ed25519 signing ceremonies
+pub const ED25519_DSG_LABEL: &str = "dkls23-core ed25519 dsg transcript v1";
+
+/// Running hash over a ceremony's broadcast messages
+#[derive(Clone)]
+pub struct Transcript {
+    hasher: blake3::Hasher,
+}
+
+impl Transcript {
+    /// Start a transcript bound to a label and session ID
+    ///
+    /// The versioned [`crate::consts::PROTOCOL_IDENT`] is folded in
+    /// first, so digests from different protocol revisions can never
+    /// collide even under the same label.
+    pub fn new(label: &str, session_id: &SessionId) -> Self {
+        let ident = crate::consts::PROTOCOL_IDENT;
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&(ident.len() as u64).to_be_bytes());
+        hasher.update(ident.as_bytes());
+        hasher.update(&(label.len() as u64).to_be_bytes());
+        hasher.update(label.as_bytes());
+        hasher.update(session_id);
+        Self { hasher }
+    }
+
+    /// Continue a transcript from a previously recorded digest
+    ///
+    /// The hasher state cannot be restored from a digest, so the
+    /// continuation chains onto it: the new transcript absorbs the digest
+    /// and subsequent entries fold in as usual. Used when signing resumes
+    /// from a stored pre-signature.
+    pub fn resume(digest: &[u8; 32]) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(digest);
+        Self { hasher }
+    }
+
+    /// Fold one broadcast payload into the transcript
+    ///
+    /// Every field is length- or width-delimited so distinct message flows
+    /// can never collide on the same byte stream.
+    pub fn append(&mut self, round: u32, sender: PartyId, payload: &[u8]) {
+        self.hasher.update(&round.to_be_bytes());
+        self.hasher.update(&(sender as u64).to_be_bytes());
+        self.hasher.update(&(payload.len() as u64).to_be_bytes());
+        self.hasher.update(payload);
+    }
+
+    /// Serialize a round message canonically and fold it in
+    ///
+    /// Uses [`crate::canonical`] rather than the raw wire codec so the
+    /// digest depends only on the message's value, not on field order or
+    /// serde details of the build that computed it. Callers must append
+    /// messages in a deterministic order (sorted by sender) so every
+    /// honest party computes the same digest.
+    pub fn append_message<T: Serialize>(
+        &mut self,
+        round: u32,
+        sender: PartyId,
+        message: &T,
+    ) -> Result<()> {
+        let payload = crate::canonical::to_vec(message)?;
+        self.append(round, sender, &payload);
+        Ok(())
+    }
+
+    /// Current transcript digest
+    pub fn digest(&self) -> [u8; 32] {
+        *self.hasher.finalize().as_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_flow_same_digest() {
+        let session_id = [0x42u8; 32];
+        let mut a = Transcript::new(DKG_LABEL, &session_id);
+        let mut b = Transcript::new(DKG_LABEL, &session_id);
+
+        a.append(1, 0, b"commitment");
+        b.append(1, 0, b"commitment");
+        assert_eq!(a.digest(), b.digest());
+    }
+
+    #[test]
+    fn test_splicing_changes_digest() {
+        let session_id = [0x42u8; 32];
+        let base = Transcript::new(DSG_LABEL, &session_id);
+
+        let mut original = base.clone();
+        original.append(1, 0, b"payload");
+
+        // Same payload from another session, round or sender diverges
+        let mut other_session = Transcript::new(DSG_LABEL, &[0x43u8; 32]);
+        other_session.append(1, 0, b"payload");
+        assert_ne!(original.digest(), other_session.digest());
+
+        let mut other_round = base.clone();
+        other_round.append(2, 0, b"payload");
+        assert_ne!(original.digest(), other_round.digest());
+
+        let mut other_sender = base.clone();
+        other_sender.append(1, 1, b"payload");
+        assert_ne!(original.digest(), other_sender.digest());
+    }
+
+    /// Golden vector: digests recorded on key shares and signatures must
+    /// stay reproducible by later builds. Update this only with a
+    /// protocol version bump.
+    #[test]
+    fn test_digest_is_stable_across_versions() {
+        let mut transcript = Transcript::new(DKG_LABEL, &[0x42u8; 32]);
+        transcript
+            .append_message(
+                1,
+                0,
+                &crate::keygen::DkgRound1Message {
+                    party_id: 0,
+                    commitments: vec![vec![1, 2, 3]],
+                    capabilities: 0,
+                    pok_point: Vec::new(),
+                    pok_response: Vec::new(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            hex::encode(transcript.digest()),
+            "c8b76f2e5592305dd3e74fea7c0e26409805404026d1c0123150805e5a51c7be"
+        );
+    }
+
+    #[test]
+    fn test_field_boundaries_are_unambiguous() {
+        let session_id = [0u8; 32];
+        let mut a = Transcript::new(DKG_LABEL, &session_id);
+        let mut b = Transcript::new(DKG_LABEL, &session_id);
+
+        // Shifting bytes between adjacent payloads must not collide
+        a.append(1, 0, b"ab");
+        a.append(1, 0, b"c");
+        b.append(1, 0, b"a");
+        b.append(1, 0, b"bc");
+        assert_ne!(a.digest(), b.digest());
+    }
+}
@@ -1,9 +1,10 @@
 //! Core types for DKLs23 protocol
 
+use crate::curve::ThresholdCurve;
 use k256::{
     ecdsa,
-    elliptic_curve::{bigint::U256, ops::Reduce, sec1::FromEncodedPoint},
-    AffinePoint, ProjectivePoint, Scalar,
+    elliptic_curve::{bigint::U256, ops::Reduce},
+    Scalar,
 };
 use serde::{Deserialize, Serialize};
 use zeroize::{Zeroize, ZeroizeOnDrop};
@@ -26,12 +27,23 @@ pub struct Signature {
     pub s: [u8; 32],
     /// Recovery ID (0 or 1)
     pub recovery_id: u8,
+    /// Digest of the DSG broadcast transcript that produced this signature
+    ///
+    /// Zero for signatures assembled outside a ceremony (or by older
+    /// builds); see [`crate::transcript`].
+    #[serde(default)]
+    pub transcript_digest: [u8; 32],
 }
 
 impl Signature {
     /// Create a new signature
     pub fn new(r: [u8; 32], s: [u8; 32], recovery_id: u8) -> Self {
-        Self { r, s, recovery_id }
+        Self {
+            r,
+            s,
+            recovery_id,
+            transcript_digest: [0u8; 32],
+        }
     }
 
     /// Convert to DER format
@@ -51,6 +63,231 @@ impl Signature {
         bytes[32..].copy_from_slice(&self.s);
         bytes
     }
+
+    /// Normalize to the canonical low-s form
+    ///
+    /// ECDSA signatures come in malleable pairs: (r, s) and (r, n - s)
+    /// both verify. Bitcoin and Ethereum accept only the low-s sibling,
+    /// so if s exceeds n/2 this replaces it with n - s and flips the
+    /// Y-parity bit of the recovery ID (negating s mirrors the nonce
+    /// point across the x-axis). Returns whether anything changed.
+    pub fn normalize_s(&mut self) -> bool {
+        use k256::elliptic_curve::{scalar::IsHigh, PrimeField};
+
+        let Some(s) = Option::<Scalar>::from(Scalar::from_repr(self.s.into())) else {
+            return false;
+        };
+        if !bool::from(s.is_high()) {
+            return false;
+        }
+        self.s = (-s).to_bytes().into();
+        self.recovery_id ^= 1;
+        true
+    }
+
+    /// Parse a signature from strict DER encoding
+    ///
+    /// Accepts exactly one encoding per signature: minimal short-form
+    /// lengths, minimally-encoded positive INTEGERs, no trailing bytes,
+    /// and a low-s value. The BER leniencies that permissive parsers
+    /// tolerate -- long-form lengths for short values, padded or negative
+    /// integers, the high-s sibling -- are all rejected, so third-party
+    /// blobs cannot smuggle in a malleable sibling of a known signature.
+    ///
+    /// DER carries no recovery ID; the result's is zero.
+    pub fn from_der_strict(bytes: &[u8]) -> crate::Result<Self> {
+        use k256::elliptic_curve::{scalar::IsHigh, PrimeField};
+
+        if bytes.len() < 2 {
+            return Err(strict_der_error("truncated input"));
+        }
+        if bytes[0] != 0x30 {
+            return Err(strict_der_error("not a SEQUENCE"));
+        }
+        if bytes[1] >= 0x80 {
+            // A signature body is at most 70 bytes, so the long form is
+            // never the minimal encoding
+            return Err(strict_der_error("non-minimal SEQUENCE length"));
+        }
+        if bytes[1] as usize != bytes.len() - 2 {
+            return Err(strict_der_error("SEQUENCE length mismatch"));
+        }
+
+        let (r, rest) = read_strict_der_integer(&bytes[2..])?;
+        let (s, rest) = read_strict_der_integer(rest)?;
+        if !rest.is_empty() {
+            return Err(strict_der_error("trailing bytes after s"));
+        }
+
+        let r_scalar = Option::<Scalar>::from(Scalar::from_repr(r.into()))
+            .ok_or_else(|| strict_der_error("r is not a valid scalar"))?;
+        let s_scalar = Option::<Scalar>::from(Scalar::from_repr(s.into()))
+            .ok_or_else(|| strict_der_error("s is not a valid scalar"))?;
+        if bool::from(r_scalar.is_zero()) || bool::from(s_scalar.is_zero()) {
+            return Err(strict_der_error("zero scalar"));
+        }
+        if bool::from(s_scalar.is_high()) {
+            return Err(strict_der_error("high-s value"));
+        }
+
+        Ok(Self::new(r, s, 0))
+    }
+
+    /// Assemble the `k256` signature object from the stored scalars
+    fn to_ecdsa(&self) -> crate::Result<ecdsa::Signature> {
+        ecdsa::Signature::from_scalars(
+            *k256::FieldBytes::from_slice(&self.r),
+            *k256::FieldBytes::from_slice(&self.s),
+        )
+        .map_err(|e| crate::Error::VerificationFailed(format!("Invalid signature scalars: {}", e)))
+    }
+
+    /// Verify against a SEC1-encoded public key and a 32-byte message hash
+    ///
+    /// The hash is taken as-is (no further digesting), matching what the
+    /// signing ceremony consumed.
+    pub fn verify(&self, public_key: &[u8], msg_hash: &[u8; 32]) -> crate::Result<()> {
+        use ecdsa::signature::hazmat::PrehashVerifier;
+
+        let verifying_key = ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+            .map_err(|e| crate::Error::VerificationFailed(format!("Invalid public key: {}", e)))?;
+        verifying_key
+            .verify_prehash(msg_hash, &self.to_ecdsa()?)
+            .map_err(|_| {
+                crate::Error::VerificationFailed(
+                    "Signature does not verify against the public key".into(),
+                )
+            })
+    }
+
+    /// Recover the compressed public key that produced this signature
+    ///
+    /// Uses the stored recovery ID, so the result is unambiguous; compare
+    /// it against the expected key to verify without carrying the key.
+    pub fn recover_pubkey(&self, msg_hash: &[u8; 32]) -> crate::Result<Vec<u8>> {
+        let recovery_id = ecdsa::RecoveryId::from_byte(self.recovery_id).ok_or_else(|| {
+            crate::Error::VerificationFailed(format!(
+                "Invalid recovery ID {}",
+                self.recovery_id
+            ))
+        })?;
+        let verifying_key =
+            ecdsa::VerifyingKey::recover_from_prehash(msg_hash, &self.to_ecdsa()?, recovery_id)
+                .map_err(|e| {
+                    crate::Error::VerificationFailed(format!("Key recovery failed: {}", e))
+                })?;
+        Ok(verifying_key.to_encoded_point(true).as_bytes().to_vec())
+    }
+
+    /// Encode as the 65-byte Ethereum `r || s || v` form
+    ///
+    /// With a chain ID, v follows EIP-155: `recovery_id + chain_id * 2 +
+    /// 35`, which replay-protects the signature on that chain. Without
+    /// one, the pre-fork `recovery_id + 27` is used. Rejects recovery
+    /// IDs 2 and 3 (the r-overflow cases Ethereum does not encode) and
+    /// chain IDs whose v would not fit the single byte.
+    pub fn to_eth(&self, chain_id: Option<u64>) -> crate::Result<[u8; 65]> {
+        if self.recovery_id > 1 {
+            return Err(crate::Error::Serialization(format!(
+                "Recovery ID {} has no Ethereum encoding",
+                self.recovery_id
+            )));
+        }
+        let v = match chain_id {
+            Some(chain_id) => (self.recovery_id as u64)
+                .checked_add(chain_id.checked_mul(2).and_then(|x| x.checked_add(35)).ok_or_else(
+                    || crate::Error::Serialization("Chain ID overflows v".into()),
+                )?)
+                .ok_or_else(|| crate::Error::Serialization("Chain ID overflows v".into()))?,
+            None => self.recovery_id as u64 + 27,
+        };
+        let v: u8 = v.try_into().map_err(|_| {
+            crate::Error::Serialization(format!(
+                "v = {} does not fit the one-byte r||s||v encoding",
+                v
+            ))
+        })?;
+
+        let mut bytes = [0u8; 65];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..64].copy_from_slice(&self.s);
+        bytes[64] = v;
+        Ok(bytes)
+    }
+
+    /// Parse the 65-byte Ethereum `r || s || v` form
+    ///
+    /// Returns the signature and the EIP-155 chain ID when v encodes
+    /// one; raw v values 0/1 and legacy 27/28 yield `None`.
+    pub fn from_eth(bytes: &[u8]) -> crate::Result<(Self, Option<u64>)> {
+        let bytes: &[u8; 65] = bytes.try_into().map_err(|_| {
+            crate::Error::Deserialization(format!(
+                "Ethereum signature must be 65 bytes, got {}",
+                bytes.len()
+            ))
+        })?;
+        let (recovery_id, chain_id) = match bytes[64] {
+            v @ 0..=1 => (v, None),
+            v @ 27..=28 => (v - 27, None),
+            v @ 35.. => ((v - 35) % 2, Some(((v - 35) / 2) as u64)),
+            v => {
+                return Err(crate::Error::Deserialization(format!(
+                    "Invalid Ethereum v value {}",
+                    v
+                )))
+            }
+        };
+        Ok((
+            Self::new(
+                bytes[..32].try_into().expect("sliced to 32 bytes"),
+                bytes[32..64].try_into().expect("sliced to 32 bytes"),
+                recovery_id,
+            ),
+            chain_id,
+        ))
+    }
+}
+
+/// Build the error for a strict-DER parsing failure
+fn strict_der_error(detail: &str) -> crate::Error {
+    crate::Error::Deserialization(format!("Strict DER: {}", detail))
+}
+
+/// Read one minimally-encoded positive INTEGER, returning its value as a
+/// 32-byte big-endian array and the remaining input
+fn read_strict_der_integer(input: &[u8]) -> crate::Result<([u8; 32], &[u8])> {
+    if input.len() < 3 {
+        return Err(strict_der_error("truncated INTEGER"));
+    }
+    if input[0] != 0x02 {
+        return Err(strict_der_error("not an INTEGER"));
+    }
+    if input[1] >= 0x80 {
+        return Err(strict_der_error("non-minimal INTEGER length"));
+    }
+    let len = input[1] as usize;
+    if len == 0 {
+        return Err(strict_der_error("empty INTEGER"));
+    }
+    let content = input
+        .get(2..2 + len)
+        .ok_or_else(|| strict_der_error("INTEGER overruns input"))?;
+
+    if content[0] & 0x80 != 0 {
+        return Err(strict_der_error("negative INTEGER"));
+    }
+    if len > 1 && content[0] == 0 && content[1] & 0x80 == 0 {
+        return Err(strict_der_error("padded INTEGER"));
+    }
+
+    let digits = if content[0] == 0 { &content[1..] } else { content };
+    if digits.len() > 32 {
+        return Err(strict_der_error("INTEGER wider than 32 bytes"));
+    }
+
+    let mut value = [0u8; 32];
+    value[32 - digits.len()..].copy_from_slice(digits);
+    Ok((value, &input[2 + len..]))
 }
 
 /// Wrapper for Scalar serialization
@@ -84,14 +321,18 @@ impl<'de> Deserialize<'de> for ScalarWrapper {
 
 impl Zeroize for ScalarWrapper {
     fn zeroize(&mut self) {
-        // Scalar doesn't implement Zeroize directly, but we can't zeroize it here
-        // In a production system, you'd want to handle this more carefully
+        self.0.zeroize();
     }
 }
 
 /// Key share held by a party after DKG
-#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
-pub struct KeyShare {
+///
+/// Generic over the ceremony's curve; the default instantiation is the
+/// secp256k1 share every existing caller works with, so plain `KeyShare`
+/// keeps meaning what it always did. P-256 shares are `KeyShare<NistP256>`.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound(serialize = "", deserialize = ""))]
+pub struct KeyShare<C: ThresholdCurve = k256::Secp256k1> {
     /// This party's ID
     pub party_id: PartyId,
 
@@ -102,57 +343,153 @@ pub struct KeyShare {
     pub threshold: usize,
 
     /// This party's secret share (x_i) - stored as bytes for serialization
-    #[zeroize(skip)]
     #[serde(with = "scalar_serde")]
-    pub secret_share: Scalar,
+    pub secret_share: C::Scalar,
 
     /// Public key (compressed) - stored as Vec for serde compatibility
-    #[zeroize(skip)]
     pub public_key: Vec<u8>,
 
     /// Public key shares of all parties
-    #[zeroize(skip)]
     pub public_shares: Vec<Vec<u8>>,
 
     /// Chain code for BIP32 derivation
     pub chain_code: [u8; 32],
+
+    /// Signature scheme this share belongs to
+    #[serde(default)]
+    pub scheme: crate::scheme::SchemeId,
+
+    /// Minimum wire protocol version allowed for ceremonies with this key
+    ///
+    /// Recorded at DKG time; once a fleet upgrades past a version with a
+    /// known weakness, refreshing this prevents downgrade to the old flow.
+    #[serde(default)]
+    pub min_protocol_version: u32,
+
+    /// Digest of the DKG broadcast transcript that produced this share
+    ///
+    /// All honest participants record the same value; zero for shares
+    /// imported from older builds. See [`crate::transcript`].
+    #[serde(default)]
+    pub transcript_digest: [u8; 32],
+
+    /// Keyed integrity tag over every other field
+    ///
+    /// Stamped by [`KeyShare::seal_integrity`] before the share is
+    /// persisted and checked by [`KeyShare::verify_integrity`] at load
+    /// time, so a flipped bit in a share file surfaces as a structured
+    /// error instead of a garbage signature. Zero while a share lives
+    /// in memory and for files written by older builds, which are
+    /// accepted untagged.
+    #[serde(default)]
+    pub integrity_tag: [u8; 32],
+}
+
+// The zeroize derives cannot see through `C::Scalar`, so the wipe-on-drop
+// behavior is spelled out by hand; [`ThresholdCurve`] requires scalars
+// to be zeroizable so the secret share itself is scrubbed too
+impl<C: ThresholdCurve> Zeroize for KeyShare<C> {
+    fn zeroize(&mut self) {
+        self.party_id.zeroize();
+        self.n_parties.zeroize();
+        self.threshold.zeroize();
+        self.secret_share.zeroize();
+        self.chain_code.zeroize();
+        self.min_protocol_version.zeroize();
+    }
+}
+
+impl<C: ThresholdCurve> Drop for KeyShare<C> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
 }
 
+impl<C: ThresholdCurve> ZeroizeOnDrop for KeyShare<C> {}
+
 mod scalar_serde {
-    use k256::{elliptic_curve::{bigint::U256, ops::Reduce}, Scalar};
+    use k256::elliptic_curve::{
+        bigint::U256, consts::U32, generic_array::GenericArray, ops::Reduce, PrimeField,
+    };
     use serde::{Deserialize, Deserializer, Serializer};
 
-    pub fn serialize<S>(scalar: &Scalar, serializer: S) -> Result<S::Ok, S::Error>
+    pub fn serialize<S, Sc>(scalar: &Sc, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
+        Sc: PrimeField,
     {
-        let bytes = scalar.to_bytes();
-        serializer.serialize_bytes(bytes.as_slice())
+        let bytes = scalar.to_repr();
+        serializer.serialize_bytes(bytes.as_ref())
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<Scalar, D::Error>
+    pub fn deserialize<'de, D, Sc>(deserializer: D) -> Result<Sc, D::Error>
     where
         D: Deserializer<'de>,
+        Sc: Reduce<U256, Bytes = GenericArray<u8, U32>>,
     {
         let bytes: Vec<u8> = Vec::deserialize(deserializer)?;
         let array: [u8; 32] = bytes
             .try_into()
             .map_err(|_| serde::de::Error::custom("Invalid scalar length"))?;
-        Ok(<Scalar as Reduce<U256>>::reduce_bytes(&array.into()))
+        Ok(Sc::reduce_bytes(&array.into()))
     }
 }
 
-impl KeyShare {
+/// Magic prefix identifying the versioned binary key share format
+const KEY_SHARE_MAGIC: &[u8; 4] = b"DKSH";
+
+/// Current version of the binary key share format
+const KEY_SHARE_FORMAT_VERSION: u32 = 1;
+
+/// Header bytes before the payload: magic, version, payload length
+const KEY_SHARE_HEADER_LEN: usize = 4 + 4 + 8;
+
+impl<C: ThresholdCurve> KeyShare<C> {
     /// Get the public key as a ProjectivePoint
-    pub fn public_key_point(&self) -> ProjectivePoint {
-        let encoded = k256::EncodedPoint::from_bytes(&self.public_key).expect("valid point");
-        let affine_opt = AffinePoint::from_encoded_point(&encoded);
-        let affine: AffinePoint = Option::<AffinePoint>::from(affine_opt).expect("valid point");
-        ProjectivePoint::from(affine)
+    pub fn public_key_point(&self) -> C::ProjectivePoint {
+        crate::curve::decode_point::<C>(&self.public_key).expect("valid point")
+    }
+
+    /// Fingerprint over this share's view of the group key material
+    ///
+    /// Covers the public key, every party's public share and the
+    /// threshold parameters; parties that disagree on any of it compute
+    /// different fingerprints.
+    pub fn key_fingerprint(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&self.public_key);
+        for public_share in &self.public_shares {
+            hasher.update(&(public_share.len() as u64).to_be_bytes());
+            hasher.update(public_share);
+        }
+        hasher.update(&(self.threshold as u64).to_be_bytes());
+        hasher.update(&(self.n_parties as u64).to_be_bytes());
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Check that the secret share matches this party's published public
+    /// share, catching a corrupted or swapped share file before it is
+    /// carried into a ceremony
+    pub fn verify_consistency(&self) -> crate::Result<()> {
+        use k256::elliptic_curve::Group;
+
+        // Shares imported without the per-party commitment set have
+        // nothing to check against
+        let Some(public_share) = self.public_shares.get(self.party_id) else {
+            return Ok(());
+        };
+        let expected =
+            crate::curve::encode_point::<C>(&(C::ProjectivePoint::generator() * self.secret_share));
+        if expected != public_share.as_slice() {
+            return Err(crate::Error::VerificationFailed(
+                "Secret share does not match this party's published public share".into(),
+            ));
+        }
+        Ok(())
     }
 
     /// Derive a child key share using non-hardened BIP32 derivation
-    pub fn derive_child(&self, path: &str) -> crate::Result<KeyShare> {
+    pub fn derive_child(&self, path: &str) -> crate::Result<KeyShare<C>> {
         use derivation_path::DerivationPath;
 
         let derivation_path: DerivationPath = path
@@ -192,14 +529,136 @@ impl KeyShare {
         current_share.chain_code = current_chain_code;
         Ok(current_share)
     }
+
+    /// Tag over every field except the tag itself, keyed by a fixed
+    /// BLAKE3 derivation context so it can never collide with any other
+    /// hash this crate computes over the same material
+    fn compute_integrity_tag(&self) -> [u8; 32] {
+        let mut hasher =
+            blake3::Hasher::new_derive_key(crate::consts::KEY_SHARE_INTEGRITY_CONTEXT);
+        hasher.update(&(self.party_id as u64).to_be_bytes());
+        hasher.update(&(self.n_parties as u64).to_be_bytes());
+        hasher.update(&(self.threshold as u64).to_be_bytes());
+        hasher.update(&crate::curve::scalar_to_bytes::<C>(&self.secret_share));
+        hasher.update(&(self.public_key.len() as u64).to_be_bytes());
+        hasher.update(&self.public_key);
+        for public_share in &self.public_shares {
+            hasher.update(&(public_share.len() as u64).to_be_bytes());
+            hasher.update(public_share);
+        }
+        hasher.update(&self.chain_code);
+        hasher.update(self.scheme.to_string().as_bytes());
+        hasher.update(&self.min_protocol_version.to_be_bytes());
+        hasher.update(&self.transcript_digest);
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Stamp the integrity tag; call just before persisting the share
+    pub fn seal_integrity(&mut self) {
+        self.integrity_tag = self.compute_integrity_tag();
+    }
+
+    /// Check the stored integrity tag against the current field values
+    ///
+    /// Shares written before tagging existed carry a zero tag and pass
+    /// unchecked; any other tag must match exactly, so silent disk
+    /// corruption or manual edits to a share file are caught at load
+    /// time rather than producing garbage signatures later.
+    pub fn verify_integrity(&self) -> crate::Result<()> {
+        if self.integrity_tag == [0u8; 32] {
+            return Ok(());
+        }
+        if self.integrity_tag != self.compute_integrity_tag() {
+            return Err(crate::Error::CorruptKeyShare(
+                "Integrity tag does not match the share's fields".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Serialize into the versioned, length-prefixed binary share format
+    ///
+    /// Layout: the 4-byte magic `DKSH`, a big-endian u32 format version,
+    /// a big-endian u64 payload length, then that version's payload.
+    /// Unlike the free-form JSON encoding, a reader can tell exactly
+    /// which layout it is holding, so future field changes never brick
+    /// shares already on disk. Plain serde JSON remains available as an
+    /// export format. The payload carries a fresh integrity tag, so a
+    /// blob corrupted in storage fails [`KeyShare::verify_integrity`]
+    /// on the way back in.
+    pub fn to_bytes_versioned(&self) -> crate::Result<Vec<u8>> {
+        let mut sealed = self.clone();
+        sealed.seal_integrity();
+        let payload =
+            serde_json::to_vec(&sealed).map_err(|e| crate::Error::Serialization(e.to_string()))?;
+        let mut bytes = Vec::with_capacity(KEY_SHARE_HEADER_LEN + payload.len());
+        bytes.extend_from_slice(KEY_SHARE_MAGIC);
+        bytes.extend_from_slice(&KEY_SHARE_FORMAT_VERSION.to_be_bytes());
+        bytes.extend_from_slice(&(payload.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(&payload);
+        Ok(bytes)
+    }
+
+    /// Parse the versioned binary format, migrating old versions forward
+    ///
+    /// Every historical version keeps a dedicated migration function, so
+    /// a share written by any past release either deserializes into the
+    /// current layout or fails with a precise version error instead of a
+    /// confusing field mismatch.
+    pub fn from_bytes_versioned(bytes: &[u8]) -> crate::Result<Self> {
+        if bytes.len() < KEY_SHARE_HEADER_LEN {
+            return Err(crate::Error::Deserialization(
+                "Key share blob shorter than its header".into(),
+            ));
+        }
+        if &bytes[..4] != KEY_SHARE_MAGIC {
+            return Err(crate::Error::Deserialization(
+                "Not a versioned key share blob (bad magic)".into(),
+            ));
+        }
+        let version = u32::from_be_bytes(bytes[4..8].try_into().expect("sliced to 4 bytes"));
+        let declared =
+            u64::from_be_bytes(bytes[8..16].try_into().expect("sliced to 8 bytes")) as usize;
+        let payload = &bytes[KEY_SHARE_HEADER_LEN..];
+        if payload.len() != declared {
+            return Err(crate::Error::Deserialization(format!(
+                "Key share payload is {} bytes but the header declares {}",
+                payload.len(),
+                declared
+            )));
+        }
+
+        match version {
+            1 => {
+                let share = migrate_v1(payload)?;
+                share.verify_integrity()?;
+                Ok(share)
+            }
+            other => Err(crate::Error::Deserialization(format!(
+                "Unsupported key share format version {} (current is {})",
+                other, KEY_SHARE_FORMAT_VERSION
+            ))),
+        }
+    }
+}
+
+/// Decode a version-1 payload into the current share layout
+///
+/// Version 1 payloads are the share's JSON encoding; fields added since
+/// the first release all carry `#[serde(default)]`, so older payloads
+/// that omit them migrate cleanly. When version 2 lands this becomes the
+/// v1-to-v2 mapping and [`KeyShare::from_bytes_versioned`] chains
+/// through it.
+fn migrate_v1<C: ThresholdCurve>(payload: &[u8]) -> crate::Result<KeyShare<C>> {
+    serde_json::from_slice(payload).map_err(|e| crate::Error::Deserialization(e.to_string()))
 }
 
 /// Derive non-hardened child key
-fn derive_non_hardened(
-    parent: &KeyShare,
+fn derive_non_hardened<C: ThresholdCurve>(
+    parent: &KeyShare<C>,
     chain_code: [u8; 32],
     index: u32,
-) -> crate::Result<(Scalar, [u8; 32])> {
+) -> crate::Result<(C::Scalar, [u8; 32])> {
     use hmac::{Hmac, Mac};
     use sha2::Sha512;
 
@@ -215,7 +674,7 @@ fn derive_non_hardened(
 
     // Split into secret addition and new chain code
     let secret_bytes: [u8; 32] = result[..32].try_into().unwrap();
-    let secret_add = <Scalar as Reduce<U256>>::reduce_bytes(&secret_bytes.into());
+    let secret_add = crate::curve::reduce_scalar_bytes::<C>(&secret_bytes);
     let new_chain_code: [u8; 32] = result[32..].try_into().unwrap();
 
     // Add to parent secret share
@@ -245,6 +704,10 @@ pub struct SessionConfig {
 
 impl SessionConfig {
     /// Create a new session configuration
+    ///
+    /// The smallest supported configuration is 2-of-2: the signing phase
+    /// needs at least two participants for the MtA exchange, so a
+    /// threshold of 1 can never produce a signature and is rejected.
     pub fn new(n_parties: usize, threshold: usize, party_id: PartyId) -> crate::Result<Self> {
         if threshold > n_parties {
             return Err(crate::Error::InvalidConfig(
@@ -253,9 +716,15 @@ impl SessionConfig {
         }
         if threshold < 2 {
             return Err(crate::Error::InvalidConfig(
-                "Threshold must be at least 2".into(),
+                "Threshold must be at least 2 (2-of-2 is the smallest supported setup)".into(),
             ));
         }
+        if party_id >= n_parties {
+            return Err(crate::Error::InvalidConfig(format!(
+                "Party ID {} out of range for {} parties",
+                party_id, n_parties
+            )));
+        }
 
         let session_id = rand::random();
         let parties = (0..n_parties).collect();
@@ -305,3 +774,339 @@ impl Message {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_config_accepts_two_of_two_and_rejects_degenerate() {
+        assert!(SessionConfig::new(2, 2, 0).is_ok());
+        assert!(SessionConfig::new(2, 2, 1).is_ok());
+        // Threshold 1 can never sign (MtA needs two participants)
+        assert!(SessionConfig::new(2, 1, 0).is_err());
+        // Threshold above the party count
+        assert!(SessionConfig::new(2, 3, 0).is_err());
+        // Party ID outside the committee
+        assert!(SessionConfig::new(2, 2, 2).is_err());
+    }
+
+    /// A minimal valid signature encoding: r = 1, s = 1
+    fn minimal_der() -> Vec<u8> {
+        vec![0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01]
+    }
+
+    fn scalar_bytes(value: u64) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes[24..].copy_from_slice(&value.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_strict_der_accepts_canonical_encoding() {
+        let sig = Signature::from_der_strict(&minimal_der()).unwrap();
+        assert_eq!(sig.r, scalar_bytes(1));
+        assert_eq!(sig.s, scalar_bytes(1));
+        assert_eq!(sig.recovery_id, 0);
+    }
+
+    #[test]
+    fn test_zeroize_wipes_secret_scalars() {
+        let mut share: KeyShare = KeyShare {
+            party_id: 0,
+            n_parties: 2,
+            threshold: 2,
+            secret_share: Scalar::from(7u64),
+            public_key: vec![2; 33],
+            public_shares: vec![vec![2; 33], vec![3; 33]],
+            chain_code: [9u8; 32],
+            scheme: crate::scheme::SchemeId::Secp256k1,
+            min_protocol_version: 1,
+            transcript_digest: [1u8; 32],
+            integrity_tag: [0u8; 32],
+        };
+        share.zeroize();
+        assert_eq!(share.secret_share, Scalar::ZERO);
+        assert_eq!(share.chain_code, [0u8; 32]);
+
+        let mut wrapped = ScalarWrapper(Scalar::from(11u64));
+        wrapped.zeroize();
+        assert_eq!(wrapped.0, Scalar::ZERO);
+    }
+
+    #[test]
+    fn test_strict_der_roundtrips_own_encoder() {
+        let sig = Signature::new(scalar_bytes(0x1234), scalar_bytes(0x77), 1);
+        let parsed = Signature::from_der_strict(&sig.to_der()).unwrap();
+        assert_eq!(parsed.r, sig.r);
+        assert_eq!(parsed.s, sig.s);
+    }
+
+    #[test]
+    fn test_strict_der_rejects_malformed_encodings() {
+        // Each case is a BER-ism or structural defect a lenient parser
+        // might wave through
+        let mut trailing = minimal_der();
+        trailing.push(0x00);
+
+        let long_form_length = vec![0x30, 0x81, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01];
+        let padded_integer = vec![0x30, 0x07, 0x02, 0x02, 0x00, 0x01, 0x02, 0x01, 0x01];
+        let negative_integer = vec![0x30, 0x06, 0x02, 0x01, 0x81, 0x02, 0x01, 0x01];
+        let zero_integer = vec![0x30, 0x06, 0x02, 0x01, 0x00, 0x02, 0x01, 0x01];
+        let empty_integer = vec![0x30, 0x05, 0x02, 0x00, 0x02, 0x01, 0x01];
+        let wrong_outer_tag = vec![0x31, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01];
+        let length_mismatch = vec![0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01];
+
+        for (name, bytes) in [
+            ("trailing byte", trailing),
+            ("long-form length", long_form_length),
+            ("padded integer", padded_integer),
+            ("negative integer", negative_integer),
+            ("zero integer", zero_integer),
+            ("empty integer", empty_integer),
+            ("wrong outer tag", wrong_outer_tag),
+            ("length mismatch", length_mismatch),
+            ("empty input", Vec::new()),
+        ] {
+            assert!(
+                Signature::from_der_strict(&bytes).is_err(),
+                "accepted {}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_s_replaces_high_s_and_flips_recovery_id() {
+        // s = n - 5 is high; its low sibling is 5
+        let high_s = -Scalar::from(5u64);
+        let mut sig = Signature::new(scalar_bytes(1), high_s.to_bytes().into(), 0);
+
+        assert!(sig.normalize_s());
+        assert_eq!(sig.s, scalar_bytes(5));
+        assert_eq!(sig.recovery_id, 1);
+
+        // Already canonical; a second pass is a no-op
+        assert!(!sig.normalize_s());
+        assert_eq!(sig.s, scalar_bytes(5));
+        assert_eq!(sig.recovery_id, 1);
+    }
+
+    #[test]
+    fn test_normalize_s_preserves_r_overflow_recovery_ids() {
+        // IDs 2 and 3 mark the r-overflow case; normalization only flips
+        // the parity bit within that range
+        let mut sig = Signature::new(scalar_bytes(1), (-Scalar::ONE).to_bytes().into(), 3);
+        assert!(sig.normalize_s());
+        assert_eq!(sig.recovery_id, 2);
+    }
+
+    fn sample_share() -> KeyShare {
+        KeyShare {
+            party_id: 1,
+            n_parties: 3,
+            threshold: 2,
+            secret_share: Scalar::from(42u64),
+            public_key: vec![2; 33],
+            public_shares: vec![vec![2; 33], vec![3; 33], vec![4; 33]],
+            chain_code: [9u8; 32],
+            scheme: crate::scheme::SchemeId::Secp256k1,
+            min_protocol_version: 1,
+            transcript_digest: [7u8; 32],
+            integrity_tag: [0u8; 32],
+        }
+    }
+
+    /// Pin the curve parameter; inference cannot pick the default through
+    /// an associated function call
+    fn decode_share(bytes: &[u8]) -> crate::Result<KeyShare> {
+        KeyShare::from_bytes_versioned(bytes)
+    }
+
+    #[test]
+    fn test_versioned_share_roundtrip() {
+        let share = sample_share();
+        let bytes = share.to_bytes_versioned().unwrap();
+        assert_eq!(&bytes[..4], b"DKSH");
+
+        let restored = decode_share(&bytes).unwrap();
+        assert_eq!(restored.party_id, share.party_id);
+        assert_eq!(restored.secret_share, share.secret_share);
+        assert_eq!(restored.public_key, share.public_key);
+        assert_eq!(restored.transcript_digest, share.transcript_digest);
+        assert_eq!(restored.min_protocol_version, share.min_protocol_version);
+    }
+
+    #[test]
+    fn test_versioned_share_migrates_sparse_v1_payload() {
+        // A v1 payload written before scheme, min_protocol_version and
+        // transcript_digest existed; migration must fill the defaults
+        let payload = serde_json::json!({
+            "party_id": 0,
+            "n_parties": 2,
+            "threshold": 2,
+            "secret_share": Scalar::from(5u64).to_bytes().to_vec(),
+            "public_key": vec![2u8; 33],
+            "public_shares": [vec![2u8; 33], vec![3u8; 33]],
+            "chain_code": vec![0u8; 32],
+        })
+        .to_string()
+        .into_bytes();
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"DKSH");
+        bytes.extend_from_slice(&1u32.to_be_bytes());
+        bytes.extend_from_slice(&(payload.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(&payload);
+
+        let share = decode_share(&bytes).unwrap();
+        assert_eq!(share.secret_share, Scalar::from(5u64));
+        assert_eq!(share.scheme, crate::scheme::SchemeId::Secp256k1);
+        assert_eq!(share.min_protocol_version, 0);
+        assert_eq!(share.transcript_digest, [0u8; 32]);
+    }
+
+    #[test]
+    fn test_verify_and_recover_roundtrip() {
+        use k256::ecdsa::SigningKey;
+
+        let signing_key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let public_key = signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+        let msg_hash = [0x42u8; 32];
+
+        let (sig, recovery_id) = signing_key.sign_prehash_recoverable(&msg_hash).unwrap();
+        let bytes = sig.to_bytes();
+        let signature = Signature::new(
+            bytes[..32].try_into().unwrap(),
+            bytes[32..].try_into().unwrap(),
+            recovery_id.to_byte(),
+        );
+
+        signature.verify(&public_key, &msg_hash).unwrap();
+        assert_eq!(signature.recover_pubkey(&msg_hash).unwrap(), public_key);
+
+        // A different hash neither verifies nor recovers the same key
+        assert!(signature.verify(&public_key, &[0u8; 32]).is_err());
+        assert_ne!(signature.recover_pubkey(&[0u8; 32]).unwrap(), public_key);
+
+        // Recovery IDs outside the valid range are rejected up front
+        let mut bad = signature.clone();
+        bad.recovery_id = 9;
+        assert!(bad.recover_pubkey(&msg_hash).is_err());
+    }
+
+    #[test]
+    fn test_eth_encoding_roundtrips_and_computes_v() {
+        let sig = Signature::new(scalar_bytes(0xaa), scalar_bytes(0xbb), 1);
+
+        // Pre-EIP-155: v = 27 + recovery_id
+        let legacy = sig.to_eth(None).unwrap();
+        assert_eq!(legacy[64], 28);
+        let (parsed, chain_id) = Signature::from_eth(&legacy).unwrap();
+        assert_eq!(parsed.r, sig.r);
+        assert_eq!(parsed.s, sig.s);
+        assert_eq!(parsed.recovery_id, 1);
+        assert_eq!(chain_id, None);
+
+        // EIP-155 on mainnet: v = recovery_id + 2 * 1 + 35
+        let mainnet = sig.to_eth(Some(1)).unwrap();
+        assert_eq!(mainnet[64], 38);
+        let (parsed, chain_id) = Signature::from_eth(&mainnet).unwrap();
+        assert_eq!(parsed.recovery_id, 1);
+        assert_eq!(chain_id, Some(1));
+    }
+
+    #[test]
+    fn test_eth_encoding_rejects_unencodable_inputs() {
+        // r-overflow recovery IDs have no Ethereum form
+        let sig = Signature::new(scalar_bytes(1), scalar_bytes(1), 2);
+        assert!(sig.to_eth(None).is_err());
+
+        // A large chain ID pushes v past one byte
+        let sig = Signature::new(scalar_bytes(1), scalar_bytes(1), 0);
+        assert!(sig.to_eth(Some(1_000_000)).is_err());
+
+        // Wrong length and a v in the dead zone between raw and legacy
+        assert!(Signature::from_eth(&[0u8; 64]).is_err());
+        let mut bytes = sig.to_eth(None).unwrap();
+        bytes[64] = 29;
+        assert!(Signature::from_eth(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_integrity_tag_detects_tampered_fields() {
+        let mut share = sample_share();
+        share.seal_integrity();
+        assert!(share.verify_integrity().is_ok());
+
+        // Any field edit after sealing must surface as corruption
+        share.n_parties = 4;
+        let err = share.verify_integrity().err().unwrap();
+        assert!(matches!(err, crate::Error::CorruptKeyShare(_)));
+    }
+
+    #[test]
+    fn test_untagged_legacy_share_passes_integrity_check() {
+        // Shares written before tagging existed carry a zero tag
+        assert!(sample_share().verify_integrity().is_ok());
+    }
+
+    #[test]
+    fn test_versioned_decode_rejects_corrupted_payload() {
+        let bytes = sample_share().to_bytes_versioned().unwrap();
+
+        // Flip the threshold inside the JSON payload; the blob still
+        // parses but no longer matches its integrity tag
+        let needle = b"\"threshold\":2";
+        let pos = bytes
+            .windows(needle.len())
+            .position(|window| window == needle)
+            .unwrap();
+        let mut tampered = bytes;
+        tampered[pos + needle.len() - 1] = b'3';
+
+        let err = decode_share(&tampered).err().unwrap();
+        assert!(matches!(err, crate::Error::CorruptKeyShare(_)));
+    }
+
+    #[test]
+    fn test_versioned_share_rejects_unknown_version() {
+        let mut bytes = sample_share().to_bytes_versioned().unwrap();
+        bytes[4..8].copy_from_slice(&99u32.to_be_bytes());
+
+        let err = decode_share(&bytes).err().unwrap();
+        assert!(err.to_string().contains("version 99"));
+    }
+
+    #[test]
+    fn test_versioned_share_rejects_malformed_blobs() {
+        let good = sample_share().to_bytes_versioned().unwrap();
+
+        // Wrong magic
+        let mut bad_magic = good.clone();
+        bad_magic[0] = b'X';
+        assert!(decode_share(&bad_magic).is_err());
+
+        // Shorter than the header
+        assert!(decode_share(&good[..10]).is_err());
+
+        // Truncated payload no longer matches the declared length
+        assert!(decode_share(&good[..good.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_strict_der_rejects_high_s() {
+        // s = n - 1 is the high-s sibling of s = 1; its top bit is set so
+        // the canonical encoding carries one pad byte
+        let high_s = -Scalar::ONE;
+        let mut der = vec![0x30, 0x26, 0x02, 0x01, 0x01, 0x02, 0x21, 0x00];
+        der.extend_from_slice(&high_s.to_bytes());
+
+        let err = Signature::from_der_strict(&der).unwrap_err();
+        assert!(err.to_string().contains("high-s"));
+    }
+}
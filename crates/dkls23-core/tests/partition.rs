@@ -0,0 +1,252 @@
+//! Chaos tests: network partitions during live ceremonies
+//!
+//! A relay wrapper cuts a configurable party off from the network at a
+//! configurable round and bounds every collect with a deadline, then the
+//! tests assert the two properties that matter before production:
+//! safety — no two parties ever finish with inconsistent keys or
+//! signatures — and liveness under failure — every party reaches a
+//! terminal abort within the configured deadline instead of hanging.
+
+use dkls23_core::keygen::run_dkg;
+use dkls23_core::mpc::{async_trait, MemoryRelay, Relay};
+use dkls23_core::sign::run_dsg;
+use dkls23_core::{Error, KeyShare, PartyId, Result, SessionConfig, SessionId};
+use serde::{de::DeserializeOwned, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long a collect may wait before the party declares the round dead
+const COLLECT_DEADLINE: Duration = Duration::from_secs(2);
+
+/// Relay wrapper simulating a network partition
+///
+/// From `cut_from` onward (in broadcast-round numbering: echo sub-rounds
+/// and the MtA flights count as part of their parent round) this party's
+/// sends vanish like dropped packets and its collects see nothing, so
+/// both sides of the partition experience it the way a real outage
+/// presents: silence, not errors. Every collect is bounded by
+/// [`COLLECT_DEADLINE`]; a round that cannot complete aborts with
+/// [`Error::Timeout`].
+struct PartitionRelay {
+    inner: Arc<MemoryRelay>,
+    cut_from: u32,
+}
+
+impl PartitionRelay {
+    fn new(inner: Arc<MemoryRelay>, cut_from: u32) -> Self {
+        Self { inner, cut_from }
+    }
+
+    /// A healthy party: never partitioned, but still deadline-bounded
+    fn healthy(inner: Arc<MemoryRelay>) -> Self {
+        Self::new(inner, u32::MAX)
+    }
+
+    /// Map wire round numbers onto the broadcast round they belong to:
+    /// echo confirmations run on round + 100 and the MtA flights (11-14)
+    /// sit between broadcast rounds 1 and 2
+    fn effective_round(round: u32) -> u32 {
+        match round {
+            11..=14 => 2,
+            r if r >= 100 => r - 100,
+            r => r,
+        }
+    }
+
+    fn is_cut(&self, round: u32) -> bool {
+        Self::effective_round(round) >= self.cut_from
+    }
+
+    /// A partitioned party receives nothing: wait out the deadline, then
+    /// abort the round
+    async fn starve(&self, round: u32) -> Error {
+        tokio::time::sleep(COLLECT_DEADLINE).await;
+        Error::Timeout(format!("Partitioned from relay in round {}", round))
+    }
+}
+
+#[async_trait]
+impl Relay for PartitionRelay {
+    async fn broadcast<T: Serialize + Send + Sync>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        message: &T,
+    ) -> Result<()> {
+        if self.is_cut(round) {
+            return Ok(()); // dropped on the floor, like a lost packet
+        }
+        self.inner.broadcast(session_id, round, message).await
+    }
+
+    async fn send_direct<T: Serialize + Send + Sync>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        to: PartyId,
+        message: &T,
+    ) -> Result<()> {
+        if self.is_cut(round) {
+            return Ok(());
+        }
+        self.inner.send_direct(session_id, round, to, message).await
+    }
+
+    async fn collect_broadcasts<T: DeserializeOwned + Send>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        count: usize,
+    ) -> Result<Vec<T>> {
+        if self.is_cut(round) {
+            return Err(self.starve(round).await);
+        }
+        tokio::time::timeout(
+            COLLECT_DEADLINE,
+            self.inner.collect_broadcasts(session_id, round, count),
+        )
+        .await
+        .map_err(|_| Error::Timeout(format!("{} broadcasts in round {}", count, round)))?
+    }
+
+    async fn collect_direct<T: DeserializeOwned + Send>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        my_id: PartyId,
+        count: usize,
+    ) -> Result<Vec<T>> {
+        if self.is_cut(round) {
+            return Err(self.starve(round).await);
+        }
+        tokio::time::timeout(
+            COLLECT_DEADLINE,
+            self.inner.collect_direct(session_id, round, my_id, count),
+        )
+        .await
+        .map_err(|_| Error::Timeout(format!("{} direct messages in round {}", count, round)))?
+    }
+}
+
+fn config(session_id: SessionId, party_id: PartyId, n: usize) -> SessionConfig {
+    SessionConfig {
+        session_id,
+        n_parties: n,
+        threshold: 2,
+        party_id,
+        parties: (0..n).collect(),
+    }
+}
+
+/// Run a clean DKG so the DSG scenarios have real key shares
+async fn keygen(relay: &Arc<MemoryRelay>, session_id: SessionId, n: usize) -> Vec<KeyShare> {
+    let mut handles = Vec::new();
+    for party_id in 0..n {
+        let relay = PartitionRelay::healthy(relay.clone());
+        handles.push(tokio::spawn(async move {
+            run_dkg(&config(session_id, party_id, n), &relay).await.unwrap()
+        }));
+    }
+    let mut shares = Vec::new();
+    for handle in handles {
+        shares.push(handle.await.unwrap());
+    }
+    shares
+}
+
+/// Partitioning one party mid-DKG must abort every party within the
+/// deadline — nobody may emerge holding a key share
+#[tokio::test]
+async fn test_dkg_partition_aborts_all_parties_within_deadline() {
+    let relay = Arc::new(MemoryRelay::new());
+    let session_id = [0x10u8; 32];
+    let n = 3;
+
+    let started = std::time::Instant::now();
+    let mut handles = Vec::new();
+    for party_id in 0..n {
+        // Party 2 falls off the network at round 2, after the round 1
+        // commitments went through
+        let cut_from = if party_id == 2 { 2 } else { u32::MAX };
+        let relay = PartitionRelay::new(relay.clone(), cut_from);
+        handles.push(tokio::spawn(async move {
+            run_dkg(&config(session_id, party_id, n), &relay).await
+        }));
+    }
+
+    for handle in handles {
+        let result = handle.await.unwrap();
+        match result {
+            Ok(_) => panic!("a party completed DKG across a partition"),
+            Err(err) => assert!(matches!(err, Error::Timeout(_)), "got {:?}", err),
+        }
+    }
+    // Terminal state within the deadline budget, not a hang: one starved
+    // round plus scheduling slack
+    assert!(
+        started.elapsed() < COLLECT_DEADLINE * 4,
+        "abort took {:?}",
+        started.elapsed()
+    );
+}
+
+/// Partitioning one signer before the final partial broadcast must abort
+/// every signer; none may combine a signature from a partial view
+#[tokio::test]
+async fn test_dsg_partition_aborts_all_signers_within_deadline() {
+    let relay = Arc::new(MemoryRelay::new());
+    let n = 3;
+    let shares = keygen(&relay, [0x20u8; 32], n).await;
+    let message = [0x5au8; 32];
+
+    let started = std::time::Instant::now();
+    let mut handles = Vec::new();
+    for key_share in shares {
+        // Party 1 falls off right before the round 3 partial broadcast
+        let cut_from = if key_share.party_id == 1 { 3 } else { u32::MAX };
+        let relay = PartitionRelay::new(relay.clone(), cut_from);
+        handles.push(tokio::spawn(async move {
+            run_dsg(&key_share, &message, &[0, 1, 2], &relay).await
+        }));
+    }
+
+    for handle in handles {
+        let result = handle.await.unwrap();
+        match result {
+            Ok(_) => panic!("a signer combined a signature across a partition"),
+            Err(err) => assert!(matches!(err, Error::Timeout(_)), "got {:?}", err),
+        }
+    }
+    assert!(
+        started.elapsed() < COLLECT_DEADLINE * 4,
+        "abort took {:?}",
+        started.elapsed()
+    );
+}
+
+/// A partition scheduled after the final round never fires: the ceremony
+/// completes and every signer holds the same signature
+#[tokio::test]
+async fn test_partition_after_final_round_is_harmless() {
+    let relay = Arc::new(MemoryRelay::new());
+    let n = 3;
+    let shares = keygen(&relay, [0x30u8; 32], n).await;
+    let message = [0xc3u8; 32];
+
+    let mut handles = Vec::new();
+    for key_share in shares {
+        let relay = PartitionRelay::new(relay.clone(), 9);
+        handles.push(tokio::spawn(async move {
+            run_dsg(&key_share, &message, &[0, 1, 2], &relay).await.unwrap()
+        }));
+    }
+
+    let mut signatures = Vec::new();
+    for handle in handles {
+        signatures.push(handle.await.unwrap());
+    }
+    for signature in &signatures[1..] {
+        assert_eq!(signature.r, signatures[0].r);
+        assert_eq!(signature.s, signatures[0].s);
+    }
+}
@@ -0,0 +1,136 @@
+//! Wire-level protocol capture
+//!
+//! Records every envelope a [`RelayClient`](crate::RelayClient) sends or
+//! receives, with timestamps, so production incidents can be replayed and
+//! debugged offline without secrets leaving the machine. Captures are
+//! append-only JSON lines of [`CapturedEnvelope`].
+
+use dkls23_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::io::BufRead;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Whether an envelope was sent by this party or received from the relay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaptureDirection {
+    /// Posted by this party
+    Sent,
+    /// Fetched from the relay
+    Received,
+}
+
+/// One captured envelope
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturedEnvelope {
+    /// Milliseconds since the Unix epoch when the envelope was recorded
+    pub timestamp_ms: u64,
+    /// Direction of the envelope
+    pub direction: CaptureDirection,
+    /// Session identifier (hex)
+    pub session_id: String,
+    /// Round number
+    pub round: u32,
+    /// Sender party ID
+    pub from: Option<usize>,
+    /// Receiver party ID (None for broadcasts)
+    pub to: Option<usize>,
+    /// Message tag
+    pub tag: String,
+    /// Payload (hex)
+    pub payload: String,
+    /// Trace ID of the originating request, if one was set on the client
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+}
+
+impl CapturedEnvelope {
+    /// Create an envelope record timestamped at the current time
+    pub fn new(
+        direction: CaptureDirection,
+        session_id: &str,
+        round: u32,
+        from: Option<usize>,
+        to: Option<usize>,
+        tag: &str,
+        payload: &[u8],
+    ) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        Self {
+            timestamp_ms,
+            direction,
+            session_id: session_id.to_string(),
+            round,
+            from,
+            to,
+            tag: tag.to_string(),
+            payload: hex::encode(payload),
+            trace_id: None,
+        }
+    }
+
+    /// Stamp the envelope with the originating request's trace ID
+    pub fn traced(mut self, trace_id: Option<&str>) -> Self {
+        self.trace_id = trace_id.map(str::to_string);
+        self
+    }
+
+    /// Decode the payload bytes
+    pub fn payload_bytes(&self) -> Result<Vec<u8>> {
+        hex::decode(&self.payload).map_err(|e| Error::Deserialization(e.to_string()))
+    }
+}
+
+/// Read all envelopes from a capture file, in recorded order
+pub fn read_capture(path: &Path) -> Result<Vec<CapturedEnvelope>> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| Error::Internal(format!("Cannot open capture file: {}", e)))?;
+
+    let mut envelopes = Vec::new();
+    for line in std::io::BufReader::new(file).lines() {
+        let line = line.map_err(|e| Error::Internal(e.to_string()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        envelopes.push(
+            serde_json::from_str(&line).map_err(|e| Error::Deserialization(e.to_string()))?,
+        );
+    }
+
+    Ok(envelopes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_round_trip() {
+        let path = std::env::temp_dir().join(format!("capture-test-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let envelope = CapturedEnvelope::new(
+            CaptureDirection::Sent,
+            "deadbeef",
+            1,
+            Some(0),
+            None,
+            msg_relay::wire::TAG_BROADCAST,
+            b"hello",
+        );
+        let mut file = std::fs::File::create(&path).unwrap();
+        use std::io::Write;
+        writeln!(file, "{}", serde_json::to_string(&envelope).unwrap()).unwrap();
+
+        let envelopes = read_capture(&path).unwrap();
+        assert_eq!(envelopes.len(), 1);
+        assert_eq!(envelopes[0].session_id, "deadbeef");
+        assert_eq!(envelopes[0].payload_bytes().unwrap(), b"hello");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -4,10 +4,30 @@
 
 use dkls23_core::mpc::{async_trait, Relay};
 use dkls23_core::{Error, PartyId, Result, SessionId};
+use msg_relay::wire::{
+    GetMessageRequest, MessageResponse, PostMessageRequest, PostMessageResponse,
+    QueryMessagesRequest, QueryMessagesResponse, TAG_BROADCAST, TAG_DIRECT,
+};
 use reqwest::Client;
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Serialize};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
 use std::time::Duration;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
+
+pub mod capture;
+
+use capture::{CaptureDirection, CapturedEnvelope};
+
+/// Result of a relay health probe
+#[derive(Debug)]
+pub struct RelayHealth {
+    /// The health document the relay returned
+    pub body: serde_json::Value,
+    /// The relay's `Date` header (RFC 2822), for clock-skew estimation
+    pub server_date: Option<String>,
+}
 
 /// HTTP-based relay client
 pub struct RelayClient {
@@ -19,6 +39,12 @@ pub struct RelayClient {
     party_id: PartyId,
     /// Request timeout
     timeout: Duration,
+    /// Participant IDs for this ceremony, when they are not dense `0..n`
+    parties: Option<Vec<PartyId>>,
+    /// Optional wire-level capture file (JSON lines, append-only)
+    capture: Option<Mutex<std::fs::File>>,
+    /// Trace ID of the originating request, stamped into posts and captures
+    trace_id: Option<String>,
 }
 
 impl RelayClient {
@@ -28,7 +54,10 @@ impl RelayClient {
             client: Client::new(),
             url: url.trim_end_matches('/').to_string(),
             party_id,
-            timeout: Duration::from_secs(30),
+            timeout: Duration::from_secs(dkls23_core::consts::DEFAULT_RELAY_TIMEOUT_SECS),
+            parties: None,
+            capture: None,
+            trace_id: None,
         }
     }
 
@@ -38,6 +67,248 @@ impl RelayClient {
         self
     }
 
+    /// Declare the participant IDs for this ceremony
+    ///
+    /// Without this, collect loops assume the senders are the dense range
+    /// `0..count` — true for a fresh DKG but wrong for a signing subset
+    /// like {0, 3, 7} of an 8-party key. Pass the same participant list
+    /// the ceremony runs with and collects will poll exactly those IDs.
+    pub fn with_parties(mut self, parties: &[PartyId]) -> Self {
+        self.parties = Some(parties.to_vec());
+        self
+    }
+
+    /// Sender IDs a collect should poll: the declared participant list,
+    /// or the historical dense assumption when none was declared
+    fn sender_ids(&self, dense_upper: usize) -> Vec<PartyId> {
+        match &self.parties {
+            Some(parties) => parties.clone(),
+            None => (0..dense_upper).collect(),
+        }
+    }
+
+    /// Authenticate every request with a bearer token
+    ///
+    /// Relays started with `--auth-token` reject unauthenticated /v1
+    /// requests; pass the current credential here. During a rotation the
+    /// relay honors old and new tokens for an overlap window, so clients
+    /// can switch at their own pace.
+    pub fn with_auth_token(mut self, token: &str) -> Result<Self> {
+        let mut value =
+            reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+                .map_err(|e| Error::InvalidConfig(format!("Invalid auth token: {}", e)))?;
+        value.set_sensitive(true);
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::AUTHORIZATION, value);
+        self.client = Client::builder()
+            .default_headers(headers)
+            .build()
+            .map_err(|e| Error::Internal(format!("Cannot build HTTP client: {}", e)))?;
+        Ok(self)
+    }
+
+    /// List the sessions with messages stored on the relay
+    ///
+    /// Operators use this to see what is in flight — for example to
+    /// confirm a relay has drained before decommissioning it.
+    pub async fn sessions(&self) -> Result<Vec<msg_relay::SessionSummary>> {
+        let response = self
+            .client
+            .get(format!("{}/v1/sessions", self.url))
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|e| Error::Relay(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let problem = response.json::<msg_relay::Problem>().await.ok();
+            return Err(problem_to_error(status, problem));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| Error::Relay(e.to_string()))
+    }
+
+    /// Record every envelope sent/received to a capture file
+    ///
+    /// The file is append-only JSON lines of [`CapturedEnvelope`]; use
+    /// [`capture::read_capture`] to feed it back through tooling.
+    pub fn with_capture(mut self, path: &Path) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| Error::Internal(format!("Cannot open capture file: {}", e)))?;
+        self.capture = Some(Mutex::new(file));
+        Ok(self)
+    }
+
+    /// Stamp every post and capture record with a trace ID
+    ///
+    /// The ID travels with each posted message into the relay's session
+    /// metadata and is written into capture envelopes, so a signature can
+    /// be pivoted back to exactly one request trace across all systems.
+    pub fn with_trace_id(mut self, trace_id: &str) -> Self {
+        self.trace_id = Some(trace_id.to_string());
+        self
+    }
+
+    /// Probe the relay's health endpoint
+    ///
+    /// Returns the health document plus the relay's `Date` header, which
+    /// callers can compare against their own clock to estimate skew.
+    pub async fn health(&self) -> Result<RelayHealth> {
+        let response = self
+            .client
+            .get(format!("{}/health", self.url))
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|e| Error::Relay(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(Error::Relay(format!(
+                "Health probe returned {}",
+                response.status()
+            )));
+        }
+
+        let server_date = response
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+        let body = response
+            .json()
+            .await
+            .map_err(|e| Error::Relay(e.to_string()))?;
+        Ok(RelayHealth { body, server_date })
+    }
+
+    /// Fetch per-session stats from the relay
+    ///
+    /// Returns `None` if the relay has no messages for the session yet.
+    /// Callers can use the per-sender timestamps to detect a peer that has
+    /// gone quiet and trigger liveness handling before a round times out.
+    pub async fn session_stats(
+        &self,
+        session_id: &SessionId,
+    ) -> Result<Option<msg_relay::SessionStats>> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/v1/sessions/{}/stats",
+                self.url,
+                hex::encode(session_id)
+            ))
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|e| Error::Relay(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            let problem = response.json::<msg_relay::Problem>().await.ok();
+            return Err(problem_to_error(status, problem));
+        }
+
+        let stats = response
+            .json()
+            .await
+            .map_err(|e| Error::Relay(e.to_string()))?;
+        Ok(Some(stats))
+    }
+
+    /// Fetch the equivocation evidence the relay recorded for a session
+    ///
+    /// The relay records a [`msg_relay::ConflictEvent`] whenever a message
+    /// ID is re-posted with different content. An empty list means no
+    /// conflicts — the healthy case.
+    pub async fn conflicts(
+        &self,
+        session_id: &SessionId,
+    ) -> Result<Vec<msg_relay::ConflictEvent>> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/v1/sessions/{}/conflicts",
+                self.url,
+                hex::encode(session_id)
+            ))
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|e| Error::Relay(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let problem = response.json::<msg_relay::Problem>().await.ok();
+            return Err(problem_to_error(status, problem));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| Error::Relay(e.to_string()))
+    }
+
+    /// Abort a collect if the relay has recorded equivocation
+    ///
+    /// Best effort on the transport: a relay that predates the conflicts
+    /// endpoint, or a transient fetch failure, leaves the collect running
+    /// — the watchdog is an early exit, not a liveness dependency. A
+    /// recorded conflict is fatal: the offending party told peers
+    /// different things and the ceremony cannot terminate honestly.
+    async fn check_equivocation(&self, session_id: &SessionId) -> Result<()> {
+        let Ok(conflicts) = self.conflicts(session_id).await else {
+            return Ok(());
+        };
+        let Some(conflict) = conflicts.first() else {
+            return Ok(());
+        };
+
+        warn!(
+            round = conflict.round,
+            from = ?conflict.from,
+            stored_hash = %conflict.stored_hash,
+            conflicting_hash = %conflict.conflicting_hash,
+            "Relay recorded conflicting posts; aborting session"
+        );
+        match conflict.from {
+            Some(party) => Err(Error::MaliciousParty(party)),
+            None => Err(Error::Relay(format!(
+                "Conflicting unattributed posts in round {}",
+                conflict.round
+            ))),
+        }
+    }
+
+    /// Append one envelope to the capture file (best effort)
+    fn record(&self, envelope: CapturedEnvelope) {
+        let Some(file) = &self.capture else {
+            return;
+        };
+
+        let envelope = envelope.traced(self.trace_id.as_deref());
+
+        let mut file = match file.lock() {
+            Ok(file) => file,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Err(e) = serde_json::to_string(&envelope)
+            .map_err(std::io::Error::other)
+            .and_then(|line| writeln!(file, "{}", line))
+        {
+            warn!(error = %e, "Failed to write capture record");
+        }
+    }
+
     /// Post a message to the relay
     #[instrument(skip(self, payload))]
     async fn post_message(
@@ -56,7 +327,10 @@ impl RelayClient {
             from: Some(self.party_id),
             to,
             tag: tag.to_string(),
+            seq: 0,
             payload: STANDARD.encode(payload),
+            trace_id: self.trace_id.clone(),
+            hops: Vec::new(),
         };
 
         let response = self
@@ -69,12 +343,35 @@ impl RelayClient {
             .map_err(|e| Error::Relay(e.to_string()))?;
 
         if !response.status().is_success() {
-            return Err(Error::Relay(format!(
-                "POST failed with status: {}",
-                response.status()
-            )));
+            let status = response.status();
+            let problem = response.json::<msg_relay::Problem>().await.ok();
+            return Err(problem_to_error(status, problem));
+        }
+
+        // The relay echoes the content hash of what it stored; compare it
+        // against what we sent so a corrupting relay is caught immediately
+        if let Ok(posted) = response.json::<PostMessageResponse>().await {
+            if let Some(stored_hash) = posted.content_hash {
+                let local_hash = msg_relay::content_hash(payload);
+                if stored_hash != local_hash {
+                    return Err(Error::Relay(format!(
+                        "Relay stored a different payload: content hash {} != {}",
+                        stored_hash, local_hash
+                    )));
+                }
+            }
         }
 
+        self.record(CapturedEnvelope::new(
+            CaptureDirection::Sent,
+            &hex::encode(session_id),
+            round,
+            Some(self.party_id),
+            to,
+            tag,
+            payload,
+        ));
+
         debug!(round, to = ?to, "Message posted");
         Ok(())
     }
@@ -97,6 +394,7 @@ impl RelayClient {
             from,
             to,
             tag: tag.to_string(),
+            seq: 0,
         };
 
         let response = self
@@ -109,10 +407,9 @@ impl RelayClient {
             .map_err(|e| Error::Relay(e.to_string()))?;
 
         if !response.status().is_success() {
-            return Err(Error::Relay(format!(
-                "GET failed with status: {}",
-                response.status()
-            )));
+            let status = response.status();
+            let problem = response.json::<msg_relay::Problem>().await.ok();
+            return Err(problem_to_error(status, problem));
         }
 
         let msg_response: MessageResponse = response
@@ -121,13 +418,151 @@ impl RelayClient {
             .map_err(|e| Error::Relay(e.to_string()))?;
 
         if msg_response.found {
-            let payload = STANDARD.decode(&msg_response.payload.unwrap_or_default())
+            let payload = STANDARD.decode(msg_response.payload.unwrap_or_default())
                 .map_err(|e| Error::Deserialization(e.to_string()))?;
+            self.record(CapturedEnvelope::new(
+                CaptureDirection::Received,
+                &hex::encode(session_id),
+                round,
+                from,
+                to,
+                tag,
+                &payload,
+            ));
             Ok(Some(payload))
         } else {
             Ok(None)
         }
     }
+
+    /// Query a round's messages, filtered server-side
+    ///
+    /// `tag_prefix` restricts to tags starting with the prefix and `from`
+    /// to an explicit sender set (e.g. the current signing subset), so
+    /// pollers of large multi-protocol sessions do not download messages
+    /// they would discard. `None` leaves a dimension unconstrained.
+    pub async fn query_messages(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        tag_prefix: Option<&str>,
+        from: Option<&[PartyId]>,
+    ) -> Result<Vec<QueriedMessage>> {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        let req = QueryMessagesRequest {
+            session_id: hex::encode(session_id),
+            round,
+            tag_prefix: tag_prefix.map(|prefix| prefix.to_string()),
+            from: from.map(|senders| senders.to_vec()),
+        };
+
+        let response = self
+            .client
+            .get(format!("{}/v1/msg/query", self.url))
+            .json(&req)
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|e| Error::Relay(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let problem = response.json::<msg_relay::Problem>().await.ok();
+            return Err(problem_to_error(status, problem));
+        }
+
+        let body: QueryMessagesResponse = response
+            .json()
+            .await
+            .map_err(|e| Error::Relay(e.to_string()))?;
+
+        let mut messages = Vec::with_capacity(body.messages.len());
+        for msg in body.messages {
+            let payload = STANDARD
+                .decode(&msg.payload)
+                .map_err(|e| Error::Deserialization(e.to_string()))?;
+            self.record(CapturedEnvelope::new(
+                CaptureDirection::Received,
+                &hex::encode(session_id),
+                msg.round,
+                msg.from,
+                msg.to,
+                &msg.tag,
+                &payload,
+            ));
+            messages.push(QueriedMessage {
+                round: msg.round,
+                from: msg.from,
+                to: msg.to,
+                tag: msg.tag,
+                seq: msg.seq,
+                payload,
+            });
+        }
+        Ok(messages)
+    }
+}
+
+/// One message returned by [`RelayClient::query_messages`], payload decoded
+#[derive(Debug, Clone)]
+pub struct QueriedMessage {
+    /// Round number
+    pub round: u32,
+    /// Sender party ID (None for unattributed messages)
+    pub from: Option<usize>,
+    /// Receiver party ID (None for broadcasts)
+    pub to: Option<usize>,
+    /// Message tag
+    pub tag: String,
+    /// Per-sender sequence number
+    pub seq: u64,
+    /// Decoded message payload
+    pub payload: Vec<u8>,
+}
+
+/// Translate a problem document (or bare error status) into a core error
+///
+/// The relay returns RFC 7807 problem+json with machine-readable codes;
+/// callers get the closest entry in the core error taxonomy instead of an
+/// opaque status string.
+fn problem_to_error(status: reqwest::StatusCode, problem: Option<msg_relay::Problem>) -> Error {
+    use msg_relay::ProblemCode;
+
+    let Some(problem) = problem else {
+        return Error::Relay(format!("Relay request failed with status: {}", status));
+    };
+
+    match problem.code {
+        ProblemCode::SessionExpired => Error::SessionNotFound(problem.detail),
+        ProblemCode::NotFound => Error::SessionNotFound(problem.detail),
+        ProblemCode::PayloadTooLarge => Error::Serialization(problem.detail),
+        ProblemCode::DuplicateMessage => {
+            Error::Relay(format!("Duplicate message: {}", problem.detail))
+        }
+        ProblemCode::EquivocationDetected => {
+            Error::Relay(format!("Equivocation detected: {}", problem.detail))
+        }
+        ProblemCode::Unauthorized => {
+            Error::Relay(format!("Unauthorized: {}", problem.detail))
+        }
+        ProblemCode::InvalidRequest => Error::Serialization(problem.detail),
+        ProblemCode::Internal => Error::Relay(problem.detail),
+    }
+}
+
+/// Initial delay between polling passes
+const POLL_MIN: Duration = Duration::from_millis(100);
+/// Maximum delay between polling passes
+const POLL_MAX: Duration = Duration::from_millis(800);
+
+/// Back off while a round is quiet, reset as soon as messages arrive
+fn next_poll_delay(current: Duration, progressed: bool) -> Duration {
+    if progressed {
+        POLL_MIN
+    } else {
+        (current * 2).min(POLL_MAX)
+    }
 }
 
 fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>> {
@@ -147,7 +582,7 @@ impl Relay for RelayClient {
         message: &T,
     ) -> Result<()> {
         let payload = serialize(message)?;
-        self.post_message(session_id, round, None, "broadcast", &payload)
+        self.post_message(session_id, round, None, TAG_BROADCAST, &payload)
             .await
     }
 
@@ -159,7 +594,7 @@ impl Relay for RelayClient {
         message: &T,
     ) -> Result<()> {
         let payload = serialize(message)?;
-        self.post_message(session_id, round, Some(to), "direct", &payload)
+        self.post_message(session_id, round, Some(to), TAG_DIRECT, &payload)
             .await
     }
 
@@ -169,35 +604,53 @@ impl Relay for RelayClient {
         round: u32,
         count: usize,
     ) -> Result<Vec<T>> {
-        let mut messages = Vec::new();
+        let mut messages: Vec<(PartyId, T)> = Vec::new();
         let mut attempts = 0;
+        let mut delay = POLL_MIN;
         const MAX_ATTEMPTS: usize = 100;
 
-        while messages.len() < count && attempts < MAX_ATTEMPTS {
-            for party_id in 0..count {
+        // A sender is polled until its message arrives and never again, so
+        // a re-poll can never collect the same message twice
+        let mut pending = self.sender_ids(count);
+        while !pending.is_empty() && attempts < MAX_ATTEMPTS {
+            let before = pending.len();
+            let mut still_pending = Vec::new();
+            for party_id in pending {
                 if let Some(payload) = self
-                    .get_message(session_id, round, Some(party_id), None, "broadcast")
+                    .get_message(session_id, round, Some(party_id), None, TAG_BROADCAST)
                     .await?
                 {
                     let msg: T = deserialize(&payload)?;
-                    messages.push(msg);
+                    messages.push((party_id, msg));
+                } else {
+                    still_pending.push(party_id);
                 }
             }
+            pending = still_pending;
 
-            if messages.len() < count {
-                tokio::time::sleep(Duration::from_millis(100)).await;
+            if !pending.is_empty() {
+                // A quiet pass may mean a peer is stalled because it
+                // equivocated; surface that instead of timing out
+                if pending.len() == before {
+                    self.check_equivocation(session_id).await?;
+                }
+                delay = next_poll_delay(delay, pending.len() < before);
+                tokio::time::sleep(delay).await;
                 attempts += 1;
             }
         }
 
         if messages.len() < count {
             return Err(Error::Timeout(format!(
-                "Waiting for {} broadcast messages in round {}",
-                count, round
+                "Waiting for broadcast messages in round {}; missing senders {:?}",
+                round, pending
             )));
         }
 
-        Ok(messages)
+        // Per-sender polling yields one message per sender; return them in
+        // the sender order the Relay contract specifies
+        messages.sort_by_key(|(sender, _)| *sender);
+        Ok(messages.into_iter().map(|(_, msg)| msg).collect())
     }
 
     async fn collect_direct<T: DeserializeOwned + Send>(
@@ -207,63 +660,80 @@ impl Relay for RelayClient {
         my_id: PartyId,
         count: usize,
     ) -> Result<Vec<T>> {
-        let mut messages = Vec::new();
+        let mut messages: Vec<(PartyId, T)> = Vec::new();
         let mut attempts = 0;
+        let mut delay = POLL_MIN;
         const MAX_ATTEMPTS: usize = 100;
 
-        while messages.len() < count && attempts < MAX_ATTEMPTS {
-            // Try to get messages from each possible sender
-            for sender in 0..count + 1 {
-                if sender == my_id {
-                    continue;
-                }
+        // As in collect_broadcasts, each sender leaves the polling set the
+        // moment its message arrives, so nothing is fetched twice
+        let mut pending: Vec<PartyId> = self
+            .sender_ids(count + 1)
+            .into_iter()
+            .filter(|&sender| sender != my_id)
+            .collect();
+        while !pending.is_empty() && attempts < MAX_ATTEMPTS {
+            let before = pending.len();
+            let mut still_pending = Vec::new();
+            for sender in pending {
                 if let Some(payload) = self
-                    .get_message(session_id, round, Some(sender), Some(my_id), "direct")
+                    .get_message(session_id, round, Some(sender), Some(my_id), TAG_DIRECT)
                     .await?
                 {
                     let msg: T = deserialize(&payload)?;
-                    messages.push(msg);
+                    messages.push((sender, msg));
+                } else {
+                    still_pending.push(sender);
                 }
             }
+            pending = still_pending;
 
-            if messages.len() < count {
-                tokio::time::sleep(Duration::from_millis(100)).await;
+            if !pending.is_empty() {
+                // As above: a quiet pass warrants an equivocation check
+                if pending.len() == before {
+                    self.check_equivocation(session_id).await?;
+                }
+                delay = next_poll_delay(delay, pending.len() < before);
+                tokio::time::sleep(delay).await;
                 attempts += 1;
             }
         }
 
         if messages.len() < count {
             return Err(Error::Timeout(format!(
-                "Waiting for {} direct messages in round {}",
-                count, round
+                "Waiting for direct messages in round {}; missing senders {:?}",
+                round, pending
             )));
         }
 
-        Ok(messages)
+        messages.sort_by_key(|(sender, _)| *sender);
+        Ok(messages.into_iter().map(|(_, msg)| msg).collect())
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct PostMessageRequest {
-    session_id: String,
-    round: u32,
-    from: Option<usize>,
-    to: Option<usize>,
-    tag: String,
-    payload: String,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use msg_relay::{Problem, ProblemCode};
 
-#[derive(Debug, Serialize, Deserialize)]
-struct GetMessageRequest {
-    session_id: String,
-    round: u32,
-    from: Option<usize>,
-    to: Option<usize>,
-    tag: String,
-}
+    #[test]
+    fn test_problem_mapping() {
+        let expired = Problem::new(ProblemCode::SessionExpired, 410, "gone");
+        assert!(matches!(
+            problem_to_error(reqwest::StatusCode::GONE, Some(expired)),
+            Error::SessionNotFound(_)
+        ));
 
-#[derive(Debug, Serialize, Deserialize)]
-struct MessageResponse {
-    found: bool,
-    payload: Option<String>,
+        let too_large = Problem::new(ProblemCode::PayloadTooLarge, 413, "limit");
+        assert!(matches!(
+            problem_to_error(reqwest::StatusCode::PAYLOAD_TOO_LARGE, Some(too_large)),
+            Error::Serialization(_)
+        ));
+
+        // A non-problem error body still yields a usable relay error
+        assert!(matches!(
+            problem_to_error(reqwest::StatusCode::BAD_GATEWAY, None),
+            Error::Relay(_)
+        ));
+    }
 }
@@ -4,14 +4,26 @@
 
 use anyhow::Result;
 use axum::{
-    extract::{Path, State, WebSocketUpgrade},
-    http::StatusCode,
-    response::IntoResponse,
-    routing::{get, post},
+    extract::{Path, Request, State, WebSocketUpgrade},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
     Json, Router,
 };
+use chrono::{DateTime, Utc};
 use clap::Parser;
-use msg_relay::{MessageId, MessageStore, StoredMessage};
+use msg_relay::shipping::{
+    AuditShipper, DirObjectStore, HttpObjectStore, ObjectStore, ShipperConfig,
+};
+use msg_relay::wire::{
+    GetMessageRequest, MessageResponse, PostMessageRequest, QueriedMessage,
+    QueryMessagesRequest, QueryMessagesResponse,
+};
+use msg_relay::{
+    MessageFilter, MessageId, MessageStore, Problem, ProblemCode, StoreLimits,
+    PROBLEM_CONTENT_TYPE,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tower_http::cors::CorsLayer;
@@ -34,40 +46,197 @@ struct Args {
     /// Message TTL in seconds
     #[arg(long, default_value = "3600")]
     ttl: i64,
+
+    /// Maximum number of concurrent sessions before LRU eviction
+    #[arg(long, default_value = "1024")]
+    max_sessions: usize,
+
+    /// Maximum total stored payload bytes before LRU eviction
+    #[arg(long, default_value = "268435456")]
+    max_bytes: usize,
+
+    /// Ship audit segments to this S3/GCS-compatible base URL
+    #[arg(long)]
+    audit_ship_url: Option<String>,
+
+    /// Ship audit segments to this local directory (e.g. a mounted bucket)
+    #[arg(long)]
+    audit_ship_dir: Option<std::path::PathBuf>,
+
+    /// Authorization header value for audit uploads
+    #[arg(long)]
+    audit_ship_auth: Option<String>,
+
+    /// Audit records per shipped segment
+    #[arg(long, default_value = "1024")]
+    audit_segment_records: usize,
+
+    /// Bearer token required on /v1 endpoints (repeatable so two tokens
+    /// stay valid during a rotation window; no tokens disables auth)
+    #[arg(long)]
+    auth_token: Vec<String>,
+
+    /// Delay every /v1 request by this much (test mode, e.g. "500ms")
+    #[arg(long, value_parser = parse_duration)]
+    simulate_latency: Option<std::time::Duration>,
+
+    /// Fail this fraction of /v1 requests with a 503 (test mode, e.g. "2%")
+    #[arg(long, value_parser = parse_loss)]
+    simulate_loss: Option<f64>,
+}
+
+/// Parse a duration argument like "500ms" or "2s"
+fn parse_duration(value: &str) -> Result<std::time::Duration, String> {
+    let unit_at = value
+        .find(|c: char| c.is_ascii_alphabetic())
+        .ok_or_else(|| format!("'{value}' is missing a unit (use e.g. 500ms or 2s)"))?;
+    let (number, unit) = value.split_at(unit_at);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("'{number}' is not a whole number"))?;
+    match unit {
+        "ms" => Ok(std::time::Duration::from_millis(number)),
+        "s" => Ok(std::time::Duration::from_secs(number)),
+        _ => Err(format!("unknown unit '{unit}' (use ms or s)")),
+    }
+}
+
+/// Parse a loss argument like "2%" into a fraction in [0, 1]
+fn parse_loss(value: &str) -> Result<f64, String> {
+    let percent: f64 = value
+        .strip_suffix('%')
+        .unwrap_or(value)
+        .parse()
+        .map_err(|_| format!("'{value}' is not a percentage"))?;
+    if !(0.0..=100.0).contains(&percent) {
+        return Err(format!("loss {percent}% is outside 0-100%"));
+    }
+    Ok(percent / 100.0)
+}
+
+/// WAN/mobile link conditions injected in front of the /v1 surface
+///
+/// Lets client retry/backoff, chunking and round deadlines be exercised
+/// against realistic latency and loss without external traffic shaping.
+/// Lost requests fail with a 503 after the simulated delay, the way a
+/// congested link times out rather than failing fast.
+struct SimulatedLink {
+    latency: std::time::Duration,
+    loss: f64,
 }
 
+/// Messages stop being forwarded after traversing this many relays
+///
+/// Deduplication (the 409 on an existing message ID) is what actually
+/// breaks forwarding loops; the hop cap just bounds the blast radius of a
+/// misconfigured mesh.
+const MAX_HOPS: usize = 4;
+
 /// Application state
 struct AppState {
     store: MessageStore,
+    /// This relay's identity in hop lists
+    relay_id: String,
+    /// Peer relays that messages are forwarded to
     peers: Vec<String>,
+    /// HTTP client for peer forwarding
+    forwarder: reqwest::Client,
+    audit: Option<AuditShipper>,
+    /// Accepted bearer tokens; `None` disables authentication
+    tokens: Option<TokenSet>,
+    /// Degraded-link simulation for client testing; `None` in production
+    simulate: Option<SimulatedLink>,
 }
 
-/// Request to post a message
-#[derive(Debug, Serialize, Deserialize)]
-struct PostMessageRequest {
-    session_id: String,
-    round: u32,
-    from: Option<usize>,
-    to: Option<usize>,
-    tag: String,
-    payload: String, // base64 encoded
+/// One accepted bearer token; rotated-out tokens carry an expiry
+struct TokenEntry {
+    token: String,
+    expires_at: Option<DateTime<Utc>>,
 }
 
-/// Request to get a message
-#[derive(Debug, Serialize, Deserialize)]
-struct GetMessageRequest {
-    session_id: String,
-    round: u32,
-    from: Option<usize>,
-    to: Option<usize>,
-    tag: String,
+/// The set of bearer tokens currently accepted on /v1 endpoints
+///
+/// Rotation keeps the old tokens valid for an overlap window so parties
+/// mid-ceremony on the old credential finish cleanly while new sessions
+/// move to the new one — credentials change without downtime.
+struct TokenSet {
+    entries: std::sync::RwLock<Vec<TokenEntry>>,
+}
+
+impl TokenSet {
+    fn new(tokens: Vec<String>) -> Self {
+        Self {
+            entries: std::sync::RwLock::new(
+                tokens
+                    .into_iter()
+                    .map(|token| TokenEntry {
+                        token,
+                        expires_at: None,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Whether a presented token matches any non-expired entry
+    fn is_valid(&self, presented: &str) -> bool {
+        let now = Utc::now();
+        self.entries
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .any(|entry| {
+                entry.token == presented && entry.expires_at.is_none_or(|expiry| expiry > now)
+            })
+    }
+
+    /// Accept `new_token` and start the expiry clock on every token that
+    /// does not already have one; returns the number of live tokens
+    fn rotate(&self, new_token: String, overlap: chrono::Duration) -> usize {
+        let now = Utc::now();
+        let mut entries = self
+            .entries
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        entries.retain(|entry| entry.expires_at.is_none_or(|expiry| expiry > now));
+        for entry in entries.iter_mut() {
+            if entry.expires_at.is_none() {
+                entry.expires_at = Some(now + overlap);
+            }
+        }
+        entries.push(TokenEntry {
+            token: new_token,
+            expires_at: None,
+        });
+        entries.len()
+    }
+}
+
+impl AppState {
+    /// Buffer one audit record, best effort; shipping must never fail a post
+    async fn audit_record(&self, record: serde_json::Value) {
+        let Some(audit) = &self.audit else {
+            return;
+        };
+        if let Err(e) = audit.append(record.to_string().as_bytes()).await {
+            tracing::warn!(error = %e, "Failed to ship audit segment");
+        }
+    }
 }
 
-/// Message response
-#[derive(Debug, Serialize, Deserialize)]
-struct MessageResponse {
-    found: bool,
-    payload: Option<String>, // base64 encoded
+/// Largest accepted message payload after base64 decoding
+const MAX_PAYLOAD_BYTES: usize = 1024 * 1024;
+
+/// Render an RFC 7807 problem document with the proper content type
+fn problem_response(problem: Problem) -> Response {
+    let status =
+        StatusCode::from_u16(problem.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (
+        status,
+        [(header::CONTENT_TYPE, PROBLEM_CONTENT_TYPE)],
+        Json(problem),
+    )
+        .into_response()
 }
 
 #[tokio::main]
@@ -87,30 +256,112 @@ async fn main() -> Result<()> {
         listen = %args.listen,
         peers = ?args.peer,
         ttl = args.ttl,
+        max_sessions = args.max_sessions,
+        max_bytes = args.max_bytes,
         "Starting message relay service"
     );
 
+    let limits = StoreLimits {
+        max_sessions: args.max_sessions,
+        max_total_bytes: args.max_bytes,
+    };
+    let audit_store: Option<Box<dyn ObjectStore>> = match (&args.audit_ship_dir, &args.audit_ship_url) {
+        (Some(dir), _) => Some(Box::new(DirObjectStore::new(dir))),
+        (None, Some(url)) => {
+            let mut store = HttpObjectStore::new(url);
+            if let Some(auth) = &args.audit_ship_auth {
+                store = store.with_auth_header(auth);
+            }
+            Some(Box::new(store))
+        }
+        (None, None) => None,
+    };
+    let simulate = match (args.simulate_latency, args.simulate_loss) {
+        (None, None) => None,
+        (latency, loss) => {
+            let link = SimulatedLink {
+                latency: latency.unwrap_or_default(),
+                loss: loss.unwrap_or_default(),
+            };
+            tracing::warn!(
+                latency_ms = link.latency.as_millis() as u64,
+                loss_percent = link.loss * 100.0,
+                "Simulating a degraded link; this is a test mode"
+            );
+            Some(link)
+        }
+    };
+
     let state = Arc::new(AppState {
-        store: MessageStore::new(args.ttl),
+        store: MessageStore::with_limits(args.ttl, limits),
+        relay_id: uuid::Uuid::new_v4().to_string(),
         peers: args.peer,
+        forwarder: reqwest::Client::new(),
+        audit: audit_store.map(|store| {
+            AuditShipper::new(
+                store,
+                ShipperConfig {
+                    max_records: args.audit_segment_records,
+                    ..ShipperConfig::default()
+                },
+            )
+        }),
+        tokens: if args.auth_token.is_empty() {
+            None
+        } else {
+            Some(TokenSet::new(args.auth_token))
+        },
+        simulate,
     });
 
-    // Spawn cleanup task
-    let cleanup_store = state.store.clone();
+    let shutdown_state = state.clone();
+    let shutdown_store = state.store.clone();
+
+    // Spawn cleanup task; also flushes a quiet audit buffer so records do
+    // not sit unshipped for longer than one interval
+    let cleanup_state = state.clone();
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
         loop {
             interval.tick().await;
-            cleanup_store.cleanup();
+            cleanup_state.store.cleanup();
+            if let Some(audit) = &cleanup_state.audit {
+                if let Err(e) = audit.flush().await {
+                    tracing::warn!(error = %e, "Periodic audit flush failed");
+                }
+            }
         }
     });
 
-    let app = Router::new()
-        .route("/health", get(health))
+    // Everything under /v1 requires a bearer token when auth is
+    // configured; the health probe stays open for load balancers
+    let api = Router::new()
         .route("/v1/msg", post(post_message))
         .route("/v1/msg", get(get_message))
+        .route("/v1/msg/query", get(query_messages))
         .route("/v1/msg/:hash", get(get_message_by_hash))
+        .route("/v1/metrics", get(metrics))
+        .route("/v1/sessions", get(list_sessions))
+        .route("/v1/sessions/:session_id", delete(delete_session))
+        .route("/v1/sessions/:session_id/stats", get(session_stats))
+        .route(
+            "/v1/sessions/:session_id/conflicts",
+            get(session_conflicts),
+        )
         .route("/v1/ws", get(websocket_handler))
+        .route("/v1/admin/token/rotate", post(rotate_token))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_token,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            simulate_link,
+        ));
+
+    let app = Router::new()
+        .route("/health", get(health))
+        .merge(api)
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive())
         .with_state(state);
@@ -118,11 +369,52 @@ async fn main() -> Result<()> {
     let listener = tokio::net::TcpListener::bind(&args.listen).await?;
     info!(address = %args.listen, "Listening");
 
-    axum::serve(listener, app).await?;
+    // Tell the service manager we are up, arm its watchdog if requested,
+    // and drain cleanly on a stop request
+    msg_relay::service::notify_ready();
+    msg_relay::service::spawn_watchdog();
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            msg_relay::service::shutdown_signal().await;
+            msg_relay::service::notify_stopping();
+            let in_flight = shutdown_store.sessions().len();
+            info!(in_flight, "Shutdown requested; draining connections");
+            if let Some(audit) = &shutdown_state.audit {
+                if let Err(e) = audit.flush().await {
+                    tracing::warn!(error = %e, "Final audit flush failed");
+                }
+            }
+        })
+        .await?;
 
     Ok(())
 }
 
+/// Test-mode middleware simulating a degraded network link
+///
+/// A no-op unless `--simulate-latency`/`--simulate-loss` were given.
+async fn simulate_link(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(link) = &state.simulate else {
+        return next.run(request).await;
+    };
+    if !link.latency.is_zero() {
+        tokio::time::sleep(link.latency).await;
+    }
+    if link.loss > 0.0 && rand::Rng::gen::<f64>(&mut rand::thread_rng()) < link.loss {
+        return problem_response(Problem::new(
+            ProblemCode::Internal,
+            503,
+            "Simulated message loss",
+        ));
+    }
+    next.run(request).await
+}
+
 /// Health check endpoint
 async fn health() -> impl IntoResponse {
     Json(serde_json::json!({
@@ -132,42 +424,269 @@ async fn health() -> impl IntoResponse {
     }))
 }
 
+/// Reject /v1 requests without a currently valid bearer token
+async fn require_token(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(tokens) = &state.tokens else {
+        return next.run(request).await;
+    };
+
+    let presented = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match presented {
+        Some(token) if tokens.is_valid(token) => next.run(request).await,
+        _ => problem_response(Problem::new(
+            ProblemCode::Unauthorized,
+            401,
+            "A valid bearer token is required",
+        )),
+    }
+}
+
+/// Request to rotate the accepted bearer token
+#[derive(Debug, Deserialize)]
+struct RotateTokenRequest {
+    /// The token new requests should switch to
+    new_token: String,
+    /// How long the outgoing tokens stay valid alongside the new one
+    #[serde(default = "default_overlap_seconds")]
+    overlap_seconds: u64,
+}
+
+fn default_overlap_seconds() -> u64 {
+    300
+}
+
+/// Rotation acknowledgement
+#[derive(Debug, Serialize)]
+struct RotateTokenResponse {
+    /// Tokens currently accepted, the new one included
+    active_tokens: usize,
+    /// The dual-validity window granted to the outgoing tokens
+    overlap_seconds: u64,
+}
+
+/// Rotate the bearer token with a dual-validity window
+///
+/// The new token is accepted immediately; every existing token stays
+/// valid for `overlap_seconds` so ceremonies in flight on the old
+/// credential complete before it dies. Authenticated with a currently
+/// valid token like every other /v1 request.
+async fn rotate_token(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<RotateTokenRequest>,
+) -> Response {
+    let Some(tokens) = &state.tokens else {
+        return problem_response(Problem::new(
+            ProblemCode::InvalidRequest,
+            400,
+            "Token rotation requires the relay to be started with --auth-token",
+        ));
+    };
+    if req.new_token.is_empty() {
+        return problem_response(Problem::new(
+            ProblemCode::InvalidRequest,
+            400,
+            "The new token must not be empty",
+        ));
+    }
+
+    let active_tokens = tokens.rotate(
+        req.new_token,
+        chrono::Duration::seconds(req.overlap_seconds as i64),
+    );
+    info!(
+        active_tokens,
+        overlap_seconds = req.overlap_seconds,
+        "Bearer token rotated"
+    );
+
+    Json(RotateTokenResponse {
+        active_tokens,
+        overlap_seconds: req.overlap_seconds,
+    })
+    .into_response()
+}
+
 /// Post a message to the relay
 async fn post_message(
     State(state): State<Arc<AppState>>,
     Json(req): Json<PostMessageRequest>,
-) -> impl IntoResponse {
-    let id = MessageId::new(&req.session_id, req.round, req.from, req.to, &req.tag);
+) -> Response {
+    let id = MessageId::new(&req.session_id, req.round, req.from, req.to, &req.tag)
+        .with_seq(req.seq);
 
     let payload = match b64::decode(&req.payload) {
         Ok(p) => p,
         Err(e) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({ "error": format!("Invalid base64: {}", e) })),
-            );
+            return problem_response(Problem::new(
+                ProblemCode::InvalidRequest,
+                400,
+                format!("Invalid base64 payload: {}", e),
+            ));
         }
     };
 
-    if let Err(e) = state.store.put(id.clone(), payload) {
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": e.to_string() })),
-        );
+    if payload.len() > MAX_PAYLOAD_BYTES {
+        return problem_response(Problem::new(
+            ProblemCode::PayloadTooLarge,
+            413,
+            format!(
+                "Payload is {} bytes; the limit is {} bytes",
+                payload.len(),
+                MAX_PAYLOAD_BYTES
+            ),
+        ));
     }
 
+    if state.store.exists(&id) {
+        // An identical re-post is an honest retry or a mesh peer's
+        // forward; the same ID carrying different bytes is the sender
+        // equivocating, and the evidence is recorded for the session's
+        // watchdogs to find
+        if let Some(conflict) = state.store.record_conflict(&id, &payload) {
+            tracing::warn!(
+                session_id = %req.session_id,
+                round = req.round,
+                from = ?req.from,
+                stored_hash = %conflict.stored_hash,
+                conflicting_hash = %conflict.conflicting_hash,
+                "Conflicting payload for a stored message; equivocation recorded"
+            );
+            state
+                .audit_record(serde_json::json!({
+                    "at": chrono::Utc::now().to_rfc3339(),
+                    "event": "equivocation_detected",
+                    "session_id": req.session_id,
+                    "round": req.round,
+                    "from": req.from,
+                    "to": req.to,
+                    "tag": req.tag,
+                    "seq": req.seq,
+                    "stored_hash": conflict.stored_hash,
+                    "conflicting_hash": conflict.conflicting_hash,
+                    "trace_id": req.trace_id,
+                }))
+                .await;
+            return problem_response(Problem::new(
+                ProblemCode::EquivocationDetected,
+                409,
+                format!(
+                    "Message {} was already stored with different content",
+                    id.hash()
+                ),
+            ));
+        }
+        return problem_response(Problem::new(
+            ProblemCode::DuplicateMessage,
+            409,
+            format!("Message {} was already stored", id.hash()),
+        ));
+    }
+
+    let content_hash = match state.store.put_traced(id.clone(), payload, req.trace_id.clone()) {
+        Ok(hash) => hash,
+        Err(e) => return problem_response(Problem::from_relay_error(&e)),
+    };
+
     info!(
         session_id = %req.session_id,
         round = req.round,
         from = ?req.from,
         to = ?req.to,
+        trace_id = ?req.trace_id,
         "Message stored"
     );
 
+    state
+        .audit_record(serde_json::json!({
+            "at": chrono::Utc::now().to_rfc3339(),
+            "event": "message_stored",
+            "session_id": req.session_id,
+            "round": req.round,
+            "from": req.from,
+            "to": req.to,
+            "tag": req.tag,
+            "seq": req.seq,
+            "content_hash": content_hash,
+            "trace_id": req.trace_id,
+            "hops": req.hops,
+        }))
+        .await;
+
+    forward_to_peers(&state, &req);
+
     (
         StatusCode::OK,
-        Json(serde_json::json!({ "hash": id.hash() })),
+        Json(serde_json::json!({
+            "hash": id.hash(),
+            "content_hash": content_hash,
+        })),
     )
+        .into_response()
+}
+
+/// Flood a freshly stored message to peer relays
+///
+/// Parties behind restrictive firewalls only make outbound connections to
+/// their own DMZ relay; forwarding is what carries their messages to
+/// parties homed on other relays (A→relayX→relayY→B). Runs in the
+/// background so a slow peer never delays the posting party. Peers that
+/// already hold the message answer 409, which — together with the hop
+/// list and [`MAX_HOPS`] — keeps mesh loops from amplifying.
+fn forward_to_peers(state: &Arc<AppState>, req: &PostMessageRequest) {
+    if state.peers.is_empty() {
+        return;
+    }
+    if req.hops.len() >= MAX_HOPS {
+        info!(hops = ?req.hops, "Hop limit reached; not forwarding");
+        return;
+    }
+    if req.hops.contains(&state.relay_id) {
+        // A peer echoed our own forward back; the store dedupe caught it
+        // and there is nothing new to propagate
+        return;
+    }
+
+    let mut forwarded = req.clone();
+    forwarded.hops.push(state.relay_id.clone());
+
+    let state = state.clone();
+    tokio::spawn(async move {
+        for peer in &state.peers {
+            let result = state
+                .forwarder
+                .post(format!("{}/v1/msg", peer))
+                .json(&forwarded)
+                .send()
+                .await;
+            match result {
+                // 409 means the peer already has the message; that is the
+                // dedupe working, not a failure
+                Ok(response) if response.status().as_u16() == 409 => {}
+                Ok(response) if !response.status().is_success() => {
+                    tracing::warn!(
+                        peer = %peer,
+                        status = %response.status(),
+                        "Peer rejected forwarded message"
+                    );
+                }
+                Ok(_) => {
+                    tracing::debug!(peer = %peer, hops = forwarded.hops.len(), "Message forwarded");
+                }
+                Err(e) => {
+                    tracing::warn!(peer = %peer, error = %e, "Forwarding to peer failed");
+                }
+            }
+        }
+    });
 }
 
 /// Get a message from the relay
@@ -175,7 +694,8 @@ async fn get_message(
     State(state): State<Arc<AppState>>,
     Json(req): Json<GetMessageRequest>,
 ) -> impl IntoResponse {
-    let id = MessageId::new(&req.session_id, req.round, req.from, req.to, &req.tag);
+    let id = MessageId::new(&req.session_id, req.round, req.from, req.to, &req.tag)
+        .with_seq(req.seq);
 
     match state.store.get(&id) {
         Ok(msg) => Json(MessageResponse {
@@ -189,17 +709,116 @@ async fn get_message(
     }
 }
 
-/// Get a message by hash
+/// Query a round's messages, filtered by tag prefix and sender set
+///
+/// Large multi-protocol sessions hold messages many pollers do not care
+/// about; filtering server-side keeps them from downloading payloads just
+/// to discard them.
+async fn query_messages(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<QueryMessagesRequest>,
+) -> impl IntoResponse {
+    let mut filter = MessageFilter::default();
+    if let Some(prefix) = &req.tag_prefix {
+        filter = filter.with_tag_prefix(prefix);
+    }
+    if let Some(from) = &req.from {
+        filter = filter.with_from(from.iter().copied());
+    }
+
+    let messages = state
+        .store
+        .query_round_messages(&req.session_id, req.round, &filter)
+        .into_iter()
+        .map(|msg| QueriedMessage {
+            round: msg.id.round,
+            from: msg.id.from,
+            to: msg.id.to,
+            tag: msg.id.tag,
+            seq: msg.id.seq,
+            payload: b64::encode(&msg.payload),
+        })
+        .collect();
+
+    Json(QueryMessagesResponse { messages })
+}
+
+/// Get a payload by its content hash
 async fn get_message_by_hash(
     State(state): State<Arc<AppState>>,
     Path(hash): Path<String>,
 ) -> impl IntoResponse {
-    // Search for message with matching hash
-    // This is a simplified implementation
-    Json(MessageResponse {
-        found: false,
-        payload: None,
-    })
+    match state.store.get_by_content_hash(&hash) {
+        Some(payload) => Json(MessageResponse {
+            found: true,
+            payload: Some(b64::encode(&payload)),
+        }),
+        None => Json(MessageResponse {
+            found: false,
+            payload: None,
+        }),
+    }
+}
+
+/// Store occupancy and eviction counters
+async fn metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    Json(state.store.metrics())
+}
+
+/// List active sessions with stored messages
+async fn list_sessions(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    Json(state.store.sessions())
+}
+
+/// Detailed stats for one session
+async fn session_stats(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+) -> Response {
+    match state.store.session_stats(&session_id) {
+        Some(stats) => Json(stats).into_response(),
+        None => problem_response(Problem::new(
+            ProblemCode::NotFound,
+            404,
+            format!("No messages stored for session {}", session_id),
+        )),
+    }
+}
+
+/// Equivocation evidence recorded for one session
+///
+/// Returns an empty list for healthy sessions — the common case for
+/// polling watchdogs — rather than a 404, so pollers need no special
+/// handling before the first conflict appears.
+async fn session_conflicts(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    Json(state.store.conflicts(&session_id))
+}
+
+/// Abort a session locally by dropping its stored messages
+async fn delete_session(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+) -> Response {
+    let removed = state.store.remove_session(&session_id);
+
+    info!(session_id = %session_id, removed, "Session aborted");
+
+    if removed == 0 {
+        problem_response(Problem::new(
+            ProblemCode::NotFound,
+            404,
+            format!("No messages stored for session {}", session_id),
+        ))
+    } else {
+        (
+            StatusCode::OK,
+            Json(serde_json::json!({ "removed": removed })),
+        )
+            .into_response()
+    }
 }
 
 /// WebSocket handler for real-time messaging
@@ -212,7 +831,7 @@ async fn websocket_handler(
 
 async fn handle_websocket(
     socket: axum::extract::ws::WebSocket,
-    state: Arc<AppState>,
+    _state: Arc<AppState>,
 ) {
     use axum::extract::ws::Message;
     use futures_util::{SinkExt, StreamExt};
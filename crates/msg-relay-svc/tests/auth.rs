@@ -0,0 +1,144 @@
+//! Bearer-token auth and rotation against a spawned relay service
+//!
+//! Exercises the dual-validity rotation window end to end: the old
+//! token keeps working for the configured overlap after a rotation and
+//! dies once it elapses, while the new token works immediately.
+
+use msg_relay::wire::PostMessageRequest;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+/// A relay service child process, killed when the test ends
+struct RelayService {
+    child: Child,
+    url: String,
+}
+
+impl RelayService {
+    /// Spawn the service binary on a free localhost port
+    fn start(extra_args: &[&str]) -> Self {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .expect("a localhost port must be available")
+            .local_addr()
+            .unwrap()
+            .port();
+        let child = Command::new(env!("CARGO_BIN_EXE_msg-relay-svc"))
+            .args(["--listen", &format!("127.0.0.1:{}", port)])
+            .args(extra_args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("the relay service binary must spawn");
+        Self {
+            child,
+            url: format!("http://127.0.0.1:{}", port),
+        }
+    }
+
+    /// Wait until the service answers its (unauthenticated) health probe
+    async fn wait_ready(&self, client: &reqwest::Client) {
+        for _ in 0..100 {
+            let probe = client.get(format!("{}/health", self.url)).send().await;
+            if matches!(probe, Ok(response) if response.status().is_success()) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("relay service did not become healthy in time");
+    }
+}
+
+impl Drop for RelayService {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn post_request(round: u32) -> PostMessageRequest {
+    PostMessageRequest {
+        session_id: "ab".repeat(32),
+        round,
+        from: Some(0),
+        to: None,
+        tag: "broadcast".to_string(),
+        seq: 0,
+        payload: "aGVsbG8=".to_string(),
+        trace_id: None,
+        hops: Vec::new(),
+    }
+}
+
+/// Post one message with the given token and return the HTTP status
+async fn post_with_token(
+    client: &reqwest::Client,
+    url: &str,
+    token: Option<&str>,
+    round: u32,
+) -> u16 {
+    let mut request = client
+        .post(format!("{}/v1/msg", url))
+        .json(&post_request(round));
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    request.send().await.unwrap().status().as_u16()
+}
+
+#[tokio::test]
+async fn test_token_rotation_honors_dual_validity_window() {
+    let service = RelayService::start(&["--auth-token", "old-token"]);
+    let client = reqwest::Client::new();
+    service.wait_ready(&client).await;
+
+    // Only the configured token gets through
+    assert_eq!(post_with_token(&client, &service.url, None, 1).await, 401);
+    assert_eq!(
+        post_with_token(&client, &service.url, Some("wrong"), 1).await,
+        401
+    );
+    assert_eq!(
+        post_with_token(&client, &service.url, Some("old-token"), 1).await,
+        200
+    );
+
+    // Rotation requires a valid token too
+    let unauthorized = client
+        .post(format!("{}/v1/admin/token/rotate", service.url))
+        .json(&serde_json::json!({ "new_token": "stolen" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(unauthorized.status().as_u16(), 401);
+
+    // Rotate with a one-second overlap window
+    let rotated = client
+        .post(format!("{}/v1/admin/token/rotate", service.url))
+        .bearer_auth("old-token")
+        .json(&serde_json::json!({ "new_token": "new-token", "overlap_seconds": 1 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(rotated.status().as_u16(), 200);
+
+    // Inside the window both tokens are honored
+    assert_eq!(
+        post_with_token(&client, &service.url, Some("old-token"), 2).await,
+        200
+    );
+    assert_eq!(
+        post_with_token(&client, &service.url, Some("new-token"), 3).await,
+        200
+    );
+
+    // After the window only the new token survives
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert_eq!(
+        post_with_token(&client, &service.url, Some("old-token"), 4).await,
+        401
+    );
+    assert_eq!(
+        post_with_token(&client, &service.url, Some("new-token"), 4).await,
+        200
+    );
+}
@@ -0,0 +1,252 @@
+//! Differential transport test: MemoryRelay vs the HTTP relay service
+//!
+//! Runs the same DKG + DSG ceremony once over the in-process
+//! [`MemoryRelay`] and once over a spawned relay service via
+//! [`RelayClient`], then compares the per-party protocol transcripts —
+//! every (round, peer) pair with its sent and received message counts,
+//! as recorded by [`MeteredRelay`]. A transport that reorders rounds,
+//! duplicates a message on a re-poll, or misattributes a sender shows up
+//! as a transcript mismatch or a failed ceremony, without writing a
+//! dedicated test for each divergence.
+
+use dkls23_core::keygen::run_dkg;
+use dkls23_core::mpc::{async_trait, MemoryRelay, MeteredRelay, Relay, RelayStats};
+use dkls23_core::sign::run_dsg;
+use dkls23_core::{PartyId, Result, SessionConfig, SessionId};
+use msg_relay_client::RelayClient;
+use serde::{de::DeserializeOwned, Serialize};
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+use std::time::Duration;
+
+const N_PARTIES: usize = 3;
+const THRESHOLD: usize = 2;
+const SIGNERS: [usize; 2] = [0, 1];
+const MESSAGE: [u8; 32] = [0xd1u8; 32];
+
+/// One party's protocol transcript: (round, peer) pairs with the number
+/// of messages sent and received there
+///
+/// Byte counts are deliberately excluded — payload sizes vary with the
+/// ceremony's randomness, while the message flow does not.
+type Transcript = Vec<((u32, Option<PartyId>), (u64, u64))>;
+
+/// What one party observed: its transcript plus the ceremony outputs
+/// the transports must agree on structurally
+struct PartyOutcome {
+    transcript: Transcript,
+    public_key: Vec<u8>,
+    transcript_digest: [u8; 32],
+    signature: Option<([u8; 32], [u8; 32])>,
+}
+
+/// Shares one [`MemoryRelay`] across parties while satisfying the
+/// by-value relay bound of [`MeteredRelay`]
+struct SharedRelay(Arc<MemoryRelay>);
+
+#[async_trait]
+impl Relay for SharedRelay {
+    async fn broadcast<T: Serialize + Send + Sync>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        message: &T,
+    ) -> Result<()> {
+        self.0.broadcast(session_id, round, message).await
+    }
+
+    async fn send_direct<T: Serialize + Send + Sync>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        to: PartyId,
+        message: &T,
+    ) -> Result<()> {
+        self.0.send_direct(session_id, round, to, message).await
+    }
+
+    async fn collect_broadcasts<T: DeserializeOwned + Send>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        count: usize,
+    ) -> Result<Vec<T>> {
+        self.0.collect_broadcasts(session_id, round, count).await
+    }
+
+    async fn collect_direct<T: DeserializeOwned + Send>(
+        &self,
+        session_id: &SessionId,
+        round: u32,
+        my_id: PartyId,
+        count: usize,
+    ) -> Result<Vec<T>> {
+        self.0.collect_direct(session_id, round, my_id, count).await
+    }
+}
+
+/// A relay service child process, killed when the test ends
+struct RelayService {
+    child: Child,
+    url: String,
+}
+
+impl RelayService {
+    /// Spawn the service binary on a free localhost port
+    fn start() -> Self {
+        // Bind to port 0 to find a free port, then hand it to the service;
+        // the window between drop and rebind is a benign test-only race
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .expect("a localhost port must be available")
+            .local_addr()
+            .unwrap()
+            .port();
+        let child = Command::new(env!("CARGO_BIN_EXE_msg-relay-svc"))
+            .args(["--listen", &format!("127.0.0.1:{}", port)])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("the relay service binary must spawn");
+        Self {
+            child,
+            url: format!("http://127.0.0.1:{}", port),
+        }
+    }
+
+    /// Wait until the service answers its health probe
+    async fn wait_ready(&self) {
+        let probe = RelayClient::new(&self.url, 0);
+        for _ in 0..100 {
+            if probe.health().await.is_ok() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("relay service did not become healthy in time");
+    }
+}
+
+impl Drop for RelayService {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Run one party's share of the ceremony over the given relay and
+/// return what it observed
+async fn run_party<R: Relay>(relay: R, session_id: SessionId, party_id: usize) -> PartyOutcome {
+    let stats = RelayStats::new();
+    let relay = MeteredRelay::new(relay, stats.clone());
+
+    let config = SessionConfig {
+        session_id,
+        n_parties: N_PARTIES,
+        threshold: THRESHOLD,
+        party_id,
+        parties: (0..N_PARTIES).collect(),
+    };
+    let key_share = run_dkg(&config, &relay).await.expect("DKG must complete");
+
+    let signature = if SIGNERS.contains(&party_id) {
+        let sig = run_dsg(&key_share, &MESSAGE, &SIGNERS, &relay)
+            .await
+            .expect("DSG must complete");
+        Some((sig.r, sig.s))
+    } else {
+        None
+    };
+
+    PartyOutcome {
+        transcript: stats
+            .snapshot()
+            .into_iter()
+            .map(|(key, counters)| {
+                (key, (counters.messages_sent, counters.messages_received))
+            })
+            .collect(),
+        public_key: key_share.public_key.clone(),
+        transcript_digest: key_share.transcript_digest,
+        signature,
+    }
+}
+
+/// Run the full ceremony, one relay per party from the factory
+async fn run_ceremony<R, F>(session_id: SessionId, make_relay: F) -> Vec<PartyOutcome>
+where
+    R: Relay + Send + Sync + 'static,
+    F: Fn(usize) -> R,
+{
+    let mut handles = Vec::new();
+    for party_id in 0..N_PARTIES {
+        let relay = make_relay(party_id);
+        handles.push(tokio::spawn(run_party(relay, session_id, party_id)));
+    }
+
+    let mut outcomes = Vec::new();
+    for handle in handles {
+        outcomes.push(handle.await.unwrap());
+    }
+    outcomes
+}
+
+/// All parties of one run must agree on the key and its transcript
+/// digest, and both signers on one signature
+fn check_internal_consistency(outcomes: &[PartyOutcome], transport: &str) {
+    for outcome in &outcomes[1..] {
+        assert_eq!(
+            outcome.public_key, outcomes[0].public_key,
+            "{}: parties disagree on the group key",
+            transport
+        );
+        assert_eq!(
+            outcome.transcript_digest, outcomes[0].transcript_digest,
+            "{}: parties disagree on the DKG transcript digest",
+            transport
+        );
+    }
+    let signatures: Vec<_> = outcomes
+        .iter()
+        .filter_map(|outcome| outcome.signature)
+        .collect();
+    assert_eq!(signatures.len(), SIGNERS.len(), "{}: a signer produced no signature", transport);
+    assert_eq!(
+        signatures[0], signatures[1],
+        "{}: signers combined different signatures",
+        transport
+    );
+}
+
+#[tokio::test]
+async fn test_memory_and_http_transports_produce_identical_transcripts() {
+    let session_id = [0x5cu8; 32];
+
+    let memory = Arc::new(MemoryRelay::new());
+    let memory_outcomes =
+        run_ceremony(session_id, |_| SharedRelay(memory.clone())).await;
+
+    let service = RelayService::start();
+    service.wait_ready().await;
+    let url = service.url.clone();
+    let http_outcomes = run_ceremony(session_id, move |party_id| {
+        RelayClient::new(&url, party_id).with_timeout(Duration::from_secs(10))
+    })
+    .await;
+
+    check_internal_consistency(&memory_outcomes, "memory");
+    check_internal_consistency(&http_outcomes, "http");
+
+    // The differential heart: each party must have seen exactly the same
+    // message flow over both transports
+    for (party_id, (memory, http)) in memory_outcomes
+        .iter()
+        .zip(&http_outcomes)
+        .enumerate()
+    {
+        assert_eq!(
+            memory.transcript, http.transcript,
+            "party {}: transcripts diverge between transports",
+            party_id
+        );
+    }
+}
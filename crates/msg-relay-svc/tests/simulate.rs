@@ -0,0 +1,134 @@
+//! Degraded-link simulation flags against a spawned relay service
+//!
+//! `--simulate-latency` must delay every /v1 request by at least the
+//! configured amount while leaving the health probe untouched, and
+//! `--simulate-loss` must fail the configured fraction of requests with
+//! a 503 — at 100%, every one of them.
+
+use msg_relay::wire::PostMessageRequest;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// A relay service child process, killed when the test ends
+struct RelayService {
+    child: Child,
+    url: String,
+}
+
+impl RelayService {
+    /// Spawn the service binary on a free localhost port
+    fn start(extra_args: &[&str]) -> Self {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .expect("a localhost port must be available")
+            .local_addr()
+            .unwrap()
+            .port();
+        let child = Command::new(env!("CARGO_BIN_EXE_msg-relay-svc"))
+            .args(["--listen", &format!("127.0.0.1:{}", port)])
+            .args(extra_args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("the relay service binary must spawn");
+        Self {
+            child,
+            url: format!("http://127.0.0.1:{}", port),
+        }
+    }
+
+    /// Wait until the service answers its (unsimulated) health probe
+    async fn wait_ready(&self, client: &reqwest::Client) {
+        for _ in 0..100 {
+            let probe = client.get(format!("{}/health", self.url)).send().await;
+            if matches!(probe, Ok(response) if response.status().is_success()) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("relay service did not become healthy in time");
+    }
+}
+
+impl Drop for RelayService {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn post_request(round: u32) -> PostMessageRequest {
+    PostMessageRequest {
+        session_id: "ab".repeat(32),
+        round,
+        from: Some(0),
+        to: None,
+        tag: "broadcast".to_string(),
+        seq: 0,
+        payload: "aGVsbG8=".to_string(),
+        trace_id: None,
+        hops: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn test_simulated_latency_delays_api_but_not_health() {
+    let service = RelayService::start(&["--simulate-latency", "300ms"]);
+    let client = reqwest::Client::new();
+    service.wait_ready(&client).await;
+
+    let started = Instant::now();
+    let response = client
+        .post(format!("{}/v1/msg", service.url))
+        .json(&post_request(1))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(
+        started.elapsed() >= Duration::from_millis(300),
+        "request completed in {:?}, before the simulated delay",
+        started.elapsed()
+    );
+
+    // The load-balancer probe must stay outside the simulation
+    let started = Instant::now();
+    let probe = client
+        .get(format!("{}/health", service.url))
+        .send()
+        .await
+        .unwrap();
+    assert!(probe.status().is_success());
+    assert!(started.elapsed() < Duration::from_millis(300));
+}
+
+#[tokio::test]
+async fn test_simulated_loss_fails_requests_with_503() {
+    let service = RelayService::start(&["--simulate-loss", "100%"]);
+    let client = reqwest::Client::new();
+    service.wait_ready(&client).await;
+
+    for round in 1..=3 {
+        let response = client
+            .post(format!("{}/v1/msg", service.url))
+            .json(&post_request(round))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 503);
+    }
+}
+
+#[tokio::test]
+async fn test_without_flags_nothing_is_simulated() {
+    let service = RelayService::start(&[]);
+    let client = reqwest::Client::new();
+    service.wait_ready(&client).await;
+
+    let response = client
+        .post(format!("{}/v1/msg", service.url))
+        .json(&post_request(1))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+}
@@ -7,9 +7,14 @@
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use thiserror::Error;
-use uuid::Uuid;
+
+pub mod service;
+pub mod shipping;
+pub mod wire;
 
 /// Relay error types
 #[derive(Debug, Error)]
@@ -39,6 +44,13 @@ pub struct MessageId {
     pub to: Option<usize>,
     /// Message tag
     pub tag: String,
+    /// Per-sender sequence number, for replay-protected envelopes
+    ///
+    /// Posts differing only in sequence number get distinct store keys, so
+    /// a replayed message cannot silently overwrite the original. Zero for
+    /// clients that predate sequence numbers.
+    #[serde(default)]
+    pub seq: u64,
 }
 
 impl MessageId {
@@ -50,18 +62,26 @@ impl MessageId {
             from,
             to,
             tag: tag.to_string(),
+            seq: 0,
         }
     }
 
+    /// Set the sequence number component of the ID
+    pub fn with_seq(mut self, seq: u64) -> Self {
+        self.seq = seq;
+        self
+    }
+
     /// Compute hash for lookup
     pub fn hash(&self) -> String {
         let data = format!(
-            "{}:{}:{}:{}:{}",
+            "{}:{}:{}:{}:{}:{}",
             self.session_id,
             self.round,
             self.from.map(|v| v.to_string()).unwrap_or_default(),
             self.to.map(|v| v.to_string()).unwrap_or_default(),
-            self.tag
+            self.tag,
+            self.seq
         );
         hex::encode(blake3::hash(data.as_bytes()).as_bytes())
     }
@@ -74,54 +94,390 @@ pub struct StoredMessage {
     pub id: MessageId,
     /// Message payload
     pub payload: Vec<u8>,
+    /// Content hash of the payload (blake3, hex)
+    #[serde(default)]
+    pub content_hash: String,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Expiration timestamp
     pub expires_at: DateTime<Utc>,
+    /// Trace ID of the originating request, if the poster supplied one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+}
+
+/// Content hash used for payload addressing (blake3, hex)
+pub fn content_hash(payload: &[u8]) -> String {
+    hex::encode(blake3::hash(payload).as_bytes())
+}
+
+/// Evidence of conflicting posts under one message ID
+///
+/// A correct party posts each round message exactly once; retries repost
+/// the same bytes. Two posts with the same ID but different payloads mean
+/// the sender told different peers different things — equivocation. The
+/// pair of content hashes is the identifiable-abort evidence: both
+/// payloads remain fetchable by hash while the session lives, so anyone
+/// can verify the sender signed off on two contradictory messages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictEvent {
+    /// Session the conflicting posts targeted
+    pub session_id: String,
+    /// Round number
+    pub round: u32,
+    /// Claimed sender of both posts (None for unattributed messages)
+    pub from: Option<usize>,
+    /// Receiver party ID (None for broadcasts)
+    pub to: Option<usize>,
+    /// Message tag
+    pub tag: String,
+    /// Per-sender sequence number
+    pub seq: u64,
+    /// Content hash of the payload the store kept
+    pub stored_hash: String,
+    /// Content hash of the conflicting payload
+    pub conflicting_hash: String,
+    /// When the conflict was observed
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Conflicts retained per session; an equivocator spamming variants must
+/// not grow the evidence log without bound
+const MAX_CONFLICTS_PER_SESSION: usize = 64;
+
+/// Resource caps for a [`MessageStore`]
+///
+/// A flood of half-completed sessions must not exhaust the relay: when a
+/// new session would exceed either cap, the least-recently-active other
+/// session is evicted wholesale.
+#[derive(Debug, Clone, Copy)]
+pub struct StoreLimits {
+    /// Maximum number of concurrent sessions
+    pub max_sessions: usize,
+    /// Maximum total payload bytes across all sessions
+    pub max_total_bytes: usize,
+}
+
+impl Default for StoreLimits {
+    fn default() -> Self {
+        Self {
+            max_sessions: 1024,
+            max_total_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+/// Point-in-time store occupancy and lifetime eviction counters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreMetrics {
+    /// Sessions currently holding messages
+    pub sessions: usize,
+    /// Total stored payload bytes
+    pub total_bytes: usize,
+    /// Sessions evicted by the resource caps since startup
+    pub sessions_evicted: u64,
+    /// Messages dropped by those evictions since startup
+    pub messages_evicted: u64,
+    /// Distinct payloads in the content-addressed blob store
+    #[serde(default)]
+    pub unique_payloads: usize,
+    /// Actual bytes held by the blob store (after deduplication)
+    #[serde(default)]
+    pub unique_payload_bytes: usize,
+}
+
+/// Per-session usage tracked for LRU eviction
+#[derive(Debug, Clone)]
+struct SessionUsage {
+    bytes: usize,
+    last_activity: DateTime<Utc>,
+}
+
+/// Message metadata; the payload lives in the content-addressed blob store
+#[derive(Debug, Clone)]
+struct StoredMeta {
+    id: MessageId,
+    content_hash: String,
+    content_len: usize,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    trace_id: Option<String>,
+}
+
+/// Reference-counted payload in the blob store
+#[derive(Debug)]
+struct PayloadEntry {
+    bytes: Vec<u8>,
+    refs: usize,
 }
 
 /// Message relay store
 #[derive(Clone)]
 pub struct MessageStore {
-    /// Messages indexed by hash
-    messages: Arc<DashMap<String, StoredMessage>>,
+    /// Message metadata indexed by message-ID hash
+    messages: Arc<DashMap<String, StoredMeta>>,
+    /// Payloads indexed by content hash, shared across identical messages
+    ///
+    /// Broadcasts and federation replication post the same bytes under many
+    /// message IDs; storing them once keeps memory proportional to distinct
+    /// content rather than to fan-out.
+    payloads: Arc<DashMap<String, PayloadEntry>>,
+    /// Per-session byte usage and recency, for cap enforcement
+    usage: Arc<DashMap<String, SessionUsage>>,
+    /// Equivocation evidence per session, capped per
+    /// [`MAX_CONFLICTS_PER_SESSION`]
+    conflicts: Arc<DashMap<String, Vec<ConflictEvent>>>,
     /// Default TTL in seconds
     ttl_seconds: i64,
+    /// Resource caps
+    limits: StoreLimits,
+    /// Sessions evicted by cap enforcement
+    sessions_evicted: Arc<AtomicU64>,
+    /// Messages dropped by cap enforcement
+    messages_evicted: Arc<AtomicU64>,
 }
 
 impl MessageStore {
-    /// Create a new message store
+    /// Create a new message store with default resource caps
     pub fn new(ttl_seconds: i64) -> Self {
+        Self::with_limits(ttl_seconds, StoreLimits::default())
+    }
+
+    /// Create a new message store with explicit resource caps
+    pub fn with_limits(ttl_seconds: i64, limits: StoreLimits) -> Self {
         Self {
             messages: Arc::new(DashMap::new()),
+            payloads: Arc::new(DashMap::new()),
+            usage: Arc::new(DashMap::new()),
+            conflicts: Arc::new(DashMap::new()),
             ttl_seconds,
+            limits,
+            sessions_evicted: Arc::new(AtomicU64::new(0)),
+            messages_evicted: Arc::new(AtomicU64::new(0)),
         }
     }
 
     /// Store a message
-    pub fn put(&self, id: MessageId, payload: Vec<u8>) -> Result<()> {
+    ///
+    /// Returns the payload's content hash.
+    pub fn put(&self, id: MessageId, payload: Vec<u8>) -> Result<String> {
+        self.put_traced(id, payload, None)
+    }
+
+    /// Store a message stamped with the originating request's trace ID
+    ///
+    /// The trace ID is kept alongside the message and surfaced in session
+    /// stats, so a suspicious signature can be pivoted back to exactly one
+    /// request trace across party, relay and caller systems.
+    ///
+    /// Returns the payload's content hash, which callers can echo back to
+    /// posters for end-to-end integrity verification.
+    pub fn put_traced(
+        &self,
+        id: MessageId,
+        payload: Vec<u8>,
+        trace_id: Option<String>,
+    ) -> Result<String> {
         let now = Utc::now();
         let expires_at = now + chrono::Duration::seconds(self.ttl_seconds);
+        let bytes = payload.len();
+        let hash = content_hash(&payload);
+
+        self.payloads
+            .entry(hash.clone())
+            .and_modify(|entry| entry.refs += 1)
+            .or_insert(PayloadEntry {
+                bytes: payload,
+                refs: 1,
+            });
 
-        let message = StoredMessage {
+        let meta = StoredMeta {
             id: id.clone(),
-            payload,
+            content_hash: hash.clone(),
+            content_len: bytes,
             created_at: now,
             expires_at,
+            trace_id,
+        };
+
+        if let Some(old) = self.messages.insert(id.hash(), meta) {
+            // Overwrite of an existing message: drop its payload reference
+            // and back out its bytes so usage stays accurate. Different
+            // content under the same ID is equivocation evidence.
+            if old.content_hash != hash {
+                self.note_conflict(&id, &old.content_hash, &hash);
+            }
+            self.release_payload(&old.content_hash);
+            if let Some(mut usage) = self.usage.get_mut(&id.session_id) {
+                usage.bytes = usage.bytes.saturating_sub(old.content_len);
+            }
+        }
+        {
+            let mut usage = self
+                .usage
+                .entry(id.session_id.clone())
+                .or_insert(SessionUsage {
+                    bytes: 0,
+                    last_activity: now,
+                });
+            usage.bytes += bytes;
+            usage.last_activity = now;
+        }
+
+        self.enforce_limits(&id.session_id);
+        Ok(hash)
+    }
+
+    /// Record equivocation evidence for a message ID
+    fn note_conflict(
+        &self,
+        id: &MessageId,
+        stored_hash: &str,
+        conflicting_hash: &str,
+    ) -> ConflictEvent {
+        let event = ConflictEvent {
+            session_id: id.session_id.clone(),
+            round: id.round,
+            from: id.from,
+            to: id.to,
+            tag: id.tag.clone(),
+            seq: id.seq,
+            stored_hash: stored_hash.to_string(),
+            conflicting_hash: conflicting_hash.to_string(),
+            detected_at: Utc::now(),
+        };
+        let mut events = self.conflicts.entry(id.session_id.clone()).or_default();
+        if events.len() < MAX_CONFLICTS_PER_SESSION {
+            events.push(event.clone());
+        }
+        event
+    }
+
+    /// Check a rejected re-post for equivocation and record it if found
+    ///
+    /// Called for posts whose message ID already exists in the store. A
+    /// byte-identical payload is an honest retry (or a mesh peer's
+    /// forward) and returns `None`; a different payload is recorded as a
+    /// [`ConflictEvent`] and returned so the boundary can report it. The
+    /// originally stored message is kept either way — first write wins,
+    /// so an equivocator cannot rewrite what honest parties already
+    /// collected.
+    pub fn record_conflict(&self, id: &MessageId, payload: &[u8]) -> Option<ConflictEvent> {
+        let stored_hash = self
+            .messages
+            .get(&id.hash())
+            .map(|entry| entry.content_hash.clone())?;
+        let incoming_hash = content_hash(payload);
+        if stored_hash == incoming_hash {
+            return None;
+        }
+        Some(self.note_conflict(id, &stored_hash, &incoming_hash))
+    }
+
+    /// Equivocation evidence recorded for a session
+    ///
+    /// Empty for healthy sessions. The events live in memory alongside
+    /// the session's messages and are dropped with them; the audit stream
+    /// is the durable record.
+    pub fn conflicts(&self, session_id: &str) -> Vec<ConflictEvent> {
+        self.conflicts
+            .get(session_id)
+            .map(|events| events.clone())
+            .unwrap_or_default()
+    }
+
+    /// Drop one reference to a blob, freeing it when nothing points at it
+    fn release_payload(&self, hash: &str) {
+        let gone = match self.payloads.get_mut(hash) {
+            Some(mut entry) => {
+                entry.refs = entry.refs.saturating_sub(1);
+                entry.refs == 0
+            }
+            None => false,
         };
+        if gone {
+            self.payloads.remove_if(hash, |_, entry| entry.refs == 0);
+        }
+    }
+
+    /// Reassemble a stored message from its metadata and blob
+    fn assemble(&self, meta: &StoredMeta) -> Option<StoredMessage> {
+        let payload = self.payloads.get(&meta.content_hash)?.bytes.clone();
+        Some(StoredMessage {
+            id: meta.id.clone(),
+            payload,
+            content_hash: meta.content_hash.clone(),
+            created_at: meta.created_at,
+            expires_at: meta.expires_at,
+            trace_id: meta.trace_id.clone(),
+        })
+    }
+
+    /// Evict least-recently-active sessions until both caps are satisfied
+    ///
+    /// The session that just received a message is never evicted, so a
+    /// single active ceremony always makes progress.
+    fn enforce_limits(&self, current_session: &str) {
+        loop {
+            let sessions = self.usage.len();
+            let total_bytes: usize = self.usage.iter().map(|e| e.bytes).sum();
+            if sessions <= self.limits.max_sessions
+                && total_bytes <= self.limits.max_total_bytes
+            {
+                return;
+            }
+
+            let victim = self
+                .usage
+                .iter()
+                .filter(|entry| entry.key() != current_session)
+                .min_by_key(|entry| entry.last_activity)
+                .map(|entry| entry.key().clone());
+
+            let Some(victim) = victim else {
+                return; // only the active session remains; nothing to evict
+            };
 
-        self.messages.insert(id.hash(), message);
-        Ok(())
+            let removed = self.remove_session(&victim);
+            self.sessions_evicted.fetch_add(1, Ordering::Relaxed);
+            self.messages_evicted
+                .fetch_add(removed as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Current occupancy and eviction counters
+    pub fn metrics(&self) -> StoreMetrics {
+        StoreMetrics {
+            sessions: self.usage.len(),
+            total_bytes: self.usage.iter().map(|e| e.bytes).sum(),
+            sessions_evicted: self.sessions_evicted.load(Ordering::Relaxed),
+            messages_evicted: self.messages_evicted.load(Ordering::Relaxed),
+            unique_payloads: self.payloads.len(),
+            unique_payload_bytes: self.payloads.iter().map(|e| e.bytes.len()).sum(),
+        }
     }
 
     /// Get a message by ID
     pub fn get(&self, id: &MessageId) -> Result<StoredMessage> {
         let hash = id.hash();
 
-        self.messages
+        let meta = self
+            .messages
             .get(&hash)
             .map(|entry| entry.value().clone())
-            .ok_or_else(|| RelayError::NotFound(hash))
+            .ok_or(RelayError::NotFound(hash))?;
+
+        self.assemble(&meta).ok_or_else(|| {
+            RelayError::Internal(format!(
+                "Blob {} missing for a stored message",
+                meta.content_hash
+            ))
+        })
+    }
+
+    /// Get a payload by its content hash
+    pub fn get_by_content_hash(&self, hash: &str) -> Option<Vec<u8>> {
+        self.payloads.get(hash).map(|entry| entry.bytes.clone())
     }
 
     /// Check if a message exists
@@ -132,17 +488,154 @@ impl MessageStore {
     /// Remove expired messages
     pub fn cleanup(&self) {
         let now = Utc::now();
-        self.messages.retain(|_, v| v.expires_at > now);
+        let expired: Vec<String> = self
+            .messages
+            .iter()
+            .filter(|entry| entry.expires_at <= now)
+            .map(|entry| entry.key().clone())
+            .collect();
+        for key in expired {
+            if let Some((_, meta)) = self.messages.remove(&key) {
+                self.release_payload(&meta.content_hash);
+            }
+        }
+        self.rebuild_usage();
+    }
+
+    /// Recompute per-session usage from the surviving messages
+    fn rebuild_usage(&self) {
+        let mut fresh: std::collections::BTreeMap<String, SessionUsage> =
+            std::collections::BTreeMap::new();
+        for entry in self.messages.iter() {
+            let msg = entry.value();
+            let usage = fresh
+                .entry(msg.id.session_id.clone())
+                .or_insert(SessionUsage {
+                    bytes: 0,
+                    last_activity: msg.created_at,
+                });
+            usage.bytes += msg.content_len;
+            usage.last_activity = usage.last_activity.max(msg.created_at);
+        }
+        self.usage.retain(|session_id, _| fresh.contains_key(session_id));
+        for (session_id, usage) in fresh {
+            self.usage.insert(session_id, usage);
+        }
+    }
+
+    /// Summarize all sessions with stored messages
+    pub fn sessions(&self) -> Vec<SessionSummary> {
+        let mut sessions: std::collections::BTreeMap<String, SessionSummary> =
+            std::collections::BTreeMap::new();
+
+        for entry in self.messages.iter() {
+            let msg = entry.value();
+            let summary = sessions
+                .entry(msg.id.session_id.clone())
+                .or_insert_with(|| SessionSummary {
+                    session_id: msg.id.session_id.clone(),
+                    latest_round: msg.id.round,
+                    senders: BTreeSet::new(),
+                    message_count: 0,
+                    first_activity: msg.created_at,
+                    last_activity: msg.created_at,
+                });
+
+            summary.latest_round = summary.latest_round.max(msg.id.round);
+            if let Some(from) = msg.id.from {
+                summary.senders.insert(from);
+            }
+            summary.message_count += 1;
+            summary.first_activity = summary.first_activity.min(msg.created_at);
+            summary.last_activity = summary.last_activity.max(msg.created_at);
+        }
+
+        sessions.into_values().collect()
+    }
+
+    /// Detailed per-session stats for client-side adaptive behavior
+    ///
+    /// Returns `None` if the session has no stored messages.
+    pub fn session_stats(&self, session_id: &str) -> Option<SessionStats> {
+        let mut stats: Option<SessionStats> = None;
+
+        for entry in self.messages.iter() {
+            let msg = entry.value();
+            if msg.id.session_id != session_id {
+                continue;
+            }
+
+            let stats = stats.get_or_insert_with(|| SessionStats {
+                session_id: session_id.to_string(),
+                messages_per_round: std::collections::BTreeMap::new(),
+                last_post_by_sender: std::collections::BTreeMap::new(),
+                trace_ids: BTreeSet::new(),
+                last_activity: msg.created_at,
+            });
+
+            *stats.messages_per_round.entry(msg.id.round).or_insert(0) += 1;
+            if let Some(from) = msg.id.from {
+                let last = stats
+                    .last_post_by_sender
+                    .entry(from)
+                    .or_insert(msg.created_at);
+                *last = (*last).max(msg.created_at);
+            }
+            if let Some(trace_id) = &msg.trace_id {
+                stats.trace_ids.insert(trace_id.clone());
+            }
+            stats.last_activity = stats.last_activity.max(msg.created_at);
+        }
+
+        stats
+    }
+
+    /// Remove all messages belonging to a session
+    ///
+    /// Returns the number of messages removed.
+    pub fn remove_session(&self, session_id: &str) -> usize {
+        let keys: Vec<String> = self
+            .messages
+            .iter()
+            .filter(|entry| entry.id.session_id == session_id)
+            .map(|entry| entry.key().clone())
+            .collect();
+        let mut removed = 0;
+        for key in keys {
+            if let Some((_, meta)) = self.messages.remove(&key) {
+                self.release_payload(&meta.content_hash);
+                removed += 1;
+            }
+        }
+        self.usage.remove(session_id);
+        self.conflicts.remove(session_id);
+        removed
     }
 
     /// Get all messages for a session and round
     pub fn get_round_messages(&self, session_id: &str, round: u32) -> Vec<StoredMessage> {
+        self.query_round_messages(session_id, round, &MessageFilter::default())
+    }
+
+    /// Get the messages for a session and round that match a filter
+    ///
+    /// Multi-protocol sessions accumulate messages under many tags and
+    /// senders; filtering at the store keeps pollers from downloading
+    /// payloads they would immediately discard.
+    pub fn query_round_messages(
+        &self,
+        session_id: &str,
+        round: u32,
+        filter: &MessageFilter,
+    ) -> Vec<StoredMessage> {
         self.messages
             .iter()
             .filter(|entry| {
-                entry.id.session_id == session_id && entry.id.round == round
+                entry.id.session_id == session_id
+                    && entry.id.round == round
+                    && filter.matches(&entry.id)
             })
-            .map(|entry| entry.value().clone())
+            .filter_map(|entry| self.assemble(entry.value()))
             .collect()
     }
 }
@@ -153,6 +646,179 @@ impl Default for MessageStore {
     }
 }
 
+/// Filter over stored message IDs for round queries
+///
+/// An empty filter matches everything; each constraint added narrows the
+/// result set. Both constraints travel the query API unchanged, so the
+/// fields are part of the wire contract.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageFilter {
+    /// Only match messages whose tag starts with this prefix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag_prefix: Option<String>,
+    /// Only match messages from these senders
+    ///
+    /// `None` matches any sender, including broadcasts with no sender at
+    /// all; an explicit set matches only messages attributed to one of its
+    /// members.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<BTreeSet<usize>>,
+}
+
+impl MessageFilter {
+    /// Restrict to tags starting with a prefix
+    pub fn with_tag_prefix(mut self, prefix: &str) -> Self {
+        self.tag_prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Restrict to an explicit sender set
+    pub fn with_from<I: IntoIterator<Item = usize>>(mut self, senders: I) -> Self {
+        self.from = Some(senders.into_iter().collect());
+        self
+    }
+
+    /// Whether a message ID satisfies every constraint
+    pub fn matches(&self, id: &MessageId) -> bool {
+        if let Some(prefix) = &self.tag_prefix {
+            if !id.tag.starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+        if let Some(senders) = &self.from {
+            match id.from {
+                Some(from) if senders.contains(&from) => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+/// Summary of one session's activity in the store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    /// Session identifier
+    pub session_id: String,
+    /// Highest round with stored messages
+    pub latest_round: u32,
+    /// Party IDs observed as senders
+    pub senders: BTreeSet<usize>,
+    /// Total stored messages
+    pub message_count: usize,
+    /// Timestamp of the first stored message
+    pub first_activity: DateTime<Utc>,
+    /// Timestamp of the most recent stored message
+    pub last_activity: DateTime<Utc>,
+}
+
+/// Detailed stats for one session
+///
+/// Clients use these to adapt polling frequency and to notice that a peer
+/// has gone quiet before a full round timeout expires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionStats {
+    /// Session identifier
+    pub session_id: String,
+    /// Stored message count per round
+    pub messages_per_round: std::collections::BTreeMap<u32, usize>,
+    /// Most recent post timestamp per sender
+    pub last_post_by_sender: std::collections::BTreeMap<usize, DateTime<Utc>>,
+    /// Trace IDs of the requests that posted into this session
+    #[serde(default)]
+    pub trace_ids: BTreeSet<String>,
+    /// Timestamp of the most recent stored message
+    pub last_activity: DateTime<Utc>,
+}
+
+/// Content type for RFC 7807 problem documents
+pub const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+/// Machine-readable error code carried in a [`Problem`] document
+///
+/// Clients switch on this rather than parsing `detail`, so the set is part
+/// of the wire contract: codes may be added but never renamed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ProblemCode {
+    /// Session TTL elapsed; messages were reaped
+    SessionExpired,
+    /// Message payload exceeds the relay's size cap
+    PayloadTooLarge,
+    /// A message with the same ID was already stored
+    DuplicateMessage,
+    /// A message with the same ID was already stored with different
+    /// content — the sender is telling peers different things
+    EquivocationDetected,
+    /// Caller lacks credentials for this operation
+    Unauthorized,
+    /// Request was malformed (bad encoding, missing fields)
+    InvalidRequest,
+    /// Referenced session or message does not exist
+    NotFound,
+    /// Unexpected relay-side failure
+    Internal,
+}
+
+impl ProblemCode {
+    /// Human-readable title for the code
+    pub fn title(&self) -> &'static str {
+        match self {
+            ProblemCode::SessionExpired => "Session expired",
+            ProblemCode::PayloadTooLarge => "Payload too large",
+            ProblemCode::DuplicateMessage => "Duplicate message",
+            ProblemCode::EquivocationDetected => "Equivocation detected",
+            ProblemCode::Unauthorized => "Unauthorized",
+            ProblemCode::InvalidRequest => "Invalid request",
+            ProblemCode::NotFound => "Not found",
+            ProblemCode::Internal => "Internal error",
+        }
+    }
+}
+
+/// RFC 7807 problem document returned by the relay HTTP boundary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Problem {
+    /// Problem type URI; `about:blank` since codes carry the semantics
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// Short human-readable summary
+    pub title: String,
+    /// HTTP status code
+    pub status: u16,
+    /// Human-readable explanation of this occurrence
+    pub detail: String,
+    /// Machine-readable error code
+    pub code: ProblemCode,
+}
+
+impl Problem {
+    /// Build a problem document for a code, status and detail message
+    pub fn new(code: ProblemCode, status: u16, detail: impl Into<String>) -> Self {
+        Self {
+            problem_type: "about:blank".to_string(),
+            title: code.title().to_string(),
+            status,
+            detail: detail.into(),
+            code,
+        }
+    }
+
+    /// Map a store error to the problem document the boundary should return
+    pub fn from_relay_error(error: &RelayError) -> Self {
+        match error {
+            RelayError::NotFound(detail) => Self::new(ProblemCode::NotFound, 404, detail.clone()),
+            RelayError::InvalidFormat(detail) => {
+                Self::new(ProblemCode::InvalidRequest, 400, detail.clone())
+            }
+            RelayError::SessionExpired(detail) => {
+                Self::new(ProblemCode::SessionExpired, 410, detail.clone())
+            }
+            RelayError::Internal(detail) => Self::new(ProblemCode::Internal, 500, detail.clone()),
+        }
+    }
+}
+
 /// Peer relay connection info
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PeerInfo {
@@ -176,6 +842,243 @@ mod tests {
 
         assert_eq!(id1.hash(), id2.hash());
         assert_ne!(id1.hash(), id3.hash());
+
+        // Sequence numbers key distinct slots, so a replay cannot
+        // overwrite the message it copies
+        assert_ne!(id1.hash(), id1.clone().with_seq(1).hash());
+    }
+
+    #[test]
+    fn test_session_summaries_and_removal() {
+        let store = MessageStore::new(3600);
+        store
+            .put(MessageId::new("s1", 1, Some(0), None, "broadcast"), vec![1])
+            .unwrap();
+        store
+            .put(MessageId::new("s1", 2, Some(1), None, "broadcast"), vec![2])
+            .unwrap();
+        store
+            .put(MessageId::new("s2", 1, Some(2), None, "broadcast"), vec![3])
+            .unwrap();
+
+        let sessions = store.sessions();
+        assert_eq!(sessions.len(), 2);
+
+        let s1 = sessions.iter().find(|s| s.session_id == "s1").unwrap();
+        assert_eq!(s1.latest_round, 2);
+        assert_eq!(s1.message_count, 2);
+        assert!(s1.senders.contains(&0) && s1.senders.contains(&1));
+
+        assert_eq!(store.remove_session("s1"), 2);
+        assert_eq!(store.sessions().len(), 1);
+    }
+
+    #[test]
+    fn test_session_stats() {
+        let store = MessageStore::new(3600);
+        store
+            .put(MessageId::new("s1", 1, Some(0), None, "broadcast"), vec![1])
+            .unwrap();
+        store
+            .put(MessageId::new("s1", 1, Some(1), None, "broadcast"), vec![2])
+            .unwrap();
+        store
+            .put(MessageId::new("s1", 2, Some(0), None, "broadcast"), vec![3])
+            .unwrap();
+
+        let stats = store.session_stats("s1").unwrap();
+        assert_eq!(stats.messages_per_round[&1], 2);
+        assert_eq!(stats.messages_per_round[&2], 1);
+        assert_eq!(stats.last_post_by_sender.len(), 2);
+
+        assert!(store.session_stats("unknown").is_none());
+    }
+
+    #[test]
+    fn test_problem_wire_format() {
+        let problem = Problem::new(ProblemCode::PayloadTooLarge, 413, "limit is 1 MiB");
+        let json = serde_json::to_value(&problem).unwrap();
+
+        assert_eq!(json["type"], "about:blank");
+        assert_eq!(json["status"], 413);
+        assert_eq!(json["code"], "PAYLOAD_TOO_LARGE");
+
+        let back: Problem = serde_json::from_value(json).unwrap();
+        assert_eq!(back.code, ProblemCode::PayloadTooLarge);
+    }
+
+
+    #[test]
+    fn test_trace_id_surfaces_in_session_stats() {
+        let store = MessageStore::new(3600);
+        store
+            .put_traced(
+                MessageId::new("sess", 1, Some(0), None, "broadcast"),
+                vec![1],
+                Some("trace-abc".to_string()),
+            )
+            .unwrap();
+        store
+            .put(MessageId::new("sess", 1, Some(1), None, "broadcast"), vec![2])
+            .unwrap();
+
+        let stats = store.session_stats("sess").unwrap();
+        assert_eq!(stats.trace_ids.len(), 1);
+        assert!(stats.trace_ids.contains("trace-abc"));
+    }
+
+    #[test]
+    fn test_session_cap_evicts_lru() {
+        let store = MessageStore::with_limits(
+            3600,
+            StoreLimits {
+                max_sessions: 2,
+                max_total_bytes: usize::MAX,
+            },
+        );
+
+        store
+            .put(MessageId::new("old", 1, Some(0), None, "broadcast"), vec![1])
+            .unwrap();
+        store
+            .put(MessageId::new("mid", 1, Some(0), None, "broadcast"), vec![2])
+            .unwrap();
+        store
+            .put(MessageId::new("new", 1, Some(0), None, "broadcast"), vec![3])
+            .unwrap();
+
+        // The least-recently-active session was dropped wholesale
+        assert!(store.session_stats("old").is_none());
+        assert!(store.session_stats("mid").is_some());
+        assert!(store.session_stats("new").is_some());
+
+        let metrics = store.metrics();
+        assert_eq!(metrics.sessions, 2);
+        assert_eq!(metrics.sessions_evicted, 1);
+        assert_eq!(metrics.messages_evicted, 1);
+    }
+
+    #[test]
+    fn test_byte_cap_never_evicts_active_session() {
+        let store = MessageStore::with_limits(
+            3600,
+            StoreLimits {
+                max_sessions: 100,
+                max_total_bytes: 10,
+            },
+        );
+
+        // A single session may exceed the byte cap; there is nothing safe
+        // to evict and the active ceremony must keep working
+        store
+            .put(MessageId::new("only", 1, Some(0), None, "broadcast"), vec![0; 64])
+            .unwrap();
+        assert!(store.session_stats("only").is_some());
+
+        // A second session pushes the store over; the older one goes
+        store
+            .put(MessageId::new("next", 1, Some(0), None, "broadcast"), vec![0; 4])
+            .unwrap();
+        assert!(store.session_stats("only").is_none());
+        assert!(store.session_stats("next").is_some());
+    }
+
+    #[test]
+    fn test_round_query_filters_by_tag_prefix_and_sender_set() {
+        let store = MessageStore::new(3600);
+        store
+            .put(MessageId::new("s1", 2, Some(0), None, "dsg.broadcast"), vec![1])
+            .unwrap();
+        store
+            .put(MessageId::new("s1", 2, Some(1), None, "dsg.broadcast"), vec![2])
+            .unwrap();
+        store
+            .put(MessageId::new("s1", 2, Some(2), None, "dkg.broadcast"), vec![3])
+            .unwrap();
+        store
+            .put(MessageId::new("s1", 2, None, None, "dsg.announce"), vec![4])
+            .unwrap();
+        store
+            .put(MessageId::new("s1", 3, Some(0), None, "dsg.broadcast"), vec![5])
+            .unwrap();
+
+        // An empty filter matches the whole round
+        assert_eq!(store.get_round_messages("s1", 2).len(), 4);
+
+        // Tag prefix drops the other protocol's messages
+        let dsg = store.query_round_messages(
+            "s1",
+            2,
+            &MessageFilter::default().with_tag_prefix("dsg."),
+        );
+        assert_eq!(dsg.len(), 3);
+        assert!(dsg.iter().all(|msg| msg.id.tag.starts_with("dsg.")));
+
+        // A sender set drops non-members and unattributed messages
+        let subset = store.query_round_messages(
+            "s1",
+            2,
+            &MessageFilter::default().with_from([0, 1]),
+        );
+        assert_eq!(subset.len(), 2);
+        assert!(subset.iter().all(|msg| matches!(msg.id.from, Some(0 | 1))));
+
+        // Constraints compose
+        let both = store.query_round_messages(
+            "s1",
+            2,
+            &MessageFilter::default().with_tag_prefix("dsg.").with_from([1, 2]),
+        );
+        assert_eq!(both.len(), 1);
+        assert_eq!(both[0].payload, vec![2]);
+    }
+
+    #[test]
+    fn test_conflicting_repost_is_recorded_as_equivocation() {
+        let store = MessageStore::new(3600);
+        let id = MessageId::new("s1", 1, Some(2), None, "broadcast");
+        store.put(id.clone(), vec![1, 2, 3]).unwrap();
+
+        // A byte-identical retry is honest and leaves no evidence
+        assert!(store.record_conflict(&id, &[1, 2, 3]).is_none());
+        assert!(store.conflicts("s1").is_empty());
+
+        // Different content under the same ID is equivocation
+        let event = store.record_conflict(&id, &[4, 5, 6]).unwrap();
+        assert_eq!(event.from, Some(2));
+        assert_eq!(event.stored_hash, content_hash(&[1, 2, 3]));
+        assert_eq!(event.conflicting_hash, content_hash(&[4, 5, 6]));
+
+        let conflicts = store.conflicts("s1");
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].round, 1);
+
+        // First write wins: the original payload is untouched
+        assert_eq!(store.get(&id).unwrap().payload, vec![1, 2, 3]);
+
+        // An unknown ID has nothing to conflict with
+        let other = MessageId::new("s1", 2, Some(2), None, "broadcast");
+        assert!(store.record_conflict(&other, &[7]).is_none());
+
+        // The evidence goes with the session
+        store.remove_session("s1");
+        assert!(store.conflicts("s1").is_empty());
+    }
+
+    #[test]
+    fn test_put_overwrite_with_different_content_is_recorded() {
+        let store = MessageStore::new(3600);
+        let id = MessageId::new("s1", 1, Some(0), None, "broadcast");
+        store.put(id.clone(), vec![1]).unwrap();
+
+        // Callers going straight through put (no boundary dedupe) still
+        // leave evidence when they replace a message with different bytes
+        store.put(id.clone(), vec![2]).unwrap();
+        assert_eq!(store.conflicts("s1").len(), 1);
+
+        // Re-putting identical bytes does not
+        store.put(id, vec![2]).unwrap();
+        assert_eq!(store.conflicts("s1").len(), 1);
     }
 
     #[test]
@@ -183,11 +1086,47 @@ mod tests {
         let store = MessageStore::new(3600);
         let id = MessageId::new("session1", 1, Some(0), None, "broadcast");
 
-        store.put(id.clone(), vec![1, 2, 3]).unwrap();
+        let hash = store.put(id.clone(), vec![1, 2, 3]).unwrap();
 
         assert!(store.exists(&id));
+        assert_eq!(hash, content_hash(&[1, 2, 3]));
 
         let msg = store.get(&id).unwrap();
         assert_eq!(msg.payload, vec![1, 2, 3]);
+        assert_eq!(msg.content_hash, hash);
+        assert_eq!(store.get_by_content_hash(&hash), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_identical_payloads_are_stored_once() {
+        let store = MessageStore::new(3600);
+        let payload = vec![7u8; 128];
+
+        // The same broadcast replicated to two sessions and a second
+        // recipient shares one blob
+        store
+            .put(MessageId::new("s1", 1, Some(0), None, "broadcast"), payload.clone())
+            .unwrap();
+        store
+            .put(MessageId::new("s1", 1, Some(0), Some(1), "direct"), payload.clone())
+            .unwrap();
+        store
+            .put(MessageId::new("s2", 1, Some(0), None, "broadcast"), payload.clone())
+            .unwrap();
+
+        let metrics = store.metrics();
+        assert_eq!(metrics.unique_payloads, 1);
+        assert_eq!(metrics.unique_payload_bytes, 128);
+        assert_eq!(metrics.total_bytes, 3 * 128);
+
+        // The blob survives while any message still references it
+        let hash = content_hash(&payload);
+        assert_eq!(store.remove_session("s1"), 2);
+        assert!(store.get_by_content_hash(&hash).is_some());
+
+        // ...and is freed when the last reference goes
+        assert_eq!(store.remove_session("s2"), 1);
+        assert!(store.get_by_content_hash(&hash).is_none());
+        assert_eq!(store.metrics().unique_payloads, 0);
     }
 }
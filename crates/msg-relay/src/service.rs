@@ -0,0 +1,160 @@
+//! Service-manager integration
+//!
+//! Helpers for running the relay and party binaries as managed services.
+//! On Linux this speaks the systemd notify protocol (readiness, status,
+//! watchdog heartbeats) over `NOTIFY_SOCKET`; everywhere else the calls
+//! are no-ops. [`shutdown_signal`] resolves on Ctrl-C and, on Unix,
+//! SIGTERM -- which also covers Windows service wrappers (WinSW, NSSM)
+//! that deliver stop requests as console control events -- so binaries
+//! can finish in-flight work before exiting.
+
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Send a state notification to the service manager, if one is listening
+///
+/// No-op when `NOTIFY_SOCKET` is unset or on non-Unix platforms; failures
+/// are logged and swallowed since a lost notification must never take the
+/// service down.
+pub fn sd_notify(state: &str) {
+    let Ok(socket_path) = std::env::var("NOTIFY_SOCKET") else {
+        return;
+    };
+    if let Err(e) = send_to_socket(&socket_path, state) {
+        warn!(error = %e, state, "Failed to notify service manager");
+    }
+}
+
+/// Report that the service is up and accepting work (`READY=1`)
+pub fn notify_ready() {
+    sd_notify("READY=1");
+}
+
+/// Report that the service is beginning a clean shutdown (`STOPPING=1`)
+pub fn notify_stopping() {
+    sd_notify("STOPPING=1");
+}
+
+/// Report a free-form status line shown by `systemctl status`
+pub fn notify_status(status: &str) {
+    sd_notify(&format!("STATUS={}", status));
+}
+
+/// Start the watchdog heartbeat if the service manager requested one
+///
+/// Reads `WATCHDOG_USEC` (and `WATCHDOG_PID`, when set) and spawns a task
+/// sending `WATCHDOG=1` at half the configured interval. Returns whether
+/// a watchdog was armed.
+pub fn spawn_watchdog() -> bool {
+    let Some(usec) = std::env::var("WATCHDOG_USEC")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+    else {
+        return false;
+    };
+    if let Ok(pid) = std::env::var("WATCHDOG_PID") {
+        if pid.parse::<u32>().ok() != Some(std::process::id()) {
+            debug!("Watchdog is armed for a different process");
+            return false;
+        }
+    }
+
+    let interval = Duration::from_micros(usec / 2).max(Duration::from_millis(100));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            sd_notify("WATCHDOG=1");
+        }
+    });
+    debug!(?interval, "Watchdog heartbeat armed");
+    true
+}
+
+/// Wait for a shutdown request from the platform
+///
+/// Resolves on Ctrl-C everywhere and additionally on SIGTERM on Unix,
+/// which is what systemd (and most service wrappers) send to stop a unit.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!(error = %e, "Failed to install Ctrl-C handler");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to install SIGTERM handler");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+/// Send a notification datagram to a concrete socket address
+#[cfg(unix)]
+fn send_to_socket(socket_path: &str, state: &str) -> std::io::Result<()> {
+    use std::os::unix::net::UnixDatagram;
+
+    let socket = UnixDatagram::unbound()?;
+    if let Some(name) = socket_path.strip_prefix('@') {
+        // Abstract-namespace socket (Linux only)
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::linux::net::SocketAddrExt;
+            let addr = std::os::unix::net::SocketAddr::from_abstract_name(name.as_bytes())?;
+            socket.send_to_addr(state.as_bytes(), &addr)?;
+            return Ok(());
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = name;
+            return Err(std::io::Error::other("abstract sockets are Linux-only"));
+        }
+    }
+    socket.send_to(state.as_bytes(), socket_path)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send_to_socket(_socket_path: &str, _state: &str) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixDatagram;
+
+    #[test]
+    fn test_send_to_socket_delivers_state() {
+        let path = std::env::temp_dir().join(format!("sd-notify-test-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let receiver = UnixDatagram::bind(&path).unwrap();
+
+        send_to_socket(path.to_str().unwrap(), "READY=1").unwrap();
+
+        let mut buf = [0u8; 64];
+        let len = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"READY=1");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_missing_socket_is_an_error_not_a_panic() {
+        assert!(send_to_socket("/nonexistent/notify.sock", "READY=1").is_err());
+    }
+}
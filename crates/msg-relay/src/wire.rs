@@ -0,0 +1,149 @@
+//! HTTP wire contract shared by the relay service and its clients
+//!
+//! One definition of every request and response body on the `/v1/msg`
+//! surface, so the service and the client cannot drift apart field by
+//! field. Payloads travel base64-encoded inside JSON; older peers that
+//! predate a field are covered by `#[serde(default)]` on everything
+//! added after the initial contract.
+
+use serde::{Deserialize, Serialize};
+
+/// Tag for messages addressed to every party in the session
+pub const TAG_BROADCAST: &str = "broadcast";
+
+/// Tag for messages addressed to a single party
+pub const TAG_DIRECT: &str = "direct";
+
+/// Request body for `POST /v1/msg`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostMessageRequest {
+    /// Hex-encoded session identifier
+    pub session_id: String,
+    /// Round number
+    pub round: u32,
+    /// Sender party ID (None for unattributed messages)
+    pub from: Option<usize>,
+    /// Receiver party ID (None for broadcasts)
+    pub to: Option<usize>,
+    /// Message tag
+    pub tag: String,
+    /// Per-sender sequence number, for replay-protected envelopes
+    #[serde(default)]
+    pub seq: u64,
+    /// Base64-encoded message payload
+    pub payload: String,
+    /// Trace ID of the originating request, for cross-system audit pivots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// Relay IDs this message has already traversed, for loop prevention
+    #[serde(default)]
+    pub hops: Vec<String>,
+}
+
+/// Response body for `POST /v1/msg`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PostMessageResponse {
+    /// Store key of the accepted message
+    #[serde(default)]
+    pub hash: Option<String>,
+    /// Content hash of the stored payload; absent from older relays
+    #[serde(default)]
+    pub content_hash: Option<String>,
+}
+
+/// Request body for `GET /v1/msg`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetMessageRequest {
+    /// Hex-encoded session identifier
+    pub session_id: String,
+    /// Round number
+    pub round: u32,
+    /// Sender party ID (None for unattributed messages)
+    pub from: Option<usize>,
+    /// Receiver party ID (None for broadcasts)
+    pub to: Option<usize>,
+    /// Message tag
+    pub tag: String,
+    /// Per-sender sequence number
+    #[serde(default)]
+    pub seq: u64,
+}
+
+/// Response body for `GET /v1/msg` and `GET /v1/msg/:hash`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MessageResponse {
+    /// Whether a matching message was stored
+    pub found: bool,
+    /// Base64-encoded payload, when found
+    pub payload: Option<String>,
+}
+
+/// Request body for `GET /v1/msg/query`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueryMessagesRequest {
+    /// Hex-encoded session identifier
+    pub session_id: String,
+    /// Round number
+    pub round: u32,
+    /// Only return messages whose tag starts with this prefix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag_prefix: Option<String>,
+    /// Only return messages from these senders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<Vec<usize>>,
+}
+
+/// One message in a query response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueriedMessage {
+    /// Round number
+    pub round: u32,
+    /// Sender party ID (None for unattributed messages)
+    pub from: Option<usize>,
+    /// Receiver party ID (None for broadcasts)
+    pub to: Option<usize>,
+    /// Message tag
+    pub tag: String,
+    /// Per-sender sequence number
+    #[serde(default)]
+    pub seq: u64,
+    /// Base64-encoded message payload
+    pub payload: String,
+}
+
+/// Response body for `GET /v1/msg/query`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueryMessagesResponse {
+    /// Matching messages, in store order
+    pub messages: Vec<QueriedMessage>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_post_request_defaults_cover_older_peers() {
+        // The initial contract had no seq, trace_id, or hops; bodies from
+        // peers that predate them must still parse
+        let legacy = serde_json::json!({
+            "session_id": "ab",
+            "round": 1,
+            "from": 0,
+            "to": null,
+            "tag": "broadcast",
+            "payload": "aGk=",
+        });
+        let req: PostMessageRequest = serde_json::from_value(legacy).unwrap();
+        assert_eq!(req.seq, 0);
+        assert!(req.trace_id.is_none());
+        assert!(req.hops.is_empty());
+    }
+
+    #[test]
+    fn test_post_response_tolerates_older_relays() {
+        let legacy: PostMessageResponse = serde_json::from_str("{}").unwrap();
+        assert!(legacy.hash.is_none());
+        assert!(legacy.content_hash.is_none());
+    }
+}
@@ -0,0 +1,36 @@
+//! Fuzz the client's response parsing
+//!
+//! Feeds arbitrary bytes through everything the client decodes from a
+//! relay: message and query response bodies, post acknowledgements, and
+//! RFC 7807 problem documents, plus the base64 payload decoding behind
+//! them. Nothing here may panic — a hostile or corrupting relay must
+//! only ever produce a clean error.
+
+#![no_main]
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use libfuzzer_sys::fuzz_target;
+use msg_relay::wire::{MessageResponse, PostMessageResponse, QueryMessagesResponse};
+use msg_relay::Problem;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(response) = serde_json::from_slice::<MessageResponse>(data) {
+        if response.found {
+            let _ = STANDARD.decode(response.payload.unwrap_or_default());
+        }
+    }
+
+    if let Ok(response) = serde_json::from_slice::<QueryMessagesResponse>(data) {
+        for msg in response.messages {
+            let _ = STANDARD.decode(&msg.payload);
+        }
+    }
+
+    let _ = serde_json::from_slice::<PostMessageResponse>(data);
+
+    // Problem documents drive the client's error mapping; display them
+    // like the client would log them
+    if let Ok(problem) = serde_json::from_slice::<Problem>(data) {
+        let _ = format!("{} ({})", problem.detail, problem.status);
+    }
+});
@@ -0,0 +1,44 @@
+//! Fuzz the relay's request parsing and store paths
+//!
+//! Feeds arbitrary bytes through the same pipeline `POST /v1/msg` and
+//! `GET /v1/msg` run: JSON into the shared wire structs, base64 payload
+//! decoding, message ID construction, and a store put/get roundtrip.
+//! Panics only on violated invariants — a stored message must be found
+//! again under its ID, and its content hash must match the payload.
+
+#![no_main]
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use libfuzzer_sys::fuzz_target;
+use msg_relay::wire::{GetMessageRequest, PostMessageRequest, QueryMessagesRequest};
+use msg_relay::{content_hash, MessageId, MessageStore};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(req) = serde_json::from_slice::<PostMessageRequest>(data) {
+        let id = MessageId::new(&req.session_id, req.round, req.from, req.to, &req.tag)
+            .with_seq(req.seq);
+        // Hashing must be total over whatever the parser let through
+        let _ = id.hash();
+
+        if let Ok(payload) = STANDARD.decode(&req.payload) {
+            let store = MessageStore::new(60);
+            let expected_hash = content_hash(&payload);
+            let stored_hash = store
+                .put(id.clone(), payload.clone())
+                .expect("a fresh store must accept one message");
+            assert_eq!(stored_hash, expected_hash, "stored hash must match the payload");
+
+            let stored = store.get(&id).expect("a stored message must be found by its ID");
+            assert_eq!(stored.payload, payload, "the store must return the payload verbatim");
+            assert_eq!(
+                store.get_by_content_hash(&expected_hash),
+                Some(payload),
+                "the payload must be addressable by content hash"
+            );
+        }
+    }
+
+    // The read paths share the parser; they must never panic either
+    let _ = serde_json::from_slice::<GetMessageRequest>(data);
+    let _ = serde_json::from_slice::<QueryMessagesRequest>(data);
+});